@@ -0,0 +1,134 @@
+//! WebAssembly bindings for `staking-math`.
+//!
+//! Lets the web frontend compute pending rewards, projected APR and tier
+//! boundaries locally with exactly the program's arithmetic. `u64` values
+//! map to JS `bigint`; 128-bit accumulator values are passed as decimal
+//! strings. Functions return `undefined` where the program would fail with
+//! `MathOverflow`.
+
+use wasm_bindgen::prelude::*;
+
+fn parse_u128(value: &str) -> Option<u128> {
+    value.parse().ok()
+}
+
+fn parse_tier(tier: u8) -> Option<staking_math::Tier> {
+    match tier {
+        0 => Some(staking_math::Tier::None),
+        1 => Some(staking_math::Tier::Holder),
+        2 => Some(staking_math::Tier::Premium),
+        3 => Some(staking_math::Tier::Vip),
+        _ => None,
+    }
+}
+
+/// Tier index for a staked amount: 0 = None, 1 = Holder, 2 = Premium, 3 = VIP
+#[wasm_bindgen(js_name = calculateTier)]
+pub fn calculate_tier(staked_amount: u64) -> u8 {
+    staking_math::calculate_tier(staked_amount) as u8
+}
+
+/// Tier index for a staked amount and lock duration under a pool's minimum
+/// lock per tier (`[holder, premium, vip]` seconds), or `undefined` if
+/// `min_lock_durations` doesn't have three entries
+#[wasm_bindgen(js_name = calculateTierV2)]
+pub fn calculate_tier_v2(staked_amount: u64, lock_duration: i64, min_lock_durations: Vec<i64>) -> Option<u8> {
+    let config = staking_math::TierConfig {
+        min_lock_durations: min_lock_durations.try_into().ok()?,
+        ..Default::default()
+    };
+    Some(staking_math::calculate_tier_v2(staked_amount, lock_duration, &config) as u8)
+}
+
+/// Tier thresholds `[holder, premium, vip]` in raw token units
+#[wasm_bindgen(js_name = tierThresholds)]
+pub fn tier_thresholds() -> Vec<u64> {
+    vec![
+        staking_math::HOLDER_THRESHOLD,
+        staking_math::PREMIUM_THRESHOLD,
+        staking_math::VIP_THRESHOLD,
+    ]
+}
+
+/// Tier thresholds `[holder, premium, vip]` rescaled to a mint with
+/// `decimals`, or `undefined` if they overflow
+#[wasm_bindgen(js_name = tierThresholdsForDecimals)]
+pub fn tier_thresholds_for_decimals(decimals: u8) -> Option<Vec<u64>> {
+    Some(staking_math::TierConfig::for_decimals(decimals)?.min_amounts.to_vec())
+}
+
+/// Amount needed to reach the next tier, or `undefined` at VIP
+#[wasm_bindgen(js_name = nextTierThreshold)]
+pub fn next_tier_threshold(staked_amount: u64) -> Option<u64> {
+    staking_math::next_tier_threshold(staked_amount)
+}
+
+/// Lock weight multiplier in basis points
+#[wasm_bindgen(js_name = calculateWeightMultiplier)]
+pub fn calculate_weight_multiplier(lock_duration: i64, min_duration: i64, max_duration: i64) -> u64 {
+    staking_math::calculate_weight_multiplier(lock_duration, min_duration, max_duration)
+}
+
+/// Accumulated reward per share (decimal string) after `elapsed` seconds
+#[wasm_bindgen(js_name = updateRewardPerShare)]
+pub fn update_reward_per_share(
+    accumulated_reward_per_share: &str,
+    total_weighted_stake: u64,
+    reward_rate: u64,
+    elapsed: i64,
+) -> Option<String> {
+    staking_math::update_reward_per_share(
+        parse_u128(accumulated_reward_per_share)?,
+        total_weighted_stake,
+        reward_rate,
+        elapsed,
+    )
+    .map(|value| value.to_string())
+}
+
+/// Pending rewards for a position; `reward_debt` and the accumulator are
+/// decimal strings as stored on-chain
+#[wasm_bindgen(js_name = calculatePendingRewards)]
+pub fn calculate_pending_rewards(
+    weighted_stake: u64,
+    reward_debt: &str,
+    accumulated_reward_per_share: &str,
+) -> Option<u64> {
+    staking_math::calculate_pending_rewards(
+        weighted_stake,
+        parse_u128(reward_debt)?,
+        parse_u128(accumulated_reward_per_share)?,
+    )
+}
+
+/// Projected APR in basis points at the current reward rate
+#[wasm_bindgen(js_name = projectedAprBps)]
+pub fn projected_apr_bps(
+    staked_amount: u64,
+    weighted_stake: u64,
+    total_weighted_stake: u64,
+    reward_rate: u64,
+) -> Option<u64> {
+    staking_math::projected_apr_bps(staked_amount, weighted_stake, total_weighted_stake, reward_rate)
+}
+
+/// Launchpad sale allocation for a staker. `tier` is a tier index as
+/// returned by `calculateTier`, `total_allocation_weight` a decimal string
+/// and `tier_multipliers_bps` the four per-tier multipliers.
+#[wasm_bindgen(js_name = allocationFor)]
+pub fn allocation_for(
+    tier: u8,
+    weighted_stake: u64,
+    total_allocation: u64,
+    total_allocation_weight: &str,
+    tier_multipliers_bps: Vec<u64>,
+    max_per_wallet: u64,
+) -> Option<u64> {
+    let sale_config = staking_math::SaleConfig {
+        total_allocation,
+        total_allocation_weight: parse_u128(total_allocation_weight)?,
+        tier_multipliers_bps: tier_multipliers_bps.try_into().ok()?,
+        max_per_wallet,
+    };
+    staking_math::allocation_for(parse_tier(tier)?, weighted_stake, &sale_config)
+}
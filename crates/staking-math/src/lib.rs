@@ -0,0 +1,494 @@
+//! Reward math for the KR8TIV staking program.
+//!
+//! The on-chain program calls into this crate for tiers, lock weights and
+//! reward accrual, so bots and backends that link it get results that are
+//! bit-identical to the program's. Everything is `no_std` and integer-only;
+//! fallible helpers return `None` on overflow or division by zero.
+
+#![no_std]
+
+pub mod u256;
+
+/// Precision multiplier for accumulated rewards (1e18)
+pub const PRECISION: u128 = 1_000_000_000_000_000_000;
+
+/// Basis points denominator (10000 = 100%)
+pub const BPS_DENOMINATOR: u64 = 10000;
+
+/// Minimum weight multiplier (1x = 10000 bps)
+pub const MIN_WEIGHT_MULTIPLIER: u64 = 10000;
+
+/// Maximum weight multiplier (2x = 20000 bps)
+pub const MAX_WEIGHT_MULTIPLIER: u64 = 20000;
+
+/// Tier thresholds (in token smallest units, assuming 9 decimals)
+pub const HOLDER_THRESHOLD: u64 = 1_000_000_000_000;     // 1,000 tokens
+pub const PREMIUM_THRESHOLD: u64 = 10_000_000_000_000;   // 10,000 tokens
+pub const VIP_THRESHOLD: u64 = 100_000_000_000_000;      // 100,000 tokens
+
+/// Decimals the built-in tier thresholds are denominated in
+pub const THRESHOLD_DECIMALS: u8 = 9;
+
+/// Seconds in a 365-day year, for APR projections
+pub const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+
+/// Staking tier by staked amount
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Tier {
+    None,
+    Holder,
+    Premium,
+    Vip,
+}
+
+/// Calculate the staking tier based on staked amount
+pub fn calculate_tier(staked_amount: u64) -> Tier {
+    if staked_amount >= VIP_THRESHOLD {
+        Tier::Vip
+    } else if staked_amount >= PREMIUM_THRESHOLD {
+        Tier::Premium
+    } else if staked_amount >= HOLDER_THRESHOLD {
+        Tier::Holder
+    } else {
+        Tier::None
+    }
+}
+
+/// Requirements for each tier above `None`, ordered Holder, Premium, VIP
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TierConfig {
+    /// Minimum staked amount per tier
+    pub min_amounts: [u64; 3],
+
+    /// Minimum lock duration (seconds) per tier; 0 = no lock requirement
+    pub min_lock_durations: [i64; 3],
+}
+
+impl Default for TierConfig {
+    /// The built-in thresholds with no lock requirements, matching
+    /// `calculate_tier`
+    fn default() -> Self {
+        TierConfig {
+            min_amounts: [HOLDER_THRESHOLD, PREMIUM_THRESHOLD, VIP_THRESHOLD],
+            min_lock_durations: [0; 3],
+        }
+    }
+}
+
+impl TierConfig {
+    /// The built-in thresholds rescaled to a mint with `decimals`, so they
+    /// keep meaning 1,000 / 10,000 / 100,000 whole tokens
+    ///
+    /// # Returns
+    /// * `Option<TierConfig>` - `None` if the thresholds overflow `u64`
+    pub fn for_decimals(decimals: u8) -> Option<TierConfig> {
+        let mut config = TierConfig::default();
+        for amount in config.min_amounts.iter_mut() {
+            *amount = if decimals >= THRESHOLD_DECIMALS {
+                amount.checked_mul(10u64.checked_pow((decimals - THRESHOLD_DECIMALS) as u32)?)?
+            } else {
+                *amount / 10u64.pow((THRESHOLD_DECIMALS - decimals) as u32)
+            };
+        }
+        Some(config)
+    }
+}
+
+/// Calculate the staking tier from both the staked amount and the lock
+/// duration: the highest tier whose amount and lock requirements are both met
+pub fn calculate_tier_v2(amount: u64, lock_duration: i64, config: &TierConfig) -> Tier {
+    const TIERS: [Tier; 3] = [Tier::Holder, Tier::Premium, Tier::Vip];
+    (0..TIERS.len())
+        .rev()
+        .find(|&i| amount >= config.min_amounts[i] && lock_duration >= config.min_lock_durations[i])
+        .map_or(Tier::None, |i| TIERS[i])
+}
+
+/// Smallest staked amount that reaches the next tier above `staked_amount`
+///
+/// # Returns
+/// * `Option<u64>` - Next tier threshold, `None` once VIP is reached
+pub fn next_tier_threshold(staked_amount: u64) -> Option<u64> {
+    match calculate_tier(staked_amount) {
+        Tier::None => Some(HOLDER_THRESHOLD),
+        Tier::Holder => Some(PREMIUM_THRESHOLD),
+        Tier::Premium => Some(VIP_THRESHOLD),
+        Tier::Vip => None,
+    }
+}
+
+/// Calculate weight multiplier based on lock duration
+/// Linear interpolation from 1x (min duration) to 2x (max duration)
+///
+/// # Returns
+/// * `u64` - Weight multiplier in basis points (10000 = 1x, 20000 = 2x)
+pub fn calculate_weight_multiplier(
+    lock_duration: i64,
+    min_duration: i64,
+    max_duration: i64,
+) -> u64 {
+    // Clamp duration to valid range
+    let duration = lock_duration.max(min_duration).min(max_duration);
+
+    // Calculate how far through the range we are (0 to 10000)
+    let range = max_duration - min_duration;
+    if range == 0 {
+        return MIN_WEIGHT_MULTIPLIER;
+    }
+
+    let progress = duration - min_duration;
+    let progress_bps = ((progress as u128) * 10000 / (range as u128)) as u64;
+
+    // Linear interpolation: min_mult + (max_mult - min_mult) * progress / 10000
+    let multiplier_range = MAX_WEIGHT_MULTIPLIER - MIN_WEIGHT_MULTIPLIER;
+    MIN_WEIGHT_MULTIPLIER + (multiplier_range * progress_bps / BPS_DENOMINATOR)
+}
+
+/// Advance the accumulated reward per share by `elapsed` seconds of
+/// emission at `reward_rate`, shared across `total_weighted_stake`
+/// acc_reward_per_share += elapsed * reward_rate * PRECISION / total_weighted_stake
+///
+/// # Returns
+/// * `Option<u128>` - New accumulator; unchanged when nothing is staked or no time passed
+pub fn update_reward_per_share(
+    accumulated_reward_per_share: u128,
+    total_weighted_stake: u64,
+    reward_rate: u64,
+    elapsed: i64,
+) -> Option<u128> {
+    if total_weighted_stake == 0 || elapsed <= 0 {
+        return Some(accumulated_reward_per_share);
+    }
+
+    let new_rewards = (elapsed as u128).checked_mul(reward_rate as u128)?;
+    let increase = u256::mul_div(new_rewards, PRECISION, total_weighted_stake as u128)?;
+    accumulated_reward_per_share.checked_add(increase)
+}
+
+/// Calculate the reward debt for a weighted stake at the current accumulator
+/// reward_debt = weighted_stake * accumulated_reward_per_share / PRECISION
+pub fn calculate_reward_debt(weighted_stake: u64, accumulated_reward_per_share: u128) -> Option<u128> {
+    u256::mul_div(weighted_stake as u128, accumulated_reward_per_share, PRECISION)
+}
+
+/// Calculate pending rewards for a weighted stake
+/// pending = (weighted_stake * acc_reward_per_share / PRECISION) - reward_debt
+///
+/// # Returns
+/// * `Option<u64>` - Pending rewards (saturating at `u64::MAX`), `None` on overflow
+pub fn calculate_pending_rewards(
+    weighted_stake: u64,
+    reward_debt: u128,
+    accumulated_reward_per_share: u128,
+) -> Option<u64> {
+    if weighted_stake == 0 {
+        return Some(0);
+    }
+
+    let accumulated = calculate_reward_debt(weighted_stake, accumulated_reward_per_share)?;
+    let pending = accumulated.saturating_sub(reward_debt);
+
+    Some(pending.min(u64::MAX as u128) as u64)
+}
+
+/// Projected APR (bps) for a position at the current reward rate, assuming
+/// reward and stake tokens are priced equally (scale by the price ratio
+/// otherwise). The position's weight is taken as already part of
+/// `total_weighted_stake`.
+/// apr = reward_rate * SECONDS_PER_YEAR * weighted_stake / total_weighted_stake / staked_amount
+///
+/// # Returns
+/// * `Option<u64>` - APR in basis points, `None` if nothing is staked or on overflow
+pub fn projected_apr_bps(
+    staked_amount: u64,
+    weighted_stake: u64,
+    total_weighted_stake: u64,
+    reward_rate: u64,
+) -> Option<u64> {
+    if staked_amount == 0 || total_weighted_stake == 0 {
+        return None;
+    }
+
+    let yearly_emission = (reward_rate as u128).checked_mul(SECONDS_PER_YEAR as u128)?;
+    let yearly_rewards = u256::mul_div(
+        yearly_emission,
+        weighted_stake as u128,
+        total_weighted_stake as u128,
+    )?;
+    let apr = u256::mul_div(yearly_rewards, BPS_DENOMINATOR as u128, staked_amount as u128)?;
+    u64::try_from(apr).ok()
+}
+
+/// Reward rate that pays `apr_bps` on `total_staked`, assuming reward and
+/// stake tokens are priced equally. Lock weights only redistribute the
+/// emission, so this is the pool-wide average APR.
+/// reward_rate = total_staked * apr_bps / BPS_DENOMINATOR / SECONDS_PER_YEAR
+///
+/// # Returns
+/// * `Option<u64>` - Reward rate per second, `None` on overflow
+pub fn reward_rate_for_apr(total_staked: u64, apr_bps: u64) -> Option<u64> {
+    let rate = u256::mul_div(
+        total_staked as u128,
+        apr_bps as u128,
+        BPS_DENOMINATOR as u128 * SECONDS_PER_YEAR as u128,
+    )?;
+    u64::try_from(rate).ok()
+}
+
+/// Rewards a fixed-rate position earns on `amount` over `duration` seconds
+/// at `apr_bps`, rounded down.
+/// rewards = amount * apr_bps * duration / BPS_DENOMINATOR / SECONDS_PER_YEAR
+///
+/// # Returns
+/// * `Option<u64>` - Rewards owed, `None` on overflow
+pub fn fixed_rate_rewards(amount: u64, apr_bps: u64, duration: u64) -> Option<u64> {
+    let rewards = u256::mul_div(
+        (amount as u128).checked_mul(duration as u128)?,
+        apr_bps as u128,
+        BPS_DENOMINATOR as u128 * SECONDS_PER_YEAR as u128,
+    )?;
+    u64::try_from(rewards).ok()
+}
+
+/// Curve-gauge style boost a farm position earns from its owner's weight in
+/// a source pool: the full `max_bonus_bps` while the position's share of
+/// the farm is no larger than the owner's share of the source pool's
+/// weight, scaled down proportionally beyond that.
+/// bonus = max_bonus_bps * min(1, (source_weight / source_total_weight) / (staked_amount / total_staked))
+///
+/// # Returns
+/// * `Option<u64>` - Bonus in basis points, `None` on overflow
+pub fn cross_pool_boost_bps(
+    source_weight: u64,
+    source_total_weight: u64,
+    staked_amount: u64,
+    total_staked: u64,
+    max_bonus_bps: u64,
+) -> Option<u64> {
+    if source_weight == 0 || source_total_weight == 0 || staked_amount == 0 || total_staked == 0 {
+        return Some(0);
+    }
+
+    let ratio_bps = u256::mul_div(
+        source_weight as u128 * total_staked as u128,
+        BPS_DENOMINATOR as u128,
+        source_total_weight as u128 * staked_amount as u128,
+    )?
+    .min(BPS_DENOMINATOR as u128);
+    u64::try_from(max_bonus_bps as u128 * ratio_bps / BPS_DENOMINATOR as u128).ok()
+}
+
+/// Launchpad sale parameters that size each staker's allocation
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct SaleConfig {
+    /// Sale tokens reserved for stakers
+    pub total_allocation: u64,
+
+    /// Sum of every participant's `allocation_weight`
+    pub total_allocation_weight: u128,
+
+    /// Allocation multiplier (bps) per tier, indexed None, Holder, Premium, Vip
+    pub tier_multipliers_bps: [u64; 4],
+
+    /// Per-wallet cap on the allocation (0 = uncapped)
+    pub max_per_wallet: u64,
+}
+
+impl SaleConfig {
+    /// A participant's share of the sale: weighted stake scaled by their
+    /// tier's allocation multiplier
+    /// weight = weighted_stake * tier_multiplier_bps / BPS_DENOMINATOR
+    pub fn allocation_weight(&self, tier: Tier, weighted_stake: u64) -> u128 {
+        weighted_stake as u128 * self.tier_multipliers_bps[tier as usize] as u128
+            / BPS_DENOMINATOR as u128
+    }
+}
+
+/// Sale allocation for a staker; the launchpad program and frontend both
+/// size allocations with this so they can never disagree
+/// allocation = total_allocation * allocation_weight / total_allocation_weight
+///
+/// # Returns
+/// * `Option<u64>` - Allocation capped at `max_per_wallet`, `None` if no
+///   weight is registered or the participant's weight exceeds the total
+pub fn allocation_for(tier: Tier, weighted_stake: u64, sale_config: &SaleConfig) -> Option<u64> {
+    let weight = sale_config.allocation_weight(tier, weighted_stake);
+    if sale_config.total_allocation_weight == 0 || weight > sale_config.total_allocation_weight {
+        return None;
+    }
+
+    let allocation = u256::mul_div(
+        sale_config.total_allocation as u128,
+        weight,
+        sale_config.total_allocation_weight,
+    )? as u64;
+
+    if sale_config.max_per_wallet > 0 {
+        Some(allocation.min(sale_config.max_per_wallet))
+    } else {
+        Some(allocation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calculate_tier() {
+        assert_eq!(calculate_tier(0), Tier::None);
+        assert_eq!(calculate_tier(HOLDER_THRESHOLD - 1), Tier::None);
+        assert_eq!(calculate_tier(HOLDER_THRESHOLD), Tier::Holder);
+        assert_eq!(calculate_tier(PREMIUM_THRESHOLD), Tier::Premium);
+        assert_eq!(calculate_tier(VIP_THRESHOLD), Tier::Vip);
+    }
+
+    #[test]
+    fn test_update_reward_per_share() {
+        // 100 seconds at 10/s over 1,000 weight
+        assert_eq!(
+            update_reward_per_share(0, 1_000, 10, 100),
+            Some(PRECISION)
+        );
+        // Nothing staked or no time passed leaves the accumulator alone
+        assert_eq!(update_reward_per_share(5, 0, 10, 100), Some(5));
+        assert_eq!(update_reward_per_share(5, 1_000, 10, 0), Some(5));
+        // Overflowing the accumulator is reported
+        assert_eq!(update_reward_per_share(u128::MAX, 1, 1, 1), None);
+    }
+
+    #[test]
+    fn test_pending_rewards() {
+        assert_eq!(calculate_pending_rewards(1_000, 0, PRECISION), Some(1_000));
+        assert_eq!(calculate_pending_rewards(1_000, 400, PRECISION), Some(600));
+        // Debt above the accumulated amount never goes negative
+        assert_eq!(calculate_pending_rewards(1_000, 5_000, PRECISION), Some(0));
+        assert_eq!(calculate_pending_rewards(0, 0, PRECISION), Some(0));
+    }
+
+    #[test]
+    fn test_next_tier_threshold() {
+        assert_eq!(next_tier_threshold(0), Some(HOLDER_THRESHOLD));
+        assert_eq!(next_tier_threshold(HOLDER_THRESHOLD), Some(PREMIUM_THRESHOLD));
+        assert_eq!(next_tier_threshold(VIP_THRESHOLD - 1), Some(VIP_THRESHOLD));
+        assert_eq!(next_tier_threshold(VIP_THRESHOLD), None);
+    }
+
+    #[test]
+    fn test_projected_apr() {
+        // Sole staker of 1,000,000 at 2x weight earning 1/s: 31,536,000 a year (3,153.6%)
+        assert_eq!(
+            projected_apr_bps(1_000_000, 2_000_000, 2_000_000, 1),
+            Some(315_360)
+        );
+        // Half the pool's weight earns half the emission
+        assert_eq!(
+            projected_apr_bps(1_000_000, 1_000_000, 2_000_000, 1),
+            Some(157_680)
+        );
+        assert_eq!(projected_apr_bps(0, 1, 1, 1), None);
+        assert_eq!(projected_apr_bps(1, 1, 0, 1), None);
+    }
+
+    #[test]
+    fn test_reward_rate_for_apr() {
+        // 10% on 315,360,000 staked is 31,536,000 a year, 1 per second
+        assert_eq!(reward_rate_for_apr(315_360_000, 1_000), Some(1));
+        assert_eq!(reward_rate_for_apr(3_153_600_000, 1_000), Some(10));
+        // Round trip through the projected APR of a 1x position
+        let rate = reward_rate_for_apr(1_000_000_000_000_000, 1_200).unwrap();
+        let apr = projected_apr_bps(1_000_000_000_000_000, 1_000_000_000_000_000, 1_000_000_000_000_000, rate);
+        assert_eq!(apr, Some(1_199));
+        assert_eq!(reward_rate_for_apr(0, 1_000), Some(0));
+    }
+
+    #[test]
+    fn test_calculate_tier_v2() {
+        let day = 24 * 60 * 60;
+        let config = TierConfig {
+            min_lock_durations: [0, 30 * day, 90 * day],
+            ..TierConfig::default()
+        };
+
+        // A VIP-sized stake on a short lock only gets what its lock allows
+        assert_eq!(calculate_tier_v2(VIP_THRESHOLD, 7 * day, &config), Tier::Holder);
+        assert_eq!(calculate_tier_v2(VIP_THRESHOLD, 30 * day, &config), Tier::Premium);
+        assert_eq!(calculate_tier_v2(VIP_THRESHOLD, 365 * day, &config), Tier::Vip);
+        // A long lock doesn't make up for the amount
+        assert_eq!(calculate_tier_v2(PREMIUM_THRESHOLD, 365 * day, &config), Tier::Premium);
+        assert_eq!(calculate_tier_v2(HOLDER_THRESHOLD - 1, 365 * day, &config), Tier::None);
+
+        // Without lock requirements it matches calculate_tier
+        for amount in [0, HOLDER_THRESHOLD, PREMIUM_THRESHOLD, VIP_THRESHOLD] {
+            assert_eq!(calculate_tier_v2(amount, 0, &TierConfig::default()), calculate_tier(amount));
+        }
+    }
+
+    #[test]
+    fn test_tier_config_for_decimals() {
+        assert_eq!(TierConfig::for_decimals(THRESHOLD_DECIMALS), Some(TierConfig::default()));
+
+        let usdc = TierConfig::for_decimals(6).unwrap();
+        assert_eq!(usdc.min_amounts, [1_000_000_000, 10_000_000_000, 100_000_000_000]);
+        let whole = TierConfig::for_decimals(0).unwrap();
+        assert_eq!(whole.min_amounts, [1_000, 10_000, 100_000]);
+        let wide = TierConfig::for_decimals(12).unwrap();
+        assert_eq!(wide.min_amounts[2], VIP_THRESHOLD * 1_000);
+
+        // 100,000 tokens no longer fit in a u64
+        assert_eq!(TierConfig::for_decimals(15), None);
+    }
+
+    #[test]
+    fn test_cross_pool_boost_bps() {
+        // 10% of the source weight covers up to 10% of the farm
+        assert_eq!(cross_pool_boost_bps(100, 1_000, 50, 1_000, 15_000), Some(15_000));
+        assert_eq!(cross_pool_boost_bps(100, 1_000, 100, 1_000, 15_000), Some(15_000));
+        // Twice that farm share earns half the bonus
+        assert_eq!(cross_pool_boost_bps(100, 1_000, 200, 1_000, 15_000), Some(7_500));
+        // No source weight, nothing staked or no boost configured
+        assert_eq!(cross_pool_boost_bps(0, 1_000, 100, 1_000, 15_000), Some(0));
+        assert_eq!(cross_pool_boost_bps(100, 1_000, 0, 1_000, 15_000), Some(0));
+        assert_eq!(cross_pool_boost_bps(100, 1_000, 100, 1_000, 0), Some(0));
+        // Extreme balances don't overflow
+        assert_eq!(cross_pool_boost_bps(u64::MAX, u64::MAX, u64::MAX, u64::MAX, 15_000), Some(15_000));
+    }
+
+    #[test]
+    fn test_fixed_rate_rewards() {
+        // 10% for a full year
+        assert_eq!(fixed_rate_rewards(1_000_000, 1_000, SECONDS_PER_YEAR), Some(100_000));
+        // Half a year earns half, and a zero rate or duration earns nothing
+        assert_eq!(fixed_rate_rewards(1_000_000, 1_000, SECONDS_PER_YEAR / 2), Some(50_000));
+        assert_eq!(fixed_rate_rewards(1_000_000, 0, SECONDS_PER_YEAR), Some(0));
+        assert_eq!(fixed_rate_rewards(1_000_000, 1_000, 0), Some(0));
+        // Rounds down
+        assert_eq!(fixed_rate_rewards(1, 1_000, SECONDS_PER_YEAR), Some(0));
+        assert_eq!(fixed_rate_rewards(u64::MAX, 100_000, SECONDS_PER_YEAR), None);
+    }
+
+    #[test]
+    fn test_allocation_for() {
+        let mut sale = SaleConfig {
+            total_allocation: 1_000_000,
+            total_allocation_weight: 0,
+            tier_multipliers_bps: [0, 10_000, 15_000, 30_000],
+            max_per_wallet: 0,
+        };
+        // A VIP at 3x and a Holder at 1x with equal weighted stake
+        sale.total_allocation_weight = sale.allocation_weight(Tier::Vip, 1_000)
+            + sale.allocation_weight(Tier::Holder, 1_000);
+
+        assert_eq!(allocation_for(Tier::Vip, 1_000, &sale), Some(750_000));
+        assert_eq!(allocation_for(Tier::Holder, 1_000, &sale), Some(250_000));
+        assert_eq!(allocation_for(Tier::None, 1_000, &sale), Some(0));
+
+        sale.max_per_wallet = 500_000;
+        assert_eq!(allocation_for(Tier::Vip, 1_000, &sale), Some(500_000));
+
+        // Weight beyond the registered total is inconsistent
+        assert_eq!(allocation_for(Tier::Vip, 10_000, &sale), None);
+        sale.total_allocation_weight = 0;
+        assert_eq!(allocation_for(Tier::Holder, 1_000, &sale), None);
+    }
+}
@@ -0,0 +1,62 @@
+//! 256-bit unsigned integer used for reward accrual intermediates.
+//!
+//! `accumulated_reward_per_share` is scaled by `PRECISION`, so products like
+//! `weighted_stake * accumulated_reward_per_share` can exceed `u128` long
+//! before the final (divided) result does. All such products go through
+//! [`mul_div`] so the intermediate never overflows.
+
+#![allow(clippy::assign_op_pattern)]
+#![allow(clippy::ptr_offset_with_cast)]
+#![allow(clippy::manual_range_contains)]
+#![allow(clippy::manual_div_ceil)]
+
+use uint::construct_uint;
+
+construct_uint! {
+    pub struct U256(4);
+}
+
+/// Compute `a * b / denominator` with a 256-bit intermediate product
+///
+/// # Returns
+/// * `Option<u128>` - `None` on division by zero or if the result does not fit in `u128`
+pub fn mul_div(a: u128, b: u128, denominator: u128) -> Option<u128> {
+    if denominator == 0 {
+        return None;
+    }
+
+    let result = U256::from(a)
+        .checked_mul(U256::from(b))?
+        .checked_div(U256::from(denominator))?;
+
+    if result > U256::from(u128::MAX) {
+        None
+    } else {
+        Some(result.as_u128())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mul_div_basic() {
+        assert_eq!(mul_div(6, 7, 3), Some(14));
+        assert_eq!(mul_div(10, 1, 3), Some(3));
+        assert_eq!(mul_div(0, u128::MAX, 1), Some(0));
+    }
+
+    #[test]
+    fn test_mul_div_wide_intermediate() {
+        // a * b overflows u128 but the quotient fits
+        assert_eq!(mul_div(u128::MAX, 4, 8), Some(u128::MAX / 2));
+        assert_eq!(mul_div(u128::MAX, u128::MAX, u128::MAX), Some(u128::MAX));
+    }
+
+    #[test]
+    fn test_mul_div_errors() {
+        assert_eq!(mul_div(1, 1, 0), None);
+        assert_eq!(mul_div(u128::MAX, 2, 1), None);
+    }
+}
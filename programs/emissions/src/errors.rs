@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum EmissionsError {
+    #[msg("Invalid authority for this operation")]
+    InvalidAuthority,
+
+    #[msg("Invalid emission schedule")]
+    InvalidSchedule,
+
+    #[msg("Recipient weights must be non-zero and sum to 10000 bps")]
+    InvalidRecipients,
+
+    #[msg("The current epoch has not ended yet")]
+    EpochNotEnded,
+
+    #[msg("Remaining accounts don't match the recipient list")]
+    RecipientMismatch,
+
+    #[msg("Nothing left to emit")]
+    EmissionsExhausted,
+
+    #[msg("Math overflow occurred")]
+    MathOverflow,
+}
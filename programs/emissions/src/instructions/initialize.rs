@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::state::{EmissionController, BPS_DENOMINATOR};
+use crate::errors::EmissionsError;
+
+/// Create an emission controller and its vault for a reward mint
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    /// Authority managing the recipient list
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The controller PDA
+    #[account(
+        init,
+        payer = authority,
+        space = EmissionController::LEN,
+        seeds = [EmissionController::SEED_PREFIX, reward_mint.key().as_ref()],
+        bump
+    )]
+    pub controller: Box<Account<'info, EmissionController>>,
+
+    /// Token mint being emitted
+    pub reward_mint: Account<'info, Mint>,
+
+    /// Vault holding tokens not yet emitted; fund it with a plain transfer
+    #[account(
+        init,
+        payer = authority,
+        token::mint = reward_mint,
+        token::authority = controller,
+        seeds = [b"emission_vault", controller.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Emission schedule
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitializeParams {
+    /// Schedule start (Unix timestamp)
+    pub start_time: i64,
+    /// Length of one epoch in seconds
+    pub epoch_duration: i64,
+    /// Emission for epoch 0
+    pub initial_epoch_emission: u64,
+    /// Per-epoch reduction (bps)
+    pub decay_bps: u64,
+    /// Lifetime emission cap
+    pub max_total_emission: u64,
+}
+
+pub fn handler(ctx: Context<Initialize>, params: InitializeParams) -> Result<()> {
+    require!(params.epoch_duration > 0, EmissionsError::InvalidSchedule);
+    require!(params.initial_epoch_emission > 0, EmissionsError::InvalidSchedule);
+    require!(params.decay_bps < BPS_DENOMINATOR, EmissionsError::InvalidSchedule);
+    require!(params.max_total_emission > 0, EmissionsError::InvalidSchedule);
+
+    let controller = &mut ctx.accounts.controller;
+    controller.authority = ctx.accounts.authority.key();
+    controller.reward_mint = ctx.accounts.reward_mint.key();
+    controller.vault = ctx.accounts.vault.key();
+    controller.start_time = params.start_time;
+    controller.epoch_duration = params.epoch_duration;
+    controller.initial_epoch_emission = params.initial_epoch_emission;
+    controller.decay_bps = params.decay_bps;
+    controller.max_total_emission = params.max_total_emission;
+    controller.total_released = 0;
+    controller.next_epoch = 0;
+    controller.recipient_count = 0;
+    controller.bump = ctx.bumps.controller;
+
+    msg!("Emission controller initialized for {}", controller.reward_mint);
+    msg!("Epoch: {}s, initial emission: {}, decay: {} bps", params.epoch_duration, params.initial_epoch_emission, params.decay_bps);
+    msg!("Lifetime cap: {}", params.max_total_emission);
+
+    Ok(())
+}
@@ -0,0 +1,21 @@
+pub mod initialize;
+pub mod set_recipients;
+pub mod release_epoch;
+
+// Handlers are called by module path; only accounts, params and events
+// are re-exported so the per-module `handler` fns don't collide
+pub use initialize::{Initialize, InitializeParams};
+pub use set_recipients::{SetRecipients, RecipientsUpdatedEvent};
+pub use release_epoch::{ReleaseEpoch, EpochReleasedEvent};
+
+// Client account modules generated by `#[derive(Accounts)]`, which
+// `#[program]` expects at the crate root
+pub(crate) use initialize::__client_accounts_initialize;
+pub(crate) use set_recipients::__client_accounts_set_recipients;
+pub(crate) use release_epoch::__client_accounts_release_epoch;
+#[cfg(feature = "cpi")]
+pub(crate) use initialize::__cpi_client_accounts_initialize;
+#[cfg(feature = "cpi")]
+pub(crate) use set_recipients::__cpi_client_accounts_set_recipients;
+#[cfg(feature = "cpi")]
+pub(crate) use release_epoch::__cpi_client_accounts_release_epoch;
@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::state::{split_by_weight, EmissionController};
+use crate::errors::EmissionsError;
+
+/// Release the next finished epoch's emission (permissionless crank)
+///
+/// Remaining accounts: the recipients' reward vaults, writable, in the
+/// controller's recipient order.
+#[derive(Accounts)]
+pub struct ReleaseEpoch<'info> {
+    /// The controller PDA
+    #[account(
+        mut,
+        seeds = [EmissionController::SEED_PREFIX, controller.reward_mint.as_ref()],
+        bump = controller.bump
+    )]
+    pub controller: Account<'info, EmissionController>,
+
+    /// Vault holding tokens not yet emitted
+    #[account(
+        mut,
+        constraint = vault.key() == controller.vault @ EmissionsError::RecipientMismatch
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Event emitted for every released epoch
+#[event]
+pub struct EpochReleasedEvent {
+    pub controller: Pubkey,
+    pub epoch: u64,
+    pub amount: u64,
+    pub total_released: u64,
+    pub timestamp: i64,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ReleaseEpoch<'info>>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let controller = &ctx.accounts.controller;
+    let epoch = controller.next_epoch;
+
+    // Only whole epochs are released, one per call
+    require!(now >= controller.epoch_end(epoch)?, EmissionsError::EpochNotEnded);
+
+    let recipients = controller.active_recipients();
+    require!(
+        !recipients.is_empty() && ctx.remaining_accounts.len() == recipients.len(),
+        EmissionsError::RecipientMismatch
+    );
+    for (recipient, info) in recipients.iter().zip(ctx.remaining_accounts.iter()) {
+        require_keys_eq!(recipient.reward_vault, info.key(), EmissionsError::RecipientMismatch);
+    }
+
+    // Never emit more than the schedule, the lifetime cap, or the vault holds
+    let amount = controller.emission_for_epoch(epoch)?.min(ctx.accounts.vault.amount);
+    require!(amount > 0, EmissionsError::EmissionsExhausted);
+    let shares = split_by_weight(amount, recipients)?;
+
+    let reward_mint = controller.reward_mint;
+    let bump = controller.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        EmissionController::SEED_PREFIX,
+        reward_mint.as_ref(),
+        &[bump],
+    ]];
+
+    for (share, reward_vault) in shares.iter().zip(ctx.remaining_accounts.iter()) {
+        if *share == 0 {
+            continue;
+        }
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: reward_vault.clone(),
+                authority: ctx.accounts.controller.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, *share)?;
+    }
+
+    let controller = &mut ctx.accounts.controller;
+    controller.total_released = controller.total_released
+        .checked_add(amount)
+        .ok_or(EmissionsError::MathOverflow)?;
+    controller.next_epoch = epoch
+        .checked_add(1)
+        .ok_or(EmissionsError::MathOverflow)?;
+
+    emit!(EpochReleasedEvent {
+        controller: controller.key(),
+        epoch,
+        amount,
+        total_released: controller.total_released,
+        timestamp: now,
+    });
+
+    msg!("Released epoch {}: {} tokens", epoch, amount);
+
+    Ok(())
+}
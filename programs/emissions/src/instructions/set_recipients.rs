@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{validate_recipients, EmissionController, EmissionRecipient};
+use crate::errors::EmissionsError;
+
+/// Replace the list of reward vaults fed by the controller (authority only)
+#[derive(Accounts)]
+pub struct SetRecipients<'info> {
+    /// Controller authority
+    pub authority: Signer<'info>,
+
+    /// The controller PDA
+    #[account(
+        mut,
+        seeds = [EmissionController::SEED_PREFIX, controller.reward_mint.as_ref()],
+        bump = controller.bump,
+        has_one = authority @ EmissionsError::InvalidAuthority
+    )]
+    pub controller: Account<'info, EmissionController>,
+}
+
+/// Event emitted when the recipient list changes
+#[event]
+pub struct RecipientsUpdatedEvent {
+    pub controller: Pubkey,
+    pub recipients: Vec<EmissionRecipient>,
+    pub timestamp: i64,
+}
+
+pub fn handler(ctx: Context<SetRecipients>, recipients: Vec<EmissionRecipient>) -> Result<()> {
+    validate_recipients(&recipients)?;
+
+    let controller = &mut ctx.accounts.controller;
+    controller.recipients = Default::default();
+    controller.recipients[..recipients.len()].copy_from_slice(&recipients);
+    controller.recipient_count = recipients.len() as u8;
+
+    emit!(RecipientsUpdatedEvent {
+        controller: controller.key(),
+        recipients,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Emission recipients: {}", controller.recipient_count);
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+
+pub mod errors;
+pub mod instructions;
+pub mod state;
+
+use instructions::*;
+use state::*;
+
+declare_id!("KR8TivEmission11111111111111111111111111111");
+
+#[program]
+pub mod emissions {
+    use super::*;
+
+    /// Create an emission controller and its vault
+    ///
+    /// # Arguments
+    /// * `ctx` - Initialize context
+    /// * `params` - Epoch length, initial emission, decay and lifetime cap
+    pub fn initialize(ctx: Context<Initialize>, params: InitializeParams) -> Result<()> {
+        instructions::initialize::handler(ctx, params)
+    }
+
+    /// Replace the reward vaults fed each epoch (authority only)
+    ///
+    /// # Arguments
+    /// * `ctx` - SetRecipients context
+    /// * `recipients` - Reward vaults and their weights (must sum to 10000 bps)
+    pub fn set_recipients(ctx: Context<SetRecipients>, recipients: Vec<EmissionRecipient>) -> Result<()> {
+        instructions::set_recipients::handler(ctx, recipients)
+    }
+
+    /// Release the next finished epoch to the recipients (permissionless)
+    ///
+    /// # Arguments
+    /// * `ctx` - ReleaseEpoch context; remaining accounts are the recipient
+    ///   reward vaults in order
+    pub fn release_epoch<'info>(ctx: Context<'_, '_, '_, 'info, ReleaseEpoch<'info>>) -> Result<()> {
+        instructions::release_epoch::handler(ctx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scheduled_emission_declines() {
+        assert_eq!(scheduled_emission(1_000_000, 1_000, 0).unwrap(), 1_000_000);
+        assert_eq!(scheduled_emission(1_000_000, 1_000, 1).unwrap(), 900_000);
+        assert_eq!(scheduled_emission(1_000_000, 1_000, 2).unwrap(), 810_000);
+        assert_eq!(scheduled_emission(1_000_000, 1_000, 10).unwrap(), 348_678);
+        // No decay keeps a flat schedule
+        assert_eq!(scheduled_emission(1_000_000, 0, 500).unwrap(), 1_000_000);
+        // Far-future epochs round down to nothing instead of overflowing
+        assert_eq!(scheduled_emission(u64::MAX, 5_000, u64::MAX).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_emission_respects_lifetime_cap() {
+        let controller = EmissionController {
+            initial_epoch_emission: 1_000,
+            max_total_emission: 2_500,
+            total_released: 2_000,
+            ..Default::default()
+        };
+        assert_eq!(controller.emission_for_epoch(2).unwrap(), 500);
+    }
+
+    #[test]
+    fn test_split_by_weight() {
+        let recipient = |weight_bps| EmissionRecipient { reward_vault: Pubkey::new_unique(), weight_bps };
+        let recipients = [recipient(3_333), recipient(3_333), recipient(3_334)];
+        assert!(validate_recipients(&recipients).is_ok());
+
+        let shares = split_by_weight(1_000, &recipients).unwrap();
+        assert_eq!(shares.iter().sum::<u64>(), 1_000);
+        assert_eq!(shares, vec![334, 333, 333]);
+
+        assert!(validate_recipients(&[recipient(5_000)]).is_err());
+        assert!(validate_recipients(&[recipient(10_000), recipient(0)]).is_err());
+    }
+}
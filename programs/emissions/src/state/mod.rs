@@ -0,0 +1,170 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::EmissionsError;
+
+/// Basis points denominator (10000 = 100%)
+pub const BPS_DENOMINATOR: u64 = 10000;
+
+/// Fixed-point scale for the decay factor
+const DECAY_PRECISION: u128 = 1_000_000_000_000_000_000;
+
+/// Most reward vaults a controller can feed
+pub const MAX_RECIPIENTS: usize = 8;
+
+/// A reward vault receiving a share of each epoch's emission
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct EmissionRecipient {
+    /// Pool reward vault (token account of the controller's mint)
+    pub reward_vault: Pubkey,
+
+    /// Share of each epoch's emission (bps)
+    pub weight_bps: u16,
+}
+
+impl EmissionRecipient {
+    pub const LEN: usize = 32 + 2;
+}
+
+/// Releases a funded emissions vault to pool reward vaults on a declining
+/// per-epoch schedule
+#[account]
+#[derive(Default)]
+pub struct EmissionController {
+    /// Authority allowed to change the recipient list
+    pub authority: Pubkey,
+
+    /// Token mint being emitted
+    pub reward_mint: Pubkey,
+
+    /// Vault holding tokens not yet emitted
+    pub vault: Pubkey,
+
+    /// Schedule start (Unix timestamp)
+    pub start_time: i64,
+
+    /// Length of one epoch in seconds
+    pub epoch_duration: i64,
+
+    /// Emission for epoch 0
+    pub initial_epoch_emission: u64,
+
+    /// Reduction applied each epoch (bps of the previous epoch's emission)
+    pub decay_bps: u64,
+
+    /// Hard cap on everything this controller will ever release
+    pub max_total_emission: u64,
+
+    /// Total released so far
+    pub total_released: u64,
+
+    /// Next epoch to release
+    pub next_epoch: u64,
+
+    /// Recipient slots; only the first `recipient_count` are used
+    pub recipients: [EmissionRecipient; MAX_RECIPIENTS],
+
+    /// Number of recipients in use
+    pub recipient_count: u8,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+
+    /// Reserved space for future upgrades
+    pub _reserved: [u8; 32],
+}
+
+impl EmissionController {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // authority
+        32 +  // reward_mint
+        32 +  // vault
+        8 +   // start_time
+        8 +   // epoch_duration
+        8 +   // initial_epoch_emission
+        8 +   // decay_bps
+        8 +   // max_total_emission
+        8 +   // total_released
+        8 +   // next_epoch
+        EmissionRecipient::LEN * MAX_RECIPIENTS + // recipients
+        1 +   // recipient_count
+        1 +   // bump
+        32;   // _reserved
+
+    pub const SEED_PREFIX: &'static [u8] = b"emission_controller";
+
+    /// Recipients in use
+    pub fn active_recipients(&self) -> &[EmissionRecipient] {
+        &self.recipients[..self.recipient_count as usize]
+    }
+
+    /// Timestamp at which `epoch` ends
+    pub fn epoch_end(&self, epoch: u64) -> Result<i64> {
+        let epochs = i64::try_from(epoch)
+            .ok()
+            .and_then(|epoch| epoch.checked_add(1))
+            .ok_or(EmissionsError::MathOverflow)?;
+        epochs
+            .checked_mul(self.epoch_duration)
+            .and_then(|offset| self.start_time.checked_add(offset))
+            .ok_or(EmissionsError::MathOverflow.into())
+    }
+
+    /// Scheduled emission for `epoch`, limited by the lifetime cap
+    pub fn emission_for_epoch(&self, epoch: u64) -> Result<u64> {
+        let scheduled = scheduled_emission(self.initial_epoch_emission, self.decay_bps, epoch)?;
+        Ok(scheduled.min(self.max_total_emission.saturating_sub(self.total_released)))
+    }
+}
+
+/// `initial * (1 - decay_bps / 10000) ^ epoch`, rounded down
+pub fn scheduled_emission(initial: u64, decay_bps: u64, epoch: u64) -> Result<u64> {
+    require!(decay_bps <= BPS_DENOMINATOR, EmissionsError::InvalidSchedule);
+
+    // Exponentiation by squaring in 1e18 fixed point
+    let mut factor = (BPS_DENOMINATOR - decay_bps) as u128 * DECAY_PRECISION / BPS_DENOMINATOR as u128;
+    let mut result = DECAY_PRECISION;
+    let mut exp = epoch;
+    while exp > 0 && result > 0 {
+        if exp & 1 == 1 {
+            result = result * factor / DECAY_PRECISION;
+        }
+        factor = factor * factor / DECAY_PRECISION;
+        exp >>= 1;
+    }
+
+    let emission = initial as u128 * result / DECAY_PRECISION;
+    u64::try_from(emission).map_err(|_| EmissionsError::MathOverflow.into())
+}
+
+/// Split `amount` by recipient weights. Rounding dust goes to the first
+/// recipient so the full amount is always distributed.
+pub fn split_by_weight(amount: u64, recipients: &[EmissionRecipient]) -> Result<Vec<u64>> {
+    let mut shares = Vec::with_capacity(recipients.len());
+    let mut distributed: u64 = 0;
+    for recipient in recipients {
+        let share = (amount as u128 * recipient.weight_bps as u128 / BPS_DENOMINATOR as u128) as u64;
+        distributed = distributed.checked_add(share).ok_or(EmissionsError::MathOverflow)?;
+        shares.push(share);
+    }
+    if let Some(first) = shares.first_mut() {
+        *first = first
+            .checked_add(amount.saturating_sub(distributed))
+            .ok_or(EmissionsError::MathOverflow)?;
+    }
+    Ok(shares)
+}
+
+/// Reject recipient lists that don't distribute exactly 100%
+pub fn validate_recipients(recipients: &[EmissionRecipient]) -> Result<()> {
+    require!(
+        !recipients.is_empty() && recipients.len() <= MAX_RECIPIENTS,
+        EmissionsError::InvalidRecipients
+    );
+    let mut total: u64 = 0;
+    for recipient in recipients {
+        require!(recipient.weight_bps > 0, EmissionsError::InvalidRecipients);
+        total += recipient.weight_bps as u64;
+    }
+    require!(total == BPS_DENOMINATOR, EmissionsError::InvalidRecipients);
+    Ok(())
+}
@@ -0,0 +1,213 @@
+//! Typed helpers for programs that stake through CPI.
+//!
+//! Enable the `cpi` feature and use `StakeCpiAccounts`, `UnstakeCpiAccounts`
+//! and `ClaimRewardsCpiAccounts` instead of hand-building the instructions:
+//!
+//! ```ignore
+//! staking::cpi_client::StakeCpiAccounts {
+//!     user, stake_pool, program_config, pool_stats, user_stake,
+//!     user_token_account, stake_vault, token_program, memo_program,
+//!     system_program,
+//! }
+//! .stake(staking_program, amount, lock_duration, signer_seeds)?;
+//! ```
+//!
+//! Positions can be owned by a PDA of the calling program (a DAO or program
+//! treasury): pass that PDA's seeds as `signer_seeds` and it signs as
+//! `user`. Stake pays the position's rent from `user`, so a PDA owner must be
+//! a system-owned account holding lamports, not a data account.
+
+use anchor_lang::prelude::*;
+
+use crate::state::{PoolStats, ProgramConfig, StakePool, UserStake};
+
+/// Stake pool PDA for a stake mint and pool id
+pub fn stake_pool_address(stake_mint: &Pubkey, pool_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[StakePool::SEED_PREFIX, stake_mint.as_ref(), &StakePool::pool_id_seed(pool_id)],
+        &crate::ID,
+    )
+    .0
+}
+
+/// Program config PDA, passed to stake so the protocol-wide pause applies
+pub fn program_config_address() -> Pubkey {
+    Pubkey::find_program_address(&[ProgramConfig::SEED_PREFIX], &crate::ID).0
+}
+
+/// Pool statistics PDA for a stake pool
+pub fn pool_stats_address(stake_pool: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[PoolStats::SEED_PREFIX, stake_pool.as_ref()], &crate::ID).0
+}
+
+/// User stake PDA for a stake pool and owner
+pub fn user_stake_address(stake_pool: &Pubkey, user: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[UserStake::SEED_PREFIX, stake_pool.as_ref(), user.as_ref()],
+        &crate::ID,
+    )
+    .0
+}
+
+/// Stake pool vault PDA holding staked tokens
+pub fn stake_vault_address(stake_pool: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"stake_vault", stake_pool.as_ref()], &crate::ID).0
+}
+
+/// Accounts required to stake through CPI. The optional credential, hook
+/// and reward stream accounts are left out; pools that need them must be
+/// called through `crate::cpi::stake` directly.
+pub struct StakeCpiAccounts<'info> {
+    pub user: AccountInfo<'info>,
+    pub stake_pool: AccountInfo<'info>,
+    pub program_config: AccountInfo<'info>,
+    pub pool_stats: AccountInfo<'info>,
+    pub user_stake: AccountInfo<'info>,
+    pub user_token_account: AccountInfo<'info>,
+    pub stake_vault: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub memo_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+impl<'info> StakeCpiAccounts<'info> {
+    /// Convert into the generated `stake` account struct
+    pub fn into_cpi_accounts(self) -> crate::cpi::accounts::Stake<'info> {
+        crate::cpi::accounts::Stake {
+            user: self.user,
+            stake_pool: self.stake_pool,
+            program_config: self.program_config,
+            pool_stats: self.pool_stats,
+            user_stake: self.user_stake,
+            user_token_account: self.user_token_account,
+            stake_vault: self.stake_vault,
+            reward_vault: None,
+            credential_account: None,
+            hook_registry: None,
+            reward_streams: None,
+            user_reward_streams: None,
+            token_program: self.token_program,
+            memo_program: self.memo_program,
+            system_program: self.system_program,
+        }
+    }
+
+    /// Stake `amount` for `lock_duration` seconds. Pass `signer_seeds` when
+    /// `user` is a PDA of the calling program.
+    pub fn stake(
+        self,
+        staking_program: AccountInfo<'info>,
+        amount: u64,
+        lock_duration: i64,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let cpi_ctx = CpiContext::new_with_signer(
+            staking_program,
+            self.into_cpi_accounts(),
+            signer_seeds,
+        );
+        crate::cpi::stake(cpi_ctx, amount, lock_duration)
+    }
+}
+
+/// Accounts required to unstake through CPI. The optional hook, reward
+/// stream, penalty and insurance accounts are left out; pools that need them
+/// must be called through `crate::cpi::unstake` directly.
+pub struct UnstakeCpiAccounts<'info> {
+    pub user: AccountInfo<'info>,
+    pub stake_pool: AccountInfo<'info>,
+    pub program_config: AccountInfo<'info>,
+    pub user_stake: AccountInfo<'info>,
+    pub user_token_account: AccountInfo<'info>,
+    pub stake_vault: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub memo_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+impl<'info> UnstakeCpiAccounts<'info> {
+    /// Convert into the generated `unstake` account struct
+    pub fn into_cpi_accounts(self) -> crate::cpi::accounts::Unstake<'info> {
+        crate::cpi::accounts::Unstake {
+            user: self.user,
+            stake_pool: self.stake_pool,
+            program_config: self.program_config,
+            user_stake: self.user_stake,
+            user_token_account: self.user_token_account,
+            stake_vault: self.stake_vault,
+            reward_vault: None,
+            hook_registry: None,
+            reward_streams: None,
+            user_reward_streams: None,
+            stake_mint: None,
+            penalty_treasury: None,
+            insurance_fund: None,
+            insurance_vault: None,
+            token_program: self.token_program,
+            memo_program: self.memo_program,
+            system_program: self.system_program,
+        }
+    }
+
+    /// Unstake `amount`. Pass `signer_seeds` when `user` is a PDA of the
+    /// calling program.
+    pub fn unstake(
+        self,
+        staking_program: AccountInfo<'info>,
+        amount: u64,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let cpi_ctx = CpiContext::new_with_signer(
+            staking_program,
+            self.into_cpi_accounts(),
+            signer_seeds,
+        );
+        crate::cpi::unstake(cpi_ctx, amount)
+    }
+}
+
+/// Accounts required to claim rewards through CPI. The fee treasury is left
+/// out; pools that charge a claim fee must be called through
+/// `crate::cpi::claim_rewards` directly.
+pub struct ClaimRewardsCpiAccounts<'info> {
+    pub user: AccountInfo<'info>,
+    pub stake_pool: AccountInfo<'info>,
+    pub pool_stats: AccountInfo<'info>,
+    pub user_stake: AccountInfo<'info>,
+    pub user_reward_account: AccountInfo<'info>,
+    pub reward_vault: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub memo_program: AccountInfo<'info>,
+}
+
+impl<'info> ClaimRewardsCpiAccounts<'info> {
+    /// Convert into the generated `claim_rewards` account struct
+    pub fn into_cpi_accounts(self) -> crate::cpi::accounts::ClaimRewards<'info> {
+        crate::cpi::accounts::ClaimRewards {
+            user: self.user,
+            stake_pool: self.stake_pool,
+            pool_stats: self.pool_stats,
+            user_stake: self.user_stake,
+            user_reward_account: self.user_reward_account,
+            reward_vault: self.reward_vault,
+            fee_treasury: None,
+            token_program: self.token_program,
+            memo_program: self.memo_program,
+        }
+    }
+
+    /// Claim pending rewards. Pass `signer_seeds` when `user` is a PDA of
+    /// the calling program.
+    pub fn claim_rewards(
+        self,
+        staking_program: AccountInfo<'info>,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let cpi_ctx = CpiContext::new_with_signer(
+            staking_program,
+            self.into_cpi_accounts(),
+            signer_seeds,
+        );
+        crate::cpi::claim_rewards(cpi_ctx)
+    }
+}
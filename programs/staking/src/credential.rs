@@ -0,0 +1,55 @@
+//! Credential gate for KYC-restricted pools.
+//!
+//! A pool with a `credential_mint` only accepts stakers holding a
+//! non-transferable (soulbound) Token-2022 token of that mint.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    non_transferable::NonTransferableAccount, BaseStateWithExtensions, StateWithExtensions,
+};
+
+use crate::errors::StakingError;
+use crate::state::StakePool;
+
+/// Verify `user` holds the pool's credential token, if the pool requires one
+///
+/// # Arguments
+/// * `stake_pool` - The stake pool being staked into
+/// * `user` - The staker
+/// * `credential_account` - The staker's Token-2022 credential token account
+pub fn verify_credential(
+    stake_pool: &StakePool,
+    user: &Pubkey,
+    credential_account: Option<&AccountInfo>,
+) -> Result<()> {
+    if !stake_pool.requires_credential() {
+        return Ok(());
+    }
+
+    let info = credential_account.ok_or(StakingError::MissingCredential)?;
+    require_keys_eq!(
+        *info.owner,
+        spl_token_2022::ID,
+        StakingError::InvalidCredential
+    );
+
+    let data = info.try_borrow_data()?;
+    let account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)
+        .map_err(|_| StakingError::InvalidCredential)?;
+
+    require_keys_eq!(
+        account.base.mint,
+        stake_pool.credential_mint,
+        StakingError::InvalidCredential
+    );
+    require_keys_eq!(account.base.owner, *user, StakingError::InvalidCredential);
+    require!(account.base.amount > 0, StakingError::InvalidCredential);
+
+    // Soulbound: the mint must be non-transferable
+    account
+        .get_extension::<NonTransferableAccount>()
+        .map_err(|_| StakingError::InvalidCredential)?;
+
+    Ok(())
+}
@@ -31,4 +31,217 @@ pub enum StakingError {
 
     #[msg("Invalid mint address")]
     InvalidMint,
+
+    #[msg("Stake pool does not accept native SOL")]
+    NotNativePool,
+
+    #[msg("Shared vault requires matching stake and reward mints")]
+    SharedVaultMintMismatch,
+
+    #[msg("Hook registry is full")]
+    HookRegistryFull,
+
+    #[msg("Hook program is already registered")]
+    HookAlreadyRegistered,
+
+    #[msg("Hook program is not registered")]
+    HookNotFound,
+
+    #[msg("Hook registry or hook program account missing")]
+    MissingHookAccount,
+
+    #[msg("Invalid pool configuration value")]
+    InvalidConfig,
+
+    #[msg("Position has no expired lock bonus to remove")]
+    NothingToKick,
+
+    #[msg("Claims are not allowed at this time")]
+    ClaimWindowClosed,
+
+    #[msg("User stake account is frozen")]
+    AccountFrozen,
+
+    #[msg("This pool requires a credential token account")]
+    MissingCredential,
+
+    #[msg("Credential token is missing, empty, or not soulbound")]
+    InvalidCredential,
+
+    #[msg("Stake amount is below the pool minimum")]
+    BelowMinimumStake,
+
+    #[msg("Merkle proof does not match the snapshot root")]
+    InvalidMerkleProof,
+
+    #[msg("Claim would exceed the snapshot's funded total")]
+    SnapshotOverclaimed,
+
+    #[msg("Reward stream accounts are required for this pool")]
+    MissingRewardStreamAccount,
+
+    #[msg("An active reward stream already exists for this mint")]
+    RewardStreamExists,
+
+    #[msg("All reward stream slots are in use")]
+    RewardStreamsFull,
+
+    #[msg("Reward stream not found")]
+    RewardStreamNotFound,
+
+    #[msg("Position already carries its current reward multiplier")]
+    TierUnchanged,
+
+    #[msg("Early unstake is not enabled for this pool")]
+    EarlyUnstakeDisabled,
+
+    #[msg("Pool metadata field is too long")]
+    MetadataTooLong,
+
+    #[msg("Tokens of this mint belong to the pool and can't be recovered")]
+    ProtectedMint,
+
+    #[msg("Reward rate exceeds the sanity cap")]
+    RewardRateTooHigh,
+
+    #[msg("Lock durations must be greater than zero")]
+    InvalidLockBounds,
+
+    #[msg("Stake mint has unexpected decimals")]
+    InvalidMintDecimals,
+
+    #[msg("Not enough loyalty points")]
+    InsufficientPoints,
+
+    #[msg("Penalty split requires its burn, treasury or insurance accounts")]
+    MissingPenaltyAccount,
+
+    #[msg("Insurance fund balance is too low")]
+    InsufficientInsuranceFunds,
+
+    #[msg("Crank accounts must be (user_stake, reward account) pairs")]
+    InvalidCrankAccounts,
+
+    #[msg("Pool does not use epoch-based rewards")]
+    NotEpochMode,
+
+    #[msg("No reward epoch has ended since the last settlement")]
+    EpochNotEnded,
+
+    #[msg("Wait for the pool's action cooldown before staking or unstaking again")]
+    ActionCooldown,
+
+    #[msg("Stake vault holds no surplus")]
+    NoVaultSurplus,
+
+    #[msg("Shared-vault pools already pay rewards from stake vault surplus")]
+    SharedVaultSurplus,
+
+    #[msg("Pool accounting invariant violated")]
+    InvariantViolated,
+
+    #[msg("Stake account belongs to a different owner")]
+    OwnerMismatch,
+
+    #[msg("Stake account belongs to a different pool")]
+    StakePoolMismatch,
+
+    #[msg("Stake vault does not match the pool")]
+    InvalidStakeVault,
+
+    #[msg("Reward vault does not match the pool")]
+    InvalidRewardVault,
+
+    #[msg("Token account is not owned by the expected wallet")]
+    TokenAccountOwnerMismatch,
+
+    #[msg("Feature is disabled in the program config")]
+    FeatureDisabled,
+
+    #[msg("Fee treasury account required to pay the protocol claim fee")]
+    MissingFeeAccount,
+
+    #[msg("Snapshot has no voting power root")]
+    NoVotingPowerRoot,
+
+    #[msg("Too many reward rate changes already scheduled")]
+    RateChangeQueueFull,
+
+    #[msg("Rate change must take effect in the future")]
+    RateChangeInPast,
+
+    #[msg("No unclaimed milestone bonus has been reached")]
+    NoMilestoneReached,
+
+    #[msg("Milestone bonus vault has insufficient funds")]
+    InsufficientBonusFunds,
+
+    #[msg("Claim accounts must be six-account groups for distinct pools")]
+    InvalidClaimAccounts,
+
+    #[msg("Account is not a stake pool owned by this program")]
+    InvalidPoolAccount,
+
+    #[msg("Pool already uses the current layout")]
+    AlreadyMigrated,
+
+    #[msg("Pool has no target APR")]
+    AprTargetDisabled,
+
+    #[msg("Reward rate was retargeted too recently")]
+    RetargetTooSoon,
+
+    #[msg("Reward vault account is required for fixed-APR pools")]
+    MissingRewardVault,
+
+    #[msg("Reward vault can't cover the guaranteed rewards for this stake")]
+    InsufficientRewardReserve,
+
+    #[msg("Reward rate can't be changed on a fixed-APR pool")]
+    FixedRatePool,
+
+    #[msg("Pool is not issuing certificates")]
+    CertificatesClosed,
+
+    #[msg("Certificate term is outside the pool's offered range")]
+    InvalidCertificateTerm,
+
+    #[msg("Certificate has not reached maturity")]
+    CertificateNotMatured,
+
+    #[msg("Lending program is not whitelisted for collateral")]
+    LendingProgramNotWhitelisted,
+
+    #[msg("Signer is not the lending program's collateral authority")]
+    InvalidCollateralAuthority,
+
+    #[msg("Position is locked as collateral")]
+    PositionCollateralized,
+
+    #[msg("Position is not locked as collateral")]
+    PositionNotCollateralized,
+
+    #[msg("Minimum interval since the last claim has not passed")]
+    ClaimTooSoon,
+
+    #[msg("Mint has an active freeze authority")]
+    FreezableMint,
+
+    #[msg("Account is not the pool's boost source pool")]
+    InvalidBoostSource,
+
+    #[msg("Operator position rewards must be paid into its escrow")]
+    OperatorEscrowRequired,
+
+    #[msg("Operator root totals don't match the position")]
+    InvalidOperatorTotals,
+
+    #[msg("Account is not frozen")]
+    AccountNotFrozen,
+
+    #[msg("Account is not a user stake owned by this program")]
+    InvalidUserStakeAccount,
+
+    #[msg("Position already uses the current layout")]
+    PositionAlreadyMigrated,
 }
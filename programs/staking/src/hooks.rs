@@ -0,0 +1,124 @@
+//! CPI notifications to whitelisted hook programs on stake/unstake.
+//!
+//! Hook programs implement a single Anchor-style instruction,
+//! `on_stake_changed(kind, user, amount, new_tier)`, and receive the stake
+//! pool PDA as a signer so they can authenticate the caller.
+//!
+//! Unstake hooks are best effort. A failing CPI aborts the transaction, so a
+//! hook that's left out of the accounts is skipped instead: a broken or
+//! hostile hook can never block withdrawals.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+
+use crate::errors::StakingError;
+use crate::state::{HookRegistry, StakePool, StakingTier};
+
+/// Name of the instruction invoked on hook programs
+pub const HOOK_INSTRUCTION: &str = "on_stake_changed";
+
+/// Which operation triggered the hook
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HookKind {
+    Stake,
+    Unstake,
+}
+
+/// Arguments passed to `on_stake_changed`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct HookPayload {
+    pub kind: HookKind,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub new_tier: StakingTier,
+}
+
+/// Anchor instruction discriminator for `on_stake_changed`
+pub fn hook_discriminator() -> [u8; 8] {
+    let preimage = format!("global:{}", HOOK_INSTRUCTION);
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hash(preimage.as_bytes()).to_bytes()[..8]);
+    discriminator
+}
+
+/// Notify every registered hook program of a stake change
+///
+/// Hook program accounts must be supplied in `remaining_accounts`. On
+/// unstake, a missing registry or hook program is logged and skipped.
+/// Does nothing when hooks are disabled for the pool.
+pub fn invoke_hooks<'info>(
+    stake_pool: &Account<'info, StakePool>,
+    hook_registry: Option<&Account<'info, HookRegistry>>,
+    remaining_accounts: &[AccountInfo<'info>],
+    user: &AccountInfo<'info>,
+    payload: HookPayload,
+) -> Result<()> {
+    if !stake_pool.hooks_enabled {
+        return Ok(());
+    }
+
+    let required = payload.kind == HookKind::Stake;
+    let registry = match hook_registry {
+        Some(registry) => registry,
+        None => {
+            require!(!required, StakingError::MissingHookAccount);
+            msg!("Hook registry not supplied; unstake hooks skipped");
+            return Ok(());
+        }
+    };
+    require_keys_eq!(
+        registry.stake_pool,
+        stake_pool.key(),
+        StakingError::MissingHookAccount
+    );
+
+    let mut data = hook_discriminator().to_vec();
+    payload.serialize(&mut data)?;
+
+    let stake_mint_key = stake_pool.stake_mint;
+    let pool_id_seed = stake_pool.id_seed();
+    let pool_bump = stake_pool.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        StakePool::SEED_PREFIX,
+        stake_mint_key.as_ref(),
+        &pool_id_seed,
+        &[pool_bump],
+    ]];
+
+    for hook_program in registry.active_hooks() {
+        let program_info = match remaining_accounts
+            .iter()
+            .find(|info| info.key == hook_program && info.executable)
+        {
+            Some(program_info) => program_info,
+            None => {
+                require!(!required, StakingError::MissingHookAccount);
+                msg!("Hook {} not supplied; skipped", hook_program);
+                continue;
+            }
+        };
+
+        let ix = Instruction {
+            program_id: *hook_program,
+            accounts: vec![
+                AccountMeta::new_readonly(stake_pool.key(), true),
+                AccountMeta::new_readonly(user.key(), false),
+            ],
+            data: data.clone(),
+        };
+
+        invoke_signed(
+            &ix,
+            &[
+                stake_pool.to_account_info(),
+                user.clone(),
+                program_info.clone(),
+            ],
+            signer_seeds,
+        )?;
+    }
+
+    Ok(())
+}
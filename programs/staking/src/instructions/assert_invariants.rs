@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::state::{StakePool, UserStake};
+use crate::errors::StakingError;
+
+/// Check pool accounting invariants, failing loudly if any is violated
+#[derive(Accounts)]
+pub struct AssertInvariants<'info> {
+    /// The stake pool
+    #[account(
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// Pool's stake vault
+    #[account(
+        constraint = stake_vault.key() == stake_pool.stake_vault @ StakingError::InvalidStakeVault
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, AssertInvariants<'info>>) -> Result<()> {
+    let stake_pool = &ctx.accounts.stake_pool;
+
+    if let Err(violation) = stake_pool.check_invariants(ctx.accounts.stake_vault.amount) {
+        msg!("Invariant violated for pool {}: {}", stake_pool.key(), violation);
+        return err!(StakingError::InvariantViolated);
+    }
+
+    for account in ctx.remaining_accounts {
+        let user_stake = Account::<UserStake>::try_from(account)?;
+        require_keys_eq!(user_stake.stake_pool, stake_pool.key(), StakingError::InvalidAuthority);
+
+        if let Err(violation) = user_stake.check_invariants(stake_pool) {
+            msg!("Invariant violated for position {}: {}", account.key(), violation);
+            return err!(StakingError::InvariantViolated);
+        }
+    }
+
+    msg!("Invariants hold for pool and {} positions", ctx.remaining_accounts.len());
+
+    Ok(())
+}
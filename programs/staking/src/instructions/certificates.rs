@@ -0,0 +1,527 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::memo::Memo;
+
+use crate::state::{Certificate, CertificateTerms, ProgramConfig, StakePool};
+use crate::memo::{tag_transfer, MemoAction};
+use crate::errors::StakingError;
+use crate::constants;
+
+/// Open a pool's certificate product and create its escrow vaults
+/// (pool authority only)
+#[derive(Accounts)]
+pub struct InitializeCertificates<'info> {
+    /// Pool authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The stake pool
+    #[account(
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump,
+        has_one = authority @ StakingError::InvalidAuthority
+    )]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    /// The certificate terms to create
+    #[account(
+        init,
+        payer = authority,
+        space = CertificateTerms::LEN,
+        seeds = [CertificateTerms::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump
+    )]
+    pub certificate_terms: Box<Account<'info, CertificateTerms>>,
+
+    /// The stake mint (principal is escrowed in it)
+    #[account(address = stake_pool.stake_mint @ StakingError::InvalidMint)]
+    pub stake_mint: Box<Account<'info, Mint>>,
+
+    /// The reward mint (payouts are escrowed in it)
+    #[account(address = stake_pool.reward_mint @ StakingError::InvalidMint)]
+    pub reward_mint: Box<Account<'info, Mint>>,
+
+    /// Vault escrowing certificate principal
+    #[account(
+        init,
+        payer = authority,
+        token::mint = stake_mint,
+        token::authority = stake_pool,
+        seeds = [CertificateTerms::PRINCIPAL_VAULT_SEED_PREFIX, stake_pool.key().as_ref()],
+        bump
+    )]
+    pub principal_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Vault escrowing certificate payouts
+    #[account(
+        init,
+        payer = authority,
+        token::mint = reward_mint,
+        token::authority = stake_pool,
+        seeds = [CertificateTerms::PAYOUT_VAULT_SEED_PREFIX, stake_pool.key().as_ref()],
+        bump
+    )]
+    pub payout_vault: Box<Account<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Change the terms offered to new certificates (pool authority only).
+/// Certificates already issued keep theirs.
+#[derive(Accounts)]
+pub struct SetCertificateTerms<'info> {
+    /// Pool authority
+    pub authority: Signer<'info>,
+
+    /// The stake pool
+    #[account(
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump,
+        has_one = authority @ StakingError::InvalidAuthority
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// The pool's certificate terms
+    #[account(
+        mut,
+        seeds = [CertificateTerms::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump = certificate_terms.bump
+    )]
+    pub certificate_terms: Account<'info, CertificateTerms>,
+}
+
+/// Buy a fixed-term certificate: escrow the principal and its fixed payout
+#[derive(Accounts)]
+#[instruction(certificate_id: u64)]
+pub struct IssueCertificate<'info> {
+    /// Certificate buyer
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// The stake pool
+    #[account(
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    /// Program config PDA carrying the protocol-wide pause (may not exist yet)
+    /// CHECK: address checked by seeds; read by `ProgramConfig::protocol_paused`
+    #[account(seeds = [ProgramConfig::SEED_PREFIX], bump)]
+    pub program_config: UncheckedAccount<'info>,
+
+    /// The pool's certificate terms
+    #[account(
+        mut,
+        seeds = [CertificateTerms::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump = certificate_terms.bump
+    )]
+    pub certificate_terms: Box<Account<'info, CertificateTerms>>,
+
+    /// The certificate to create
+    #[account(
+        init,
+        payer = user,
+        space = Certificate::LEN,
+        seeds = [
+            Certificate::SEED_PREFIX,
+            stake_pool.key().as_ref(),
+            user.key().as_ref(),
+            &certificate_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub certificate: Box<Account<'info, Certificate>>,
+
+    /// User's stake token account paying the principal
+    #[account(
+        mut,
+        constraint = user_token_account.mint == stake_pool.stake_mint @ StakingError::InvalidMint,
+        constraint = user_token_account.owner == user.key() @ StakingError::TokenAccountOwnerMismatch
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Vault escrowing certificate principal
+    #[account(
+        mut,
+        address = certificate_terms.principal_vault @ StakingError::InvalidStakeVault
+    )]
+    pub principal_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Pool's reward vault funding the payout
+    #[account(
+        mut,
+        constraint = reward_vault.key() == stake_pool.reward_vault @ StakingError::InvalidRewardVault
+    )]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Vault escrowing certificate payouts
+    #[account(
+        mut,
+        address = certificate_terms.payout_vault @ StakingError::InvalidRewardVault
+    )]
+    pub payout_vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub memo_program: Program<'info, Memo>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Redeem a matured certificate for its principal and payout
+#[derive(Accounts)]
+pub struct RedeemCertificate<'info> {
+    /// Certificate holder; receives the certificate's rent
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// The stake pool
+    #[account(
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    /// Program config PDA carrying the protocol-wide pause (may not exist yet)
+    /// CHECK: address checked by seeds; read by `ProgramConfig::protocol_paused`
+    #[account(seeds = [ProgramConfig::SEED_PREFIX], bump)]
+    pub program_config: UncheckedAccount<'info>,
+
+    /// The pool's certificate terms
+    #[account(
+        mut,
+        seeds = [CertificateTerms::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump = certificate_terms.bump
+    )]
+    pub certificate_terms: Box<Account<'info, CertificateTerms>>,
+
+    /// The certificate to redeem
+    #[account(
+        mut,
+        close = user,
+        seeds = [
+            Certificate::SEED_PREFIX,
+            stake_pool.key().as_ref(),
+            user.key().as_ref(),
+            &certificate.certificate_id.to_le_bytes()
+        ],
+        bump = certificate.bump,
+        has_one = stake_pool @ StakingError::StakePoolMismatch,
+        constraint = certificate.owner == user.key() @ StakingError::OwnerMismatch
+    )]
+    pub certificate: Box<Account<'info, Certificate>>,
+
+    /// User's stake token account receiving the principal
+    #[account(
+        mut,
+        constraint = user_token_account.mint == stake_pool.stake_mint @ StakingError::InvalidMint,
+        constraint = user_token_account.owner == user.key() @ StakingError::TokenAccountOwnerMismatch
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// User's reward token account receiving the payout
+    #[account(
+        mut,
+        constraint = user_reward_account.mint == stake_pool.reward_mint @ StakingError::InvalidMint,
+        constraint = user_reward_account.owner == user.key() @ StakingError::TokenAccountOwnerMismatch
+    )]
+    pub user_reward_account: Box<Account<'info, TokenAccount>>,
+
+    /// Vault escrowing certificate principal
+    #[account(
+        mut,
+        address = certificate_terms.principal_vault @ StakingError::InvalidStakeVault
+    )]
+    pub principal_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Vault escrowing certificate payouts
+    #[account(
+        mut,
+        address = certificate_terms.payout_vault @ StakingError::InvalidRewardVault
+    )]
+    pub payout_vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub memo_program: Program<'info, Memo>,
+}
+
+/// Event emitted when a certificate is issued
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CertificateIssuedEvent {
+    pub user: Pubkey,
+    pub stake_pool: Pubkey,
+    pub certificate: Pubkey,
+    pub principal: u64,
+    pub payout: u64,
+    pub apr_bps: u64,
+    pub maturity_time: i64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a certificate is redeemed
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CertificateRedeemedEvent {
+    pub user: Pubkey,
+    pub stake_pool: Pubkey,
+    pub certificate: Pubkey,
+    pub principal: u64,
+    pub payout: u64,
+    pub timestamp: i64,
+}
+
+/// Check a set of certificate terms
+fn validate_terms(apr_bps: u64, min_term: i64, max_term: i64) -> Result<()> {
+    require!(apr_bps <= constants::MAX_TARGET_APR_BPS, StakingError::InvalidConfig);
+    require!(min_term > 0 && max_term >= min_term, StakingError::InvalidConfig);
+    Ok(())
+}
+
+pub fn initialize_certificates_handler(
+    ctx: Context<InitializeCertificates>,
+    apr_bps: u64,
+    min_term: i64,
+    max_term: i64,
+) -> Result<()> {
+    validate_terms(apr_bps, min_term, max_term)?;
+
+    let certificate_terms = &mut ctx.accounts.certificate_terms;
+    certificate_terms.stake_pool = ctx.accounts.stake_pool.key();
+    certificate_terms.principal_vault = ctx.accounts.principal_vault.key();
+    certificate_terms.payout_vault = ctx.accounts.payout_vault.key();
+    certificate_terms.apr_bps = apr_bps;
+    certificate_terms.min_term = min_term;
+    certificate_terms.max_term = max_term;
+    certificate_terms.bump = ctx.bumps.certificate_terms;
+
+    msg!(
+        "Certificates for pool {}: {} bps for {} to {} seconds",
+        certificate_terms.stake_pool,
+        apr_bps,
+        min_term,
+        max_term
+    );
+
+    Ok(())
+}
+
+pub fn set_terms_handler(
+    ctx: Context<SetCertificateTerms>,
+    apr_bps: u64,
+    min_term: i64,
+    max_term: i64,
+) -> Result<()> {
+    validate_terms(apr_bps, min_term, max_term)?;
+
+    let certificate_terms = &mut ctx.accounts.certificate_terms;
+    certificate_terms.apr_bps = apr_bps;
+    certificate_terms.min_term = min_term;
+    certificate_terms.max_term = max_term;
+
+    msg!("Certificate terms: {} bps for {} to {} seconds", apr_bps, min_term, max_term);
+
+    Ok(())
+}
+
+pub fn issue_handler(
+    ctx: Context<IssueCertificate>,
+    certificate_id: u64,
+    principal: u64,
+    term: i64,
+) -> Result<()> {
+    let stake_pool = &ctx.accounts.stake_pool;
+    let certificate_terms = &ctx.accounts.certificate_terms;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(
+        !stake_pool.is_paused(ProgramConfig::protocol_paused(&ctx.accounts.program_config)?),
+        StakingError::PoolPaused
+    );
+    require!(certificate_terms.apr_bps > 0, StakingError::CertificatesClosed);
+    require!(principal > 0, StakingError::InvalidAmount);
+    require!(
+        principal >= stake_pool.min_stake_amount,
+        StakingError::BelowMinimumStake
+    );
+    require!(certificate_terms.accepts_term(term), StakingError::InvalidCertificateTerm);
+
+    let payout = certificate_terms
+        .payout_for(principal, term)
+        .ok_or(StakingError::MathOverflow)?;
+    require!(payout > 0, StakingError::InvalidAmount);
+    // Only rewards nobody else has been promised can back a certificate
+    require!(
+        payout <= stake_pool.unreserved_rewards(ctx.accounts.reward_vault.amount),
+        StakingError::InsufficientRewardReserve
+    );
+    let maturity_time = now.checked_add(term).ok_or(StakingError::MathOverflow)?;
+
+    let certificate = &mut ctx.accounts.certificate;
+    certificate.owner = ctx.accounts.user.key();
+    certificate.stake_pool = stake_pool.key();
+    certificate.certificate_id = certificate_id;
+    certificate.principal = principal;
+    certificate.payout = payout;
+    certificate.apr_bps = certificate_terms.apr_bps;
+    certificate.issued_at = now;
+    certificate.maturity_time = maturity_time;
+    certificate.bump = ctx.bumps.certificate;
+
+    // Escrow the principal
+    let principal_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.user_token_account.to_account_info(),
+            to: ctx.accounts.principal_vault.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    );
+    token::transfer(principal_ctx, principal)?;
+    tag_transfer(
+        &ctx.accounts.memo_program,
+        MemoAction::Stake,
+        &stake_pool.key(),
+        &ctx.accounts.user.key(),
+    )?;
+
+    // Escrow the payout out of the reward vault via PDA signer
+    let stake_mint_key = stake_pool.stake_mint;
+    let pool_id_seed = stake_pool.id_seed();
+    let pool_bump = stake_pool.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        StakePool::SEED_PREFIX,
+        stake_mint_key.as_ref(),
+        &pool_id_seed,
+        &[pool_bump],
+    ]];
+
+    let payout_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.reward_vault.to_account_info(),
+            to: ctx.accounts.payout_vault.to_account_info(),
+            authority: stake_pool.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(payout_ctx, payout)?;
+
+    let certificate_terms = &mut ctx.accounts.certificate_terms;
+    certificate_terms.outstanding_principal = certificate_terms.outstanding_principal
+        .checked_add(principal)
+        .ok_or(StakingError::MathOverflow)?;
+    certificate_terms.outstanding_payout = certificate_terms.outstanding_payout
+        .checked_add(payout)
+        .ok_or(StakingError::MathOverflow)?;
+    certificate_terms.certificates_issued = certificate_terms.certificates_issued
+        .checked_add(1)
+        .ok_or(StakingError::MathOverflow)?;
+
+    emit!(CertificateIssuedEvent {
+        user: ctx.accounts.user.key(),
+        stake_pool: ctx.accounts.stake_pool.key(),
+        certificate: ctx.accounts.certificate.key(),
+        principal,
+        payout,
+        apr_bps: ctx.accounts.certificate.apr_bps,
+        maturity_time,
+        timestamp: now,
+    });
+
+    msg!(
+        "Issued certificate for {} paying {} at {}",
+        principal,
+        payout,
+        maturity_time
+    );
+
+    Ok(())
+}
+
+pub fn redeem_handler(ctx: Context<RedeemCertificate>) -> Result<()> {
+    let stake_pool = &ctx.accounts.stake_pool;
+    let certificate = &ctx.accounts.certificate;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(
+        stake_pool.unstake_allowed(ProgramConfig::protocol_paused(&ctx.accounts.program_config)?),
+        StakingError::PoolPaused
+    );
+    require!(certificate.is_mature(now), StakingError::CertificateNotMatured);
+
+    let principal = certificate.principal;
+    let payout = certificate.payout;
+
+    let stake_mint_key = stake_pool.stake_mint;
+    let pool_id_seed = stake_pool.id_seed();
+    let pool_bump = stake_pool.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        StakePool::SEED_PREFIX,
+        stake_mint_key.as_ref(),
+        &pool_id_seed,
+        &[pool_bump],
+    ]];
+
+    let principal_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.principal_vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: stake_pool.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(principal_ctx, principal)?;
+    tag_transfer(
+        &ctx.accounts.memo_program,
+        MemoAction::Unstake,
+        &stake_pool.key(),
+        &ctx.accounts.user.key(),
+    )?;
+
+    let payout_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.payout_vault.to_account_info(),
+            to: ctx.accounts.user_reward_account.to_account_info(),
+            authority: stake_pool.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(payout_ctx, payout)?;
+
+    let certificate_terms = &mut ctx.accounts.certificate_terms;
+    certificate_terms.outstanding_principal = certificate_terms.outstanding_principal.saturating_sub(principal);
+    certificate_terms.outstanding_payout = certificate_terms.outstanding_payout.saturating_sub(payout);
+
+    emit!(CertificateRedeemedEvent {
+        user: ctx.accounts.user.key(),
+        stake_pool: ctx.accounts.stake_pool.key(),
+        certificate: ctx.accounts.certificate.key(),
+        principal,
+        payout,
+        timestamp: now,
+    });
+
+    msg!("Redeemed certificate: {} principal, {} payout", principal, payout);
+
+    Ok(())
+}
@@ -0,0 +1,249 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::memo::Memo;
+
+use crate::state::{HookRegistry, PoolStats, ProgramConfig, RewardStreams, StakePool, UserRewardStreams, UserStake};
+use crate::memo::{tag_transfer, MemoAction};
+use crate::errors::StakingError;
+use crate::constants;
+use crate::streams::{sync_reward_streams, WeightChange};
+use crate::hooks::{invoke_hooks, HookKind, HookPayload};
+
+use super::claim_rewards::{pay_owed_rewards, ClaimEvent, PayoutAccounts};
+use super::unstake::{emit_unstake_event, process_unstake};
+
+/// Claim rewards and unstake in one instruction
+#[derive(Accounts)]
+pub struct ClaimAndUnstake<'info> {
+    /// User claiming and unstaking
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// The stake pool
+    #[account(
+        mut,
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    /// Program config PDA carrying the protocol-wide pause (may not exist yet)
+    /// CHECK: address checked by seeds; read by `ProgramConfig::protocol_paused`
+    #[account(seeds = [ProgramConfig::SEED_PREFIX], bump)]
+    pub program_config: UncheckedAccount<'info>,
+
+    /// The pool's statistics account
+    #[account(
+        mut,
+        seeds = [PoolStats::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump = pool_stats.bump
+    )]
+    pub pool_stats: Box<Account<'info, PoolStats>>,
+
+    /// User's stake account
+    #[account(
+        mut,
+        seeds = [UserStake::SEED_PREFIX, stake_pool.key().as_ref(), user.key().as_ref()],
+        bump = user_stake.bump,
+        has_one = stake_pool @ StakingError::StakePoolMismatch,
+        constraint = user_stake.owner == user.key() @ StakingError::OwnerMismatch
+    )]
+    pub user_stake: Box<Account<'info, UserStake>>,
+
+    /// User's token account to receive unstaked tokens
+    #[account(
+        mut,
+        constraint = user_token_account.mint == stake_pool.stake_mint @ StakingError::InvalidMint,
+        constraint = user_token_account.owner == user.key() @ StakingError::TokenAccountOwnerMismatch
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Pool's stake vault
+    #[account(
+        mut,
+        constraint = stake_vault.key() == stake_pool.stake_vault @ StakingError::InvalidStakeVault
+    )]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+
+    /// User's reward token account
+    #[account(
+        mut,
+        constraint = user_reward_account.mint == stake_pool.reward_mint @ StakingError::InvalidMint,
+        constraint = user_reward_account.owner == user.key() @ StakingError::TokenAccountOwnerMismatch
+    )]
+    pub user_reward_account: Box<Account<'info, TokenAccount>>,
+
+    /// Pool's reward vault
+    #[account(
+        mut,
+        constraint = reward_vault.key() == stake_pool.reward_vault @ StakingError::InvalidRewardVault
+    )]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Protocol treasury's reward token account (required when the pool
+    /// charges a claim fee)
+    #[account(
+        mut,
+        constraint = fee_treasury.mint == stake_pool.reward_mint @ StakingError::InvalidMint,
+        constraint = fee_treasury.owner == constants::TREASURY_ADDRESS @ StakingError::TokenAccountOwnerMismatch
+    )]
+    pub fee_treasury: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// The pool's hook registry (unstake hooks are skipped without it)
+    #[account(
+        seeds = [HookRegistry::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump = hook_registry.bump
+    )]
+    pub hook_registry: Option<Account<'info, HookRegistry>>,
+
+    /// The pool's extra reward streams (required once the pool has streams)
+    #[account(
+        mut,
+        seeds = [RewardStreams::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump = reward_streams.bump
+    )]
+    pub reward_streams: Option<Box<Account<'info, RewardStreams>>>,
+
+    /// User's extra reward stream positions (required once the pool has streams)
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserRewardStreams::LEN,
+        seeds = [UserRewardStreams::SEED_PREFIX, stake_pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_reward_streams: Option<Box<Account<'info, UserRewardStreams>>>,
+
+    pub token_program: Program<'info, Token>,
+    pub memo_program: Program<'info, Memo>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ClaimAndUnstake<'info>>,
+    amount: u64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let user_key = ctx.accounts.user.key();
+
+    let total_weighted_before = ctx.accounts.stake_pool.total_weighted_stake;
+    let user_weighted_before = ctx.accounts.user_stake.weighted_stake;
+
+    // Settles pending rewards into owed_rewards before the stake shrinks
+    let weighted_to_remove = process_unstake(
+        &mut ctx.accounts.stake_pool,
+        &mut ctx.accounts.user_stake,
+        ProgramConfig::protocol_paused(&ctx.accounts.program_config)?,
+        amount,
+        now,
+        true,
+    )?;
+
+    sync_reward_streams(
+        &ctx.accounts.stake_pool,
+        ctx.accounts.reward_streams.as_deref_mut(),
+        ctx.accounts.user_reward_streams.as_deref_mut(),
+        user_key,
+        ctx.bumps.user_reward_streams,
+        WeightChange {
+            total_weighted_before,
+            user_weighted_before,
+            user_weighted_after: ctx.accounts.user_stake.weighted_stake,
+        },
+        now,
+    )?;
+
+    // Return the principal first so a shared vault's reward balance is
+    // measured without it
+    let stake_pool = &ctx.accounts.stake_pool;
+    let stake_mint_key = stake_pool.stake_mint;
+    let pool_id_seed = stake_pool.id_seed();
+    let pool_bump = stake_pool.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        StakePool::SEED_PREFIX,
+        stake_mint_key.as_ref(),
+        &pool_id_seed,
+        &[pool_bump],
+    ]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.stake_vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: stake_pool.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)?;
+    tag_transfer(
+        &ctx.accounts.memo_program,
+        MemoAction::Unstake,
+        &ctx.accounts.stake_pool.key(),
+        &ctx.accounts.user.key(),
+    )?;
+    ctx.accounts.reward_vault.reload()?;
+
+    // Pay what the vault can cover; anything else stays owed and can be
+    // claimed later even if the position is now empty. Outside the claim
+    // window, or within the claim interval, the rewards simply stay owed.
+    let can_claim = ctx.accounts.stake_pool.is_claim_window_open(now)
+        && ctx.accounts.user_stake.claim_interval_elapsed(ctx.accounts.stake_pool.min_claim_interval, now);
+    let claimed = if can_claim {
+        pay_owed_rewards(
+            &mut ctx.accounts.stake_pool,
+            &mut ctx.accounts.user_stake,
+            &mut ctx.accounts.pool_stats,
+            PayoutAccounts {
+                reward_vault: &ctx.accounts.reward_vault,
+                user_reward_account: &ctx.accounts.user_reward_account,
+                fee_treasury: ctx.accounts.fee_treasury.as_deref(),
+                token_program: &ctx.accounts.token_program,
+                memo_program: &ctx.accounts.memo_program,
+            },
+            now,
+        )?
+    } else {
+        0
+    };
+
+    invoke_hooks(
+        &ctx.accounts.stake_pool,
+        ctx.accounts.hook_registry.as_ref(),
+        ctx.remaining_accounts,
+        &ctx.accounts.user.to_account_info(),
+        HookPayload {
+            kind: HookKind::Unstake,
+            user: user_key,
+            amount,
+            new_tier: ctx.accounts.user_stake.effective_tier(now),
+        },
+    )?;
+
+    if claimed > 0 {
+        emit!(ClaimEvent {
+            user: user_key,
+            stake_pool: ctx.accounts.stake_pool.key(),
+            amount: claimed,
+            tier: ctx.accounts.user_stake.effective_tier(now),
+            tier_multiplier_applied: ctx.accounts.user_stake.applied_multiplier_bps,
+            total_claimed: ctx.accounts.user_stake.total_claimed,
+            timestamp: now,
+        });
+        msg!("Claimed {} reward tokens", claimed);
+    }
+
+    emit_unstake_event(
+        &ctx.accounts.stake_pool,
+        &ctx.accounts.user_stake,
+        amount,
+        weighted_to_remove,
+        now,
+    );
+
+    Ok(())
+}
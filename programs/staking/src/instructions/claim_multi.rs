@@ -0,0 +1,168 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use anchor_spl::memo::Memo;
+
+use crate::state::{PoolStats, StakePool, UserStake};
+use crate::errors::StakingError;
+use crate::constants;
+use crate::{update_rewards, settle_rewards};
+
+use super::claim_rewards::{pay_owed_rewards, ClaimEvent, PayoutAccounts};
+
+/// Accounts per pool in `claim_multi`'s remaining accounts
+pub const CLAIM_MULTI_GROUP_LEN: usize = 6;
+
+/// Claim rewards from several pools in one transaction
+///
+/// Remaining accounts are groups of `(stake_pool, pool_stats, user_stake,
+/// reward_vault, user_reward_account, fee_treasury)`, all writable. Pass the
+/// staking program id as `fee_treasury` for pools without a claim fee.
+/// Pools with nothing owed, or claimed within their minimum claim interval,
+/// are skipped.
+#[derive(Accounts)]
+pub struct ClaimMulti<'info> {
+    /// User claiming rewards
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub memo_program: Program<'info, Memo>,
+}
+
+/// Event emitted after a multi-pool claim
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MultiClaimEvent {
+    pub user: Pubkey,
+    pub pools: u32,
+    pub pools_claimed: u32,
+    pub total_claimed: u64,
+    pub timestamp: i64,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimMulti<'info>>) -> Result<()> {
+    let clock = Clock::get()?;
+    let user = ctx.accounts.user.key();
+    let remaining = ctx.remaining_accounts;
+    require!(
+        !remaining.is_empty() && remaining.len().is_multiple_of(CLAIM_MULTI_GROUP_LEN),
+        StakingError::InvalidClaimAccounts
+    );
+
+    // A pool listed twice would be written back from a stale copy
+    let pool_keys: Vec<Pubkey> = remaining
+        .chunks(CLAIM_MULTI_GROUP_LEN)
+        .map(|group| group[0].key())
+        .collect();
+    for (i, key) in pool_keys.iter().enumerate() {
+        require!(!pool_keys[..i].contains(key), StakingError::InvalidClaimAccounts);
+    }
+
+    let mut pools_claimed: u32 = 0;
+    let mut total_claimed: u64 = 0;
+
+    for group in remaining.chunks(CLAIM_MULTI_GROUP_LEN) {
+        let mut stake_pool = Account::<StakePool>::try_from(&group[0])?;
+        let stake_pool_key = stake_pool.key();
+
+        let mut pool_stats = Account::<PoolStats>::try_from(&group[1])?;
+        require_keys_eq!(pool_stats.stake_pool, stake_pool_key, StakingError::InvalidClaimAccounts);
+
+        let mut user_stake = Account::<UserStake>::try_from(&group[2])?;
+        require_keys_eq!(user_stake.stake_pool, stake_pool_key, StakingError::StakePoolMismatch);
+        require_keys_eq!(user_stake.owner, user, StakingError::OwnerMismatch);
+        require!(!user_stake.frozen, StakingError::AccountFrozen);
+
+        let reward_vault = Account::<TokenAccount>::try_from(&group[3])?;
+        require_keys_eq!(reward_vault.key(), stake_pool.reward_vault, StakingError::InvalidRewardVault);
+
+        let user_reward_account = Account::<TokenAccount>::try_from(&group[4])?;
+        require_keys_eq!(user_reward_account.mint, stake_pool.reward_mint, StakingError::InvalidMint);
+        require_keys_eq!(
+            user_reward_account.owner,
+            user,
+            StakingError::TokenAccountOwnerMismatch
+        );
+
+        let fee_treasury = if group[5].key() == crate::ID {
+            None
+        } else {
+            let fee_treasury = Account::<TokenAccount>::try_from(&group[5])?;
+            require_keys_eq!(fee_treasury.mint, stake_pool.reward_mint, StakingError::InvalidMint);
+            require_keys_eq!(
+                fee_treasury.owner,
+                constants::TREASURY_ADDRESS,
+                StakingError::TokenAccountOwnerMismatch
+            );
+            Some(fee_treasury)
+        };
+
+        require!(
+            stake_pool.is_claim_window_open(clock.unix_timestamp),
+            StakingError::ClaimWindowClosed
+        );
+
+        update_rewards(&mut stake_pool, clock.unix_timestamp)?;
+        settle_rewards(
+            &mut user_stake,
+            stake_pool.accumulated_reward_per_share,
+            clock.unix_timestamp,
+        )?;
+
+        let interval_elapsed = user_stake.claim_interval_elapsed(
+            stake_pool.min_claim_interval,
+            clock.unix_timestamp,
+        );
+        let claimed = if user_stake.owed_rewards > 0 && interval_elapsed {
+            pay_owed_rewards(
+                &mut stake_pool,
+                &mut user_stake,
+                &mut pool_stats,
+                PayoutAccounts {
+                    reward_vault: &reward_vault,
+                    user_reward_account: &user_reward_account,
+                    fee_treasury: fee_treasury.as_ref(),
+                    token_program: &ctx.accounts.token_program,
+                    memo_program: &ctx.accounts.memo_program,
+                },
+                clock.unix_timestamp,
+            )?
+        } else {
+            0
+        };
+
+        stake_pool.exit(&crate::ID)?;
+        pool_stats.exit(&crate::ID)?;
+        user_stake.exit(&crate::ID)?;
+
+        if claimed > 0 {
+            pools_claimed += 1;
+            total_claimed = total_claimed
+                .checked_add(claimed)
+                .ok_or(StakingError::MathOverflow)?;
+
+            emit!(ClaimEvent {
+                user,
+                stake_pool: stake_pool_key,
+                amount: claimed,
+                tier: user_stake.effective_tier(clock.unix_timestamp),
+                tier_multiplier_applied: user_stake.applied_multiplier_bps,
+                total_claimed: user_stake.total_claimed,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+    }
+
+    require!(total_claimed > 0, StakingError::NoPendingRewards);
+
+    emit!(MultiClaimEvent {
+        user,
+        pools: pool_keys.len() as u32,
+        pools_claimed,
+        total_claimed,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Claimed {} reward tokens across {} pools", total_claimed, pools_claimed);
+
+    Ok(())
+}
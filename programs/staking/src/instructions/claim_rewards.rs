@@ -1,9 +1,11 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::memo::Memo;
 
-use crate::state::{StakePool, UserStake, StakingTier};
+use crate::state::{ActionKind, PoolStats, StakePool, UserStake, StakingTier};
+use crate::memo::{tag_transfer, MemoAction};
 use crate::errors::StakingError;
-use crate::{update_rewards, calculate_pending_rewards, calculate_tier};
+use crate::{constants, math, update_rewards, settle_rewards, distribute_to_other_stakers};
 
 /// Claim rewards instruction
 #[derive(Accounts)]
@@ -15,17 +17,30 @@ pub struct ClaimRewards<'info> {
     /// The stake pool
     #[account(
         mut,
-        seeds = [StakePool::SEED_PREFIX, stake_pool.stake_mint.as_ref()],
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
         bump = stake_pool.bump
     )]
     pub stake_pool: Account<'info, StakePool>,
 
+    /// The pool's statistics account
+    #[account(
+        mut,
+        seeds = [PoolStats::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump = pool_stats.bump
+    )]
+    pub pool_stats: Account<'info, PoolStats>,
+
     /// User's stake account
     #[account(
         mut,
         seeds = [UserStake::SEED_PREFIX, stake_pool.key().as_ref(), user.key().as_ref()],
         bump = user_stake.bump,
-        constraint = user_stake.owner == user.key() @ StakingError::InvalidAuthority
+        has_one = stake_pool @ StakingError::StakePoolMismatch,
+        constraint = user_stake.owner == user.key() @ StakingError::OwnerMismatch
     )]
     pub user_stake: Account<'info, UserStake>,
 
@@ -33,22 +48,72 @@ pub struct ClaimRewards<'info> {
     #[account(
         mut,
         constraint = user_reward_account.mint == stake_pool.reward_mint @ StakingError::InvalidMint,
-        constraint = user_reward_account.owner == user.key() @ StakingError::InvalidAuthority
+        constraint = user_reward_account.owner == user.key() @ StakingError::TokenAccountOwnerMismatch
     )]
     pub user_reward_account: Account<'info, TokenAccount>,
 
     /// Pool's reward vault
     #[account(
         mut,
-        constraint = reward_vault.key() == stake_pool.reward_vault @ StakingError::InvalidMint
+        constraint = reward_vault.key() == stake_pool.reward_vault @ StakingError::InvalidRewardVault
     )]
     pub reward_vault: Account<'info, TokenAccount>,
 
+    /// Protocol treasury's reward token account (required when the pool
+    /// charges a claim fee)
+    #[account(
+        mut,
+        constraint = fee_treasury.mint == stake_pool.reward_mint @ StakingError::InvalidMint,
+        constraint = fee_treasury.owner == constants::TREASURY_ADDRESS @ StakingError::TokenAccountOwnerMismatch
+    )]
+    pub fee_treasury: Option<Account<'info, TokenAccount>>,
+
     pub token_program: Program<'info, Token>,
+    pub memo_program: Program<'info, Memo>,
+}
+
+/// Event emitted when the reward vault can't cover a claim in full.
+/// The unpaid remainder stays in `owed_rewards` and is paid first on the
+/// next claim.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClaimShortfall {
+    pub user: Pubkey,
+    pub stake_pool: Pubkey,
+    pub owed: u64,
+    pub paid: u64,
+    pub shortfall: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a claim pays the protocol fee to the treasury
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClaimFeeEvent {
+    pub user: Pubkey,
+    pub stake_pool: Pubkey,
+    pub fee_bps: u64,
+    pub fee: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a claim pays the pool's decaying claim tax
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClaimTaxEvent {
+    pub user: Pubkey,
+    pub stake_pool: Pubkey,
+    pub tax_bps: u64,
+    pub tax: u64,
+    /// Whether other stakers received the tax; otherwise it stays in the
+    /// reward vault unallocated
+    pub distributed: bool,
+    pub timestamp: i64,
 }
 
 /// Event emitted when rewards are claimed
 #[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ClaimEvent {
     pub user: Pubkey,
     pub stake_pool: Pubkey,
@@ -65,80 +130,220 @@ pub fn handler(ctx: Context<ClaimRewards>) -> Result<()> {
     let clock = Clock::get()?;
 
     // Validate user has stake
-    require!(user_stake.staked_amount > 0, StakingError::InsufficientStake);
+    require!(!user_stake.frozen, StakingError::AccountFrozen);
+    // A fully exited position may still hold owed rewards
+    require!(
+        user_stake.staked_amount > 0 || user_stake.owed_rewards > 0,
+        StakingError::InsufficientStake
+    );
+    require!(
+        stake_pool.is_claim_window_open(clock.unix_timestamp),
+        StakingError::ClaimWindowClosed
+    );
+    require!(
+        user_stake.claim_interval_elapsed(stake_pool.min_claim_interval, clock.unix_timestamp),
+        StakingError::ClaimTooSoon
+    );
 
     // Update accumulated rewards
     update_rewards(stake_pool, clock.unix_timestamp)?;
 
-    // Calculate pending rewards
-    let pending = calculate_pending_rewards(user_stake, stake_pool.accumulated_reward_per_share)?;
+    let tier = user_stake.effective_tier(clock.unix_timestamp);
 
-    require!(pending > 0, StakingError::NoPendingRewards);
+    // Settle pending rewards into owed rewards, on top of any shortfall
+    // carried over from earlier claims. The tier multiplier is already
+    // folded into weighted_stake, so this never exceeds the emission budget.
+    settle_rewards(user_stake, stake_pool.accumulated_reward_per_share, clock.unix_timestamp)?;
+    let tier_multiplier = user_stake.applied_multiplier_bps;
+    let reward_amount = user_stake.owed_rewards;
 
-    // Get user's tier and apply multiplier
-    let tier = calculate_tier(user_stake.staked_amount);
-    let tier_multiplier = tier.reward_multiplier_bps();
+    require!(reward_amount > 0, StakingError::NoPendingRewards);
 
-    // Apply tier multiplier: reward_with_bonus = pending * multiplier / 10000
-    let reward_amount = (pending as u128)
-        .checked_mul(tier_multiplier as u128)
-        .ok_or(StakingError::MathOverflow)?
-        .checked_div(10000)
-        .ok_or(StakingError::MathOverflow)? as u64;
+    let actual_reward = pay_owed_rewards(
+        stake_pool,
+        user_stake,
+        &mut ctx.accounts.pool_stats,
+        PayoutAccounts {
+            reward_vault: &ctx.accounts.reward_vault,
+            user_reward_account: &ctx.accounts.user_reward_account,
+            fee_treasury: ctx.accounts.fee_treasury.as_ref(),
+            token_program: &ctx.accounts.token_program,
+            memo_program: &ctx.accounts.memo_program,
+        },
+        clock.unix_timestamp,
+    )?;
+
+    require!(actual_reward > 0, StakingError::NoPendingRewards);
+    let shortfall = user_stake.owed_rewards;
 
-    // Check vault has sufficient balance
-    let vault_balance = ctx.accounts.reward_vault.amount;
+    // Emit event
+    emit!(ClaimEvent {
+        user: ctx.accounts.user.key(),
+        stake_pool: stake_pool.key(),
+        amount: actual_reward,
+        tier,
+        tier_multiplier_applied: tier_multiplier,
+        total_claimed: user_stake.total_claimed,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Claimed {} reward tokens", actual_reward);
+    msg!("Tier: {:?} ({}x multiplier)", tier, tier_multiplier as f64 / 10000.0);
+    msg!("Total claimed to date: {}", user_stake.total_claimed);
+    if shortfall > 0 {
+        msg!("Vault short by {}; carried forward as owed", shortfall);
+    }
+
+    Ok(())
+}
+
+/// Token accounts and programs a reward payout moves funds through
+pub(crate) struct PayoutAccounts<'a, 'info> {
+    pub reward_vault: &'a Account<'info, TokenAccount>,
+    /// Destination of the payout (the operator escrow for operator positions)
+    pub user_reward_account: &'a Account<'info, TokenAccount>,
+    /// Receives the protocol fee; required when a fee is due
+    pub fee_treasury: Option<&'a Account<'info, TokenAccount>>,
+    pub token_program: &'a Program<'info, Token>,
+    pub memo_program: &'a Program<'info, Memo>,
+}
+
+/// Pay a user's owed rewards out of the reward vault. Whatever the vault can't
+/// cover stays in `owed_rewards` and a `ClaimShortfall` event is emitted.
+/// The pool's protocol fee, discounted by the holder's tier, goes to the
+/// treasury. The caller must have settled rewards first.
+///
+/// # Returns
+/// * `Result<u64>` - Amount actually transferred to the user
+pub(crate) fn pay_owed_rewards<'info>(
+    stake_pool: &mut Account<'info, StakePool>,
+    user_stake: &mut UserStake,
+    pool_stats: &mut PoolStats,
+    accounts: PayoutAccounts<'_, 'info>,
+    current_time: i64,
+) -> Result<u64> {
+    let PayoutAccounts {
+        reward_vault,
+        user_reward_account,
+        fee_treasury,
+        token_program,
+        memo_program,
+    } = accounts;
+    let reward_amount = user_stake.owed_rewards;
+
+    // Operator positions pay only into their escrow, for beneficiaries
+    require!(
+        !user_stake.is_operator() || user_reward_account.key() == user_stake.operator_escrow,
+        StakingError::OperatorEscrowRequired
+    );
+
+    // Check vault has sufficient balance (excluding principal for single-vault pools)
+    let vault_balance = stake_pool.available_rewards(reward_vault.amount);
     let actual_reward = reward_amount.min(vault_balance);
+    if actual_reward == 0 {
+        return Ok(0);
+    }
 
-    require!(actual_reward > 0, StakingError::NoPendingRewards);
+    // Keep whatever the vault couldn't cover on the ledger
+    let shortfall = reward_amount - actual_reward;
+    user_stake.owed_rewards = shortfall;
+    user_stake.last_claim_time = current_time;
+    // Pools created before liability tracking may under-count it
+    stake_pool.reward_liability = stake_pool.reward_liability.saturating_sub(actual_reward);
+    if user_stake.fixed_apr_bps > 0 {
+        stake_pool.fixed_reward_reserved = stake_pool.fixed_reward_reserved.saturating_sub(actual_reward);
+    }
 
-    // Update reward debt to current accumulation
-    // reward_debt = weighted_stake * accumulated_reward_per_share / 1e12
-    user_stake.reward_debt = (user_stake.weighted_stake as u128)
-        .checked_mul(stake_pool.accumulated_reward_per_share)
-        .ok_or(StakingError::MathOverflow)?
-        .checked_div(1_000_000_000_000)
-        .ok_or(StakingError::MathOverflow)?;
+    // Early claims pay the decaying claim tax, which stays in the reward
+    // vault for the other stakers. The claimant's debt absorbs their own
+    // share of the accumulator bump.
+    let tax_bps = stake_pool.claim_tax_bps(user_stake.position_opened_at, current_time);
+    let tax = math::apply_bps(actual_reward, tax_bps)?;
+    if tax > 0 {
+        let distributed = distribute_to_other_stakers(stake_pool, user_stake, tax)?;
+
+        emit!(ClaimTaxEvent {
+            user: user_stake.owner,
+            stake_pool: stake_pool.key(),
+            tax_bps,
+            tax,
+            distributed,
+            timestamp: current_time,
+        });
+    }
+    let taxed_reward = actual_reward - tax;
+
+    // Take the protocol fee, discounted by the holder's tier
+    let fee_bps = stake_pool.claim_fee_bps(user_stake.effective_tier(current_time));
+    let fee = math::apply_bps(taxed_reward, fee_bps)?;
+    let payout = taxed_reward - fee;
 
     // Update total claimed
     user_stake.total_claimed = user_stake.total_claimed
-        .checked_add(actual_reward)
+        .checked_add(payout)
         .ok_or(StakingError::MathOverflow)?;
 
+    user_stake.record_action(ActionKind::Claim, payout, current_time);
+    pool_stats.record_claim(payout);
+
     // Transfer rewards to user via PDA signer
     let stake_mint_key = stake_pool.stake_mint;
+    let pool_id_seed = stake_pool.id_seed();
     let pool_bump = stake_pool.bump;
     let signer_seeds: &[&[&[u8]]] = &[&[
         StakePool::SEED_PREFIX,
         stake_mint_key.as_ref(),
+        &pool_id_seed,
         &[pool_bump],
     ]];
 
     let transfer_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
+        token_program.to_account_info(),
         Transfer {
-            from: ctx.accounts.reward_vault.to_account_info(),
-            to: ctx.accounts.user_reward_account.to_account_info(),
+            from: reward_vault.to_account_info(),
+            to: user_reward_account.to_account_info(),
             authority: stake_pool.to_account_info(),
         },
         signer_seeds,
     );
-    token::transfer(transfer_ctx, actual_reward)?;
+    token::transfer(transfer_ctx, payout)?;
+    tag_transfer(memo_program, MemoAction::Claim, &stake_pool.key(), &user_stake.owner)?;
 
-    // Emit event
-    emit!(ClaimEvent {
-        user: ctx.accounts.user.key(),
-        stake_pool: stake_pool.key(),
-        amount: actual_reward,
-        tier,
-        tier_multiplier_applied: tier_multiplier,
-        total_claimed: user_stake.total_claimed,
-        timestamp: clock.unix_timestamp,
-    });
+    if fee > 0 {
+        let fee_treasury = fee_treasury.ok_or(StakingError::MissingFeeAccount)?;
+        let fee_ctx = CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: reward_vault.to_account_info(),
+                to: fee_treasury.to_account_info(),
+                authority: stake_pool.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(fee_ctx, fee)?;
+        stake_pool.protocol_fees_collected = stake_pool.protocol_fees_collected
+            .checked_add(fee)
+            .ok_or(StakingError::MathOverflow)?;
 
-    msg!("Claimed {} reward tokens", actual_reward);
-    msg!("Tier: {:?} ({}x multiplier)", tier, tier_multiplier as f64 / 10000.0);
-    msg!("Total claimed to date: {}", user_stake.total_claimed);
+        emit!(ClaimFeeEvent {
+            user: user_stake.owner,
+            stake_pool: stake_pool.key(),
+            fee_bps,
+            fee,
+            timestamp: current_time,
+        });
+    }
 
-    Ok(())
+    if shortfall > 0 {
+        emit!(ClaimShortfall {
+            user: user_stake.owner,
+            stake_pool: stake_pool.key(),
+            owed: reward_amount,
+            paid: actual_reward,
+            shortfall,
+            timestamp: current_time,
+        });
+    }
+
+    Ok(payout)
 }
@@ -0,0 +1,166 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::memo::Memo;
+
+use crate::state::{RewardStreams, StakePool, UserRewardStreams, UserStake};
+use crate::memo::{tag_transfer, MemoAction};
+use crate::errors::StakingError;
+use crate::streams::{sync_reward_streams, WeightChange};
+
+/// Claim rewards from one extra reward stream
+#[derive(Accounts)]
+pub struct ClaimStreamRewards<'info> {
+    /// User claiming rewards
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// The stake pool
+    #[account(
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// User's stake account
+    #[account(
+        seeds = [UserStake::SEED_PREFIX, stake_pool.key().as_ref(), user.key().as_ref()],
+        bump = user_stake.bump,
+        has_one = stake_pool @ StakingError::StakePoolMismatch,
+        constraint = user_stake.owner == user.key() @ StakingError::OwnerMismatch
+    )]
+    pub user_stake: Account<'info, UserStake>,
+
+    /// The pool's reward streams
+    #[account(
+        mut,
+        seeds = [RewardStreams::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump = reward_streams.bump
+    )]
+    pub reward_streams: Account<'info, RewardStreams>,
+
+    /// User's stream positions (created for stakers who predate the stream)
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserRewardStreams::LEN,
+        seeds = [UserRewardStreams::SEED_PREFIX, stake_pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_reward_streams: Account<'info, UserRewardStreams>,
+
+    /// The stream's reward vault
+    #[account(mut)]
+    pub stream_vault: Account<'info, TokenAccount>,
+
+    /// User's token account for the stream's mint
+    #[account(
+        mut,
+        constraint = user_reward_account.mint == stream_vault.mint @ StakingError::InvalidMint,
+        constraint = user_reward_account.owner == user.key() @ StakingError::TokenAccountOwnerMismatch
+    )]
+    pub user_reward_account: Account<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub memo_program: Program<'info, Memo>,
+}
+
+/// Event emitted when stream rewards are claimed
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StreamClaimEvent {
+    pub user: Pubkey,
+    pub stake_pool: Pubkey,
+    pub stream_id: u64,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+pub fn handler(ctx: Context<ClaimStreamRewards>, stream_id: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let user_stake = &ctx.accounts.user_stake;
+
+    require!(!user_stake.frozen, StakingError::AccountFrozen);
+
+    // Settle without changing the user's weight
+    let weighted_stake = user_stake.weighted_stake;
+    sync_reward_streams(
+        &ctx.accounts.stake_pool,
+        Some(&mut ctx.accounts.reward_streams),
+        Some(&mut ctx.accounts.user_reward_streams),
+        ctx.accounts.user.key(),
+        ctx.bumps.user_reward_streams,
+        WeightChange {
+            total_weighted_before: ctx.accounts.stake_pool.total_weighted_stake,
+            user_weighted_before: weighted_stake,
+            user_weighted_after: weighted_stake,
+        },
+        clock.unix_timestamp,
+    )?;
+
+    let slot = ctx
+        .accounts
+        .reward_streams
+        .streams
+        .iter()
+        .position(|stream| stream.is_occupied() && stream.stream_id == stream_id)
+        .ok_or(StakingError::RewardStreamNotFound)?;
+    let stream = ctx.accounts.reward_streams.streams[slot];
+    require_keys_eq!(
+        ctx.accounts.stream_vault.key(),
+        stream.vault,
+        StakingError::InvalidMint
+    );
+
+    let entry = &mut ctx.accounts.user_reward_streams.entries[slot];
+    let amount = entry.owed_rewards.min(ctx.accounts.stream_vault.amount);
+    require!(amount > 0, StakingError::NoPendingRewards);
+    entry.owed_rewards -= amount;
+
+    // Transfer rewards to user via PDA signer
+    let stake_pool = &ctx.accounts.stake_pool;
+    let stake_mint_key = stake_pool.stake_mint;
+    let pool_id_seed = stake_pool.id_seed();
+    let pool_bump = stake_pool.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        StakePool::SEED_PREFIX,
+        stake_mint_key.as_ref(),
+        &pool_id_seed,
+        &[pool_bump],
+    ]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.stream_vault.to_account_info(),
+            to: ctx.accounts.user_reward_account.to_account_info(),
+            authority: stake_pool.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)?;
+    tag_transfer(
+        &ctx.accounts.memo_program,
+        MemoAction::Claim,
+        &ctx.accounts.stake_pool.key(),
+        &ctx.accounts.user.key(),
+    )?;
+
+    emit!(StreamClaimEvent {
+        user: ctx.accounts.user.key(),
+        stake_pool: stake_pool.key(),
+        stream_id,
+        mint: stream.mint,
+        amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Claimed {} tokens from reward stream #{}", amount, stream_id);
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+
+use timelock::state::Timelock;
+
+use crate::state::{ProgramConfig, StakePool, UserStake};
+use crate::errors::StakingError;
+use crate::{update_rewards, settle_rewards};
+
+/// Cancel a frozen account's unclaimed rewards (compliance clawback)
+///
+/// Needs both the guardian and the governance timelock PDA, so the clawback
+/// is queued through the timelock and executed by the guardian once the
+/// delay has passed. The cancelled rewards stay in the reward vault and are
+/// released from the pool's liability for the other stakers. Principal is
+/// untouched.
+#[derive(Accounts)]
+pub struct ClawbackRewards<'info> {
+    /// Protocol guardian
+    pub guardian: Signer<'info>,
+
+    /// The governance timelock PDA, signing for the executed queued transaction
+    #[account(
+        seeds = [Timelock::SEED_PREFIX],
+        bump,
+        seeds::program = timelock::ID
+    )]
+    pub timelock: Signer<'info>,
+
+    /// The program config holding the guardian
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = program_config.bump,
+        constraint = program_config.is_guardian(&guardian.key()) @ StakingError::InvalidAuthority
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// The stake pool
+    #[account(
+        mut,
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// The frozen user stake account
+    #[account(
+        mut,
+        seeds = [UserStake::SEED_PREFIX, stake_pool.key().as_ref(), user_stake.owner.as_ref()],
+        bump = user_stake.bump,
+        has_one = stake_pool @ StakingError::StakePoolMismatch,
+        constraint = user_stake.frozen @ StakingError::AccountNotFrozen
+    )]
+    pub user_stake: Account<'info, UserStake>,
+}
+
+/// Event emitted when a frozen account's rewards are clawed back
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RewardsClawedBackEvent {
+    pub user: Pubkey,
+    pub stake_pool: Pubkey,
+    pub amount: u64,
+    pub guardian: Pubkey,
+    pub timestamp: i64,
+}
+
+pub fn clawback_handler(ctx: Context<ClawbackRewards>) -> Result<()> {
+    let stake_pool = &mut ctx.accounts.stake_pool;
+    let user_stake = &mut ctx.accounts.user_stake;
+    let now = Clock::get()?.unix_timestamp;
+
+    // Everything earned up to now is cancelled
+    update_rewards(stake_pool, now)?;
+    settle_rewards(user_stake, stake_pool.accumulated_reward_per_share, now)?;
+
+    let amount = stake_pool.cancel_owed_rewards(user_stake);
+    require!(amount > 0, StakingError::NoPendingRewards);
+
+    emit!(RewardsClawedBackEvent {
+        user: user_stake.owner,
+        stake_pool: stake_pool.key(),
+        amount,
+        guardian: ctx.accounts.guardian.key(),
+        timestamp: now,
+    });
+
+    msg!("Clawed back {} reward tokens from {}", amount, user_stake.owner);
+
+    Ok(())
+}
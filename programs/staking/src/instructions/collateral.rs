@@ -0,0 +1,149 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{ProgramConfig, StakePool, UserStake};
+use crate::errors::StakingError;
+use crate::constants;
+
+/// The PDA `lending_program` signs with to acknowledge a collateral lock or
+/// release
+pub fn collateral_authority_address(lending_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[constants::COLLATERAL_AUTHORITY_SEED], lending_program).0
+}
+
+/// Lock a position as collateral for a whitelisted lending program
+///
+/// Needs both the owner's signature and the lending program's collateral
+/// authority PDA, so the lending program calls this via CPI while the owner
+/// signs its outer instruction. Unstaking is blocked until that program
+/// releases the position.
+#[derive(Accounts)]
+#[instruction(lending_program: Pubkey)]
+pub struct LockPositionAsCollateral<'info> {
+    /// Position owner
+    pub user: Signer<'info>,
+
+    /// Collateral authority PDA of `lending_program`
+    #[account(
+        address = collateral_authority_address(&lending_program) @ StakingError::InvalidCollateralAuthority
+    )]
+    pub collateral_authority: Signer<'info>,
+
+    /// The program config holding the lending program whitelist
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = program_config.bump,
+        constraint = program_config.is_lending_program(&lending_program) @ StakingError::LendingProgramNotWhitelisted
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// The stake pool
+    #[account(
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// User's stake account
+    #[account(
+        mut,
+        seeds = [UserStake::SEED_PREFIX, stake_pool.key().as_ref(), user.key().as_ref()],
+        bump = user_stake.bump,
+        has_one = stake_pool @ StakingError::StakePoolMismatch,
+        constraint = user_stake.owner == user.key() @ StakingError::OwnerMismatch
+    )]
+    pub user_stake: Account<'info, UserStake>,
+}
+
+/// Release a position from collateral (the lending program holding it only,
+/// through its collateral authority PDA)
+#[derive(Accounts)]
+pub struct ReleasePositionCollateral<'info> {
+    /// Collateral authority PDA of the lending program holding the position
+    pub collateral_authority: Signer<'info>,
+
+    /// The stake pool
+    #[account(
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// The collateralized stake account
+    #[account(
+        mut,
+        seeds = [UserStake::SEED_PREFIX, stake_pool.key().as_ref(), user_stake.owner.as_ref()],
+        bump = user_stake.bump,
+        has_one = stake_pool @ StakingError::StakePoolMismatch,
+        constraint = user_stake.is_collateralized() @ StakingError::PositionNotCollateralized,
+        constraint = collateral_authority.key() == collateral_authority_address(&user_stake.collateral_program)
+            @ StakingError::InvalidCollateralAuthority
+    )]
+    pub user_stake: Account<'info, UserStake>,
+}
+
+/// Event emitted when a position is locked as or released from collateral
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PositionCollateralChangedEvent {
+    pub user: Pubkey,
+    pub stake_pool: Pubkey,
+    pub lending_program: Pubkey,
+    pub locked: bool,
+    pub staked_amount: u64,
+    pub timestamp: i64,
+}
+
+pub fn lock_collateral_handler(ctx: Context<LockPositionAsCollateral>, lending_program: Pubkey) -> Result<()> {
+    let user_stake = &mut ctx.accounts.user_stake;
+
+    require!(!user_stake.frozen, StakingError::AccountFrozen);
+    require!(!user_stake.is_collateralized(), StakingError::PositionCollateralized);
+    require!(user_stake.staked_amount > 0, StakingError::InsufficientStake);
+
+    user_stake.collateral_program = lending_program;
+
+    emit!(PositionCollateralChangedEvent {
+        user: user_stake.owner,
+        stake_pool: ctx.accounts.stake_pool.key(),
+        lending_program,
+        locked: true,
+        staked_amount: user_stake.staked_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Position of {} locked as collateral for {}",
+        user_stake.staked_amount,
+        lending_program
+    );
+
+    Ok(())
+}
+
+pub fn release_collateral_handler(ctx: Context<ReleasePositionCollateral>) -> Result<()> {
+    let user_stake = &mut ctx.accounts.user_stake;
+    let lending_program = user_stake.collateral_program;
+
+    user_stake.collateral_program = Pubkey::default();
+
+    emit!(PositionCollateralChangedEvent {
+        user: user_stake.owner,
+        stake_pool: ctx.accounts.stake_pool.key(),
+        lending_program,
+        locked: false,
+        staked_amount: user_stake.staked_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Position released from collateral by {}", lending_program);
+
+    Ok(())
+}
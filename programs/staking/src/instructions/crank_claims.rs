@@ -0,0 +1,231 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::compute_units::sol_remaining_compute_units;
+use anchor_spl::token::{Token, TokenAccount};
+use anchor_spl::memo::Memo;
+
+use crate::state::{PoolStats, StakePool, UserStake};
+use crate::errors::StakingError;
+use crate::constants;
+use crate::{update_rewards, settle_rewards};
+
+use super::claim_rewards::{pay_owed_rewards, ClaimEvent, PayoutAccounts};
+
+/// Opt a position in or out of keeper auto-claims
+#[derive(Accounts)]
+pub struct SetAutoClaim<'info> {
+    /// Position owner
+    pub user: Signer<'info>,
+
+    /// The stake pool
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// User's stake account
+    #[account(
+        mut,
+        seeds = [UserStake::SEED_PREFIX, stake_pool.key().as_ref(), user.key().as_ref()],
+        bump = user_stake.bump,
+        has_one = stake_pool @ StakingError::StakePoolMismatch,
+        constraint = user_stake.owner == user.key() @ StakingError::OwnerMismatch
+    )]
+    pub user_stake: Account<'info, UserStake>,
+}
+
+/// Claim rewards for opted-in positions (permissionless)
+///
+/// Remaining accounts are `(user_stake, user_reward_account)` pairs, both
+/// writable. Positions that haven't opted in or have nothing owed are skipped.
+#[derive(Accounts)]
+pub struct CrankClaims<'info> {
+    /// Keeper running the crank
+    pub keeper: Signer<'info>,
+
+    /// The stake pool
+    #[account(
+        mut,
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// The pool's statistics account
+    #[account(
+        mut,
+        seeds = [PoolStats::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump = pool_stats.bump
+    )]
+    pub pool_stats: Account<'info, PoolStats>,
+
+    /// Pool's reward vault
+    #[account(
+        mut,
+        constraint = reward_vault.key() == stake_pool.reward_vault @ StakingError::InvalidRewardVault
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    /// Protocol treasury's reward token account (required when the pool
+    /// charges a claim fee)
+    #[account(
+        mut,
+        constraint = fee_treasury.mint == stake_pool.reward_mint @ StakingError::InvalidMint,
+        constraint = fee_treasury.owner == constants::TREASURY_ADDRESS @ StakingError::TokenAccountOwnerMismatch
+    )]
+    pub fee_treasury: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub memo_program: Program<'info, Memo>,
+}
+
+/// Event emitted when a position's auto-claim setting changes
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AutoClaimUpdatedEvent {
+    pub user: Pubkey,
+    pub stake_pool: Pubkey,
+    pub enabled: bool,
+    pub timestamp: i64,
+}
+
+/// Event emitted after each crank run
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClaimsCrankedEvent {
+    pub stake_pool: Pubkey,
+    pub keeper: Pubkey,
+    pub positions_processed: u32,
+    pub positions_claimed: u32,
+    pub total_claimed: u64,
+    pub timestamp: i64,
+}
+
+pub fn set_auto_claim_handler(ctx: Context<SetAutoClaim>, enabled: bool) -> Result<()> {
+    let user_stake = &mut ctx.accounts.user_stake;
+    user_stake.auto_claim = enabled;
+
+    emit!(AutoClaimUpdatedEvent {
+        user: user_stake.owner,
+        stake_pool: ctx.accounts.stake_pool.key(),
+        enabled,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Auto-claim {}", if enabled { "enabled" } else { "disabled" });
+
+    Ok(())
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CrankClaims<'info>>) -> Result<()> {
+    let clock = Clock::get()?;
+    let remaining = ctx.remaining_accounts;
+    require!(
+        !remaining.is_empty() && remaining.len().is_multiple_of(2),
+        StakingError::InvalidCrankAccounts
+    );
+    require!(
+        ctx.accounts.stake_pool.is_claim_window_open(clock.unix_timestamp),
+        StakingError::ClaimWindowClosed
+    );
+
+    update_rewards(&mut ctx.accounts.stake_pool, clock.unix_timestamp)?;
+
+    let stake_pool_key = ctx.accounts.stake_pool.key();
+    let mut positions_processed: u32 = 0;
+    let mut positions_claimed: u32 = 0;
+    let mut total_claimed: u64 = 0;
+
+    for pair in remaining.chunks(2) {
+        // Leave the rest of the batch for the next run rather than failing
+        if sol_remaining_compute_units() < constants::CRANK_CLAIM_COMPUTE_UNITS {
+            break;
+        }
+        positions_processed += 1;
+
+        let mut user_stake = Account::<UserStake>::try_from(&pair[0])?;
+        require_keys_eq!(user_stake.stake_pool, stake_pool_key, StakingError::InvalidCrankAccounts);
+        if !user_stake.auto_claim || user_stake.frozen {
+            continue;
+        }
+
+        let user_reward_account = Account::<TokenAccount>::try_from(&pair[1])?;
+        require_keys_eq!(
+            user_reward_account.mint,
+            ctx.accounts.stake_pool.reward_mint,
+            StakingError::InvalidMint
+        );
+        require_keys_eq!(
+            user_reward_account.owner,
+            user_stake.owner,
+            StakingError::TokenAccountOwnerMismatch
+        );
+
+        settle_rewards(
+            &mut user_stake,
+            ctx.accounts.stake_pool.accumulated_reward_per_share,
+            clock.unix_timestamp,
+        )?;
+        let interval_elapsed = user_stake.claim_interval_elapsed(
+            ctx.accounts.stake_pool.min_claim_interval,
+            clock.unix_timestamp,
+        );
+        if user_stake.owed_rewards == 0 || !interval_elapsed {
+            user_stake.exit(&crate::ID)?;
+            continue;
+        }
+
+        let claimed = pay_owed_rewards(
+            &mut ctx.accounts.stake_pool,
+            &mut user_stake,
+            &mut ctx.accounts.pool_stats,
+            PayoutAccounts {
+                reward_vault: &ctx.accounts.reward_vault,
+                user_reward_account: &user_reward_account,
+                fee_treasury: ctx.accounts.fee_treasury.as_ref(),
+                token_program: &ctx.accounts.token_program,
+                memo_program: &ctx.accounts.memo_program,
+            },
+            clock.unix_timestamp,
+        )?;
+        user_stake.exit(&crate::ID)?;
+
+        // Keep the vault balance current for the next position
+        ctx.accounts.reward_vault.reload()?;
+
+        if claimed > 0 {
+            positions_claimed += 1;
+            total_claimed = total_claimed
+                .checked_add(claimed)
+                .ok_or(StakingError::MathOverflow)?;
+
+            emit!(ClaimEvent {
+                user: user_stake.owner,
+                stake_pool: stake_pool_key,
+                amount: claimed,
+                tier: user_stake.effective_tier(clock.unix_timestamp),
+                tier_multiplier_applied: user_stake.applied_multiplier_bps,
+                total_claimed: user_stake.total_claimed,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+    }
+
+    emit!(ClaimsCrankedEvent {
+        stake_pool: stake_pool_key,
+        keeper: ctx.accounts.keeper.key(),
+        positions_processed,
+        positions_claimed,
+        total_claimed,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Crank processed {} positions, claimed {} for {}",
+        positions_processed,
+        total_claimed,
+        positions_claimed
+    );
+
+    Ok(())
+}
@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{RewardStreams, StakePool, UserRewardStreams, UserStake};
+use crate::errors::StakingError;
+use crate::streams::{sync_reward_streams, WeightChange};
+use crate::{update_rewards, settle_rewards, rebase_weight};
+
+/// Refresh a farm position's cross-pool boost from its owner's weighted
+/// stake in the pool's boost source pool
+///
+/// Permissionless, Curve-gauge style: owners refresh to pick up a larger
+/// source stake, and anyone can refresh a position whose owner has since
+/// left the source pool to bring its boost back down.
+#[derive(Accounts)]
+pub struct RefreshCrossBoost<'info> {
+    /// Anyone may refresh a boost (pays rent for stream positions)
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    /// The boosted (farm) stake pool
+    #[account(
+        mut,
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    /// The farm position being refreshed
+    #[account(
+        mut,
+        seeds = [UserStake::SEED_PREFIX, stake_pool.key().as_ref(), user_stake.owner.as_ref()],
+        bump = user_stake.bump,
+        has_one = stake_pool @ StakingError::StakePoolMismatch
+    )]
+    pub user_stake: Box<Account<'info, UserStake>>,
+
+    /// The pool's boost source pool
+    #[account(
+        seeds = [
+            StakePool::SEED_PREFIX,
+            source_pool.stake_mint.as_ref(),
+            &source_pool.id_seed()
+        ],
+        bump = source_pool.bump,
+        constraint = source_pool.key() == stake_pool.boost_source_pool @ StakingError::InvalidBoostSource
+    )]
+    pub source_pool: Box<Account<'info, StakePool>>,
+
+    /// The same owner's position in the source pool
+    #[account(
+        seeds = [UserStake::SEED_PREFIX, source_pool.key().as_ref(), user_stake.owner.as_ref()],
+        bump = source_user_stake.bump
+    )]
+    pub source_user_stake: Box<Account<'info, UserStake>>,
+
+    /// The pool's extra reward streams (required once the pool has streams)
+    #[account(
+        mut,
+        seeds = [RewardStreams::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump = reward_streams.bump
+    )]
+    pub reward_streams: Option<Account<'info, RewardStreams>>,
+
+    /// Owner's extra reward stream positions (required once the pool has streams)
+    #[account(
+        init_if_needed,
+        payer = caller,
+        space = UserRewardStreams::LEN,
+        seeds = [UserRewardStreams::SEED_PREFIX, stake_pool.key().as_ref(), user_stake.owner.as_ref()],
+        bump
+    )]
+    pub user_reward_streams: Option<Account<'info, UserRewardStreams>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Event emitted when a position's cross-pool boost is refreshed
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CrossBoostRefreshedEvent {
+    pub user: Pubkey,
+    pub stake_pool: Pubkey,
+    pub source_pool: Pubkey,
+    pub source_weight: u64,
+    pub source_total_weight: u64,
+    pub boost_bps: u64,
+    pub caller: Pubkey,
+    pub timestamp: i64,
+}
+
+pub fn handler(ctx: Context<RefreshCrossBoost>) -> Result<()> {
+    let stake_pool = &mut ctx.accounts.stake_pool;
+    let user_stake = &mut ctx.accounts.user_stake;
+    let source_pool = &ctx.accounts.source_pool;
+    let now = Clock::get()?.unix_timestamp;
+    let total_weighted_before = stake_pool.total_weighted_stake;
+    let user_weighted_before = user_stake.weighted_stake;
+
+    // Rewards so far are paid at the old boost
+    update_rewards(stake_pool, now)?;
+    settle_rewards(user_stake, stake_pool.accumulated_reward_per_share, now)?;
+
+    user_stake.boost_source_pool = source_pool.key();
+    user_stake.boost_source_weight = ctx.accounts.source_user_stake.weighted_stake;
+    user_stake.boost_source_total_weight = source_pool.total_weighted_stake;
+    rebase_weight(stake_pool, user_stake, now)?;
+
+    let owner = user_stake.owner;
+    sync_reward_streams(
+        &ctx.accounts.stake_pool,
+        ctx.accounts.reward_streams.as_mut(),
+        ctx.accounts.user_reward_streams.as_mut(),
+        owner,
+        ctx.bumps.user_reward_streams,
+        WeightChange {
+            total_weighted_before,
+            user_weighted_before,
+            user_weighted_after: ctx.accounts.user_stake.weighted_stake,
+        },
+        now,
+    )?;
+
+    let stake_pool = &ctx.accounts.stake_pool;
+    let user_stake = &ctx.accounts.user_stake;
+    let boost_bps = stake_pool.cross_boost_bps(user_stake);
+
+    emit!(CrossBoostRefreshedEvent {
+        user: owner,
+        stake_pool: stake_pool.key(),
+        source_pool: user_stake.boost_source_pool,
+        source_weight: user_stake.boost_source_weight,
+        source_total_weight: user_stake.boost_source_total_weight,
+        boost_bps,
+        caller: ctx.accounts.caller.key(),
+        timestamp: now,
+    });
+
+    msg!("Cross-pool boost now {} bps", boost_bps);
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{StakePool, UserStake};
+use crate::errors::StakingError;
+use crate::calculate_early_unstake_penalty;
+
+use super::unstake::{execute_unstake, Unstake};
+
+/// Read-only accounts for previewing an early unstake penalty
+#[derive(Accounts)]
+pub struct PreviewEarlyUnstakeFee<'info> {
+    /// The stake pool
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// The position being previewed
+    #[account(
+        seeds = [UserStake::SEED_PREFIX, stake_pool.key().as_ref(), user_stake.owner.as_ref()],
+        bump = user_stake.bump,
+        has_one = stake_pool @ StakingError::StakePoolMismatch
+    )]
+    pub user_stake: Account<'info, UserStake>,
+}
+
+/// What an early exit would return, as shown before the user confirms
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EarlyExitPreview {
+    /// Penalty withheld from the unstaked amount
+    pub penalty: u64,
+    /// Tokens the user would receive
+    pub net_out: u64,
+    /// Rewards lost by exiting early. Early exits settle pending rewards
+    /// into `owed_rewards`, so this is currently always zero.
+    pub forfeited_rewards: u64,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, Unstake<'info>>,
+    amount: u64,
+) -> Result<()> {
+    execute_unstake(ctx, amount, true)
+}
+
+pub fn preview_handler(ctx: Context<PreviewEarlyUnstakeFee>, amount: u64) -> Result<u64> {
+    calculate_early_unstake_penalty(
+        amount,
+        ctx.accounts.stake_pool.early_unstake_penalty_bps,
+        ctx.accounts.user_stake.lock_end_time,
+        ctx.accounts.user_stake.lock_duration,
+        Clock::get()?.unix_timestamp,
+    )
+}
+
+/// Quote an early exit of `amount` from `user_stake` at `now`
+pub fn preview_early_exit(
+    stake_pool: &StakePool,
+    user_stake: &UserStake,
+    amount: u64,
+    now: i64,
+) -> Result<EarlyExitPreview> {
+    require!(amount > 0, StakingError::InvalidAmount);
+    require!(amount <= user_stake.staked_amount, StakingError::InsufficientStake);
+
+    let penalty = calculate_early_unstake_penalty(
+        amount,
+        stake_pool.early_unstake_penalty_bps,
+        user_stake.lock_end_time,
+        user_stake.lock_duration,
+        now,
+    )?;
+
+    Ok(EarlyExitPreview {
+        penalty,
+        net_out: amount - penalty,
+        forfeited_rewards: 0,
+    })
+}
+
+pub fn preview_exit_handler(ctx: Context<PreviewEarlyUnstakeFee>, amount: u64) -> Result<EarlyExitPreview> {
+    preview_early_exit(
+        &ctx.accounts.stake_pool,
+        &ctx.accounts.user_stake,
+        amount,
+        Clock::get()?.unix_timestamp,
+    )
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{StakePool, UserStake};
+use crate::errors::StakingError;
+
+/// Freeze or thaw a single user's stake account (compliance hold)
+#[derive(Accounts)]
+pub struct SetUserFrozen<'info> {
+    /// Pool authority
+    pub authority: Signer<'info>,
+
+    /// The stake pool
+    #[account(
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump,
+        has_one = authority @ StakingError::InvalidAuthority
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// The user stake account to freeze or thaw
+    #[account(
+        mut,
+        seeds = [UserStake::SEED_PREFIX, stake_pool.key().as_ref(), user_stake.owner.as_ref()],
+        bump = user_stake.bump,
+        has_one = stake_pool @ StakingError::StakePoolMismatch
+    )]
+    pub user_stake: Account<'info, UserStake>,
+}
+
+/// Event emitted when a user stake is frozen or thawed
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UserStakeFrozenEvent {
+    pub user: Pubkey,
+    pub stake_pool: Pubkey,
+    pub frozen: bool,
+    pub timestamp: i64,
+}
+
+pub fn freeze_handler(ctx: Context<SetUserFrozen>) -> Result<()> {
+    set_frozen(ctx, true)
+}
+
+pub fn thaw_handler(ctx: Context<SetUserFrozen>) -> Result<()> {
+    set_frozen(ctx, false)
+}
+
+fn set_frozen(ctx: Context<SetUserFrozen>, frozen: bool) -> Result<()> {
+    let user_stake = &mut ctx.accounts.user_stake;
+    user_stake.frozen = frozen;
+
+    emit!(UserStakeFrozenEvent {
+        user: user_stake.owner,
+        stake_pool: ctx.accounts.stake_pool.key(),
+        frozen,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("User stake {} frozen: {}", user_stake.owner, frozen);
+
+    Ok(())
+}
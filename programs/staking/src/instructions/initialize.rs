@@ -1,11 +1,16 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_lang::system_program;
+use anchor_spl::token::{spl_token, Mint, Token, TokenAccount};
 
-use crate::state::StakePool;
+use crate::state::{
+    PoolMetadata, PoolRegistry, PoolStats, ProgramConfig, ProtocolTreasury, RegistryEntry, StakePool,
+};
 use crate::errors::StakingError;
+use crate::constants;
 
 /// Initialize a new staking pool
 #[derive(Accounts)]
+#[instruction(params: InitializeParams)]
 pub struct Initialize<'info> {
     /// Authority who will manage the stake pool
     #[account(mut)]
@@ -16,11 +21,71 @@ pub struct Initialize<'info> {
         init,
         payer = authority,
         space = StakePool::LEN,
-        seeds = [StakePool::SEED_PREFIX, stake_mint.key().as_ref()],
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_mint.key().as_ref(),
+            &StakePool::pool_id_seed(params.pool_id)
+        ],
         bump
     )]
     pub stake_pool: Account<'info, StakePool>,
 
+    /// Statistics account for the pool
+    #[account(
+        init,
+        payer = authority,
+        space = PoolStats::LEN,
+        seeds = [PoolStats::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump
+    )]
+    pub pool_stats: Account<'info, PoolStats>,
+
+    /// Human-readable metadata for the pool
+    #[account(
+        init,
+        payer = authority,
+        space = PoolMetadata::LEN,
+        seeds = [PoolMetadata::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump
+    )]
+    pub pool_metadata: Box<Account<'info, PoolMetadata>>,
+
+    /// Global pool counter (created with the first pool)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = PoolRegistry::LEN,
+        seeds = [PoolRegistry::SEED_PREFIX],
+        bump
+    )]
+    pub pool_registry: Box<Account<'info, PoolRegistry>>,
+
+    /// Registry entry for this pool at the next free index
+    #[account(
+        init,
+        payer = authority,
+        space = RegistryEntry::LEN,
+        seeds = [RegistryEntry::SEED_PREFIX, &pool_registry.pool_count.to_le_bytes()],
+        bump
+    )]
+    pub registry_entry: Box<Account<'info, RegistryEntry>>,
+
+    /// Protocol treasury receiving the pool creation fee
+    #[account(
+        mut,
+        seeds = [ProtocolTreasury::SEED_PREFIX],
+        bump = protocol_treasury.bump
+    )]
+    pub protocol_treasury: Account<'info, ProtocolTreasury>,
+
+    /// Global program config; when present its creation fee and feature
+    /// switches apply instead of the treasury's fee
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = program_config.bump
+    )]
+    pub program_config: Option<Box<Account<'info, ProgramConfig>>>,
+
     /// The token mint for staking (KR8TIV token)
     pub stake_mint: Account<'info, Mint>,
 
@@ -38,7 +103,8 @@ pub struct Initialize<'info> {
     )]
     pub stake_vault: Account<'info, TokenAccount>,
 
-    /// Vault to hold reward tokens
+    /// Vault to hold reward tokens. Omit when `stake_mint == reward_mint`
+    /// to share the stake vault for rewards (single-vault pool).
     #[account(
         init,
         payer = authority,
@@ -47,7 +113,7 @@ pub struct Initialize<'info> {
         seeds = [b"reward_vault", stake_pool.key().as_ref()],
         bump
     )]
-    pub reward_vault: Account<'info, TokenAccount>,
+    pub reward_vault: Option<Account<'info, TokenAccount>>,
 
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
@@ -57,33 +123,142 @@ pub struct Initialize<'info> {
 /// Parameters for initializing a stake pool
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct InitializeParams {
+    /// Pool identifier, distinguishing multiple pools for the same stake mint
+    pub pool_id: u64,
     /// Reward rate per second
     pub reward_rate: u64,
     /// Minimum lock duration in seconds (default: 7 days)
     pub min_lock_duration: i64,
     /// Maximum lock duration in seconds (default: 365 days)
     pub max_lock_duration: i64,
+    /// Display name (max 32 bytes)
+    pub name: String,
+    /// Symbol (max 10 bytes)
+    pub symbol: String,
+    /// Off-chain metadata URI (max 128 bytes)
+    pub uri: String,
+    /// Accept stake or reward mints with an active freeze authority, whose
+    /// holder could freeze the pool's vaults
+    pub allow_freezable: bool,
+}
+
+/// Event emitted when a pool is created, carrying its full initial config
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PoolInitializedEvent {
+    pub stake_pool: Pubkey,
+    pub authority: Pubkey,
+    pub pool_id: u64,
+    pub registry_index: u64,
+    pub stake_mint: Pubkey,
+    pub reward_mint: Pubkey,
+    pub stake_vault: Pubkey,
+    pub reward_vault: Pubkey,
+    pub shared_vault: bool,
+    pub is_native: bool,
+    pub reward_rate: u64,
+    pub min_lock_duration: i64,
+    pub max_lock_duration: i64,
+    pub tier_thresholds: [u64; 3],
+    pub stake_mint_decimals: u8,
+    pub reward_mint_decimals: u8,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub creation_fee: u64,
+    pub timestamp: i64,
+}
+
+impl InitializeParams {
+    /// Reject parameter sets that would create a broken pool
+    ///
+    /// # Arguments
+    /// * `stake_mint_decimals` - Decimals of the pool's stake mint
+    pub fn validate(&self, stake_mint_decimals: u8) -> Result<()> {
+        require!(
+            self.reward_rate <= constants::MAX_REWARD_RATE,
+            StakingError::RewardRateTooHigh
+        );
+        require!(
+            self.min_lock_duration > 0 && self.max_lock_duration > 0,
+            StakingError::InvalidLockBounds
+        );
+        require!(
+            self.max_lock_duration >= self.min_lock_duration,
+            StakingError::DurationTooLong
+        );
+        // Tier thresholds must be expressible in the mint's units
+        require!(
+            staking_math::TierConfig::for_decimals(stake_mint_decimals).is_some(),
+            StakingError::InvalidMintDecimals
+        );
+        Ok(())
+    }
 }
 
 pub fn handler(ctx: Context<Initialize>, params: InitializeParams) -> Result<()> {
-    // Validate durations
-    require!(
-        params.min_lock_duration >= 0,
-        StakingError::DurationTooShort
-    );
+    params.validate(ctx.accounts.stake_mint.decimals)?;
+
+    // A frozen vault would trap every staker's tokens or rewards
     require!(
-        params.max_lock_duration >= params.min_lock_duration,
-        StakingError::DurationTooLong
+        params.allow_freezable
+            || (ctx.accounts.stake_mint.freeze_authority.is_none()
+                && ctx.accounts.reward_mint.freeze_authority.is_none()),
+        StakingError::FreezableMint
     );
 
+    // Charge the pool creation fee
+    let creation_fee = match &ctx.accounts.program_config {
+        Some(config) => {
+            require!(
+                config.is_enabled(ProgramConfig::FEATURE_POOL_CREATION),
+                StakingError::FeatureDisabled
+            );
+            require!(
+                ctx.accounts.stake_mint.key() != spl_token::native_mint::ID
+                    || config.is_enabled(ProgramConfig::FEATURE_NATIVE_POOLS),
+                StakingError::FeatureDisabled
+            );
+            config.pool_creation_fee
+        }
+        None => ctx.accounts.protocol_treasury.pool_creation_fee,
+    };
+    if creation_fee > 0 {
+        let fee_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.authority.to_account_info(),
+                to: ctx.accounts.protocol_treasury.to_account_info(),
+            },
+        );
+        system_program::transfer(fee_ctx, creation_fee)?;
+
+        let treasury = &mut ctx.accounts.protocol_treasury;
+        treasury.total_fees_collected = treasury.total_fees_collected
+            .checked_add(creation_fee)
+            .ok_or(StakingError::MathOverflow)?;
+    }
+
     let stake_pool = &mut ctx.accounts.stake_pool;
     let clock = Clock::get()?;
 
     stake_pool.authority = ctx.accounts.authority.key();
     stake_pool.stake_mint = ctx.accounts.stake_mint.key();
+    stake_pool.pool_id = params.pool_id;
     stake_pool.reward_mint = ctx.accounts.reward_mint.key();
     stake_pool.stake_vault = ctx.accounts.stake_vault.key();
-    stake_pool.reward_vault = ctx.accounts.reward_vault.key();
+    stake_pool.reward_vault = match &ctx.accounts.reward_vault {
+        Some(reward_vault) => reward_vault.key(),
+        None => {
+            // Single-vault pool: rewards are paid from the stake vault surplus
+            require!(
+                ctx.accounts.stake_mint.key() == ctx.accounts.reward_mint.key(),
+                StakingError::SharedVaultMintMismatch
+            );
+            stake_pool.shared_vault = true;
+            ctx.accounts.stake_vault.key()
+        }
+    };
     stake_pool.total_staked = 0;
     stake_pool.total_weighted_stake = 0;
     stake_pool.reward_rate = params.reward_rate;
@@ -92,12 +267,85 @@ pub fn handler(ctx: Context<Initialize>, params: InitializeParams) -> Result<()>
     stake_pool.min_lock_duration = params.min_lock_duration;
     stake_pool.max_lock_duration = params.max_lock_duration;
     stake_pool.paused = false;
+    stake_pool.allow_unstake_when_paused = true;
     stake_pool.bump = ctx.bumps.stake_pool;
+    stake_pool.version = StakePool::CURRENT_VERSION;
+    stake_pool.is_native = stake_pool.stake_mint == spl_token::native_mint::ID;
+    stake_pool.tier_thresholds = staking_math::TierConfig::for_decimals(ctx.accounts.stake_mint.decimals)
+        .ok_or(StakingError::InvalidMintDecimals)?
+        .min_amounts;
+    stake_pool.stake_mint_decimals = ctx.accounts.stake_mint.decimals;
+    stake_pool.reward_mint_decimals = ctx.accounts.reward_mint.decimals;
+
+    let pool_stats = &mut ctx.accounts.pool_stats;
+    pool_stats.stake_pool = stake_pool.key();
+    pool_stats.bump = ctx.bumps.pool_stats;
+
+    let pool_metadata = &mut ctx.accounts.pool_metadata;
+    pool_metadata.stake_pool = stake_pool.key();
+    pool_metadata.bump = ctx.bumps.pool_metadata;
+    pool_metadata.set(&params.name, &params.symbol, &params.uri)?;
+
+    let pool_registry = &mut ctx.accounts.pool_registry;
+    pool_registry.bump = ctx.bumps.pool_registry;
+    let registry_entry = &mut ctx.accounts.registry_entry;
+    registry_entry.index = pool_registry.pool_count;
+    registry_entry.stake_pool = stake_pool.key();
+    registry_entry.stake_mint = stake_pool.stake_mint;
+    registry_entry.reward_mint = stake_pool.reward_mint;
+    registry_entry.authority = stake_pool.authority;
+    registry_entry.created_at = clock.unix_timestamp;
+    registry_entry.bump = ctx.bumps.registry_entry;
+    pool_registry.pool_count = pool_registry.pool_count
+        .checked_add(1)
+        .ok_or(StakingError::MathOverflow)?;
+
+    emit!(PoolInitializedEvent {
+        stake_pool: stake_pool.key(),
+        authority: stake_pool.authority,
+        pool_id: stake_pool.pool_id,
+        registry_index: registry_entry.index,
+        stake_mint: stake_pool.stake_mint,
+        reward_mint: stake_pool.reward_mint,
+        stake_vault: stake_pool.stake_vault,
+        reward_vault: stake_pool.reward_vault,
+        shared_vault: stake_pool.shared_vault,
+        is_native: stake_pool.is_native,
+        reward_rate: stake_pool.reward_rate,
+        min_lock_duration: stake_pool.min_lock_duration,
+        max_lock_duration: stake_pool.max_lock_duration,
+        tier_thresholds: stake_pool.tier_thresholds,
+        stake_mint_decimals: stake_pool.stake_mint_decimals,
+        reward_mint_decimals: stake_pool.reward_mint_decimals,
+        name: params.name.clone(),
+        symbol: params.symbol.clone(),
+        uri: params.uri.clone(),
+        creation_fee,
+        timestamp: clock.unix_timestamp,
+    });
 
     msg!("Stake pool initialized");
     msg!("Authority: {}", stake_pool.authority);
     msg!("Stake mint: {}", stake_pool.stake_mint);
+    msg!("Pool id: {}", stake_pool.pool_id);
+    msg!("Name: {}", params.name);
+    msg!("Registry index: {}", registry_entry.index);
+    if stake_pool.shared_vault {
+        msg!("Single-vault pool: stake vault also holds rewards");
+    }
+    if stake_pool.is_native {
+        msg!("Native SOL pool (wSOL vault)");
+    }
     msg!("Reward rate: {} per second", stake_pool.reward_rate);
+    msg!(
+        "Mint decimals: stake {}, reward {}",
+        stake_pool.stake_mint_decimals,
+        stake_pool.reward_mint_decimals
+    );
+    msg!("Tier thresholds: {:?}", stake_pool.tier_thresholds);
+    if creation_fee > 0 {
+        msg!("Pool creation fee paid: {} lamports", creation_fee);
+    }
 
     Ok(())
 }
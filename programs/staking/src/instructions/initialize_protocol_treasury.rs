@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::program::Staking;
+use crate::state::ProtocolTreasury;
+use crate::errors::StakingError;
+
+/// Create the global protocol treasury (program upgrade authority only)
+#[derive(Accounts)]
+pub struct InitializeProtocolTreasury<'info> {
+    /// Program upgrade authority, becomes the treasury admin
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// The protocol treasury PDA
+    #[account(
+        init,
+        payer = admin,
+        space = ProtocolTreasury::LEN,
+        seeds = [ProtocolTreasury::SEED_PREFIX],
+        bump
+    )]
+    pub protocol_treasury: Account<'info, ProtocolTreasury>,
+
+    /// This program
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ StakingError::InvalidAuthority
+    )]
+    pub program: Program<'info, Staking>,
+
+    /// This program's data account, proving the upgrade authority
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ StakingError::InvalidAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeProtocolTreasury>, pool_creation_fee: u64) -> Result<()> {
+    let treasury = &mut ctx.accounts.protocol_treasury;
+
+    treasury.admin = ctx.accounts.admin.key();
+    treasury.pool_creation_fee = pool_creation_fee;
+    treasury.total_fees_collected = 0;
+    treasury.bump = ctx.bumps.protocol_treasury;
+
+    msg!("Protocol treasury initialized");
+    msg!("Admin: {}", treasury.admin);
+    msg!("Pool creation fee: {} lamports", pool_creation_fee);
+
+    Ok(())
+}
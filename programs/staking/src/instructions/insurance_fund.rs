@@ -0,0 +1,170 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::state::{InsuranceFund, StakePool};
+use crate::errors::StakingError;
+
+/// Create a pool's insurance fund and its vault (pool authority only)
+#[derive(Accounts)]
+pub struct InitializeInsuranceFund<'info> {
+    /// Pool authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The stake pool
+    #[account(
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump,
+        has_one = authority @ StakingError::InvalidAuthority
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// The insurance fund to create
+    #[account(
+        init,
+        payer = authority,
+        space = InsuranceFund::LEN,
+        seeds = [InsuranceFund::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump
+    )]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+
+    /// The stake mint (penalties are paid in it)
+    #[account(address = stake_pool.stake_mint @ StakingError::InvalidMint)]
+    pub stake_mint: Account<'info, Mint>,
+
+    /// Vault holding the fund's tokens
+    #[account(
+        init,
+        payer = authority,
+        token::mint = stake_mint,
+        token::authority = stake_pool,
+        seeds = [InsuranceFund::VAULT_SEED_PREFIX, stake_pool.key().as_ref()],
+        bump
+    )]
+    pub insurance_vault: Account<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Top up the reward vault from the insurance fund (pool authority only)
+#[derive(Accounts)]
+pub struct CoverShortfall<'info> {
+    /// Pool authority
+    pub authority: Signer<'info>,
+
+    /// The stake pool
+    #[account(
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump,
+        has_one = authority @ StakingError::InvalidAuthority
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// The pool's insurance fund
+    #[account(
+        mut,
+        seeds = [InsuranceFund::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump = insurance_fund.bump
+    )]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+
+    /// The insurance fund's vault
+    #[account(
+        mut,
+        address = insurance_fund.vault @ StakingError::InvalidMint
+    )]
+    pub insurance_vault: Account<'info, TokenAccount>,
+
+    /// The pool's reward vault; must hold the same mint as the fund
+    #[account(
+        mut,
+        constraint = reward_vault.key() == stake_pool.reward_vault @ StakingError::InvalidRewardVault,
+        constraint = reward_vault.mint == insurance_vault.mint @ StakingError::InvalidMint
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Event emitted when the insurance fund covers a reward shortfall
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShortfallCoveredEvent {
+    pub stake_pool: Pubkey,
+    pub amount: u64,
+    pub total_covered: u64,
+    pub remaining_fund: u64,
+    pub timestamp: i64,
+}
+
+pub fn initialize_handler(ctx: Context<InitializeInsuranceFund>) -> Result<()> {
+    let insurance_fund = &mut ctx.accounts.insurance_fund;
+    insurance_fund.stake_pool = ctx.accounts.stake_pool.key();
+    insurance_fund.vault = ctx.accounts.insurance_vault.key();
+    insurance_fund.total_deposited = 0;
+    insurance_fund.total_covered = 0;
+    insurance_fund.bump = ctx.bumps.insurance_fund;
+
+    msg!("Insurance fund created for pool {}", insurance_fund.stake_pool);
+
+    Ok(())
+}
+
+pub fn cover_handler(ctx: Context<CoverShortfall>, amount: u64) -> Result<()> {
+    require!(amount > 0, StakingError::InvalidAmount);
+    require!(
+        ctx.accounts.insurance_vault.amount >= amount,
+        StakingError::InsufficientInsuranceFunds
+    );
+
+    // Transfer via PDA signer
+    let stake_pool = &ctx.accounts.stake_pool;
+    let stake_mint_key = stake_pool.stake_mint;
+    let pool_id_seed = stake_pool.id_seed();
+    let pool_bump = stake_pool.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        StakePool::SEED_PREFIX,
+        stake_mint_key.as_ref(),
+        &pool_id_seed,
+        &[pool_bump],
+    ]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.insurance_vault.to_account_info(),
+            to: ctx.accounts.reward_vault.to_account_info(),
+            authority: stake_pool.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    let insurance_fund = &mut ctx.accounts.insurance_fund;
+    insurance_fund.total_covered = insurance_fund.total_covered
+        .checked_add(amount)
+        .ok_or(StakingError::MathOverflow)?;
+
+    emit!(ShortfallCoveredEvent {
+        stake_pool: ctx.accounts.stake_pool.key(),
+        amount,
+        total_covered: insurance_fund.total_covered,
+        remaining_fund: ctx.accounts.insurance_vault.amount - amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Insurance fund covered {} reward tokens", amount);
+
+    Ok(())
+}
@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{RewardStreams, StakePool, UserRewardStreams, UserStake};
+use crate::errors::StakingError;
+use crate::constants;
+use crate::math;
+use crate::streams::{sync_reward_streams, WeightChange};
+use crate::{update_rewards, settle_rewards, rebase_weight};
+
+/// Permissionlessly drop the lock bonus from an expired position
+#[derive(Accounts)]
+pub struct KickExpired<'info> {
+    /// Anyone may call this instruction
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    /// The stake pool
+    #[account(
+        mut,
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// The expired position
+    #[account(
+        mut,
+        seeds = [UserStake::SEED_PREFIX, stake_pool.key().as_ref(), user_stake.owner.as_ref()],
+        bump = user_stake.bump,
+        has_one = stake_pool @ StakingError::StakePoolMismatch
+    )]
+    pub user_stake: Account<'info, UserStake>,
+
+    /// The pool's extra reward streams (required once the pool has streams)
+    #[account(
+        mut,
+        seeds = [RewardStreams::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump = reward_streams.bump
+    )]
+    pub reward_streams: Option<Account<'info, RewardStreams>>,
+
+    /// User's extra reward stream positions (required once the pool has streams)
+    #[account(
+        init_if_needed,
+        payer = caller,
+        space = UserRewardStreams::LEN,
+        seeds = [UserRewardStreams::SEED_PREFIX, stake_pool.key().as_ref(), user_stake.owner.as_ref()],
+        bump
+    )]
+    pub user_reward_streams: Option<Account<'info, UserRewardStreams>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Event emitted when an expired position's lock bonus is removed
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PositionKickedEvent {
+    pub user: Pubkey,
+    pub stake_pool: Pubkey,
+    pub caller: Pubkey,
+    pub weight_removed: u64,
+    pub new_weighted_stake: u64,
+    pub rewards_settled: u64,
+    pub timestamp: i64,
+}
+
+pub fn handler(ctx: Context<KickExpired>) -> Result<()> {
+    let stake_pool = &mut ctx.accounts.stake_pool;
+    let user_stake = &mut ctx.accounts.user_stake;
+    let clock = Clock::get()?;
+    let total_weighted_before = stake_pool.total_weighted_stake;
+    let user_weighted_before = user_stake.weighted_stake;
+
+    require!(
+        clock.unix_timestamp >= user_stake.lock_end_time,
+        StakingError::StillLocked
+    );
+
+    // Settle rewards earned at the old weight before changing it
+    update_rewards(stake_pool, clock.unix_timestamp)?;
+    let rewards_settled = settle_rewards(
+        user_stake,
+        stake_pool.accumulated_reward_per_share,
+        clock.unix_timestamp,
+    )?;
+
+    // Once unlocked, a position only carries base (1x) lock weight
+    let base_weight = math::apply_bps(user_stake.staked_amount, constants::MIN_WEIGHT_MULTIPLIER)?;
+    require!(
+        user_stake.lock_weighted_stake > base_weight,
+        StakingError::NothingToKick
+    );
+
+    user_stake.lock_weighted_stake = base_weight;
+    rebase_weight(stake_pool, user_stake, clock.unix_timestamp)?;
+    let weight_removed = user_weighted_before.saturating_sub(user_stake.weighted_stake);
+
+    sync_reward_streams(
+        &ctx.accounts.stake_pool,
+        ctx.accounts.reward_streams.as_mut(),
+        ctx.accounts.user_reward_streams.as_mut(),
+        ctx.accounts.user_stake.owner,
+        ctx.bumps.user_reward_streams,
+        WeightChange {
+            total_weighted_before,
+            user_weighted_before,
+            user_weighted_after: ctx.accounts.user_stake.weighted_stake,
+        },
+        clock.unix_timestamp,
+    )?;
+
+    let stake_pool = &ctx.accounts.stake_pool;
+    let user_stake = &ctx.accounts.user_stake;
+    emit!(PositionKickedEvent {
+        user: user_stake.owner,
+        stake_pool: stake_pool.key(),
+        caller: ctx.accounts.caller.key(),
+        weight_removed,
+        new_weighted_stake: user_stake.weighted_stake,
+        rewards_settled,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Removed {} expired lock weight", weight_removed);
+
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{HookRegistry, StakePool};
+use crate::errors::StakingError;
+
+/// Add or remove a hook program for a pool
+#[derive(Accounts)]
+pub struct ManageHooks<'info> {
+    /// Pool authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The stake pool
+    #[account(
+        mut,
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump,
+        has_one = authority @ StakingError::InvalidAuthority
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// The pool's hook registry (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = HookRegistry::LEN,
+        seeds = [HookRegistry::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump
+    )]
+    pub hook_registry: Account<'info, HookRegistry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Event emitted when the hook registry changes
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HookRegistryUpdatedEvent {
+    pub stake_pool: Pubkey,
+    pub hook_program: Pubkey,
+    pub added: bool,
+    pub hook_count: u8,
+}
+
+pub fn add_hook_handler(ctx: Context<ManageHooks>, hook_program: Pubkey) -> Result<()> {
+    let stake_pool = &mut ctx.accounts.stake_pool;
+    let registry = &mut ctx.accounts.hook_registry;
+
+    registry.stake_pool = stake_pool.key();
+    registry.bump = ctx.bumps.hook_registry;
+
+    require!(
+        !registry.active_hooks().contains(&hook_program),
+        StakingError::HookAlreadyRegistered
+    );
+    require!(
+        (registry.hook_count as usize) < HookRegistry::MAX_HOOKS,
+        StakingError::HookRegistryFull
+    );
+
+    let index = registry.hook_count as usize;
+    registry.hooks[index] = hook_program;
+    registry.hook_count += 1;
+    stake_pool.hooks_enabled = true;
+
+    emit!(HookRegistryUpdatedEvent {
+        stake_pool: stake_pool.key(),
+        hook_program,
+        added: true,
+        hook_count: registry.hook_count,
+    });
+
+    msg!("Hook program added: {}", hook_program);
+
+    Ok(())
+}
+
+pub fn remove_hook_handler(ctx: Context<ManageHooks>, hook_program: Pubkey) -> Result<()> {
+    let stake_pool = &mut ctx.accounts.stake_pool;
+    let registry = &mut ctx.accounts.hook_registry;
+
+    let index = registry
+        .active_hooks()
+        .iter()
+        .position(|hook| *hook == hook_program)
+        .ok_or(StakingError::HookNotFound)?;
+
+    // Swap-remove to keep the active hooks contiguous
+    let last = registry.hook_count as usize - 1;
+    registry.hooks[index] = registry.hooks[last];
+    registry.hooks[last] = Pubkey::default();
+    registry.hook_count -= 1;
+    stake_pool.hooks_enabled = registry.hook_count > 0;
+
+    emit!(HookRegistryUpdatedEvent {
+        stake_pool: stake_pool.key(),
+        hook_program,
+        added: false,
+        hook_count: registry.hook_count,
+    });
+
+    msg!("Hook program removed: {}", hook_program);
+
+    Ok(())
+}
@@ -0,0 +1,188 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::state::{RewardStream, RewardStreams, StakePool};
+use crate::errors::StakingError;
+
+/// Start emitting an extra reward token (pool authority only)
+#[derive(Accounts)]
+pub struct AddRewardToken<'info> {
+    /// Pool authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The stake pool
+    #[account(
+        mut,
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump,
+        has_one = authority @ StakingError::InvalidAuthority
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// The pool's reward streams (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = RewardStreams::LEN,
+        seeds = [RewardStreams::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump
+    )]
+    pub reward_streams: Account<'info, RewardStreams>,
+
+    /// The extra reward token
+    pub reward_mint: Account<'info, Mint>,
+
+    /// Vault holding the stream's reward tokens (funded by the authority)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        token::mint = reward_mint,
+        token::authority = stake_pool,
+        seeds = [b"stream_vault", stake_pool.key().as_ref(), reward_mint.key().as_ref()],
+        bump
+    )]
+    pub stream_vault: Account<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Stop emitting an extra reward token (pool authority only)
+#[derive(Accounts)]
+pub struct RemoveRewardToken<'info> {
+    /// Pool authority
+    pub authority: Signer<'info>,
+
+    /// The stake pool
+    #[account(
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump,
+        has_one = authority @ StakingError::InvalidAuthority
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// The pool's reward streams
+    #[account(
+        mut,
+        seeds = [RewardStreams::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump = reward_streams.bump
+    )]
+    pub reward_streams: Account<'info, RewardStreams>,
+}
+
+/// Event emitted when a reward stream is added or stopped
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RewardStreamUpdatedEvent {
+    pub stake_pool: Pubkey,
+    pub stream_id: u64,
+    pub mint: Pubkey,
+    pub reward_rate: u64,
+    pub active: bool,
+    pub timestamp: i64,
+}
+
+pub fn add_stream_handler(ctx: Context<AddRewardToken>, reward_rate: u64) -> Result<()> {
+    require!(reward_rate > 0, StakingError::InvalidAmount);
+
+    let stake_pool = &mut ctx.accounts.stake_pool;
+    let streams = &mut ctx.accounts.reward_streams;
+    let mint = ctx.accounts.reward_mint.key();
+    let now = Clock::get()?.unix_timestamp;
+
+    if streams.stake_pool == Pubkey::default() {
+        streams.stake_pool = stake_pool.key();
+        streams.next_stream_id = 1;
+        streams.bump = ctx.bumps.reward_streams;
+    }
+
+    require!(
+        !streams.streams.iter().any(|stream| stream.active && stream.mint == mint),
+        StakingError::RewardStreamExists
+    );
+
+    // Bring existing streams up to date before the slot layout changes
+    streams.update_all(stake_pool.total_weighted_stake, now)?;
+
+    // Prefer an empty slot, otherwise replace a stopped stream
+    let slot = streams
+        .streams
+        .iter()
+        .position(|stream| !stream.is_occupied())
+        .or_else(|| streams.streams.iter().position(|stream| !stream.active))
+        .ok_or(StakingError::RewardStreamsFull)?;
+
+    if !streams.streams[slot].is_occupied() {
+        stake_pool.reward_stream_count = stake_pool.reward_stream_count
+            .checked_add(1)
+            .ok_or(StakingError::MathOverflow)?;
+    }
+
+    let stream_id = streams.next_stream_id;
+    streams.next_stream_id = stream_id
+        .checked_add(1)
+        .ok_or(StakingError::MathOverflow)?;
+    streams.streams[slot] = RewardStream {
+        stream_id,
+        mint,
+        vault: ctx.accounts.stream_vault.key(),
+        reward_rate,
+        accumulated_reward_per_share: 0,
+        last_update_time: now,
+        active: true,
+    };
+
+    emit!(RewardStreamUpdatedEvent {
+        stake_pool: stake_pool.key(),
+        stream_id,
+        mint,
+        reward_rate,
+        active: true,
+        timestamp: now,
+    });
+
+    msg!("Added reward stream #{} for {} at {} per second", stream_id, mint, reward_rate);
+
+    Ok(())
+}
+
+pub fn remove_stream_handler(ctx: Context<RemoveRewardToken>, stream_id: u64) -> Result<()> {
+    let stake_pool = &ctx.accounts.stake_pool;
+    let streams = &mut ctx.accounts.reward_streams;
+    let now = Clock::get()?.unix_timestamp;
+
+    let stream = streams
+        .streams
+        .iter_mut()
+        .find(|stream| stream.active && stream.stream_id == stream_id)
+        .ok_or(StakingError::RewardStreamNotFound)?;
+
+    // Accrue up to now, then freeze the accumulator. The slot stays
+    // occupied so users can still claim what they earned.
+    stream.update(stake_pool.total_weighted_stake, now)?;
+    stream.active = false;
+    stream.reward_rate = 0;
+
+    emit!(RewardStreamUpdatedEvent {
+        stake_pool: stake_pool.key(),
+        stream_id,
+        mint: stream.mint,
+        reward_rate: 0,
+        active: false,
+        timestamp: now,
+    });
+
+    msg!("Stopped reward stream #{}", stream_id);
+
+    Ok(())
+}
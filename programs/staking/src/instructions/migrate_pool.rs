@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+
+use crate::state::StakePool;
+use crate::errors::StakingError;
+
+/// Grow a pool created under an older layout to the current `StakePool`
+/// size and bring it to the current version (pool authority only)
+#[derive(Accounts)]
+pub struct MigratePool<'info> {
+    /// Pool authority; pays the extra rent
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The stake pool to migrate. Unchecked because a pool created under an
+    /// older layout is too short to deserialize; owner, discriminator and
+    /// authority are checked by the handler.
+    /// CHECK: validated in the handler
+    #[account(mut, owner = crate::ID @ StakingError::InvalidPoolAccount)]
+    pub stake_pool: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Event emitted when a pool is migrated to a new layout
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PoolMigratedEvent {
+    pub stake_pool: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+    pub old_size: u64,
+    pub new_size: u64,
+    pub timestamp: i64,
+}
+
+pub fn handler(ctx: Context<MigratePool>) -> Result<()> {
+    let pool_info = ctx.accounts.stake_pool.to_account_info();
+    let old_size = pool_info.data_len();
+
+    {
+        let data = pool_info.try_borrow_data()?;
+        require!(
+            data.len() >= 8 + 32 && data[..8] == StakePool::DISCRIMINATOR,
+            StakingError::InvalidPoolAccount
+        );
+        // `authority` is the first field in every layout
+        let authority = Pubkey::try_from(&data[8..40]).map_err(|_| StakingError::InvalidPoolAccount)?;
+        require_keys_eq!(authority, ctx.accounts.authority.key(), StakingError::InvalidAuthority);
+    }
+
+    if old_size < StakePool::LEN {
+        let rent_due = Rent::get()?
+            .minimum_balance(StakePool::LEN)
+            .saturating_sub(pool_info.lamports());
+        if rent_due > 0 {
+            let rent_ctx = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: pool_info.clone(),
+                },
+            );
+            system_program::transfer(rent_ctx, rent_due)?;
+        }
+        // New fields sit where the old reserved bytes and the new tail are,
+        // so a zeroed extension leaves them at their defaults
+        pool_info.realloc(StakePool::LEN, true)?;
+    }
+
+    let mut stake_pool = StakePool::try_deserialize(&mut &pool_info.try_borrow_data()?[..])?;
+    let from_version = stake_pool.version;
+    require!(
+        from_version < StakePool::CURRENT_VERSION,
+        StakingError::AlreadyMigrated
+    );
+
+    stake_pool.migrate_to_v2()?;
+    stake_pool.try_serialize(&mut &mut pool_info.try_borrow_mut_data()?[..])?;
+
+    emit!(PoolMigratedEvent {
+        stake_pool: pool_info.key(),
+        from_version,
+        to_version: stake_pool.version,
+        old_size: old_size as u64,
+        new_size: StakePool::LEN as u64,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Migrated pool {} from v{} ({} bytes) to v{} ({} bytes)",
+        pool_info.key(),
+        from_version,
+        old_size,
+        stake_pool.version,
+        StakePool::LEN
+    );
+
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+
+use crate::state::{StakePool, UserStake};
+use crate::errors::StakingError;
+
+/// Grow a position created under the original 32-byte-reserve layout to the
+/// current `UserStake` size (permissionless; the caller pays the extra rent)
+///
+/// The pool must already be migrated. Every field added since sits in the
+/// zero-filled extension and starts at its default; legacy weights are
+/// picked up by `settle_rewards` on the position's next action.
+/// `reward_debt` is kept in token units, so it carries over unchanged when
+/// the pool's accumulator is rescaled.
+#[derive(Accounts)]
+pub struct MigrateUserStake<'info> {
+    /// Anyone may migrate a position (pays the extra rent)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The (already migrated) stake pool the position belongs to
+    #[account(
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// The position to migrate. Unchecked because a legacy position is too
+    /// short to deserialize; discriminator and pool are checked by the
+    /// handler.
+    /// CHECK: validated in the handler
+    #[account(mut, owner = crate::ID @ StakingError::InvalidUserStakeAccount)]
+    pub user_stake: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Event emitted when a position is migrated to the current layout
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UserStakeMigratedEvent {
+    pub user: Pubkey,
+    pub stake_pool: Pubkey,
+    pub old_size: u64,
+    pub new_size: u64,
+    pub payer: Pubkey,
+    pub timestamp: i64,
+}
+
+pub fn handler(ctx: Context<MigrateUserStake>) -> Result<()> {
+    let user_stake_info = ctx.accounts.user_stake.to_account_info();
+    let old_size = user_stake_info.data_len();
+
+    let owner = {
+        let data = user_stake_info.try_borrow_data()?;
+        require!(
+            data.len() >= 8 + 32 + 32 && data[..8] == UserStake::DISCRIMINATOR,
+            StakingError::InvalidUserStakeAccount
+        );
+        // `owner` and `stake_pool` lead every layout
+        let owner = Pubkey::try_from(&data[8..40]).map_err(|_| StakingError::InvalidUserStakeAccount)?;
+        let stake_pool = Pubkey::try_from(&data[40..72]).map_err(|_| StakingError::InvalidUserStakeAccount)?;
+        require_keys_eq!(stake_pool, ctx.accounts.stake_pool.key(), StakingError::StakePoolMismatch);
+        owner
+    };
+    require!(old_size < UserStake::LEN, StakingError::PositionAlreadyMigrated);
+
+    let rent_due = Rent::get()?
+        .minimum_balance(UserStake::LEN)
+        .saturating_sub(user_stake_info.lamports());
+    if rent_due > 0 {
+        let rent_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.payer.to_account_info(),
+                to: user_stake_info.clone(),
+            },
+        );
+        system_program::transfer(rent_ctx, rent_due)?;
+    }
+    // New fields sit where the old reserved bytes and the new tail are, so a
+    // zeroed extension leaves them at their defaults
+    user_stake_info.realloc(UserStake::LEN, true)?;
+
+    emit!(UserStakeMigratedEvent {
+        user: owner,
+        stake_pool: ctx.accounts.stake_pool.key(),
+        old_size: old_size as u64,
+        new_size: UserStake::LEN as u64,
+        payer: ctx.accounts.payer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Migrated position of {} from {} to {} bytes",
+        owner,
+        old_size,
+        UserStake::LEN
+    );
+
+    Ok(())
+}
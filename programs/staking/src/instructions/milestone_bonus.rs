@@ -0,0 +1,248 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::memo::Memo;
+
+use crate::state::{MilestoneBonus, StakePool, UserStake};
+use crate::memo::{tag_transfer, MemoAction};
+use crate::errors::StakingError;
+
+/// Create a pool's milestone bonus vault (pool authority only)
+#[derive(Accounts)]
+pub struct InitializeMilestoneBonus<'info> {
+    /// Pool authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The stake pool
+    #[account(
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump,
+        has_one = authority @ StakingError::InvalidAuthority
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// The milestone bonus config to create
+    #[account(
+        init,
+        payer = authority,
+        space = MilestoneBonus::LEN,
+        seeds = [MilestoneBonus::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump
+    )]
+    pub milestone_bonus: Account<'info, MilestoneBonus>,
+
+    /// The reward mint (bonuses are paid in it)
+    #[account(address = stake_pool.reward_mint @ StakingError::InvalidMint)]
+    pub reward_mint: Account<'info, Mint>,
+
+    /// Vault holding the bonus funds; anyone may top it up
+    #[account(
+        init,
+        payer = authority,
+        token::mint = reward_mint,
+        token::authority = stake_pool,
+        seeds = [MilestoneBonus::VAULT_SEED_PREFIX, stake_pool.key().as_ref()],
+        bump
+    )]
+    pub bonus_vault: Account<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Change the bonus paid per milestone (pool authority only)
+#[derive(Accounts)]
+pub struct SetMilestoneBonuses<'info> {
+    /// Pool authority
+    pub authority: Signer<'info>,
+
+    /// The stake pool
+    #[account(
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump,
+        has_one = authority @ StakingError::InvalidAuthority
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// The pool's milestone bonus config
+    #[account(
+        mut,
+        seeds = [MilestoneBonus::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump = milestone_bonus.bump
+    )]
+    pub milestone_bonus: Account<'info, MilestoneBonus>,
+}
+
+/// Claim every milestone bonus the position's streak has reached
+#[derive(Accounts)]
+pub struct ClaimMilestoneBonus<'info> {
+    /// Position owner
+    pub user: Signer<'info>,
+
+    /// The stake pool
+    #[account(
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// User's stake account
+    #[account(
+        mut,
+        seeds = [UserStake::SEED_PREFIX, stake_pool.key().as_ref(), user.key().as_ref()],
+        bump = user_stake.bump,
+        has_one = stake_pool @ StakingError::StakePoolMismatch,
+        constraint = user_stake.owner == user.key() @ StakingError::OwnerMismatch
+    )]
+    pub user_stake: Account<'info, UserStake>,
+
+    /// The pool's milestone bonus config
+    #[account(
+        mut,
+        seeds = [MilestoneBonus::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump = milestone_bonus.bump
+    )]
+    pub milestone_bonus: Account<'info, MilestoneBonus>,
+
+    /// The bonus vault
+    #[account(
+        mut,
+        address = milestone_bonus.vault @ StakingError::InvalidRewardVault
+    )]
+    pub bonus_vault: Account<'info, TokenAccount>,
+
+    /// User's reward token account
+    #[account(
+        mut,
+        constraint = user_reward_account.mint == stake_pool.reward_mint @ StakingError::InvalidMint,
+        constraint = user_reward_account.owner == user.key() @ StakingError::TokenAccountOwnerMismatch
+    )]
+    pub user_reward_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub memo_program: Program<'info, Memo>,
+}
+
+/// Event emitted when milestone bonuses are paid
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MilestoneBonusClaimedEvent {
+    pub user: Pubkey,
+    pub stake_pool: Pubkey,
+    /// Bitflags of the milestones paid by this claim
+    pub milestones: u8,
+    pub amount: u64,
+    pub unbroken_since: i64,
+    pub timestamp: i64,
+}
+
+pub fn initialize_handler(
+    ctx: Context<InitializeMilestoneBonus>,
+    bonus_amounts: [u64; 3],
+) -> Result<()> {
+    let milestone_bonus = &mut ctx.accounts.milestone_bonus;
+    milestone_bonus.stake_pool = ctx.accounts.stake_pool.key();
+    milestone_bonus.vault = ctx.accounts.bonus_vault.key();
+    milestone_bonus.bonus_amounts = bonus_amounts;
+    milestone_bonus.total_paid = 0;
+    milestone_bonus.bump = ctx.bumps.milestone_bonus;
+
+    msg!(
+        "Milestone bonuses for pool {}: {:?}",
+        milestone_bonus.stake_pool,
+        bonus_amounts
+    );
+
+    Ok(())
+}
+
+pub fn set_bonuses_handler(ctx: Context<SetMilestoneBonuses>, bonus_amounts: [u64; 3]) -> Result<()> {
+    ctx.accounts.milestone_bonus.bonus_amounts = bonus_amounts;
+
+    msg!("Milestone bonuses set to {:?}", bonus_amounts);
+
+    Ok(())
+}
+
+pub fn claim_handler(ctx: Context<ClaimMilestoneBonus>) -> Result<()> {
+    let user_stake = &mut ctx.accounts.user_stake;
+    let clock = Clock::get()?;
+
+    require!(!user_stake.frozen, StakingError::AccountFrozen);
+
+    let milestones = user_stake.claimable_milestones(clock.unix_timestamp);
+    require!(milestones != 0, StakingError::NoMilestoneReached);
+
+    let amount = ctx.accounts.milestone_bonus
+        .bonus_for(milestones)
+        .ok_or(StakingError::MathOverflow)?;
+    require!(
+        ctx.accounts.bonus_vault.amount >= amount,
+        StakingError::InsufficientBonusFunds
+    );
+
+    // Flag before paying so a milestone can never be paid twice
+    user_stake.claimed_milestones |= milestones;
+
+    if amount > 0 {
+        let stake_pool = &ctx.accounts.stake_pool;
+        let stake_mint_key = stake_pool.stake_mint;
+        let pool_id_seed = stake_pool.id_seed();
+        let pool_bump = stake_pool.bump;
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            StakePool::SEED_PREFIX,
+            stake_mint_key.as_ref(),
+            &pool_id_seed,
+            &[pool_bump],
+        ]];
+
+        tag_transfer(
+            &ctx.accounts.memo_program,
+            MemoAction::Bonus,
+            &stake_pool.key(),
+            &ctx.accounts.user.key(),
+        )?;
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.bonus_vault.to_account_info(),
+                to: ctx.accounts.user_reward_account.to_account_info(),
+                authority: stake_pool.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, amount)?;
+    }
+
+    let milestone_bonus = &mut ctx.accounts.milestone_bonus;
+    milestone_bonus.total_paid = milestone_bonus.total_paid
+        .checked_add(amount)
+        .ok_or(StakingError::MathOverflow)?;
+
+    emit!(MilestoneBonusClaimedEvent {
+        user: ctx.accounts.user.key(),
+        stake_pool: ctx.accounts.stake_pool.key(),
+        milestones,
+        amount,
+        unbroken_since: user_stake.unbroken_since,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Paid milestone bonus of {} (milestones {:#05b})", amount, milestones);
+
+    Ok(())
+}
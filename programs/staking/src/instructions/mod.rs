@@ -2,8 +2,315 @@ pub mod initialize;
 pub mod stake;
 pub mod unstake;
 pub mod claim_rewards;
+pub mod stake_native;
+pub mod unstake_native;
+pub mod manage_hooks;
+pub mod update_pool_config;
+pub mod kick_expired;
+pub mod freeze_user_stake;
+pub mod initialize_protocol_treasury;
+pub mod set_pool_creation_fee;
+pub mod sweep_protocol_fees;
+pub mod snapshot;
+pub mod manage_reward_streams;
+pub mod claim_stream_rewards;
+pub mod partner_boost;
+pub mod refresh_tier;
+pub mod early_unstake;
+pub mod claim_and_unstake;
+pub mod update_pool_metadata;
+pub mod recover_token;
+pub mod tier_benefits;
+pub mod set_pool_authority;
+pub mod spend_points;
+pub mod insurance_fund;
+pub mod crank_claims;
+pub mod settle_epoch;
+pub mod sweep_surplus;
+pub mod sync_vaults;
+pub mod assert_invariants;
+pub mod refresh_pool_snapshot;
+pub mod program_config;
+pub mod schedule_rate_change;
+pub mod milestone_bonus;
+pub mod claim_multi;
+pub mod migrate_pool;
+pub mod set_upgrade_authority;
+pub mod pause_pool;
+pub mod retarget_reward_rate;
+pub mod certificates;
+pub mod collateral;
+pub mod cross_boost;
+pub mod operator;
+pub mod clawback_rewards;
+pub mod migrate_user_stake;
 
-pub use initialize::*;
-pub use stake::*;
-pub use unstake::*;
-pub use claim_rewards::*;
+// Handlers are called by module path; only accounts, params, events and
+// shared helpers are re-exported so the per-module handler fns don't collide
+pub use initialize::{Initialize, InitializeParams, PoolInitializedEvent};
+pub use stake::{Stake, StakeEvent};
+pub use unstake::{Unstake, UnstakeEvent, EarlyUnstakeEvent};
+pub use claim_rewards::{ClaimRewards, ClaimShortfall, ClaimFeeEvent, ClaimTaxEvent, ClaimEvent};
+pub use stake_native::StakeNative;
+pub use unstake_native::UnstakeNative;
+pub use manage_hooks::{ManageHooks, HookRegistryUpdatedEvent};
+pub use update_pool_config::{UpdatePoolConfig, UpdatePoolConfigParams, PoolConfigUpdatedEvent};
+pub use kick_expired::{KickExpired, PositionKickedEvent};
+pub use freeze_user_stake::{SetUserFrozen, UserStakeFrozenEvent};
+pub use initialize_protocol_treasury::InitializeProtocolTreasury;
+pub use set_pool_creation_fee::SetPoolCreationFee;
+pub use sweep_protocol_fees::{SweepProtocolFees, ProtocolFeesSweptEvent};
+pub use snapshot::{
+    PublishSnapshotRoot, ClaimSnapshotReward, VerifySnapshotEntry, SnapshotPublishedEvent,
+    SnapshotClaimedEvent,
+};
+pub use manage_reward_streams::{AddRewardToken, RemoveRewardToken, RewardStreamUpdatedEvent};
+pub use claim_stream_rewards::{ClaimStreamRewards, StreamClaimEvent};
+pub use partner_boost::{
+    SetPartnerBoost, MovePartnerTokens, PartnerBoostUpdatedEvent, PartnerEscrowEvent,
+};
+pub use refresh_tier::{RefreshTier, TierRefreshedEvent};
+pub use early_unstake::{PreviewEarlyUnstakeFee, EarlyExitPreview, preview_early_exit};
+pub use claim_and_unstake::ClaimAndUnstake;
+pub use update_pool_metadata::{UpdatePoolMetadata, PoolMetadataUpdatedEvent};
+pub use recover_token::{RecoverToken, TokenRecoveredEvent};
+pub use tier_benefits::{InitializeTierBenefits, UpdateTierBenefits, TierBenefitUpdatedEvent};
+pub use set_pool_authority::{SetPoolAuthority, PoolAuthorityChangedEvent};
+pub use spend_points::{SpendPoints, PointsSpentEvent};
+pub use insurance_fund::{InitializeInsuranceFund, CoverShortfall, ShortfallCoveredEvent};
+pub use crank_claims::{SetAutoClaim, CrankClaims, AutoClaimUpdatedEvent, ClaimsCrankedEvent};
+pub use settle_epoch::{SettleEpoch, EpochSettledEvent};
+pub use sweep_surplus::{SweepSurplus, SurplusSweptEvent};
+pub use sync_vaults::{SyncVaults, VaultDiscrepancy, VaultsSyncedEvent};
+pub use assert_invariants::AssertInvariants;
+pub use refresh_pool_snapshot::RefreshPoolSnapshot;
+pub use program_config::{
+    InitializeProgramConfig, UpdateProgramConfig, ProgramConfigParams, ProgramConfigUpdate,
+    ProgramConfigUpdatedEvent,
+};
+pub use schedule_rate_change::{ScheduleRateChange, RateChangeScheduledEvent};
+pub use milestone_bonus::{
+    InitializeMilestoneBonus, SetMilestoneBonuses, ClaimMilestoneBonus, MilestoneBonusClaimedEvent,
+};
+pub use claim_multi::{CLAIM_MULTI_GROUP_LEN, ClaimMulti, MultiClaimEvent};
+pub use migrate_pool::{MigratePool, PoolMigratedEvent};
+pub use set_upgrade_authority::{SetUpgradeAuthority, UpgradeAuthorityChangedEvent};
+pub use pause_pool::{
+    SetPoolPaused, GuardianPause, PauseAll, UnpauseAll, PoolPauseChangedEvent,
+    ProtocolPauseChangedEvent,
+};
+pub use retarget_reward_rate::{RetargetRewardRate, RewardRateRetargetedEvent};
+pub use certificates::{
+    InitializeCertificates, SetCertificateTerms, IssueCertificate, RedeemCertificate,
+    CertificateIssuedEvent, CertificateRedeemedEvent,
+};
+pub use collateral::{
+    collateral_authority_address, LockPositionAsCollateral, ReleasePositionCollateral,
+    PositionCollateralChangedEvent,
+};
+pub use cross_boost::{RefreshCrossBoost, CrossBoostRefreshedEvent};
+pub use operator::{
+    EnableOperatorMode, PublishOperatorRoot, CollectOperatorRewards, ClaimOperatorRewards,
+    OperatorModeEnabledEvent, OperatorRootPublishedEvent, OperatorRewardsCollectedEvent,
+    OperatorRewardsClaimedEvent,
+};
+pub use clawback_rewards::{ClawbackRewards, RewardsClawedBackEvent};
+pub use migrate_user_stake::{MigrateUserStake, UserStakeMigratedEvent};
+
+// Client account modules generated by `#[derive(Accounts)]`, which
+// `#[program]` expects at the crate root
+pub(crate) use initialize::__client_accounts_initialize;
+pub(crate) use stake::__client_accounts_stake;
+pub(crate) use unstake::__client_accounts_unstake;
+pub(crate) use claim_rewards::__client_accounts_claim_rewards;
+pub(crate) use stake_native::__client_accounts_stake_native;
+pub(crate) use unstake_native::__client_accounts_unstake_native;
+pub(crate) use manage_hooks::__client_accounts_manage_hooks;
+pub(crate) use update_pool_config::__client_accounts_update_pool_config;
+pub(crate) use kick_expired::__client_accounts_kick_expired;
+pub(crate) use freeze_user_stake::__client_accounts_set_user_frozen;
+pub(crate) use initialize_protocol_treasury::__client_accounts_initialize_protocol_treasury;
+pub(crate) use set_pool_creation_fee::__client_accounts_set_pool_creation_fee;
+pub(crate) use sweep_protocol_fees::__client_accounts_sweep_protocol_fees;
+pub(crate) use snapshot::{
+    __client_accounts_publish_snapshot_root, __client_accounts_claim_snapshot_reward,
+    __client_accounts_verify_snapshot_entry,
+};
+pub(crate) use manage_reward_streams::{
+    __client_accounts_add_reward_token, __client_accounts_remove_reward_token,
+};
+pub(crate) use claim_stream_rewards::__client_accounts_claim_stream_rewards;
+pub(crate) use partner_boost::{
+    __client_accounts_set_partner_boost, __client_accounts_move_partner_tokens,
+};
+pub(crate) use refresh_tier::__client_accounts_refresh_tier;
+pub(crate) use early_unstake::__client_accounts_preview_early_unstake_fee;
+pub(crate) use claim_and_unstake::__client_accounts_claim_and_unstake;
+pub(crate) use update_pool_metadata::__client_accounts_update_pool_metadata;
+pub(crate) use recover_token::__client_accounts_recover_token;
+pub(crate) use tier_benefits::{
+    __client_accounts_initialize_tier_benefits, __client_accounts_update_tier_benefits,
+};
+pub(crate) use set_pool_authority::__client_accounts_set_pool_authority;
+pub(crate) use spend_points::__client_accounts_spend_points;
+pub(crate) use insurance_fund::{
+    __client_accounts_initialize_insurance_fund, __client_accounts_cover_shortfall,
+};
+pub(crate) use crank_claims::{__client_accounts_set_auto_claim, __client_accounts_crank_claims};
+pub(crate) use settle_epoch::__client_accounts_settle_epoch;
+pub(crate) use sweep_surplus::__client_accounts_sweep_surplus;
+pub(crate) use sync_vaults::__client_accounts_sync_vaults;
+pub(crate) use assert_invariants::__client_accounts_assert_invariants;
+pub(crate) use refresh_pool_snapshot::__client_accounts_refresh_pool_snapshot;
+pub(crate) use program_config::{
+    __client_accounts_initialize_program_config, __client_accounts_update_program_config,
+};
+pub(crate) use schedule_rate_change::__client_accounts_schedule_rate_change;
+pub(crate) use milestone_bonus::{
+    __client_accounts_initialize_milestone_bonus, __client_accounts_set_milestone_bonuses,
+    __client_accounts_claim_milestone_bonus,
+};
+pub(crate) use claim_multi::__client_accounts_claim_multi;
+pub(crate) use migrate_pool::__client_accounts_migrate_pool;
+pub(crate) use set_upgrade_authority::__client_accounts_set_upgrade_authority;
+pub(crate) use pause_pool::{
+    __client_accounts_set_pool_paused, __client_accounts_guardian_pause,
+    __client_accounts_pause_all, __client_accounts_unpause_all,
+};
+pub(crate) use retarget_reward_rate::__client_accounts_retarget_reward_rate;
+pub(crate) use certificates::{
+    __client_accounts_initialize_certificates, __client_accounts_set_certificate_terms,
+    __client_accounts_issue_certificate, __client_accounts_redeem_certificate,
+};
+pub(crate) use collateral::{
+    __client_accounts_lock_position_as_collateral, __client_accounts_release_position_collateral,
+};
+pub(crate) use cross_boost::__client_accounts_refresh_cross_boost;
+pub(crate) use operator::{
+    __client_accounts_enable_operator_mode, __client_accounts_publish_operator_root,
+    __client_accounts_collect_operator_rewards, __client_accounts_claim_operator_rewards,
+};
+pub(crate) use clawback_rewards::__client_accounts_clawback_rewards;
+pub(crate) use migrate_user_stake::__client_accounts_migrate_user_stake;
+#[cfg(feature = "cpi")]
+pub(crate) use initialize::__cpi_client_accounts_initialize;
+#[cfg(feature = "cpi")]
+pub(crate) use stake::__cpi_client_accounts_stake;
+#[cfg(feature = "cpi")]
+pub(crate) use unstake::__cpi_client_accounts_unstake;
+#[cfg(feature = "cpi")]
+pub(crate) use claim_rewards::__cpi_client_accounts_claim_rewards;
+#[cfg(feature = "cpi")]
+pub(crate) use stake_native::__cpi_client_accounts_stake_native;
+#[cfg(feature = "cpi")]
+pub(crate) use unstake_native::__cpi_client_accounts_unstake_native;
+#[cfg(feature = "cpi")]
+pub(crate) use manage_hooks::__cpi_client_accounts_manage_hooks;
+#[cfg(feature = "cpi")]
+pub(crate) use update_pool_config::__cpi_client_accounts_update_pool_config;
+#[cfg(feature = "cpi")]
+pub(crate) use kick_expired::__cpi_client_accounts_kick_expired;
+#[cfg(feature = "cpi")]
+pub(crate) use freeze_user_stake::__cpi_client_accounts_set_user_frozen;
+#[cfg(feature = "cpi")]
+pub(crate) use initialize_protocol_treasury::__cpi_client_accounts_initialize_protocol_treasury;
+#[cfg(feature = "cpi")]
+pub(crate) use set_pool_creation_fee::__cpi_client_accounts_set_pool_creation_fee;
+#[cfg(feature = "cpi")]
+pub(crate) use sweep_protocol_fees::__cpi_client_accounts_sweep_protocol_fees;
+#[cfg(feature = "cpi")]
+pub(crate) use snapshot::{
+    __cpi_client_accounts_publish_snapshot_root, __cpi_client_accounts_claim_snapshot_reward,
+    __cpi_client_accounts_verify_snapshot_entry,
+};
+#[cfg(feature = "cpi")]
+pub(crate) use manage_reward_streams::{
+    __cpi_client_accounts_add_reward_token, __cpi_client_accounts_remove_reward_token,
+};
+#[cfg(feature = "cpi")]
+pub(crate) use claim_stream_rewards::__cpi_client_accounts_claim_stream_rewards;
+#[cfg(feature = "cpi")]
+pub(crate) use partner_boost::{
+    __cpi_client_accounts_set_partner_boost, __cpi_client_accounts_move_partner_tokens,
+};
+#[cfg(feature = "cpi")]
+pub(crate) use refresh_tier::__cpi_client_accounts_refresh_tier;
+#[cfg(feature = "cpi")]
+pub(crate) use early_unstake::__cpi_client_accounts_preview_early_unstake_fee;
+#[cfg(feature = "cpi")]
+pub(crate) use claim_and_unstake::__cpi_client_accounts_claim_and_unstake;
+#[cfg(feature = "cpi")]
+pub(crate) use update_pool_metadata::__cpi_client_accounts_update_pool_metadata;
+#[cfg(feature = "cpi")]
+pub(crate) use recover_token::__cpi_client_accounts_recover_token;
+#[cfg(feature = "cpi")]
+pub(crate) use tier_benefits::{
+    __cpi_client_accounts_initialize_tier_benefits, __cpi_client_accounts_update_tier_benefits,
+};
+#[cfg(feature = "cpi")]
+pub(crate) use set_pool_authority::__cpi_client_accounts_set_pool_authority;
+#[cfg(feature = "cpi")]
+pub(crate) use spend_points::__cpi_client_accounts_spend_points;
+#[cfg(feature = "cpi")]
+pub(crate) use insurance_fund::{
+    __cpi_client_accounts_initialize_insurance_fund, __cpi_client_accounts_cover_shortfall,
+};
+#[cfg(feature = "cpi")]
+pub(crate) use crank_claims::{
+    __cpi_client_accounts_set_auto_claim, __cpi_client_accounts_crank_claims,
+};
+#[cfg(feature = "cpi")]
+pub(crate) use settle_epoch::__cpi_client_accounts_settle_epoch;
+#[cfg(feature = "cpi")]
+pub(crate) use sweep_surplus::__cpi_client_accounts_sweep_surplus;
+#[cfg(feature = "cpi")]
+pub(crate) use sync_vaults::__cpi_client_accounts_sync_vaults;
+#[cfg(feature = "cpi")]
+pub(crate) use assert_invariants::__cpi_client_accounts_assert_invariants;
+#[cfg(feature = "cpi")]
+pub(crate) use refresh_pool_snapshot::__cpi_client_accounts_refresh_pool_snapshot;
+#[cfg(feature = "cpi")]
+pub(crate) use program_config::{
+    __cpi_client_accounts_initialize_program_config, __cpi_client_accounts_update_program_config,
+};
+#[cfg(feature = "cpi")]
+pub(crate) use schedule_rate_change::__cpi_client_accounts_schedule_rate_change;
+#[cfg(feature = "cpi")]
+pub(crate) use milestone_bonus::{
+    __cpi_client_accounts_initialize_milestone_bonus, __cpi_client_accounts_set_milestone_bonuses,
+    __cpi_client_accounts_claim_milestone_bonus,
+};
+#[cfg(feature = "cpi")]
+pub(crate) use claim_multi::__cpi_client_accounts_claim_multi;
+#[cfg(feature = "cpi")]
+pub(crate) use migrate_pool::__cpi_client_accounts_migrate_pool;
+#[cfg(feature = "cpi")]
+pub(crate) use set_upgrade_authority::__cpi_client_accounts_set_upgrade_authority;
+#[cfg(feature = "cpi")]
+pub(crate) use pause_pool::{
+    __cpi_client_accounts_set_pool_paused, __cpi_client_accounts_guardian_pause,
+    __cpi_client_accounts_pause_all, __cpi_client_accounts_unpause_all,
+};
+#[cfg(feature = "cpi")]
+pub(crate) use retarget_reward_rate::__cpi_client_accounts_retarget_reward_rate;
+#[cfg(feature = "cpi")]
+pub(crate) use certificates::{
+    __cpi_client_accounts_initialize_certificates, __cpi_client_accounts_set_certificate_terms,
+    __cpi_client_accounts_issue_certificate, __cpi_client_accounts_redeem_certificate,
+};
+#[cfg(feature = "cpi")]
+pub(crate) use collateral::{
+    __cpi_client_accounts_lock_position_as_collateral,
+    __cpi_client_accounts_release_position_collateral,
+};
+#[cfg(feature = "cpi")]
+pub(crate) use cross_boost::__cpi_client_accounts_refresh_cross_boost;
+#[cfg(feature = "cpi")]
+pub(crate) use operator::{
+    __cpi_client_accounts_enable_operator_mode, __cpi_client_accounts_publish_operator_root,
+    __cpi_client_accounts_collect_operator_rewards, __cpi_client_accounts_claim_operator_rewards,
+};
+#[cfg(feature = "cpi")]
+pub(crate) use clawback_rewards::__cpi_client_accounts_clawback_rewards;
+#[cfg(feature = "cpi")]
+pub(crate) use migrate_user_stake::__cpi_client_accounts_migrate_user_stake;
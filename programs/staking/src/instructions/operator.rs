@@ -0,0 +1,452 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::memo::Memo;
+
+use crate::state::{OperatorBook, OperatorClaim, PoolStats, StakePool, UserStake};
+use crate::memo::{tag_transfer, MemoAction};
+use crate::errors::StakingError;
+use crate::constants;
+use crate::{update_rewards, settle_rewards};
+
+use super::claim_rewards::{pay_owed_rewards, PayoutAccounts};
+
+/// Turn a position into an operator position (position owner only)
+///
+/// A custodial operator (exchange, fund) stakes a pooled amount under its
+/// own `UserStake` and tracks each beneficiary's share off-chain. From here
+/// on the position's rewards, including any already owed, are paid only
+/// into the escrow, from which beneficiaries claim with a merkle proof.
+/// There is no way back to a regular position.
+#[derive(Accounts)]
+pub struct EnableOperatorMode<'info> {
+    /// Operator owning the position
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    /// The stake pool
+    #[account(
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// The operator's stake account
+    #[account(
+        mut,
+        seeds = [UserStake::SEED_PREFIX, stake_pool.key().as_ref(), operator.key().as_ref()],
+        bump = user_stake.bump,
+        has_one = stake_pool @ StakingError::StakePoolMismatch,
+        constraint = user_stake.owner == operator.key() @ StakingError::OwnerMismatch
+    )]
+    pub user_stake: Account<'info, UserStake>,
+
+    /// The position's sub-account ledger
+    #[account(
+        init,
+        payer = operator,
+        space = OperatorBook::LEN,
+        seeds = [OperatorBook::SEED_PREFIX, user_stake.key().as_ref()],
+        bump
+    )]
+    pub operator_book: Account<'info, OperatorBook>,
+
+    /// The pool's reward mint
+    #[account(address = stake_pool.reward_mint @ StakingError::InvalidMint)]
+    pub reward_mint: Account<'info, Mint>,
+
+    /// Escrow holding collected rewards until beneficiaries claim them
+    #[account(
+        init,
+        payer = operator,
+        token::mint = reward_mint,
+        token::authority = stake_pool,
+        seeds = [b"operator_escrow", operator_book.key().as_ref()],
+        bump
+    )]
+    pub operator_escrow: Account<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Publish a new sub-account root for an operator position (operator only)
+#[derive(Accounts)]
+pub struct PublishOperatorRoot<'info> {
+    /// Operator owning the position
+    pub operator: Signer<'info>,
+
+    /// The operator's stake account
+    #[account(
+        constraint = user_stake.key() == operator_book.user_stake @ StakingError::StakePoolMismatch
+    )]
+    pub user_stake: Account<'info, UserStake>,
+
+    /// The position's sub-account ledger
+    #[account(
+        mut,
+        seeds = [OperatorBook::SEED_PREFIX, user_stake.key().as_ref()],
+        bump = operator_book.bump,
+        has_one = operator @ StakingError::InvalidAuthority
+    )]
+    pub operator_book: Account<'info, OperatorBook>,
+}
+
+/// Move an operator position's rewards into its escrow (permissionless)
+#[derive(Accounts)]
+pub struct CollectOperatorRewards<'info> {
+    /// Anyone may collect on the beneficiaries' behalf
+    pub caller: Signer<'info>,
+
+    /// The stake pool
+    #[account(
+        mut,
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// The pool's statistics account
+    #[account(
+        mut,
+        seeds = [PoolStats::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump = pool_stats.bump
+    )]
+    pub pool_stats: Account<'info, PoolStats>,
+
+    /// The operator's stake account
+    #[account(
+        mut,
+        seeds = [UserStake::SEED_PREFIX, stake_pool.key().as_ref(), user_stake.owner.as_ref()],
+        bump = user_stake.bump,
+        has_one = stake_pool @ StakingError::StakePoolMismatch,
+        constraint = user_stake.is_operator() @ StakingError::OperatorEscrowRequired
+    )]
+    pub user_stake: Account<'info, UserStake>,
+
+    /// The position's sub-account ledger
+    #[account(
+        mut,
+        seeds = [OperatorBook::SEED_PREFIX, user_stake.key().as_ref()],
+        bump = operator_book.bump
+    )]
+    pub operator_book: Account<'info, OperatorBook>,
+
+    /// The position's reward escrow
+    #[account(
+        mut,
+        address = user_stake.operator_escrow @ StakingError::OperatorEscrowRequired
+    )]
+    pub operator_escrow: Account<'info, TokenAccount>,
+
+    /// Pool's reward vault
+    #[account(
+        mut,
+        constraint = reward_vault.key() == stake_pool.reward_vault @ StakingError::InvalidRewardVault
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    /// Protocol treasury's reward token account (required when the pool
+    /// charges a claim fee)
+    #[account(
+        mut,
+        constraint = fee_treasury.mint == stake_pool.reward_mint @ StakingError::InvalidMint,
+        constraint = fee_treasury.owner == constants::TREASURY_ADDRESS @ StakingError::TokenAccountOwnerMismatch
+    )]
+    pub fee_treasury: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub memo_program: Program<'info, Memo>,
+}
+
+/// Claim a beneficiary's rewards from an operator position's escrow
+#[derive(Accounts)]
+pub struct ClaimOperatorRewards<'info> {
+    /// Beneficiary named in the operator's root
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    /// The stake pool
+    #[account(
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// The position's sub-account ledger
+    #[account(
+        mut,
+        seeds = [OperatorBook::SEED_PREFIX, operator_book.user_stake.as_ref()],
+        bump = operator_book.bump,
+        has_one = stake_pool @ StakingError::StakePoolMismatch
+    )]
+    pub operator_book: Account<'info, OperatorBook>,
+
+    /// Beneficiary's claim record (created on first claim)
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        space = OperatorClaim::LEN,
+        seeds = [OperatorClaim::SEED_PREFIX, operator_book.key().as_ref(), beneficiary.key().as_ref()],
+        bump
+    )]
+    pub operator_claim: Account<'info, OperatorClaim>,
+
+    /// The position's reward escrow
+    #[account(
+        mut,
+        address = operator_book.escrow @ StakingError::OperatorEscrowRequired
+    )]
+    pub operator_escrow: Account<'info, TokenAccount>,
+
+    /// Beneficiary's reward token account
+    #[account(
+        mut,
+        constraint = beneficiary_token_account.mint == stake_pool.reward_mint @ StakingError::InvalidMint,
+        constraint = beneficiary_token_account.owner == beneficiary.key() @ StakingError::TokenAccountOwnerMismatch
+    )]
+    pub beneficiary_token_account: Account<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub memo_program: Program<'info, Memo>,
+}
+
+/// Event emitted when a position switches to operator mode
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OperatorModeEnabledEvent {
+    pub operator: Pubkey,
+    pub stake_pool: Pubkey,
+    pub operator_book: Pubkey,
+    pub escrow: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Event emitted when an operator publishes a sub-account root
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OperatorRootPublishedEvent {
+    pub operator_book: Pubkey,
+    pub merkle_root: [u8; 32],
+    pub root_version: u64,
+    pub total_balance: u64,
+    pub total_rewards: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when an operator position's rewards reach its escrow
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OperatorRewardsCollectedEvent {
+    pub operator_book: Pubkey,
+    pub stake_pool: Pubkey,
+    pub amount: u64,
+    pub total_collected: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a beneficiary claims from an operator position
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OperatorRewardsClaimedEvent {
+    pub beneficiary: Pubkey,
+    pub operator_book: Pubkey,
+    pub amount: u64,
+    pub cumulative_claimed: u64,
+    pub timestamp: i64,
+}
+
+pub fn enable_handler(ctx: Context<EnableOperatorMode>) -> Result<()> {
+    require!(!ctx.accounts.user_stake.frozen, StakingError::AccountFrozen);
+
+    let now = Clock::get()?.unix_timestamp;
+    let book = &mut ctx.accounts.operator_book;
+    book.stake_pool = ctx.accounts.stake_pool.key();
+    book.user_stake = ctx.accounts.user_stake.key();
+    book.operator = ctx.accounts.operator.key();
+    book.escrow = ctx.accounts.operator_escrow.key();
+    book.updated_at = now;
+    book.bump = ctx.bumps.operator_book;
+
+    ctx.accounts.user_stake.operator_escrow = book.escrow;
+
+    emit!(OperatorModeEnabledEvent {
+        operator: book.operator,
+        stake_pool: book.stake_pool,
+        operator_book: book.key(),
+        escrow: book.escrow,
+        timestamp: now,
+    });
+
+    msg!("Operator mode enabled; rewards now go to escrow {}", book.escrow);
+
+    Ok(())
+}
+
+pub fn publish_root_handler(
+    ctx: Context<PublishOperatorRoot>,
+    merkle_root: [u8; 32],
+    total_balance: u64,
+    total_rewards: u64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let book = &mut ctx.accounts.operator_book;
+    book.publish_root(
+        merkle_root,
+        total_balance,
+        total_rewards,
+        ctx.accounts.user_stake.staked_amount,
+        now,
+    )?;
+
+    emit!(OperatorRootPublishedEvent {
+        operator_book: book.key(),
+        merkle_root,
+        root_version: book.root_version,
+        total_balance,
+        total_rewards,
+        timestamp: now,
+    });
+
+    msg!(
+        "Operator root v{}: {} staked, {} rewards allocated",
+        book.root_version,
+        total_balance,
+        total_rewards
+    );
+
+    Ok(())
+}
+
+pub fn collect_handler(ctx: Context<CollectOperatorRewards>) -> Result<()> {
+    let stake_pool = &mut ctx.accounts.stake_pool;
+    let user_stake = &mut ctx.accounts.user_stake;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(!user_stake.frozen, StakingError::AccountFrozen);
+    require!(stake_pool.is_claim_window_open(now), StakingError::ClaimWindowClosed);
+    require!(
+        user_stake.claim_interval_elapsed(stake_pool.min_claim_interval, now),
+        StakingError::ClaimTooSoon
+    );
+
+    update_rewards(stake_pool, now)?;
+    settle_rewards(user_stake, stake_pool.accumulated_reward_per_share, now)?;
+    require!(user_stake.owed_rewards > 0, StakingError::NoPendingRewards);
+
+    let collected = pay_owed_rewards(
+        stake_pool,
+        user_stake,
+        &mut ctx.accounts.pool_stats,
+        PayoutAccounts {
+            reward_vault: &ctx.accounts.reward_vault,
+            user_reward_account: &ctx.accounts.operator_escrow,
+            fee_treasury: ctx.accounts.fee_treasury.as_ref(),
+            token_program: &ctx.accounts.token_program,
+            memo_program: &ctx.accounts.memo_program,
+        },
+        now,
+    )?;
+    require!(collected > 0, StakingError::NoPendingRewards);
+
+    let book = &mut ctx.accounts.operator_book;
+    book.total_collected = book.total_collected
+        .checked_add(collected)
+        .ok_or(StakingError::MathOverflow)?;
+
+    emit!(OperatorRewardsCollectedEvent {
+        operator_book: book.key(),
+        stake_pool: stake_pool.key(),
+        amount: collected,
+        total_collected: book.total_collected,
+        timestamp: now,
+    });
+
+    msg!("Collected {} rewards into operator escrow", collected);
+
+    Ok(())
+}
+
+pub fn operator_claim_handler(
+    ctx: Context<ClaimOperatorRewards>,
+    balance: u64,
+    cumulative_rewards: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let beneficiary = ctx.accounts.beneficiary.key();
+    let book = &mut ctx.accounts.operator_book;
+
+    require!(
+        book.verify_entry(&beneficiary, balance, cumulative_rewards, &proof),
+        StakingError::InvalidMerkleProof
+    );
+
+    let claim = &mut ctx.accounts.operator_claim;
+    let amount = cumulative_rewards.saturating_sub(claim.claimed);
+    require!(amount > 0, StakingError::NoPendingRewards);
+
+    let total_claimed = book.total_claimed
+        .checked_add(amount)
+        .ok_or(StakingError::MathOverflow)?;
+    require!(
+        total_claimed <= book.total_collected,
+        StakingError::InvalidOperatorTotals
+    );
+    book.total_claimed = total_claimed;
+    claim.claimed = cumulative_rewards;
+    claim.bump = ctx.bumps.operator_claim;
+
+    // Pay out via the pool PDA, which owns the escrow
+    let stake_pool = &ctx.accounts.stake_pool;
+    let stake_mint_key = stake_pool.stake_mint;
+    let pool_id_seed = stake_pool.id_seed();
+    let pool_bump = stake_pool.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        StakePool::SEED_PREFIX,
+        stake_mint_key.as_ref(),
+        &pool_id_seed,
+        &[pool_bump],
+    ]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.operator_escrow.to_account_info(),
+            to: ctx.accounts.beneficiary_token_account.to_account_info(),
+            authority: stake_pool.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)?;
+    tag_transfer(
+        &ctx.accounts.memo_program,
+        MemoAction::Claim,
+        &stake_pool.key(),
+        &beneficiary,
+    )?;
+
+    emit!(OperatorRewardsClaimedEvent {
+        beneficiary,
+        operator_book: book.key(),
+        amount,
+        cumulative_claimed: cumulative_rewards,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Beneficiary claimed {} rewards from operator position", amount);
+
+    Ok(())
+}
@@ -0,0 +1,342 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::state::{
+    BoostTable, PartnerBoostConfig, PartnerEscrow, RewardStreams, StakePool, UserRewardStreams,
+    UserStake,
+};
+use crate::errors::StakingError;
+use crate::streams::{sync_reward_streams, WeightChange};
+use crate::{update_rewards, settle_rewards, rebase_weight};
+
+/// Add, update, or remove a partner token boost (pool authority only)
+#[derive(Accounts)]
+pub struct SetPartnerBoost<'info> {
+    /// Pool authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The stake pool
+    #[account(
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump,
+        has_one = authority @ StakingError::InvalidAuthority
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// The pool's boost table (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = BoostTable::LEN,
+        seeds = [BoostTable::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump
+    )]
+    pub boost_table: Account<'info, BoostTable>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Lock or unlock partner tokens in the user's escrow
+#[derive(Accounts)]
+pub struct MovePartnerTokens<'info> {
+    /// User locking or unlocking partner tokens
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// The stake pool being boosted
+    #[account(
+        mut,
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// User's stake account in the main pool
+    #[account(
+        mut,
+        seeds = [UserStake::SEED_PREFIX, stake_pool.key().as_ref(), user.key().as_ref()],
+        bump = user_stake.bump,
+        has_one = stake_pool @ StakingError::StakePoolMismatch,
+        constraint = user_stake.owner == user.key() @ StakingError::OwnerMismatch
+    )]
+    pub user_stake: Account<'info, UserStake>,
+
+    /// The pool's boost table
+    #[account(
+        seeds = [BoostTable::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump = boost_table.bump
+    )]
+    pub boost_table: Account<'info, BoostTable>,
+
+    /// User's partner escrow record
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = PartnerEscrow::LEN,
+        seeds = [PartnerEscrow::SEED_PREFIX, stake_pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub partner_escrow: Account<'info, PartnerEscrow>,
+
+    /// The partner token mint
+    pub partner_mint: Account<'info, Mint>,
+
+    /// Escrow vault for the user's partner tokens
+    #[account(
+        init_if_needed,
+        payer = user,
+        token::mint = partner_mint,
+        token::authority = stake_pool,
+        seeds = [
+            b"partner_vault",
+            stake_pool.key().as_ref(),
+            user.key().as_ref(),
+            partner_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub partner_vault: Account<'info, TokenAccount>,
+
+    /// User's partner token account
+    #[account(
+        mut,
+        constraint = user_partner_account.mint == partner_mint.key() @ StakingError::InvalidMint,
+        constraint = user_partner_account.owner == user.key() @ StakingError::TokenAccountOwnerMismatch
+    )]
+    pub user_partner_account: Account<'info, TokenAccount>,
+
+    /// The pool's extra reward streams (required once the pool has streams)
+    #[account(
+        mut,
+        seeds = [RewardStreams::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump = reward_streams.bump
+    )]
+    pub reward_streams: Option<Account<'info, RewardStreams>>,
+
+    /// User's extra reward stream positions (required once the pool has streams)
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserRewardStreams::LEN,
+        seeds = [UserRewardStreams::SEED_PREFIX, stake_pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_reward_streams: Option<Account<'info, UserRewardStreams>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Event emitted when the boost table changes
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PartnerBoostUpdatedEvent {
+    pub stake_pool: Pubkey,
+    pub partner_mint: Pubkey,
+    pub min_amount: u64,
+    pub bonus_bps: u64,
+}
+
+/// Event emitted when a user's partner escrow changes
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PartnerEscrowEvent {
+    pub user: Pubkey,
+    pub stake_pool: Pubkey,
+    pub partner_mint: Pubkey,
+    pub amount: u64,
+    pub locked: bool,
+    pub escrowed_amount: u64,
+    pub boost_bps: u64,
+    pub timestamp: i64,
+}
+
+pub fn set_handler(
+    ctx: Context<SetPartnerBoost>,
+    partner_mint: Pubkey,
+    min_amount: u64,
+    bonus_bps: u64,
+) -> Result<()> {
+    require!(bonus_bps <= BoostTable::MAX_BONUS_BPS, StakingError::InvalidConfig);
+    require!(partner_mint != Pubkey::default(), StakingError::InvalidMint);
+
+    let table = &mut ctx.accounts.boost_table;
+    table.stake_pool = ctx.accounts.stake_pool.key();
+    table.bump = ctx.bumps.boost_table;
+
+    let existing = table
+        .entries
+        .iter()
+        .position(|entry| entry.partner_mint == partner_mint);
+
+    if bonus_bps == 0 {
+        // A zero bonus removes the entry
+        let slot = existing.ok_or(StakingError::InvalidMint)?;
+        table.entries[slot] = PartnerBoostConfig::default();
+    } else {
+        let slot = existing
+            .or_else(|| table.entries.iter().position(|entry| entry.partner_mint == Pubkey::default()))
+            .ok_or(StakingError::InvalidConfig)?;
+        table.entries[slot] = PartnerBoostConfig {
+            partner_mint,
+            min_amount,
+            bonus_bps,
+        };
+    }
+
+    emit!(PartnerBoostUpdatedEvent {
+        stake_pool: table.stake_pool,
+        partner_mint,
+        min_amount,
+        bonus_bps,
+    });
+
+    msg!("Partner boost for {}: {} bps at {} tokens", partner_mint, bonus_bps, min_amount);
+
+    Ok(())
+}
+
+pub fn lock_partner_handler(ctx: Context<MovePartnerTokens>, amount: u64) -> Result<()> {
+    require!(amount > 0, StakingError::InvalidAmount);
+
+    let partner_mint = ctx.accounts.partner_mint.key();
+    let escrow = &mut ctx.accounts.partner_escrow;
+    if escrow.owner == Pubkey::default() {
+        escrow.owner = ctx.accounts.user.key();
+        escrow.stake_pool = ctx.accounts.stake_pool.key();
+        escrow.bump = ctx.bumps.partner_escrow;
+    }
+
+    // One partner token at a time; unlock fully before switching
+    require!(
+        escrow.partner_mint == Pubkey::default() || escrow.partner_mint == partner_mint,
+        StakingError::InvalidMint
+    );
+    require!(
+        ctx.accounts.boost_table.find(&partner_mint).is_some(),
+        StakingError::InvalidMint
+    );
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.user_partner_account.to_account_info(),
+            to: ctx.accounts.partner_vault.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    let escrow = &mut ctx.accounts.partner_escrow;
+    escrow.partner_mint = partner_mint;
+    escrow.amount = escrow.amount
+        .checked_add(amount)
+        .ok_or(StakingError::MathOverflow)?;
+
+    apply_boost(ctx, amount, true)
+}
+
+pub fn unlock_partner_handler(ctx: Context<MovePartnerTokens>, amount: u64) -> Result<()> {
+    require!(amount > 0, StakingError::InvalidAmount);
+
+    let escrow = &ctx.accounts.partner_escrow;
+    require_keys_eq!(
+        escrow.partner_mint,
+        ctx.accounts.partner_mint.key(),
+        StakingError::InvalidMint
+    );
+    require!(escrow.amount >= amount, StakingError::InsufficientStake);
+
+    // Return partner tokens via PDA signer
+    let stake_pool = &ctx.accounts.stake_pool;
+    let stake_mint_key = stake_pool.stake_mint;
+    let pool_id_seed = stake_pool.id_seed();
+    let pool_bump = stake_pool.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        StakePool::SEED_PREFIX,
+        stake_mint_key.as_ref(),
+        &pool_id_seed,
+        &[pool_bump],
+    ]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.partner_vault.to_account_info(),
+            to: ctx.accounts.user_partner_account.to_account_info(),
+            authority: stake_pool.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    let escrow = &mut ctx.accounts.partner_escrow;
+    escrow.amount -= amount;
+    if escrow.amount == 0 {
+        escrow.partner_mint = Pubkey::default();
+    }
+
+    apply_boost(ctx, amount, false)
+}
+
+/// Settle rewards at the old multiplier, then apply the escrow's new boost
+fn apply_boost(ctx: Context<MovePartnerTokens>, amount: u64, locked: bool) -> Result<()> {
+    let stake_pool = &mut ctx.accounts.stake_pool;
+    let user_stake = &mut ctx.accounts.user_stake;
+    let escrow = &ctx.accounts.partner_escrow;
+    let clock = Clock::get()?;
+    let total_weighted_before = stake_pool.total_weighted_stake;
+    let user_weighted_before = user_stake.weighted_stake;
+
+    update_rewards(stake_pool, clock.unix_timestamp)?;
+    settle_rewards(user_stake, stake_pool.accumulated_reward_per_share, clock.unix_timestamp)?;
+
+    user_stake.partner_boost_bps = ctx
+        .accounts
+        .boost_table
+        .bonus_for(&escrow.partner_mint, escrow.amount);
+    rebase_weight(stake_pool, user_stake, clock.unix_timestamp)?;
+
+    sync_reward_streams(
+        &ctx.accounts.stake_pool,
+        ctx.accounts.reward_streams.as_mut(),
+        ctx.accounts.user_reward_streams.as_mut(),
+        ctx.accounts.user.key(),
+        ctx.bumps.user_reward_streams,
+        WeightChange {
+            total_weighted_before,
+            user_weighted_before,
+            user_weighted_after: ctx.accounts.user_stake.weighted_stake,
+        },
+        clock.unix_timestamp,
+    )?;
+
+    let stake_pool = &ctx.accounts.stake_pool;
+    let user_stake = &ctx.accounts.user_stake;
+    let escrow = &ctx.accounts.partner_escrow;
+
+    emit!(PartnerEscrowEvent {
+        user: user_stake.owner,
+        stake_pool: stake_pool.key(),
+        partner_mint: ctx.accounts.partner_mint.key(),
+        amount,
+        locked,
+        escrowed_amount: escrow.amount,
+        boost_bps: user_stake.partner_boost_bps,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Partner boost now {} bps", user_stake.partner_boost_bps);
+
+    Ok(())
+}
@@ -0,0 +1,168 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{ProgramConfig, StakePool};
+use crate::errors::StakingError;
+
+/// Pause or unpause a pool (pool authority only)
+#[derive(Accounts)]
+pub struct SetPoolPaused<'info> {
+    /// Pool authority
+    pub authority: Signer<'info>,
+
+    /// The stake pool
+    #[account(
+        mut,
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump,
+        has_one = authority @ StakingError::InvalidAuthority
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+}
+
+/// Pause any pool for incident response (program guardian only). The
+/// guardian can't unpause; only the pool authority can.
+#[derive(Accounts)]
+pub struct GuardianPause<'info> {
+    /// Guardian from the program config
+    pub guardian: Signer<'info>,
+
+    /// The program config naming the guardian
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = program_config.bump,
+        constraint = program_config.is_guardian(&guardian.key()) @ StakingError::InvalidAuthority
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// The stake pool to pause
+    #[account(
+        mut,
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+}
+
+/// Pause every pool at once for a protocol-wide incident (program admin or
+/// guardian). Pool authorities can't override it.
+#[derive(Accounts)]
+pub struct PauseAll<'info> {
+    /// Program admin or guardian
+    pub signer: Signer<'info>,
+
+    /// The program config holding the protocol-wide pause
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = program_config.bump,
+        constraint = program_config.admin == signer.key()
+            || program_config.is_guardian(&signer.key()) @ StakingError::InvalidAuthority
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+/// Lift the protocol-wide pause (program admin only). Pools paused by their
+/// own flag stay paused.
+#[derive(Accounts)]
+pub struct UnpauseAll<'info> {
+    /// Program admin
+    pub admin: Signer<'info>,
+
+    /// The program config holding the protocol-wide pause
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = program_config.bump,
+        has_one = admin @ StakingError::InvalidAuthority
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+/// Event emitted when a pool is paused or unpaused
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PoolPauseChangedEvent {
+    pub stake_pool: Pubkey,
+    pub paused: bool,
+    /// Pool authority or guardian that made the change
+    pub changed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+pub fn set_paused_handler(ctx: Context<SetPoolPaused>, paused: bool) -> Result<()> {
+    let stake_pool = &mut ctx.accounts.stake_pool;
+    stake_pool.paused = paused;
+
+    emit!(PoolPauseChangedEvent {
+        stake_pool: stake_pool.key(),
+        paused,
+        changed_by: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Pool {}", if paused { "paused" } else { "unpaused" });
+
+    Ok(())
+}
+
+pub fn guardian_pause_handler(ctx: Context<GuardianPause>) -> Result<()> {
+    let stake_pool = &mut ctx.accounts.stake_pool;
+    stake_pool.paused = true;
+
+    emit!(PoolPauseChangedEvent {
+        stake_pool: stake_pool.key(),
+        paused: true,
+        changed_by: ctx.accounts.guardian.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Pool paused by guardian {}", ctx.accounts.guardian.key());
+
+    Ok(())
+}
+
+/// Event emitted when the protocol-wide pause is set or lifted
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProtocolPauseChangedEvent {
+    pub paused: bool,
+    /// Admin or guardian that made the change
+    pub changed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+pub fn pause_all_handler(ctx: Context<PauseAll>) -> Result<()> {
+    ctx.accounts.program_config.paused = true;
+
+    emit!(ProtocolPauseChangedEvent {
+        paused: true,
+        changed_by: ctx.accounts.signer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("All pools paused by {}", ctx.accounts.signer.key());
+
+    Ok(())
+}
+
+pub fn unpause_all_handler(ctx: Context<UnpauseAll>) -> Result<()> {
+    ctx.accounts.program_config.paused = false;
+
+    emit!(ProtocolPauseChangedEvent {
+        paused: false,
+        changed_by: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Protocol-wide pause lifted");
+
+    Ok(())
+}
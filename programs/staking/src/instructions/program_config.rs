@@ -0,0 +1,169 @@
+use anchor_lang::prelude::*;
+
+use crate::program::Staking;
+use crate::state::ProgramConfig;
+use crate::errors::StakingError;
+
+/// Create the global program config (program upgrade authority only)
+#[derive(Accounts)]
+pub struct InitializeProgramConfig<'info> {
+    /// Program upgrade authority, becomes the protocol admin
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// The program config PDA
+    #[account(
+        init,
+        payer = admin,
+        space = ProgramConfig::LEN,
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// This program
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ StakingError::InvalidAuthority
+    )]
+    pub program: Program<'info, Staking>,
+
+    /// This program's data account, proving the upgrade authority
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ StakingError::InvalidAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Update the global program config (protocol admin only)
+#[derive(Accounts)]
+pub struct UpdateProgramConfig<'info> {
+    /// Protocol admin
+    pub admin: Signer<'info>,
+
+    /// The program config PDA
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = program_config.bump,
+        has_one = admin @ StakingError::InvalidAuthority
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+/// Initial platform-wide parameters
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ProgramConfigParams {
+    /// Default treasury receiving protocol fees
+    pub fee_treasury: Pubkey,
+    /// Tier thresholds (Holder, Premium, Vip); `None` uses the built-in table
+    pub tier_thresholds: Option<[u64; 3]>,
+    /// SOL fee in lamports charged per new pool
+    pub pool_creation_fee: u64,
+    /// Feature switches; `None` enables every feature
+    pub feature_flags: Option<u64>,
+    /// Pause-only incident key; `None` leaves the role unassigned
+    pub guardian: Option<Pubkey>,
+}
+
+/// Changes to the program config; `None` leaves a field as is
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ProgramConfigUpdate {
+    pub new_admin: Option<Pubkey>,
+    pub fee_treasury: Option<Pubkey>,
+    pub tier_thresholds: Option<[u64; 3]>,
+    pub pool_creation_fee: Option<u64>,
+    pub feature_flags: Option<u64>,
+    /// `Pubkey::default()` removes the guardian
+    pub guardian: Option<Pubkey>,
+    /// Replaces the lending program whitelist; unused slots are `Pubkey::default()`
+    pub lending_programs: Option<[Pubkey; ProgramConfig::MAX_LENDING_PROGRAMS]>,
+}
+
+/// Event emitted when the program config is created or changed
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProgramConfigUpdatedEvent {
+    pub admin: Pubkey,
+    pub fee_treasury: Pubkey,
+    pub tier_thresholds: [u64; 3],
+    pub pool_creation_fee: u64,
+    pub feature_flags: u64,
+    pub guardian: Pubkey,
+    pub lending_programs: [Pubkey; ProgramConfig::MAX_LENDING_PROGRAMS],
+    pub timestamp: i64,
+}
+
+pub fn initialize_handler(
+    ctx: Context<InitializeProgramConfig>,
+    params: ProgramConfigParams,
+) -> Result<()> {
+    let config = &mut ctx.accounts.program_config;
+
+    config.admin = ctx.accounts.admin.key();
+    config.fee_treasury = params.fee_treasury;
+    config.tier_thresholds = params
+        .tier_thresholds
+        .unwrap_or(ProgramConfig::DEFAULT_TIER_THRESHOLDS);
+    config.pool_creation_fee = params.pool_creation_fee;
+    config.feature_flags = params.feature_flags.unwrap_or(ProgramConfig::ALL_FEATURES);
+    config.guardian = params.guardian.unwrap_or_default();
+    config.bump = ctx.bumps.program_config;
+    config.validate()?;
+
+    emit_config(config)?;
+
+    msg!("Program config initialized");
+    msg!("Admin: {}", config.admin);
+
+    Ok(())
+}
+
+pub fn update_handler(ctx: Context<UpdateProgramConfig>, update: ProgramConfigUpdate) -> Result<()> {
+    let config = &mut ctx.accounts.program_config;
+
+    if let Some(new_admin) = update.new_admin {
+        require!(new_admin != Pubkey::default(), StakingError::InvalidConfig);
+        config.admin = new_admin;
+    }
+    if let Some(fee_treasury) = update.fee_treasury {
+        config.fee_treasury = fee_treasury;
+    }
+    if let Some(tier_thresholds) = update.tier_thresholds {
+        config.tier_thresholds = tier_thresholds;
+    }
+    if let Some(pool_creation_fee) = update.pool_creation_fee {
+        config.pool_creation_fee = pool_creation_fee;
+    }
+    if let Some(feature_flags) = update.feature_flags {
+        config.feature_flags = feature_flags;
+    }
+    if let Some(guardian) = update.guardian {
+        config.guardian = guardian;
+    }
+    if let Some(lending_programs) = update.lending_programs {
+        config.lending_programs = lending_programs;
+    }
+    config.validate()?;
+
+    emit_config(config)?;
+
+    msg!("Program config updated");
+
+    Ok(())
+}
+
+fn emit_config(config: &ProgramConfig) -> Result<()> {
+    emit!(ProgramConfigUpdatedEvent {
+        admin: config.admin,
+        fee_treasury: config.fee_treasury,
+        tier_thresholds: config.tier_thresholds,
+        pool_creation_fee: config.pool_creation_fee,
+        feature_flags: config.feature_flags,
+        guardian: config.guardian,
+        lending_programs: config.lending_programs,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::state::{RewardStreams, StakePool};
+use crate::errors::StakingError;
+
+/// Recover tokens accidentally sent to the pool PDA's associated token account
+#[derive(Accounts)]
+pub struct RecoverToken<'info> {
+    /// Pool authority
+    pub authority: Signer<'info>,
+
+    /// The stake pool owning the stray tokens
+    #[account(
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump,
+        has_one = authority @ StakingError::InvalidAuthority
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// Mint of the tokens to recover
+    pub mint: Account<'info, Mint>,
+
+    /// The pool PDA's associated token account for `mint`. Pool vaults and
+    /// partner escrows are PDAs, never the ATA, so they can't be passed here.
+    #[account(
+        mut,
+        address = get_associated_token_address(&stake_pool.key(), &mint.key()) @ StakingError::InvalidMint
+    )]
+    pub source: Account<'info, TokenAccount>,
+
+    /// Token account receiving the recovered tokens
+    #[account(
+        mut,
+        constraint = destination.mint == mint.key() @ StakingError::InvalidMint
+    )]
+    pub destination: Account<'info, TokenAccount>,
+
+    /// The pool's extra reward streams (required once the pool has streams)
+    #[account(
+        seeds = [RewardStreams::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump = reward_streams.bump
+    )]
+    pub reward_streams: Option<Account<'info, RewardStreams>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Event emitted when stray tokens are recovered
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TokenRecoveredEvent {
+    pub stake_pool: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub destination: Pubkey,
+    pub timestamp: i64,
+}
+
+pub fn handler(ctx: Context<RecoverToken>) -> Result<()> {
+    let stake_pool = &ctx.accounts.stake_pool;
+    let mint = ctx.accounts.mint.key();
+
+    // Never touch stake or reward tokens
+    require!(!stake_pool.is_pool_mint(&mint), StakingError::ProtectedMint);
+    if stake_pool.reward_stream_count > 0 {
+        let reward_streams = ctx.accounts.reward_streams
+            .as_ref()
+            .ok_or(StakingError::MissingRewardStreamAccount)?;
+        require!(!reward_streams.has_mint(&mint), StakingError::ProtectedMint);
+    }
+
+    let amount = ctx.accounts.source.amount;
+    require!(amount > 0, StakingError::InvalidAmount);
+
+    // Transfer via PDA signer
+    let stake_mint_key = stake_pool.stake_mint;
+    let pool_id_seed = stake_pool.id_seed();
+    let pool_bump = stake_pool.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        StakePool::SEED_PREFIX,
+        stake_mint_key.as_ref(),
+        &pool_id_seed,
+        &[pool_bump],
+    ]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.source.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+            authority: stake_pool.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    emit!(TokenRecoveredEvent {
+        stake_pool: stake_pool.key(),
+        mint,
+        amount,
+        destination: ctx.accounts.destination.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Recovered {} tokens of mint {}", amount, mint);
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{HistorySample, PoolHistory, PoolSnapshot, PoolStats, StakePool};
+
+/// Permissionlessly refresh the compact pool snapshot read by light clients,
+/// appending to the pool's history at most once per sample interval
+#[derive(Accounts)]
+pub struct RefreshPoolSnapshot<'info> {
+    /// Anyone may crank the snapshot (pays rent on first use)
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    /// The stake pool
+    #[account(
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// Pool statistics
+    #[account(
+        seeds = [PoolStats::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump = pool_stats.bump
+    )]
+    pub pool_stats: Account<'info, PoolStats>,
+
+    /// The pool's snapshot (created on first refresh)
+    #[account(
+        init_if_needed,
+        payer = caller,
+        space = PoolSnapshot::LEN,
+        seeds = [PoolSnapshot::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump
+    )]
+    pub pool_snapshot: Account<'info, PoolSnapshot>,
+
+    /// The pool's APR/TVL history (created on first refresh)
+    #[account(
+        init_if_needed,
+        payer = caller,
+        space = PoolHistory::LEN,
+        seeds = [PoolHistory::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump
+    )]
+    pub pool_history: Box<Account<'info, PoolHistory>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RefreshPoolSnapshot>) -> Result<()> {
+    let clock = Clock::get()?;
+    let pool_snapshot = &mut ctx.accounts.pool_snapshot;
+
+    pool_snapshot.stake_pool = ctx.accounts.stake_pool.key();
+    pool_snapshot.bump = ctx.bumps.pool_snapshot;
+    pool_snapshot.refresh(&ctx.accounts.stake_pool, &ctx.accounts.pool_stats, clock.unix_timestamp);
+
+    let pool_history = &mut ctx.accounts.pool_history;
+    pool_history.stake_pool = ctx.accounts.stake_pool.key();
+    pool_history.bump = ctx.bumps.pool_history;
+    let sampled = pool_history.record(HistorySample {
+        timestamp: clock.unix_timestamp,
+        apr_bps: pool_snapshot.apr_bps,
+        tvl: pool_snapshot.total_staked,
+    });
+
+    msg!(
+        "Pool snapshot refreshed: TVL {}, APR {} bps{}",
+        pool_snapshot.total_staked,
+        pool_snapshot.apr_bps,
+        if sampled { ", history sampled" } else { "" }
+    );
+
+    Ok(())
+}
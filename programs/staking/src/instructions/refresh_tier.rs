@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{RewardStreams, StakePool, UserRewardStreams, UserStake};
+use crate::errors::StakingError;
+use crate::streams::{sync_reward_streams, WeightChange};
+use crate::{update_rewards, settle_rewards, rebase_weight};
+
+/// Permissionlessly re-fold a position's reward multiplier into its weight
+///
+/// Tier activations and grace expiries happen with the passage of time, so
+/// anyone may call this to bring `weighted_stake` in line with the
+/// position's effective tier. Also migrates legacy positions.
+#[derive(Accounts)]
+pub struct RefreshTier<'info> {
+    /// Anyone may call this instruction
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    /// The stake pool
+    #[account(
+        mut,
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// The position to refresh
+    #[account(
+        mut,
+        seeds = [UserStake::SEED_PREFIX, stake_pool.key().as_ref(), user_stake.owner.as_ref()],
+        bump = user_stake.bump,
+        has_one = stake_pool @ StakingError::StakePoolMismatch
+    )]
+    pub user_stake: Account<'info, UserStake>,
+
+    /// The pool's extra reward streams (required once the pool has streams)
+    #[account(
+        mut,
+        seeds = [RewardStreams::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump = reward_streams.bump
+    )]
+    pub reward_streams: Option<Account<'info, RewardStreams>>,
+
+    /// User's extra reward stream positions (required once the pool has streams)
+    #[account(
+        init_if_needed,
+        payer = caller,
+        space = UserRewardStreams::LEN,
+        seeds = [UserRewardStreams::SEED_PREFIX, stake_pool.key().as_ref(), user_stake.owner.as_ref()],
+        bump
+    )]
+    pub user_reward_streams: Option<Account<'info, UserRewardStreams>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Event emitted when a position's folded multiplier changes
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TierRefreshedEvent {
+    pub user: Pubkey,
+    pub stake_pool: Pubkey,
+    pub caller: Pubkey,
+    pub previous_multiplier_bps: u64,
+    pub new_multiplier_bps: u64,
+    pub new_weighted_stake: u64,
+    pub timestamp: i64,
+}
+
+pub fn handler(ctx: Context<RefreshTier>) -> Result<()> {
+    let stake_pool = &mut ctx.accounts.stake_pool;
+    let user_stake = &mut ctx.accounts.user_stake;
+    let clock = Clock::get()?;
+    let total_weighted_before = stake_pool.total_weighted_stake;
+    let user_weighted_before = user_stake.weighted_stake;
+    let previous_multiplier_bps = user_stake.applied_multiplier_bps;
+    // Positions opened before streaks existed start theirs here
+    let starts_streak = user_stake.unbroken_since == 0 && user_stake.staked_amount > 0;
+
+    require!(
+        user_stake.is_legacy_weight()
+            || starts_streak
+            || stake_pool.position_multiplier_bps(user_stake, clock.unix_timestamp) != previous_multiplier_bps,
+        StakingError::TierUnchanged
+    );
+
+    update_rewards(stake_pool, clock.unix_timestamp)?;
+    settle_rewards(user_stake, stake_pool.accumulated_reward_per_share, clock.unix_timestamp)?;
+    if starts_streak {
+        user_stake.unbroken_since = clock.unix_timestamp;
+    }
+    rebase_weight(stake_pool, user_stake, clock.unix_timestamp)?;
+
+    sync_reward_streams(
+        &ctx.accounts.stake_pool,
+        ctx.accounts.reward_streams.as_mut(),
+        ctx.accounts.user_reward_streams.as_mut(),
+        ctx.accounts.user_stake.owner,
+        ctx.bumps.user_reward_streams,
+        WeightChange {
+            total_weighted_before,
+            user_weighted_before,
+            user_weighted_after: ctx.accounts.user_stake.weighted_stake,
+        },
+        clock.unix_timestamp,
+    )?;
+
+    let user_stake = &ctx.accounts.user_stake;
+    emit!(TierRefreshedEvent {
+        user: user_stake.owner,
+        stake_pool: ctx.accounts.stake_pool.key(),
+        caller: ctx.accounts.caller.key(),
+        previous_multiplier_bps,
+        new_multiplier_bps: user_stake.applied_multiplier_bps,
+        new_weighted_stake: user_stake.weighted_stake,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Reward multiplier {} -> {} bps",
+        previous_multiplier_bps,
+        user_stake.applied_multiplier_bps
+    );
+
+    Ok(())
+}
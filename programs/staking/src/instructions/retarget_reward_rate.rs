@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+
+use crate::state::StakePool;
+use crate::errors::StakingError;
+use crate::constants;
+use crate::{settle_reward_epochs, update_rewards};
+
+/// Permissionlessly reset an APR-targeted pool's reward rate from its
+/// current `total_staked`, at most once per retarget interval
+#[derive(Accounts)]
+pub struct RetargetRewardRate<'info> {
+    /// Anyone may call this instruction
+    pub caller: Signer<'info>,
+
+    /// The stake pool (must have a target APR)
+    #[account(
+        mut,
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+}
+
+/// Event emitted when the reward rate is retargeted to the target APR
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RewardRateRetargetedEvent {
+    pub stake_pool: Pubkey,
+    pub caller: Pubkey,
+    pub target_apr_bps: u64,
+    pub total_staked: u64,
+    pub old_rate: u64,
+    pub new_rate: u64,
+    pub timestamp: i64,
+}
+
+pub fn handler(ctx: Context<RetargetRewardRate>) -> Result<()> {
+    let stake_pool = &mut ctx.accounts.stake_pool;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(stake_pool.target_apr_bps > 0, StakingError::AprTargetDisabled);
+    require!(
+        now >= stake_pool.last_apr_retarget.saturating_add(stake_pool.apr_retarget_interval()),
+        StakingError::RetargetTooSoon
+    );
+
+    // Everything up to now is paid at the old rate. In epoch mode only
+    // completed epochs are settled; the epoch in progress pays the new rate.
+    if stake_pool.uses_reward_epochs() {
+        let epoch_ended = now.saturating_sub(stake_pool.last_reward_time) >= stake_pool.reward_epoch_duration;
+        if epoch_ended {
+            settle_reward_epochs(stake_pool, now)?;
+        }
+    } else {
+        update_rewards(stake_pool, now)?;
+    }
+
+    let old_rate = stake_pool.reward_rate;
+    let new_rate = staking_math::reward_rate_for_apr(stake_pool.total_staked, stake_pool.target_apr_bps)
+        .ok_or(StakingError::MathOverflow)?
+        .min(constants::MAX_REWARD_RATE);
+    stake_pool.reward_rate = new_rate;
+    stake_pool.last_apr_retarget = now;
+
+    emit!(RewardRateRetargetedEvent {
+        stake_pool: stake_pool.key(),
+        caller: ctx.accounts.caller.key(),
+        target_apr_bps: stake_pool.target_apr_bps,
+        total_staked: stake_pool.total_staked,
+        old_rate,
+        new_rate,
+        timestamp: now,
+    });
+
+    msg!(
+        "Reward rate retargeted from {} to {} for {} bps APR",
+        old_rate,
+        new_rate,
+        stake_pool.target_apr_bps
+    );
+
+    Ok(())
+}
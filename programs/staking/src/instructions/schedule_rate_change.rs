@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{RateChange, StakePool};
+use crate::errors::StakingError;
+use crate::constants;
+
+/// Schedule a reward rate change (pool authority only)
+#[derive(Accounts)]
+pub struct ScheduleRateChange<'info> {
+    /// Pool authority
+    pub authority: Signer<'info>,
+
+    /// The stake pool
+    #[account(
+        mut,
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump,
+        has_one = authority @ StakingError::InvalidAuthority
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+}
+
+/// Event emitted when a reward rate change is scheduled
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RateChangeScheduledEvent {
+    pub stake_pool: Pubkey,
+    pub current_rate: u64,
+    pub new_rate: u64,
+    pub effective_time: i64,
+    pub pending_changes: u8,
+    pub timestamp: i64,
+}
+
+pub fn handler(ctx: Context<ScheduleRateChange>, new_rate: u64, effective_time: i64) -> Result<()> {
+    let stake_pool = &mut ctx.accounts.stake_pool;
+    let clock = Clock::get()?;
+
+    require!(stake_pool.fixed_apr_bps == 0, StakingError::FixedRatePool);
+    require!(
+        new_rate <= constants::MAX_REWARD_RATE,
+        StakingError::RewardRateTooHigh
+    );
+    require!(
+        effective_time > clock.unix_timestamp,
+        StakingError::RateChangeInPast
+    );
+
+    stake_pool.queue_rate_change(RateChange {
+        rate: new_rate,
+        effective_time,
+    })?;
+
+    emit!(RateChangeScheduledEvent {
+        stake_pool: stake_pool.key(),
+        current_rate: stake_pool.reward_rate,
+        new_rate,
+        effective_time,
+        pending_changes: stake_pool.rate_change_count,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Reward rate {} per second from {}", new_rate, effective_time);
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::state::StakePool;
+use crate::errors::StakingError;
+
+/// Hand pool management to a new authority, e.g. the governance timelock PDA
+#[derive(Accounts)]
+pub struct SetPoolAuthority<'info> {
+    /// Current pool authority
+    pub authority: Signer<'info>,
+
+    /// The stake pool
+    #[account(
+        mut,
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump,
+        has_one = authority @ StakingError::InvalidAuthority
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+}
+
+/// Event emitted when a pool's authority changes
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PoolAuthorityChangedEvent {
+    pub stake_pool: Pubkey,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+pub fn handler(ctx: Context<SetPoolAuthority>, new_authority: Pubkey) -> Result<()> {
+    let stake_pool = &mut ctx.accounts.stake_pool;
+    let previous_authority = stake_pool.authority;
+    stake_pool.authority = new_authority;
+
+    emit!(PoolAuthorityChangedEvent {
+        stake_pool: stake_pool.key(),
+        previous_authority,
+        new_authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Pool authority: {} -> {}", previous_authority, new_authority);
+
+    Ok(())
+}
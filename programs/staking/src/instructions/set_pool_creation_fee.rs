@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::state::ProtocolTreasury;
+use crate::errors::StakingError;
+
+/// Update the pool creation fee (treasury admin only)
+#[derive(Accounts)]
+pub struct SetPoolCreationFee<'info> {
+    /// Treasury admin
+    pub admin: Signer<'info>,
+
+    /// The protocol treasury PDA
+    #[account(
+        mut,
+        seeds = [ProtocolTreasury::SEED_PREFIX],
+        bump = protocol_treasury.bump,
+        has_one = admin @ StakingError::InvalidAuthority
+    )]
+    pub protocol_treasury: Account<'info, ProtocolTreasury>,
+}
+
+pub fn handler(ctx: Context<SetPoolCreationFee>, pool_creation_fee: u64) -> Result<()> {
+    ctx.accounts.protocol_treasury.pool_creation_fee = pool_creation_fee;
+
+    msg!("Pool creation fee: {} lamports", pool_creation_fee);
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_lang::solana_program::program::invoke;
+
+use timelock::state::Timelock;
+
+use crate::program::Staking;
+use crate::errors::StakingError;
+
+/// Hand the program's upgrade authority to the governance timelock
+/// (current upgrade authority only)
+///
+/// Only the timelock PDA is accepted as the new authority, so the handover
+/// can't be pointed at a mistyped key. Afterwards upgrades and any further
+/// authority change are loader instructions queued through the timelock,
+/// which signs for them once the governance delay has passed.
+#[derive(Accounts)]
+pub struct SetUpgradeAuthority<'info> {
+    /// Current upgrade authority (a team key, or the timelock PDA itself
+    /// when migrating to a new timelock)
+    pub authority: Signer<'info>,
+
+    /// This program
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ StakingError::InvalidAuthority
+    )]
+    pub program: Program<'info, Staking>,
+
+    /// This program's data account, proving the upgrade authority
+    #[account(
+        mut,
+        constraint = program_data.upgrade_authority_address == Some(authority.key()) @ StakingError::InvalidAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    /// The governance timelock that will hold the upgrade authority
+    #[account(
+        seeds = [Timelock::SEED_PREFIX],
+        bump = timelock.bump,
+        seeds::program = timelock::ID
+    )]
+    pub timelock: Account<'info, Timelock>,
+
+    /// The upgradeable BPF loader
+    /// CHECK: address checked
+    #[account(address = bpf_loader_upgradeable::ID)]
+    pub bpf_loader: UncheckedAccount<'info>,
+}
+
+/// Event emitted when the upgrade authority moves to governance
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UpgradeAuthorityChangedEvent {
+    pub program: Pubkey,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+pub fn handler(ctx: Context<SetUpgradeAuthority>) -> Result<()> {
+    let previous_authority = ctx.accounts.authority.key();
+    let new_authority = ctx.accounts.timelock.key();
+    require_keys_neq!(previous_authority, new_authority, StakingError::InvalidAuthority);
+
+    let ix = bpf_loader_upgradeable::set_upgrade_authority(
+        &crate::ID,
+        &previous_authority,
+        Some(&new_authority),
+    );
+    invoke(
+        &ix,
+        &[
+            ctx.accounts.program_data.to_account_info(),
+            ctx.accounts.authority.to_account_info(),
+            ctx.accounts.timelock.to_account_info(),
+            ctx.accounts.bpf_loader.to_account_info(),
+        ],
+    )?;
+
+    emit!(UpgradeAuthorityChangedEvent {
+        program: crate::ID,
+        previous_authority,
+        new_authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Upgrade authority moved from {} to timelock {}", previous_authority, new_authority);
+
+    Ok(())
+}
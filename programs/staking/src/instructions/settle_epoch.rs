@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::state::StakePool;
+use crate::settle_reward_epochs;
+
+/// Permissionlessly distribute rewards for completed reward epochs
+#[derive(Accounts)]
+pub struct SettleEpoch<'info> {
+    /// Anyone may call this instruction
+    pub caller: Signer<'info>,
+
+    /// The stake pool (must use epoch-based rewards)
+    #[account(
+        mut,
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+}
+
+/// Event emitted when reward epochs are settled
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EpochSettledEvent {
+    pub stake_pool: Pubkey,
+    pub caller: Pubkey,
+    pub epochs_settled: u64,
+    pub reward_epoch: u64,
+    pub total_weighted_stake: u64,
+    pub accumulated_reward_per_share: u128,
+    pub timestamp: i64,
+}
+
+pub fn handler(ctx: Context<SettleEpoch>) -> Result<()> {
+    let stake_pool = &mut ctx.accounts.stake_pool;
+    let clock = Clock::get()?;
+
+    let epochs_settled = settle_reward_epochs(stake_pool, clock.unix_timestamp)?;
+
+    emit!(EpochSettledEvent {
+        stake_pool: stake_pool.key(),
+        caller: ctx.accounts.caller.key(),
+        epochs_settled,
+        reward_epoch: stake_pool.reward_epoch,
+        total_weighted_stake: stake_pool.total_weighted_stake,
+        accumulated_reward_per_share: stake_pool.accumulated_reward_per_share,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Settled {} reward epochs (now at epoch {})", epochs_settled, stake_pool.reward_epoch);
+
+    Ok(())
+}
@@ -0,0 +1,338 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::memo::Memo;
+
+use crate::merkle::{snapshot_leaf, verify_proof};
+use crate::state::{SnapshotClaim, SnapshotDistribution, StakePool};
+use crate::memo::{tag_transfer, MemoAction};
+use crate::errors::StakingError;
+
+/// Publish a merkle root for a one-off distribution (pool authority only)
+#[derive(Accounts)]
+pub struct PublishSnapshotRoot<'info> {
+    /// Pool authority funding the distribution
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The stake pool
+    #[account(
+        mut,
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump,
+        has_one = authority @ StakingError::InvalidAuthority
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// The new distribution
+    #[account(
+        init,
+        payer = authority,
+        space = SnapshotDistribution::LEN,
+        seeds = [
+            SnapshotDistribution::SEED_PREFIX,
+            stake_pool.key().as_ref(),
+            &stake_pool.snapshot_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub snapshot: Account<'info, SnapshotDistribution>,
+
+    /// Mint being distributed
+    pub mint: Account<'info, Mint>,
+
+    /// Vault holding the distribution's tokens
+    #[account(
+        init,
+        payer = authority,
+        token::mint = mint,
+        token::authority = stake_pool,
+        seeds = [b"snapshot_vault", snapshot.key().as_ref()],
+        bump
+    )]
+    pub snapshot_vault: Account<'info, TokenAccount>,
+
+    /// Authority's token account funding the distribution
+    #[account(
+        mut,
+        constraint = authority_token_account.mint == mint.key() @ StakingError::InvalidMint
+    )]
+    pub authority_token_account: Account<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Claim an allocation from a snapshot distribution
+#[derive(Accounts)]
+pub struct ClaimSnapshotReward<'info> {
+    /// User claiming their allocation
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// The stake pool
+    #[account(
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// The distribution being claimed from
+    #[account(
+        mut,
+        seeds = [
+            SnapshotDistribution::SEED_PREFIX,
+            stake_pool.key().as_ref(),
+            &snapshot.snapshot_id.to_le_bytes()
+        ],
+        bump = snapshot.bump
+    )]
+    pub snapshot: Account<'info, SnapshotDistribution>,
+
+    /// Claim receipt; its existence prevents double claims
+    #[account(
+        init,
+        payer = user,
+        space = SnapshotClaim::LEN,
+        seeds = [SnapshotClaim::SEED_PREFIX, snapshot.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub snapshot_claim: Account<'info, SnapshotClaim>,
+
+    /// Vault holding the distribution's tokens
+    #[account(
+        mut,
+        constraint = snapshot_vault.key() == snapshot.vault @ StakingError::InvalidMint
+    )]
+    pub snapshot_vault: Account<'info, TokenAccount>,
+
+    /// User's token account for the distribution mint
+    #[account(
+        mut,
+        constraint = user_token_account.mint == snapshot.mint @ StakingError::InvalidMint,
+        constraint = user_token_account.owner == user.key() @ StakingError::TokenAccountOwnerMismatch
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub memo_program: Program<'info, Memo>,
+}
+
+/// Check a voting power entry against a snapshot's root (read-only)
+#[derive(Accounts)]
+pub struct VerifySnapshotEntry<'info> {
+    /// The stake pool
+    #[account(
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// The snapshot holding the voting power root
+    #[account(
+        seeds = [
+            SnapshotDistribution::SEED_PREFIX,
+            stake_pool.key().as_ref(),
+            &snapshot.snapshot_id.to_le_bytes()
+        ],
+        bump = snapshot.bump
+    )]
+    pub snapshot: Account<'info, SnapshotDistribution>,
+}
+
+/// Event emitted when a snapshot root is published
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SnapshotPublishedEvent {
+    pub stake_pool: Pubkey,
+    pub snapshot_id: u64,
+    pub merkle_root: [u8; 32],
+    pub voting_power_root: [u8; 32],
+    pub total_weighted_stake: u64,
+    pub mint: Pubkey,
+    pub total_amount: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a snapshot allocation is claimed
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SnapshotClaimedEvent {
+    pub user: Pubkey,
+    pub stake_pool: Pubkey,
+    pub snapshot_id: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+pub fn publish_handler(
+    ctx: Context<PublishSnapshotRoot>,
+    merkle_root: [u8; 32],
+    total_amount: u64,
+    voting_power_root: Option<[u8; 32]>,
+) -> Result<()> {
+    // A governance-only snapshot needs no tokens behind it
+    require!(
+        total_amount > 0 || voting_power_root.is_some(),
+        StakingError::InvalidAmount
+    );
+
+    let clock = Clock::get()?;
+
+    // Fund the distribution vault up front
+    if total_amount > 0 {
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.authority_token_account.to_account_info(),
+                to: ctx.accounts.snapshot_vault.to_account_info(),
+                authority: ctx.accounts.authority.to_account_info(),
+            },
+        );
+        token::transfer(transfer_ctx, total_amount)?;
+    }
+
+    let stake_pool = &mut ctx.accounts.stake_pool;
+    let snapshot_id = stake_pool.snapshot_count;
+    stake_pool.snapshot_count = snapshot_id
+        .checked_add(1)
+        .ok_or(StakingError::MathOverflow)?;
+
+    let snapshot = &mut ctx.accounts.snapshot;
+    snapshot.stake_pool = stake_pool.key();
+    snapshot.snapshot_id = snapshot_id;
+    snapshot.merkle_root = merkle_root;
+    snapshot.mint = ctx.accounts.mint.key();
+    snapshot.vault = ctx.accounts.snapshot_vault.key();
+    snapshot.total_amount = total_amount;
+    snapshot.claimed_amount = 0;
+    snapshot.published_at = clock.unix_timestamp;
+    snapshot.bump = ctx.bumps.snapshot;
+    snapshot.voting_power_root = voting_power_root.unwrap_or_default();
+    snapshot.total_weighted_stake = stake_pool.total_weighted_stake;
+
+    emit!(SnapshotPublishedEvent {
+        stake_pool: snapshot.stake_pool,
+        snapshot_id,
+        merkle_root,
+        voting_power_root: snapshot.voting_power_root,
+        total_weighted_stake: snapshot.total_weighted_stake,
+        mint: snapshot.mint,
+        total_amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Published snapshot #{} for {} tokens", snapshot_id, total_amount);
+
+    Ok(())
+}
+
+pub fn claim_snapshot_handler(
+    ctx: Context<ClaimSnapshotReward>,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    require!(amount > 0, StakingError::InvalidAmount);
+
+    let user_key = ctx.accounts.user.key();
+    let snapshot = &mut ctx.accounts.snapshot;
+
+    require!(
+        verify_proof(&proof, &snapshot.merkle_root, snapshot_leaf(&user_key, amount)),
+        StakingError::InvalidMerkleProof
+    );
+
+    let claimed_amount = snapshot.claimed_amount
+        .checked_add(amount)
+        .ok_or(StakingError::MathOverflow)?;
+    require!(
+        claimed_amount <= snapshot.total_amount,
+        StakingError::SnapshotOverclaimed
+    );
+    snapshot.claimed_amount = claimed_amount;
+
+    let clock = Clock::get()?;
+    let claim = &mut ctx.accounts.snapshot_claim;
+    claim.amount = amount;
+    claim.claimed_at = clock.unix_timestamp;
+    claim.bump = ctx.bumps.snapshot_claim;
+
+    // Pay out via the pool PDA, which owns the snapshot vault
+    let stake_pool = &ctx.accounts.stake_pool;
+    let stake_mint_key = stake_pool.stake_mint;
+    let pool_id_seed = stake_pool.id_seed();
+    let pool_bump = stake_pool.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        StakePool::SEED_PREFIX,
+        stake_mint_key.as_ref(),
+        &pool_id_seed,
+        &[pool_bump],
+    ]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.snapshot_vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: stake_pool.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)?;
+    tag_transfer(
+        &ctx.accounts.memo_program,
+        MemoAction::Claim,
+        &stake_pool.key(),
+        &user_key,
+    )?;
+
+    emit!(SnapshotClaimedEvent {
+        user: user_key,
+        stake_pool: stake_pool.key(),
+        snapshot_id: snapshot.snapshot_id,
+        amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Claimed {} tokens from snapshot #{}", amount, snapshot.snapshot_id);
+
+    Ok(())
+}
+
+pub fn verify_entry_handler(
+    ctx: Context<VerifySnapshotEntry>,
+    user: Pubkey,
+    weighted_stake: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<u64> {
+    let snapshot = &ctx.accounts.snapshot;
+
+    require!(snapshot.has_voting_power(), StakingError::NoVotingPowerRoot);
+    require!(
+        snapshot.verify_voting_power(&user, weighted_stake, &proof),
+        StakingError::InvalidMerkleProof
+    );
+
+    msg!(
+        "Snapshot #{}: {} held {} of {} weighted stake",
+        snapshot.snapshot_id,
+        user,
+        weighted_stake,
+        snapshot.total_weighted_stake
+    );
+
+    Ok(weighted_stake)
+}
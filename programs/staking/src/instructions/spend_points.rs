@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+use crate::state::UserStake;
+use crate::errors::StakingError;
+
+/// Spend a user's loyalty points with a partner. Both the user (consent)
+/// and the partner (a wallet or a partner program's PDA via CPI) sign.
+#[derive(Accounts)]
+pub struct SpendPoints<'info> {
+    /// Owner of the points
+    pub user: Signer<'info>,
+
+    /// Partner redeeming the points
+    pub spender: Signer<'info>,
+
+    /// User's stake account holding the points
+    #[account(
+        mut,
+        seeds = [UserStake::SEED_PREFIX, user_stake.stake_pool.as_ref(), user.key().as_ref()],
+        bump = user_stake.bump,
+        constraint = user_stake.owner == user.key() @ StakingError::OwnerMismatch
+    )]
+    pub user_stake: Account<'info, UserStake>,
+}
+
+/// Event emitted when points are spent
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PointsSpentEvent {
+    pub user: Pubkey,
+    pub stake_pool: Pubkey,
+    pub spender: Pubkey,
+    pub amount: u64,
+    pub remaining: u64,
+    pub timestamp: i64,
+}
+
+pub fn handler(ctx: Context<SpendPoints>, amount: u64) -> Result<()> {
+    require!(amount > 0, StakingError::InvalidAmount);
+
+    let now = Clock::get()?.unix_timestamp;
+    let user_stake = &mut ctx.accounts.user_stake;
+    user_stake.accrue_points(now);
+
+    require!(
+        user_stake.available_points() >= amount,
+        StakingError::InsufficientPoints
+    );
+    user_stake.points_spent = user_stake.points_spent
+        .checked_add(amount)
+        .ok_or(StakingError::MathOverflow)?;
+
+    emit!(PointsSpentEvent {
+        user: user_stake.owner,
+        stake_pool: user_stake.stake_pool,
+        spender: ctx.accounts.spender.key(),
+        amount,
+        remaining: user_stake.available_points(),
+        timestamp: now,
+    });
+
+    msg!("Spent {} points with {}", amount, ctx.accounts.spender.key());
+
+    Ok(())
+}
@@ -1,9 +1,15 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::memo::Memo;
 
-use crate::state::{StakePool, UserStake, StakingTier};
+use crate::state::{ActionKind, HookRegistry, PoolStats, ProgramConfig, RewardStreams, StakePool, UserRewardStreams, UserStake, StakingTier};
+use crate::memo::{tag_transfer, MemoAction};
 use crate::errors::StakingError;
-use crate::{update_rewards, calculate_weight_multiplier, calculate_tier};
+use crate::credential::verify_credential;
+use crate::streams::{sync_reward_streams, WeightChange};
+use crate::hooks::{invoke_hooks, HookKind, HookPayload};
+use crate::math;
+use crate::{update_rewards, settle_rewards, rebase_weight, calculate_weight_multiplier};
 
 /// Stake tokens instruction
 #[derive(Accounts)]
@@ -15,10 +21,27 @@ pub struct Stake<'info> {
     /// The stake pool
     #[account(
         mut,
-        seeds = [StakePool::SEED_PREFIX, stake_pool.stake_mint.as_ref()],
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
         bump = stake_pool.bump
     )]
-    pub stake_pool: Account<'info, StakePool>,
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    /// Program config PDA carrying the protocol-wide pause (may not exist yet)
+    /// CHECK: address checked by seeds; read by `ProgramConfig::protocol_paused`
+    #[account(seeds = [ProgramConfig::SEED_PREFIX], bump)]
+    pub program_config: UncheckedAccount<'info>,
+
+    /// The pool's statistics account
+    #[account(
+        mut,
+        seeds = [PoolStats::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump = pool_stats.bump
+    )]
+    pub pool_stats: Box<Account<'info, PoolStats>>,
 
     /// User's stake account (created if doesn't exist)
     #[account(
@@ -28,29 +51,67 @@ pub struct Stake<'info> {
         seeds = [UserStake::SEED_PREFIX, stake_pool.key().as_ref(), user.key().as_ref()],
         bump
     )]
-    pub user_stake: Account<'info, UserStake>,
+    pub user_stake: Box<Account<'info, UserStake>>,
 
     /// User's token account to stake from
     #[account(
         mut,
         constraint = user_token_account.mint == stake_pool.stake_mint @ StakingError::InvalidMint,
-        constraint = user_token_account.owner == user.key() @ StakingError::InvalidAuthority
+        constraint = user_token_account.owner == user.key() @ StakingError::TokenAccountOwnerMismatch
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
 
     /// Pool's stake vault
     #[account(
         mut,
-        constraint = stake_vault.key() == stake_pool.stake_vault @ StakingError::InvalidMint
+        constraint = stake_vault.key() == stake_pool.stake_vault @ StakingError::InvalidStakeVault
+    )]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Pool's reward vault (required for fixed-APR pools, to check the
+    /// guaranteed rewards are covered)
+    #[account(
+        constraint = reward_vault.key() == stake_pool.reward_vault @ StakingError::InvalidRewardVault
+    )]
+    pub reward_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// User's soulbound credential token account (required for gated pools)
+    /// CHECK: validated against the pool's credential mint in the handler
+    pub credential_account: Option<UncheckedAccount<'info>>,
+
+    /// The pool's hook registry (required when hooks are enabled)
+    #[account(
+        seeds = [HookRegistry::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump = hook_registry.bump
+    )]
+    pub hook_registry: Option<Account<'info, HookRegistry>>,
+
+    /// The pool's extra reward streams (required once the pool has streams)
+    #[account(
+        mut,
+        seeds = [RewardStreams::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump = reward_streams.bump
+    )]
+    pub reward_streams: Option<Box<Account<'info, RewardStreams>>>,
+
+    /// User's extra reward stream positions (required once the pool has streams)
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserRewardStreams::LEN,
+        seeds = [UserRewardStreams::SEED_PREFIX, stake_pool.key().as_ref(), user.key().as_ref()],
+        bump
     )]
-    pub stake_vault: Account<'info, TokenAccount>,
+    pub user_reward_streams: Option<Box<Account<'info, UserRewardStreams>>>,
 
     pub token_program: Program<'info, Token>,
+    pub memo_program: Program<'info, Memo>,
     pub system_program: Program<'info, System>,
 }
 
 /// Event emitted when tokens are staked
 #[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StakeEvent {
     pub user: Pubkey,
     pub stake_pool: Pubkey,
@@ -63,14 +124,134 @@ pub struct StakeEvent {
     pub timestamp: i64,
 }
 
-pub fn handler(ctx: Context<Stake>, amount: u64, lock_duration: i64) -> Result<()> {
-    let stake_pool = &mut ctx.accounts.stake_pool;
-    let user_stake = &mut ctx.accounts.user_stake;
-    let clock = Clock::get()?;
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, Stake<'info>>,
+    amount: u64,
+    lock_duration: i64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let user_key = ctx.accounts.user.key();
+
+    verify_credential(
+        &ctx.accounts.stake_pool,
+        &user_key,
+        ctx.accounts.credential_account.as_ref().map(|account| account.as_ref()),
+    )?;
+
+    let total_weighted_before = ctx.accounts.stake_pool.total_weighted_stake;
+    let user_weighted_before = ctx.accounts.user_stake.weighted_stake;
+
+    let weighted_amount = process_stake(
+        StakeAccounts {
+            stake_pool: &mut ctx.accounts.stake_pool,
+            pool_stats: &mut ctx.accounts.pool_stats,
+            user_stake: &mut ctx.accounts.user_stake,
+            user: user_key,
+            user_stake_bump: ctx.bumps.user_stake,
+        },
+        ctx.accounts.reward_vault.as_ref().map(|vault| vault.amount),
+        ProgramConfig::protocol_paused(&ctx.accounts.program_config)?,
+        amount,
+        lock_duration,
+        now,
+    )?;
+
+    sync_reward_streams(
+        &ctx.accounts.stake_pool,
+        ctx.accounts.reward_streams.as_deref_mut(),
+        ctx.accounts.user_reward_streams.as_deref_mut(),
+        user_key,
+        ctx.bumps.user_reward_streams,
+        WeightChange {
+            total_weighted_before,
+            user_weighted_before,
+            user_weighted_after: ctx.accounts.user_stake.weighted_stake,
+        },
+        now,
+    )?;
+
+    // Transfer tokens to vault
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.user_token_account.to_account_info(),
+            to: ctx.accounts.stake_vault.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, amount)?;
+    tag_transfer(
+        &ctx.accounts.memo_program,
+        MemoAction::Stake,
+        &ctx.accounts.stake_pool.key(),
+        &ctx.accounts.user.key(),
+    )?;
+
+    // Resolve the new tier once for both the hooks and the event
+    let new_tier = ctx.accounts.user_stake.effective_tier(now);
+
+    if ctx.accounts.stake_pool.hooks_enabled {
+        invoke_hooks(
+            &ctx.accounts.stake_pool,
+            ctx.accounts.hook_registry.as_ref(),
+            ctx.remaining_accounts,
+            &ctx.accounts.user.to_account_info(),
+            HookPayload {
+                kind: HookKind::Stake,
+                user: user_key,
+                amount,
+                new_tier,
+            },
+        )?;
+    }
+
+    emit_stake_event(
+        &ctx.accounts.stake_pool,
+        &ctx.accounts.user_stake,
+        amount,
+        weighted_amount,
+        lock_duration,
+        new_tier,
+        now,
+    );
+
+    Ok(())
+}
+
+/// Pool and position accounts a stake is applied to
+pub(crate) struct StakeAccounts<'a, 'info> {
+    pub stake_pool: &'a mut Account<'info, StakePool>,
+    pub pool_stats: &'a mut PoolStats,
+    pub user_stake: &'a mut Account<'info, UserStake>,
+    /// Owner of the position
+    pub user: Pubkey,
+    /// Bump of the user stake PDA, recorded on a new position
+    pub user_stake_bump: u8,
+}
+
+/// Validate a stake and apply it to pool and user accounting.
+/// Shared by the SPL token and native SOL stake paths; the caller is
+/// responsible for moving the tokens into the stake vault.
+///
+/// # Returns
+/// * `Result<u64>` - Weighted amount added to the user's stake
+pub(crate) fn process_stake(
+    accounts: StakeAccounts,
+    reward_vault_balance: Option<u64>,
+    protocol_paused: bool,
+    amount: u64,
+    lock_duration: i64,
+    current_time: i64,
+) -> Result<u64> {
+    let StakeAccounts { stake_pool, pool_stats, user_stake, user, user_stake_bump } = accounts;
 
     // Validate inputs
-    require!(!stake_pool.paused, StakingError::PoolPaused);
+    require!(!stake_pool.is_paused(protocol_paused), StakingError::PoolPaused);
     require!(amount > 0, StakingError::InvalidAmount);
+    require!(
+        amount >= stake_pool.min_stake_amount,
+        StakingError::BelowMinimumStake
+    );
     require!(
         lock_duration >= stake_pool.min_lock_duration,
         StakingError::DurationTooShort
@@ -79,9 +260,19 @@ pub fn handler(ctx: Context<Stake>, amount: u64, lock_duration: i64) -> Result<(
         lock_duration <= stake_pool.max_lock_duration,
         StakingError::DurationTooLong
     );
+    require!(
+        user_stake.cooldown_elapsed(stake_pool.action_cooldown, current_time),
+        StakingError::ActionCooldown
+    );
+    user_stake.last_action_time = current_time;
+
+    // Free reward balance before this stake's principal lands in the pool
+    let available_rewards = reward_vault_balance.map(|balance| stake_pool.available_rewards(balance));
 
-    // Update accumulated rewards before changing stakes
-    update_rewards(stake_pool, clock.unix_timestamp)?;
+    // Update accumulated rewards and settle before the weight changes
+    update_rewards(stake_pool, current_time)?;
+    settle_rewards(user_stake, stake_pool.accumulated_reward_per_share, current_time)?;
+    user_stake.accrue_points(current_time);
 
     // Calculate weight multiplier based on lock duration
     let weight_multiplier = calculate_weight_multiplier(
@@ -91,27 +282,34 @@ pub fn handler(ctx: Context<Stake>, amount: u64, lock_duration: i64) -> Result<(
     );
 
     // weighted_amount = amount * multiplier / 10000
-    let weighted_amount = (amount as u128)
-        .checked_mul(weight_multiplier as u128)
-        .ok_or(StakingError::MathOverflow)?
-        .checked_div(10000)
-        .ok_or(StakingError::MathOverflow)? as u64;
+    let weighted_amount = math::apply_bps(amount, weight_multiplier)?;
 
-    // Initialize user stake if first time
+    // Tier in effect before this stake, for the activation delay
+    let previous_tier = user_stake.effective_tier(current_time);
+
+    // Bind a new account, or check an existing one still belongs to this
+    // user and pool. Re-staking after a full exit reuses the account and
+    // opens a fresh lock, but keeps its identity and history.
+    let is_new_staker = !user_stake.is_initialized();
+    user_stake.bind(user, stake_pool.key(), user_stake_bump, current_time)?;
     let is_first_stake = user_stake.staked_amount == 0;
 
+    // The pool's first stakers keep their bonus for the account's lifetime
+    if is_new_staker {
+        user_stake.early_adopter_bonus_bps = pool_stats.early_adopter_bonus_bps(stake_pool);
+    }
+
     if is_first_stake {
-        user_stake.owner = ctx.accounts.user.key();
-        user_stake.stake_pool = stake_pool.key();
-        user_stake.stake_start_time = clock.unix_timestamp;
-        user_stake.bump = ctx.bumps.user_stake;
+        // The position keeps the pool's fixed rate (if any) until it fully exits
+        user_stake.fixed_apr_bps = stake_pool.fixed_apr_bps;
+        user_stake.position_opened_at = current_time;
         user_stake.lock_duration = lock_duration;
-        user_stake.lock_end_time = clock.unix_timestamp
+        user_stake.lock_end_time = current_time
             .checked_add(lock_duration)
             .ok_or(StakingError::MathOverflow)?;
     } else {
         // For additional stakes, extend lock if new duration is longer
-        let new_lock_end = clock.unix_timestamp
+        let new_lock_end = current_time
             .checked_add(lock_duration)
             .ok_or(StakingError::MathOverflow)?;
 
@@ -121,51 +319,79 @@ pub fn handler(ctx: Context<Stake>, amount: u64, lock_duration: i64) -> Result<(
         }
     }
 
+    // Start a streak on the first stake (or the first since a full exit)
+    if user_stake.unbroken_since == 0 {
+        user_stake.unbroken_since = current_time;
+    }
+
     // Update user stake amounts
     user_stake.staked_amount = user_stake.staked_amount
         .checked_add(amount)
         .ok_or(StakingError::MathOverflow)?;
-    user_stake.weighted_stake = user_stake.weighted_stake
+    user_stake.lock_weighted_stake = user_stake.lock_weighted_stake
         .checked_add(weighted_amount)
         .ok_or(StakingError::MathOverflow)?;
 
-    // Update reward debt for new stake
-    // reward_debt += weighted_amount * accumulated_reward_per_share / 1e12
-    let additional_debt = (weighted_amount as u128)
-        .checked_mul(stake_pool.accumulated_reward_per_share)
-        .ok_or(StakingError::MathOverflow)?
-        .checked_div(1_000_000_000_000)
-        .ok_or(StakingError::MathOverflow)?;
-
-    user_stake.reward_debt = user_stake.reward_debt
-        .checked_add(additional_debt)
-        .ok_or(StakingError::MathOverflow)?;
-
     // Update pool totals
     stake_pool.total_staked = stake_pool.total_staked
         .checked_add(amount)
         .ok_or(StakingError::MathOverflow)?;
-    stake_pool.total_weighted_stake = stake_pool.total_weighted_stake
-        .checked_add(weighted_amount)
+    stake_pool.vault_balance = stake_pool.vault_balance
+        .checked_add(amount)
         .ok_or(StakingError::MathOverflow)?;
 
-    // Transfer tokens to vault
-    let transfer_ctx = CpiContext::new(
-        ctx.accounts.token_program.to_account_info(),
-        Transfer {
-            from: ctx.accounts.user_token_account.to_account_info(),
-            to: ctx.accounts.stake_vault.to_account_info(),
-            authority: ctx.accounts.user.to_account_info(),
-        },
+    // Fixed-rate positions reserve their whole guarantee up front, so a
+    // stake the reward vault can't cover is rejected
+    if user_stake.fixed_apr_bps > 0 {
+        let available = available_rewards.ok_or(StakingError::MissingRewardVault)?;
+        let guaranteed = staking_math::fixed_rate_rewards(
+            user_stake.staked_amount,
+            user_stake.fixed_apr_bps,
+            user_stake.lock_end_time.saturating_sub(current_time) as u64,
+        )
+        .ok_or(StakingError::MathOverflow)?;
+        let additional = guaranteed.saturating_sub(user_stake.fixed_reward_reserved);
+        let pool_reserved = stake_pool.fixed_reward_reserved
+            .checked_add(additional)
+            .ok_or(StakingError::MathOverflow)?;
+        require!(pool_reserved <= available, StakingError::InsufficientRewardReserve);
+
+        stake_pool.fixed_reward_reserved = pool_reserved;
+        user_stake.fixed_reward_reserved = user_stake.fixed_reward_reserved.max(guaranteed);
+        user_stake.fixed_accrued_until = current_time;
+    }
+
+    // Tier requirements follow the pool's current config from here on
+    user_stake.tier_min_lock_durations = stake_pool.tier_min_lock_durations;
+    user_stake.tier_thresholds = stake_pool.tier_thresholds;
+
+    user_stake.schedule_tier_activation(
+        previous_tier,
+        stake_pool.tier_activation_delay,
+        current_time,
     );
-    token::transfer(transfer_ctx, amount)?;
 
-    // Calculate new tier
-    let new_tier = calculate_tier(user_stake.staked_amount);
+    // Fold the (possibly new) tier multiplier into the pool weight
+    rebase_weight(stake_pool, user_stake, current_time)?;
+
+    user_stake.record_action(ActionKind::Stake, amount, current_time);
+    pool_stats.record_stake(amount, is_new_staker, stake_pool.total_staked);
 
-    // Emit event
+    Ok(weighted_amount)
+}
+
+/// Emit the `StakeEvent` and log the resulting position
+pub(crate) fn emit_stake_event(
+    stake_pool: &Account<StakePool>,
+    user_stake: &Account<UserStake>,
+    amount: u64,
+    weighted_amount: u64,
+    lock_duration: i64,
+    new_tier: StakingTier,
+    timestamp: i64,
+) {
     emit!(StakeEvent {
-        user: ctx.accounts.user.key(),
+        user: user_stake.owner,
         stake_pool: stake_pool.key(),
         amount,
         weighted_amount,
@@ -173,12 +399,14 @@ pub fn handler(ctx: Context<Stake>, amount: u64, lock_duration: i64) -> Result<(
         lock_end_time: user_stake.lock_end_time,
         new_tier,
         total_staked: user_stake.staked_amount,
-        timestamp: clock.unix_timestamp,
+        timestamp,
     });
 
-    msg!("Staked {} tokens with {} weighted stake", amount, weighted_amount);
-    msg!("Lock ends at: {}", user_stake.lock_end_time);
-    msg!("New tier: {:?}", new_tier);
-
-    Ok(())
+    // Logs cost compute units; the event above carries the same data
+    #[cfg(feature = "verbose-logs")]
+    {
+        msg!("Staked {} tokens with {} weighted stake", amount, weighted_amount);
+        msg!("Lock ends at: {}", user_stake.lock_end_time);
+        msg!("New tier: {:?}", new_tier);
+    }
 }
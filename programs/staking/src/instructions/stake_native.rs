@@ -0,0 +1,199 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, SyncNative, Token, TokenAccount};
+use anchor_spl::memo::Memo;
+
+use crate::state::{HookRegistry, PoolStats, ProgramConfig, RewardStreams, StakePool, UserRewardStreams, UserStake};
+use crate::memo::{tag_transfer, MemoAction};
+use crate::errors::StakingError;
+use crate::credential::verify_credential;
+use crate::streams::{sync_reward_streams, WeightChange};
+use crate::hooks::{invoke_hooks, HookKind, HookPayload};
+use super::stake::{process_stake, emit_stake_event, StakeAccounts};
+
+/// Stake native SOL into a wSOL pool
+#[derive(Accounts)]
+pub struct StakeNative<'info> {
+    /// User staking SOL
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// The stake pool (must be a native SOL pool)
+    #[account(
+        mut,
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump,
+        constraint = stake_pool.is_native @ StakingError::NotNativePool
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// Program config PDA carrying the protocol-wide pause (may not exist yet)
+    /// CHECK: address checked by seeds; read by `ProgramConfig::protocol_paused`
+    #[account(seeds = [ProgramConfig::SEED_PREFIX], bump)]
+    pub program_config: UncheckedAccount<'info>,
+
+    /// The pool's statistics account
+    #[account(
+        mut,
+        seeds = [PoolStats::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump = pool_stats.bump
+    )]
+    pub pool_stats: Account<'info, PoolStats>,
+
+    /// User's stake account (created if doesn't exist)
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserStake::LEN,
+        seeds = [UserStake::SEED_PREFIX, stake_pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_stake: Account<'info, UserStake>,
+
+    /// Pool's wSOL stake vault
+    #[account(
+        mut,
+        constraint = stake_vault.key() == stake_pool.stake_vault @ StakingError::InvalidStakeVault
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    /// Pool's reward vault (required for fixed-APR pools, to check the
+    /// guaranteed rewards are covered)
+    #[account(
+        constraint = reward_vault.key() == stake_pool.reward_vault @ StakingError::InvalidRewardVault
+    )]
+    pub reward_vault: Option<Account<'info, TokenAccount>>,
+
+    /// User's soulbound credential token account (required for gated pools)
+    /// CHECK: validated against the pool's credential mint in the handler
+    pub credential_account: Option<UncheckedAccount<'info>>,
+
+    /// The pool's hook registry (required when hooks are enabled)
+    #[account(
+        seeds = [HookRegistry::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump = hook_registry.bump
+    )]
+    pub hook_registry: Option<Account<'info, HookRegistry>>,
+
+    /// The pool's extra reward streams (required once the pool has streams)
+    #[account(
+        mut,
+        seeds = [RewardStreams::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump = reward_streams.bump
+    )]
+    pub reward_streams: Option<Account<'info, RewardStreams>>,
+
+    /// User's extra reward stream positions (required once the pool has streams)
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserRewardStreams::LEN,
+        seeds = [UserRewardStreams::SEED_PREFIX, stake_pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_reward_streams: Option<Account<'info, UserRewardStreams>>,
+
+    pub token_program: Program<'info, Token>,
+    pub memo_program: Program<'info, Memo>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, StakeNative<'info>>,
+    amount: u64,
+    lock_duration: i64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    verify_credential(
+        &ctx.accounts.stake_pool,
+        &ctx.accounts.user.key(),
+        ctx.accounts.credential_account.as_ref().map(|account| account.as_ref()),
+    )?;
+
+    let total_weighted_before = ctx.accounts.stake_pool.total_weighted_stake;
+    let user_weighted_before = ctx.accounts.user_stake.weighted_stake;
+
+    let weighted_amount = process_stake(
+        StakeAccounts {
+            stake_pool: &mut ctx.accounts.stake_pool,
+            pool_stats: &mut ctx.accounts.pool_stats,
+            user_stake: &mut ctx.accounts.user_stake,
+            user: ctx.accounts.user.key(),
+            user_stake_bump: ctx.bumps.user_stake,
+        },
+        ctx.accounts.reward_vault.as_ref().map(|vault| vault.amount),
+        ProgramConfig::protocol_paused(&ctx.accounts.program_config)?,
+        amount,
+        lock_duration,
+        clock.unix_timestamp,
+    )?;
+
+    sync_reward_streams(
+        &ctx.accounts.stake_pool,
+        ctx.accounts.reward_streams.as_mut(),
+        ctx.accounts.user_reward_streams.as_mut(),
+        ctx.accounts.user.key(),
+        ctx.bumps.user_reward_streams,
+        WeightChange {
+            total_weighted_before,
+            user_weighted_before,
+            user_weighted_after: ctx.accounts.user_stake.weighted_stake,
+        },
+        clock.unix_timestamp,
+    )?;
+
+    // Move lamports straight into the wSOL vault
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        system_program::Transfer {
+            from: ctx.accounts.user.to_account_info(),
+            to: ctx.accounts.stake_vault.to_account_info(),
+        },
+    );
+    system_program::transfer(transfer_ctx, amount)?;
+
+    // Sync the vault so its token balance reflects the new lamports
+    let sync_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        SyncNative {
+            account: ctx.accounts.stake_vault.to_account_info(),
+        },
+    );
+    token::sync_native(sync_ctx)?;
+    tag_transfer(
+        &ctx.accounts.memo_program,
+        MemoAction::Stake,
+        &ctx.accounts.stake_pool.key(),
+        &ctx.accounts.user.key(),
+    )?;
+
+    invoke_hooks(
+        &ctx.accounts.stake_pool,
+        ctx.accounts.hook_registry.as_ref(),
+        ctx.remaining_accounts,
+        &ctx.accounts.user.to_account_info(),
+        HookPayload {
+            kind: HookKind::Stake,
+            user: ctx.accounts.user.key(),
+            amount,
+            new_tier: ctx.accounts.user_stake.effective_tier(clock.unix_timestamp),
+        },
+    )?;
+
+    emit_stake_event(
+        &ctx.accounts.stake_pool,
+        &ctx.accounts.user_stake,
+        amount,
+        weighted_amount,
+        lock_duration,
+        ctx.accounts.user_stake.effective_tier(clock.unix_timestamp),
+        clock.unix_timestamp,
+    );
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::state::ProtocolTreasury;
+use crate::errors::StakingError;
+
+/// Forward collected protocol fees to the treasury program (permissionless)
+#[derive(Accounts)]
+pub struct SweepProtocolFees<'info> {
+    /// Anyone may trigger the sweep
+    pub caller: Signer<'info>,
+
+    /// The protocol fee collection PDA
+    #[account(
+        mut,
+        seeds = [ProtocolTreasury::SEED_PREFIX],
+        bump = protocol_treasury.bump
+    )]
+    pub protocol_treasury: Account<'info, ProtocolTreasury>,
+
+    /// The treasury program's treasury PDA
+    #[account(
+        mut,
+        seeds = [treasury::state::Treasury::SEED_PREFIX],
+        bump = treasury.bump,
+        seeds::program = treasury::ID
+    )]
+    pub treasury: Account<'info, treasury::state::Treasury>,
+}
+
+/// Event emitted when protocol fees are forwarded to the treasury
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProtocolFeesSweptEvent {
+    pub caller: Pubkey,
+    pub treasury: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+pub fn handler(ctx: Context<SweepProtocolFees>) -> Result<()> {
+    let fees_info = ctx.accounts.protocol_treasury.to_account_info();
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(fees_info.data_len());
+    let amount = fees_info.lamports().saturating_sub(rent_exempt_minimum);
+
+    require!(amount > 0, StakingError::InvalidAmount);
+
+    // Both accounts hold data, so lamports are moved directly
+    **fees_info.try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.treasury.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    emit!(ProtocolFeesSweptEvent {
+        caller: ctx.accounts.caller.key(),
+        treasury: ctx.accounts.treasury.key(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Swept {} lamports to the protocol treasury", amount);
+
+    Ok(())
+}
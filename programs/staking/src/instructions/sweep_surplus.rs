@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::state::StakePool;
+use crate::errors::StakingError;
+
+/// Route tokens sent straight to the stake vault to the reward vault
+#[derive(Accounts)]
+pub struct SweepSurplus<'info> {
+    /// Anyone may call this instruction
+    pub caller: Signer<'info>,
+
+    /// The stake pool
+    #[account(
+        mut,
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// Pool's stake vault
+    #[account(
+        mut,
+        constraint = stake_vault.key() == stake_pool.stake_vault @ StakingError::InvalidStakeVault
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    /// Pool's reward vault; must hold the stake mint
+    #[account(
+        mut,
+        constraint = reward_vault.key() == stake_pool.reward_vault @ StakingError::InvalidRewardVault,
+        constraint = reward_vault.mint == stake_vault.mint @ StakingError::InvalidMint
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Event emitted when donated tokens are moved to the reward side
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SurplusSweptEvent {
+    pub stake_pool: Pubkey,
+    pub caller: Pubkey,
+    pub amount: u64,
+    pub vault_balance: u64,
+    pub timestamp: i64,
+}
+
+pub fn handler(ctx: Context<SweepSurplus>) -> Result<()> {
+    let stake_pool = &mut ctx.accounts.stake_pool;
+    require!(!stake_pool.shared_vault, StakingError::SharedVaultSurplus);
+
+    let surplus = stake_pool.vault_surplus(ctx.accounts.stake_vault.amount);
+    require!(surplus > 0, StakingError::NoVaultSurplus);
+
+    // Pools created before vault tracking start tracking from here
+    stake_pool.vault_balance = stake_pool.vault_balance.max(stake_pool.total_staked);
+
+    // Transfer via PDA signer
+    let stake_pool = &ctx.accounts.stake_pool;
+    let stake_mint_key = stake_pool.stake_mint;
+    let pool_id_seed = stake_pool.id_seed();
+    let pool_bump = stake_pool.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        StakePool::SEED_PREFIX,
+        stake_mint_key.as_ref(),
+        &pool_id_seed,
+        &[pool_bump],
+    ]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.stake_vault.to_account_info(),
+            to: ctx.accounts.reward_vault.to_account_info(),
+            authority: stake_pool.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, surplus)?;
+
+    emit!(SurplusSweptEvent {
+        stake_pool: stake_pool.key(),
+        caller: ctx.accounts.caller.key(),
+        amount: surplus,
+        vault_balance: stake_pool.vault_balance,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Swept {} surplus tokens to the reward vault", surplus);
+
+    Ok(())
+}
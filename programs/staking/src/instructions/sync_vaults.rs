@@ -0,0 +1,134 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::state::StakePool;
+use crate::errors::StakingError;
+use crate::update_rewards;
+
+/// Permissionlessly reconcile vault balances with pool accounting
+#[derive(Accounts)]
+pub struct SyncVaults<'info> {
+    /// Anyone may call this instruction
+    pub caller: Signer<'info>,
+
+    /// The stake pool
+    #[account(
+        mut,
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// Pool's stake vault
+    #[account(
+        constraint = stake_vault.key() == stake_pool.stake_vault @ StakingError::InvalidStakeVault
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    /// Pool's reward vault (the stake vault again for shared-vault pools)
+    #[account(
+        constraint = reward_vault.key() == stake_pool.reward_vault @ StakingError::InvalidRewardVault
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+}
+
+/// Event emitted when a vault balance differs from pool accounting.
+/// A negative delta means the vault holds less than it should.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VaultDiscrepancy {
+    pub stake_pool: Pubkey,
+    pub vault: Pubkey,
+    pub expected: u64,
+    pub actual: u64,
+    pub delta: i128,
+    pub timestamp: i64,
+}
+
+/// Event emitted after every reconciliation
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VaultsSyncedEvent {
+    pub stake_pool: Pubkey,
+    pub caller: Pubkey,
+    pub expected_principal: u64,
+    pub reward_liability: u64,
+    pub distributable_rewards: u64,
+    pub timestamp: i64,
+}
+
+pub fn handler(ctx: Context<SyncVaults>) -> Result<()> {
+    let stake_pool = &mut ctx.accounts.stake_pool;
+    let clock = Clock::get()?;
+
+    // Bring the reward liability up to date first
+    update_rewards(stake_pool, clock.unix_timestamp)?;
+
+    let stake_pool_key = stake_pool.key();
+    let stake_vault_amount = ctx.accounts.stake_vault.amount;
+    let expected_principal = stake_pool.expected_stake_vault_balance();
+
+    // In shared-vault pools anything above principal is reward funding, so
+    // only a deficit is a stake-side discrepancy
+    if stake_vault_amount != expected_principal
+        && !(stake_pool.shared_vault && stake_vault_amount > expected_principal)
+    {
+        emit_discrepancy(
+            stake_pool_key,
+            ctx.accounts.stake_vault.key(),
+            expected_principal,
+            stake_vault_amount,
+            clock.unix_timestamp,
+        );
+    }
+
+    let reward_balance = stake_pool.available_rewards(ctx.accounts.reward_vault.amount);
+    if reward_balance != stake_pool.reward_liability {
+        emit_discrepancy(
+            stake_pool_key,
+            ctx.accounts.reward_vault.key(),
+            stake_pool.reward_liability,
+            reward_balance,
+            clock.unix_timestamp,
+        );
+    }
+
+    // Reward tokens beyond what stakers are already owed are distributable
+    stake_pool.distributable_rewards = reward_balance.saturating_sub(stake_pool.reward_liability);
+
+    emit!(VaultsSyncedEvent {
+        stake_pool: stake_pool_key,
+        caller: ctx.accounts.caller.key(),
+        expected_principal,
+        reward_liability: stake_pool.reward_liability,
+        distributable_rewards: stake_pool.distributable_rewards,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Vaults synced: {} distributable, {} owed",
+        stake_pool.distributable_rewards,
+        stake_pool.reward_liability
+    );
+
+    Ok(())
+}
+
+fn emit_discrepancy(stake_pool: Pubkey, vault: Pubkey, expected: u64, actual: u64, timestamp: i64) {
+    let delta = actual as i128 - expected as i128;
+
+    emit!(VaultDiscrepancy {
+        stake_pool,
+        vault,
+        expected,
+        actual,
+        delta,
+        timestamp,
+    });
+
+    msg!("Vault {} off by {}", vault, delta);
+}
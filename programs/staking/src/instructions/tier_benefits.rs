@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{ProtocolTreasury, StakingTier, TierBenefit, TierBenefits};
+use crate::errors::StakingError;
+
+/// Create the global tier benefits table (treasury admin only)
+#[derive(Accounts)]
+pub struct InitializeTierBenefits<'info> {
+    /// Treasury admin
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// The protocol treasury PDA, proving the admin
+    #[account(
+        seeds = [ProtocolTreasury::SEED_PREFIX],
+        bump = protocol_treasury.bump,
+        has_one = admin @ StakingError::InvalidAuthority
+    )]
+    pub protocol_treasury: Account<'info, ProtocolTreasury>,
+
+    /// The tier benefits PDA
+    #[account(
+        init,
+        payer = admin,
+        space = TierBenefits::LEN,
+        seeds = [TierBenefits::SEED_PREFIX],
+        bump
+    )]
+    pub tier_benefits: Account<'info, TierBenefits>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Edit the tier benefits table (governance only)
+#[derive(Accounts)]
+pub struct UpdateTierBenefits<'info> {
+    /// Governance authority
+    pub governance: Signer<'info>,
+
+    /// The tier benefits PDA
+    #[account(
+        mut,
+        seeds = [TierBenefits::SEED_PREFIX],
+        bump = tier_benefits.bump,
+        has_one = governance @ StakingError::InvalidAuthority
+    )]
+    pub tier_benefits: Account<'info, TierBenefits>,
+}
+
+/// Event emitted when a tier's benefits change
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TierBenefitUpdatedEvent {
+    pub tier: StakingTier,
+    pub allocation_multiplier_bps: u64,
+    pub fee_discount_bps: u16,
+    pub cooldown_exempt: bool,
+    pub timestamp: i64,
+}
+
+pub fn initialize_tier_benefits_handler(
+    ctx: Context<InitializeTierBenefits>,
+    governance: Pubkey,
+    benefits: [TierBenefit; 4],
+) -> Result<()> {
+    for benefit in benefits.iter() {
+        benefit.validate()?;
+    }
+
+    let tier_benefits = &mut ctx.accounts.tier_benefits;
+    tier_benefits.governance = governance;
+    tier_benefits.benefits = benefits;
+    tier_benefits.bump = ctx.bumps.tier_benefits;
+
+    msg!("Tier benefits initialized");
+    msg!("Governance: {}", governance);
+
+    Ok(())
+}
+
+pub fn set_benefit_handler(
+    ctx: Context<UpdateTierBenefits>,
+    tier: StakingTier,
+    benefit: TierBenefit,
+) -> Result<()> {
+    benefit.validate()?;
+    ctx.accounts.tier_benefits.benefits[tier as usize] = benefit;
+
+    emit!(TierBenefitUpdatedEvent {
+        tier,
+        allocation_multiplier_bps: benefit.allocation_multiplier_bps,
+        fee_discount_bps: benefit.fee_discount_bps,
+        cooldown_exempt: benefit.cooldown_exempt,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Tier {:?} benefits updated", tier);
+
+    Ok(())
+}
+
+pub fn set_governance_handler(ctx: Context<UpdateTierBenefits>, new_governance: Pubkey) -> Result<()> {
+    ctx.accounts.tier_benefits.governance = new_governance;
+
+    msg!("Tier benefits governance: {}", new_governance);
+
+    Ok(())
+}
@@ -1,9 +1,15 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::memo::Memo;
 
-use crate::state::{StakePool, UserStake, StakingTier};
+use crate::state::{ActionKind, HookRegistry, InsuranceFund, ProgramConfig, RewardStreams, StakePool, UserRewardStreams, UserStake, StakingTier};
+use crate::memo::{tag_transfer, MemoAction};
 use crate::errors::StakingError;
-use crate::{update_rewards, calculate_pending_rewards, calculate_tier};
+use crate::math;
+use crate::constants;
+use crate::streams::{sync_reward_streams, WeightChange};
+use crate::hooks::{invoke_hooks, HookKind, HookPayload};
+use crate::{update_rewards, settle_rewards, rebase_weight, calculate_unstake_removal, calculate_early_unstake_penalty, calculate_penalty_split, distribute_to_other_stakers};
 
 /// Unstake tokens instruction
 #[derive(Accounts)]
@@ -15,17 +21,27 @@ pub struct Unstake<'info> {
     /// The stake pool
     #[account(
         mut,
-        seeds = [StakePool::SEED_PREFIX, stake_pool.stake_mint.as_ref()],
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
         bump = stake_pool.bump
     )]
     pub stake_pool: Account<'info, StakePool>,
 
+    /// Program config PDA carrying the protocol-wide pause (may not exist yet)
+    /// CHECK: address checked by seeds; read by `ProgramConfig::protocol_paused`
+    #[account(seeds = [ProgramConfig::SEED_PREFIX], bump)]
+    pub program_config: UncheckedAccount<'info>,
+
     /// User's stake account
     #[account(
         mut,
         seeds = [UserStake::SEED_PREFIX, stake_pool.key().as_ref(), user.key().as_ref()],
         bump = user_stake.bump,
-        constraint = user_stake.owner == user.key() @ StakingError::InvalidAuthority
+        has_one = stake_pool @ StakingError::StakePoolMismatch,
+        constraint = user_stake.owner == user.key() @ StakingError::OwnerMismatch
     )]
     pub user_stake: Account<'info, UserStake>,
 
@@ -33,22 +49,92 @@ pub struct Unstake<'info> {
     #[account(
         mut,
         constraint = user_token_account.mint == stake_pool.stake_mint @ StakingError::InvalidMint,
-        constraint = user_token_account.owner == user.key() @ StakingError::InvalidAuthority
+        constraint = user_token_account.owner == user.key() @ StakingError::TokenAccountOwnerMismatch
     )]
     pub user_token_account: Account<'info, TokenAccount>,
 
     /// Pool's stake vault
     #[account(
         mut,
-        constraint = stake_vault.key() == stake_pool.stake_vault @ StakingError::InvalidMint
+        constraint = stake_vault.key() == stake_pool.stake_vault @ StakingError::InvalidStakeVault
     )]
     pub stake_vault: Account<'info, TokenAccount>,
 
+    /// Pool's reward vault (required when an early unstake retains part of
+    /// the penalty for the remaining stakers in a separate-vault pool)
+    #[account(
+        mut,
+        constraint = reward_vault.key() == stake_pool.reward_vault @ StakingError::InvalidRewardVault
+    )]
+    pub reward_vault: Option<Account<'info, TokenAccount>>,
+
+    /// The pool's hook registry (unstake hooks are skipped without it)
+    #[account(
+        seeds = [HookRegistry::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump = hook_registry.bump
+    )]
+    pub hook_registry: Option<Account<'info, HookRegistry>>,
+
+    /// The pool's extra reward streams (required once the pool has streams)
+    #[account(
+        mut,
+        seeds = [RewardStreams::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump = reward_streams.bump
+    )]
+    pub reward_streams: Option<Account<'info, RewardStreams>>,
+
+    /// User's extra reward stream positions (required once the pool has streams)
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserRewardStreams::LEN,
+        seeds = [UserRewardStreams::SEED_PREFIX, stake_pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_reward_streams: Option<Account<'info, UserRewardStreams>>,
+
+    /// The stake mint (required when an early unstake burns part of its penalty)
+    #[account(
+        mut,
+        address = stake_pool.stake_mint @ StakingError::InvalidMint
+    )]
+    pub stake_mint: Option<Account<'info, Mint>>,
+
+    /// Protocol treasury's stake token account (required only when an
+    /// early unstake sends part of its penalty to the treasury; regular
+    /// unstakes can leave it out)
+    #[account(
+        mut,
+        constraint = penalty_treasury.mint == stake_pool.stake_mint @ StakingError::InvalidMint,
+        constraint = penalty_treasury.owner == constants::TREASURY_ADDRESS @ StakingError::TokenAccountOwnerMismatch
+    )]
+    pub penalty_treasury: Option<Account<'info, TokenAccount>>,
+
+    /// The pool's insurance fund (required when an early unstake sends part
+    /// of its penalty to the fund)
+    #[account(
+        mut,
+        seeds = [InsuranceFund::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump = insurance_fund.bump
+    )]
+    pub insurance_fund: Option<Account<'info, InsuranceFund>>,
+
+    /// The insurance fund's vault
+    #[account(
+        mut,
+        seeds = [InsuranceFund::VAULT_SEED_PREFIX, stake_pool.key().as_ref()],
+        bump
+    )]
+    pub insurance_vault: Option<Account<'info, TokenAccount>>,
+
     pub token_program: Program<'info, Token>,
+    pub memo_program: Program<'info, Memo>,
+    pub system_program: Program<'info, System>,
 }
 
 /// Event emitted when tokens are unstaked
 #[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UnstakeEvent {
     pub user: Pubkey,
     pub stake_pool: Pubkey,
@@ -59,96 +145,357 @@ pub struct UnstakeEvent {
     pub timestamp: i64,
 }
 
-pub fn handler(ctx: Context<Unstake>, amount: u64) -> Result<()> {
-    let stake_pool = &mut ctx.accounts.stake_pool;
-    let user_stake = &mut ctx.accounts.user_stake;
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, Unstake<'info>>,
+    amount: u64,
+) -> Result<()> {
+    execute_unstake(ctx, amount, false)
+}
+
+/// Unstake after the lock ends, or (when `early`) before it ends in exchange
+/// for a penalty proportional to the lock time remaining
+pub(crate) fn execute_unstake<'info>(
+    ctx: Context<'_, '_, '_, 'info, Unstake<'info>>,
+    amount: u64,
+    early: bool,
+) -> Result<()> {
     let clock = Clock::get()?;
 
+    let penalty = if early {
+        let stake_pool = &ctx.accounts.stake_pool;
+        let user_stake = &ctx.accounts.user_stake;
+        require!(
+            stake_pool.early_unstake_penalty_bps > 0,
+            StakingError::EarlyUnstakeDisabled
+        );
+        calculate_early_unstake_penalty(
+            amount,
+            stake_pool.early_unstake_penalty_bps,
+            user_stake.lock_end_time,
+            user_stake.lock_duration,
+            clock.unix_timestamp,
+        )?
+    } else {
+        0
+    };
+
+    let total_weighted_before = ctx.accounts.stake_pool.total_weighted_stake;
+    let user_weighted_before = ctx.accounts.user_stake.weighted_stake;
+
+    let weighted_to_remove = process_unstake(
+        &mut ctx.accounts.stake_pool,
+        &mut ctx.accounts.user_stake,
+        ProgramConfig::protocol_paused(&ctx.accounts.program_config)?,
+        amount,
+        clock.unix_timestamp,
+        !early,
+    )?;
+
+    // The retained share of the penalty goes to the remaining stakers when
+    // they are paid in the stake mint. Otherwise it is left out of
+    // `vault_balance` as stake vault surplus for `sweep_surplus`.
+    let split = calculate_penalty_split(
+        penalty,
+        ctx.accounts.stake_pool.penalty_burn_bps,
+        ctx.accounts.stake_pool.penalty_treasury_bps,
+        ctx.accounts.stake_pool.penalty_insurance_bps,
+    )?;
+    let retained_to_stakers = split.retained > 0
+        && ctx.accounts.stake_pool.stake_mint == ctx.accounts.stake_pool.reward_mint;
+    if retained_to_stakers {
+        distribute_to_other_stakers(
+            &mut ctx.accounts.stake_pool,
+            &mut ctx.accounts.user_stake,
+            split.retained,
+        )?;
+    }
+    if split.to_insurance > 0 {
+        let insurance_fund = ctx.accounts.insurance_fund
+            .as_mut()
+            .ok_or(StakingError::MissingPenaltyAccount)?;
+        insurance_fund.total_deposited = insurance_fund.total_deposited
+            .checked_add(split.to_insurance)
+            .ok_or(StakingError::MathOverflow)?;
+    }
+
+    sync_reward_streams(
+        &ctx.accounts.stake_pool,
+        ctx.accounts.reward_streams.as_mut(),
+        ctx.accounts.user_reward_streams.as_mut(),
+        ctx.accounts.user.key(),
+        ctx.bumps.user_reward_streams,
+        WeightChange {
+            total_weighted_before,
+            user_weighted_before,
+            user_weighted_after: ctx.accounts.user_stake.weighted_stake,
+        },
+        clock.unix_timestamp,
+    )?;
+
+    // Transfer tokens back to user via PDA signer
+    let stake_pool = &ctx.accounts.stake_pool;
+    let stake_mint_key = stake_pool.stake_mint;
+    let pool_id_seed = stake_pool.id_seed();
+    let pool_bump = stake_pool.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        StakePool::SEED_PREFIX,
+        stake_mint_key.as_ref(),
+        &pool_id_seed,
+        &[pool_bump],
+    ]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.stake_vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: stake_pool.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount - penalty)?;
+    tag_transfer(
+        &ctx.accounts.memo_program,
+        MemoAction::Unstake,
+        &ctx.accounts.stake_pool.key(),
+        &ctx.accounts.user.key(),
+    )?;
+
+    if split.burned > 0 {
+        let stake_mint = ctx.accounts.stake_mint
+            .as_ref()
+            .ok_or(StakingError::MissingPenaltyAccount)?;
+        let burn_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: stake_mint.to_account_info(),
+                from: ctx.accounts.stake_vault.to_account_info(),
+                authority: stake_pool.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::burn(burn_ctx, split.burned)?;
+    }
+
+    if split.to_treasury > 0 {
+        let penalty_treasury = ctx.accounts.penalty_treasury
+            .as_ref()
+            .ok_or(StakingError::MissingPenaltyAccount)?;
+        let treasury_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.stake_vault.to_account_info(),
+                to: penalty_treasury.to_account_info(),
+                authority: stake_pool.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(treasury_ctx, split.to_treasury)?;
+    }
+
+    if retained_to_stakers && !stake_pool.shared_vault {
+        let reward_vault = ctx.accounts.reward_vault
+            .as_ref()
+            .ok_or(StakingError::MissingPenaltyAccount)?;
+        let reward_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.stake_vault.to_account_info(),
+                to: reward_vault.to_account_info(),
+                authority: stake_pool.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(reward_ctx, split.retained)?;
+    }
+
+    if split.to_insurance > 0 {
+        let insurance_vault = ctx.accounts.insurance_vault
+            .as_ref()
+            .ok_or(StakingError::MissingPenaltyAccount)?;
+        let insurance_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.stake_vault.to_account_info(),
+                to: insurance_vault.to_account_info(),
+                authority: stake_pool.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(insurance_ctx, split.to_insurance)?;
+    }
+
+    invoke_hooks(
+        &ctx.accounts.stake_pool,
+        ctx.accounts.hook_registry.as_ref(),
+        ctx.remaining_accounts,
+        &ctx.accounts.user.to_account_info(),
+        HookPayload {
+            kind: HookKind::Unstake,
+            user: ctx.accounts.user.key(),
+            amount,
+            new_tier: ctx.accounts.user_stake.effective_tier(clock.unix_timestamp),
+        },
+    )?;
+
+    emit_unstake_event(
+        &ctx.accounts.stake_pool,
+        &ctx.accounts.user_stake,
+        amount,
+        weighted_to_remove,
+        clock.unix_timestamp,
+    );
+
+    if early {
+        emit!(EarlyUnstakeEvent {
+            user: ctx.accounts.user.key(),
+            stake_pool: ctx.accounts.stake_pool.key(),
+            amount,
+            penalty,
+            burned: split.burned,
+            to_treasury: split.to_treasury,
+            to_insurance: split.to_insurance,
+            retained: split.retained,
+            net_amount: amount - penalty,
+            lock_end_time: ctx.accounts.user_stake.lock_end_time,
+            timestamp: clock.unix_timestamp,
+        });
+        msg!("Early unstake penalty: {}", penalty);
+    }
+
+    Ok(())
+}
+
+/// Event emitted when a position exits before its lock ends
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EarlyUnstakeEvent {
+    pub user: Pubkey,
+    pub stake_pool: Pubkey,
+    pub amount: u64,
+    pub penalty: u64,
+    pub burned: u64,
+    pub to_treasury: u64,
+    pub to_insurance: u64,
+    pub retained: u64,
+    pub net_amount: u64,
+    pub lock_end_time: i64,
+    pub timestamp: i64,
+}
+
+/// Validate an unstake and remove it from pool and user accounting.
+/// Shared by the SPL token, native SOL and early (penalised) unstake paths;
+/// the caller is responsible for moving the tokens out of the stake vault.
+///
+/// # Returns
+/// * `Result<u64>` - Weighted amount removed from the user's stake
+pub(crate) fn process_unstake(
+    stake_pool: &mut Account<StakePool>,
+    user_stake: &mut Account<UserStake>,
+    protocol_paused: bool,
+    amount: u64,
+    current_time: i64,
+    enforce_lock: bool,
+) -> Result<u64> {
     // Validate inputs
+    require!(stake_pool.unstake_allowed(protocol_paused), StakingError::PoolPaused);
+    require!(!user_stake.frozen, StakingError::AccountFrozen);
+    require!(!user_stake.is_collateralized(), StakingError::PositionCollateralized);
     require!(amount > 0, StakingError::InvalidAmount);
     require!(
         user_stake.staked_amount >= amount,
         StakingError::InsufficientStake
     );
     require!(
-        clock.unix_timestamp >= user_stake.lock_end_time,
+        !enforce_lock || current_time >= user_stake.lock_end_time,
         StakingError::StillLocked
     );
+    require!(
+        user_stake.cooldown_elapsed(stake_pool.action_cooldown, current_time),
+        StakingError::ActionCooldown
+    );
+    user_stake.last_action_time = current_time;
 
-    // Update accumulated rewards before changing stakes
-    update_rewards(stake_pool, clock.unix_timestamp)?;
-
-    // Calculate proportional weighted stake to remove
-    // weighted_to_remove = (amount / staked_amount) * weighted_stake
-    let weighted_to_remove = (amount as u128)
-        .checked_mul(user_stake.weighted_stake as u128)
-        .ok_or(StakingError::MathOverflow)?
-        .checked_div(user_stake.staked_amount as u128)
-        .ok_or(StakingError::MathOverflow)? as u64;
-
-    // Calculate proportional reward debt to remove
-    let debt_to_remove = (amount as u128)
-        .checked_mul(user_stake.reward_debt)
-        .ok_or(StakingError::MathOverflow)?
-        .checked_div(user_stake.staked_amount as u128)
-        .ok_or(StakingError::MathOverflow)?;
+    // Update accumulated rewards and settle before the weight changes
+    update_rewards(stake_pool, current_time)?;
+    settle_rewards(user_stake, stake_pool.accumulated_reward_per_share, current_time)?;
+    user_stake.accrue_points(current_time);
+
+    // Tier in effect before this unstake, for the grace period
+    let previous_tier = user_stake.effective_tier(current_time);
+
+    // Calculate proportional lock weight to remove (a full exit removes
+    // everything so no dust is left behind). Debt is re-based below.
+    let (weighted_to_remove, _) = calculate_unstake_removal(
+        amount,
+        user_stake.staked_amount,
+        user_stake.lock_weighted_stake,
+        0,
+    )?;
+
+    // Leaving a fixed-rate lock early forfeits the unaccrued share of the
+    // guarantee, which goes back to the pool's free reward balance
+    let fixed_released = math::to_u64(math::proportional(
+        user_stake.fixed_reward_reserved as u128,
+        amount,
+        user_stake.staked_amount,
+    )?)?;
+    user_stake.fixed_reward_reserved -= fixed_released;
+    stake_pool.fixed_reward_reserved = stake_pool.fixed_reward_reserved.saturating_sub(fixed_released);
 
     // Update user stake
     user_stake.staked_amount = user_stake.staked_amount
         .checked_sub(amount)
         .ok_or(StakingError::MathOverflow)?;
-    user_stake.weighted_stake = user_stake.weighted_stake
+    user_stake.lock_weighted_stake = user_stake.lock_weighted_stake
         .checked_sub(weighted_to_remove)
         .ok_or(StakingError::MathOverflow)?;
-    user_stake.reward_debt = user_stake.reward_debt
-        .checked_sub(debt_to_remove)
-        .ok_or(StakingError::MathOverflow)?;
 
     // Update pool totals
     stake_pool.total_staked = stake_pool.total_staked
         .checked_sub(amount)
         .ok_or(StakingError::MathOverflow)?;
-    stake_pool.total_weighted_stake = stake_pool.total_weighted_stake
-        .checked_sub(weighted_to_remove)
-        .ok_or(StakingError::MathOverflow)?;
+    // Pools created before vault tracking may hold less than their principal here
+    stake_pool.vault_balance = stake_pool.vault_balance.saturating_sub(amount);
 
-    // Transfer tokens back to user via PDA signer
-    let stake_mint_key = stake_pool.stake_mint;
-    let pool_bump = stake_pool.bump;
-    let signer_seeds: &[&[&[u8]]] = &[&[
-        StakePool::SEED_PREFIX,
-        stake_mint_key.as_ref(),
-        &[pool_bump],
-    ]];
+    // Any unstake breaks the streak; whatever remains starts a new one
+    user_stake.unbroken_since = if user_stake.staked_amount > 0 { current_time } else { 0 };
 
-    let transfer_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        Transfer {
-            from: ctx.accounts.stake_vault.to_account_info(),
-            to: ctx.accounts.user_token_account.to_account_info(),
-            authority: stake_pool.to_account_info(),
-        },
-        signer_seeds,
-    );
-    token::transfer(transfer_ctx, amount)?;
+    user_stake.start_tier_grace(previous_tier, stake_pool.tier_grace_period, current_time);
+    rebase_weight(stake_pool, user_stake, current_time)?;
+
+    // Once the last staker leaves, clear any residual weight in the pool
+    if stake_pool.total_staked == 0 {
+        stake_pool.total_weighted_stake = 0;
+    }
 
+    user_stake.record_action(ActionKind::Unstake, amount, current_time);
+
+    Ok(weighted_to_remove)
+}
+
+/// Emit the `UnstakeEvent` and log the remaining position
+pub(crate) fn emit_unstake_event(
+    stake_pool: &Account<StakePool>,
+    user_stake: &Account<UserStake>,
+    amount: u64,
+    weighted_amount_removed: u64,
+    timestamp: i64,
+) {
     // Calculate new tier
-    let new_tier = calculate_tier(user_stake.staked_amount);
+    let new_tier = user_stake.effective_tier(timestamp);
 
-    // Emit event
     emit!(UnstakeEvent {
-        user: ctx.accounts.user.key(),
+        user: user_stake.owner,
         stake_pool: stake_pool.key(),
         amount,
-        weighted_amount_removed: weighted_to_remove,
+        weighted_amount_removed,
         remaining_stake: user_stake.staked_amount,
         new_tier,
-        timestamp: clock.unix_timestamp,
+        timestamp,
     });
 
     msg!("Unstaked {} tokens", amount);
     msg!("Remaining stake: {}", user_stake.staked_amount);
     msg!("New tier: {:?}", new_tier);
-
-    Ok(())
 }
@@ -0,0 +1,194 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, spl_token, CloseAccount, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::memo::Memo;
+
+use crate::state::{HookRegistry, ProgramConfig, RewardStreams, StakePool, UserRewardStreams, UserStake};
+use crate::memo::{tag_transfer, MemoAction};
+use crate::errors::StakingError;
+use crate::streams::{sync_reward_streams, WeightChange};
+use crate::hooks::{invoke_hooks, HookKind, HookPayload};
+use super::unstake::{process_unstake, emit_unstake_event};
+
+/// Unstake from a wSOL pool and receive native SOL
+#[derive(Accounts)]
+pub struct UnstakeNative<'info> {
+    /// User unstaking SOL
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// The stake pool (must be a native SOL pool)
+    #[account(
+        mut,
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump,
+        constraint = stake_pool.is_native @ StakingError::NotNativePool
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// Program config PDA carrying the protocol-wide pause (may not exist yet)
+    /// CHECK: address checked by seeds; read by `ProgramConfig::protocol_paused`
+    #[account(seeds = [ProgramConfig::SEED_PREFIX], bump)]
+    pub program_config: UncheckedAccount<'info>,
+
+    /// User's stake account
+    #[account(
+        mut,
+        seeds = [UserStake::SEED_PREFIX, stake_pool.key().as_ref(), user.key().as_ref()],
+        bump = user_stake.bump,
+        has_one = stake_pool @ StakingError::StakePoolMismatch,
+        constraint = user_stake.owner == user.key() @ StakingError::OwnerMismatch
+    )]
+    pub user_stake: Account<'info, UserStake>,
+
+    /// Pool's wSOL stake vault
+    #[account(
+        mut,
+        constraint = stake_vault.key() == stake_pool.stake_vault @ StakingError::InvalidStakeVault
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    /// The wrapped SOL mint
+    #[account(address = spl_token::native_mint::ID @ StakingError::InvalidMint)]
+    pub native_mint: Account<'info, Mint>,
+
+    /// Temporary wSOL account, closed to the user within this instruction
+    #[account(
+        init,
+        payer = user,
+        token::mint = native_mint,
+        token::authority = user,
+        seeds = [b"unwrap", stake_pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub unwrap_account: Account<'info, TokenAccount>,
+
+    /// The pool's hook registry (unstake hooks are skipped without it)
+    #[account(
+        seeds = [HookRegistry::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump = hook_registry.bump
+    )]
+    pub hook_registry: Option<Account<'info, HookRegistry>>,
+
+    /// The pool's extra reward streams (required once the pool has streams)
+    #[account(
+        mut,
+        seeds = [RewardStreams::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump = reward_streams.bump
+    )]
+    pub reward_streams: Option<Account<'info, RewardStreams>>,
+
+    /// User's extra reward stream positions (required once the pool has streams)
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserRewardStreams::LEN,
+        seeds = [UserRewardStreams::SEED_PREFIX, stake_pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_reward_streams: Option<Account<'info, UserRewardStreams>>,
+
+    pub token_program: Program<'info, Token>,
+    pub memo_program: Program<'info, Memo>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, UnstakeNative<'info>>,
+    amount: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    let total_weighted_before = ctx.accounts.stake_pool.total_weighted_stake;
+    let user_weighted_before = ctx.accounts.user_stake.weighted_stake;
+
+    let weighted_to_remove = process_unstake(
+        &mut ctx.accounts.stake_pool,
+        &mut ctx.accounts.user_stake,
+        ProgramConfig::protocol_paused(&ctx.accounts.program_config)?,
+        amount,
+        clock.unix_timestamp,
+        true,
+    )?;
+
+    sync_reward_streams(
+        &ctx.accounts.stake_pool,
+        ctx.accounts.reward_streams.as_mut(),
+        ctx.accounts.user_reward_streams.as_mut(),
+        ctx.accounts.user.key(),
+        ctx.bumps.user_reward_streams,
+        WeightChange {
+            total_weighted_before,
+            user_weighted_before,
+            user_weighted_after: ctx.accounts.user_stake.weighted_stake,
+        },
+        clock.unix_timestamp,
+    )?;
+
+    // Transfer wSOL from the vault into the temporary account via PDA signer
+    let stake_pool = &ctx.accounts.stake_pool;
+    let stake_mint_key = stake_pool.stake_mint;
+    let pool_id_seed = stake_pool.id_seed();
+    let pool_bump = stake_pool.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        StakePool::SEED_PREFIX,
+        stake_mint_key.as_ref(),
+        &pool_id_seed,
+        &[pool_bump],
+    ]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.stake_vault.to_account_info(),
+            to: ctx.accounts.unwrap_account.to_account_info(),
+            authority: stake_pool.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)?;
+    tag_transfer(
+        &ctx.accounts.memo_program,
+        MemoAction::Unstake,
+        &ctx.accounts.stake_pool.key(),
+        &ctx.accounts.user.key(),
+    )?;
+
+    // Close the temporary account, unwrapping the SOL (plus its rent) to the user
+    let close_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.unwrap_account.to_account_info(),
+            destination: ctx.accounts.user.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    );
+    token::close_account(close_ctx)?;
+
+    invoke_hooks(
+        &ctx.accounts.stake_pool,
+        ctx.accounts.hook_registry.as_ref(),
+        ctx.remaining_accounts,
+        &ctx.accounts.user.to_account_info(),
+        HookPayload {
+            kind: HookKind::Unstake,
+            user: ctx.accounts.user.key(),
+            amount,
+            new_tier: ctx.accounts.user_stake.effective_tier(clock.unix_timestamp),
+        },
+    )?;
+
+    emit_unstake_event(
+        &ctx.accounts.stake_pool,
+        &ctx.accounts.user_stake,
+        amount,
+        weighted_to_remove,
+        clock.unix_timestamp,
+    );
+
+    Ok(())
+}
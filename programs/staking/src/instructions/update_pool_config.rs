@@ -0,0 +1,369 @@
+use anchor_lang::prelude::*;
+
+use crate::state::StakePool;
+use crate::errors::StakingError;
+use crate::constants;
+use crate::update_rewards;
+
+/// Update mutable pool configuration
+#[derive(Accounts)]
+pub struct UpdatePoolConfig<'info> {
+    /// Pool authority
+    pub authority: Signer<'info>,
+
+    /// The stake pool
+    #[account(
+        mut,
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump,
+        has_one = authority @ StakingError::InvalidAuthority
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+}
+
+/// Configuration changes; `None` leaves a value unchanged
+#[derive(AnchorSerialize, AnchorDeserialize, Default)]
+pub struct UpdatePoolConfigParams {
+    /// Seconds a tier upgrade must be held before its benefits apply
+    pub tier_activation_delay: Option<i64>,
+    /// Seconds a user keeps their prior tier after a partial unstake
+    pub tier_grace_period: Option<i64>,
+    /// Periodic claim window: (anchor timestamp, cycle period, open length)
+    /// A period of 0 disables the window.
+    pub claim_window: Option<(i64, i64, i64)>,
+    /// Claim blackout period: (start, end) timestamps; equal values disable it
+    pub claim_blackout: Option<(i64, i64)>,
+    /// Soulbound credential mint required to stake (`Pubkey::default()` disables)
+    pub credential_mint: Option<Pubkey>,
+    /// Minimum amount accepted per stake deposit
+    pub min_stake_amount: Option<u64>,
+    /// Early unstake penalty at the start of a lock (bps, 0 disables)
+    pub early_unstake_penalty_bps: Option<u64>,
+    /// Streak bonus: (bps per unbroken month, cap in bps)
+    pub streak_bonus: Option<(u64, u64)>,
+    /// Early unstake penalty split: (burn bps, treasury bps); the rest is
+    /// retained for remaining stakers
+    pub penalty_split: Option<(u64, u64)>,
+    /// Share of early unstake penalties sent to the insurance fund (bps)
+    pub penalty_insurance_bps: Option<u64>,
+    /// Reward epoch length in seconds (0 = per-second accrual)
+    pub reward_epoch_duration: Option<i64>,
+    /// Minimum seconds between a user's stake/unstake operations (0 disables)
+    pub action_cooldown: Option<i64>,
+    /// Protocol fee taken from claimed rewards, before tier discounts (bps)
+    pub protocol_fee_bps: Option<u64>,
+    /// Early adopter bonus: (number of first stakers, bonus bps); applies
+    /// to stakers joining after the change only
+    pub early_adopter: Option<(u64, u64)>,
+    /// Whether unstaking stays open while the pool is paused
+    pub allow_unstake_when_paused: Option<bool>,
+    /// Target APR (bps) retargeted by `retarget_reward_rate` (0 disables)
+    pub target_apr_bps: Option<u64>,
+    /// Guaranteed APR (bps) for new positions (0 = floating rewards); only
+    /// while the pool is empty and emits no floating rewards
+    pub fixed_apr_bps: Option<u64>,
+    /// Minimum seconds between a user's reward claims (0 disables)
+    pub min_claim_interval: Option<i64>,
+    /// Decaying claim tax: (starting bps, decay period in seconds); a zero
+    /// rate disables it
+    pub claim_tax: Option<(u64, i64)>,
+    /// Minimum lock duration for Holder, Premium and VIP (0 = none);
+    /// applies to each position from its next stake
+    pub tier_min_lock_durations: Option<[i64; 3]>,
+    /// Source pool and largest cross-pool boost (bps) its weighted stake
+    /// grants here; `(Pubkey::default(), 0)` disables. Positions pick it up
+    /// from their next `refresh_cross_boost`
+    pub cross_boost: Option<(Pubkey, u64)>,
+}
+
+/// Event emitted after a config update, carrying the full resulting config
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PoolConfigUpdatedEvent {
+    pub stake_pool: Pubkey,
+    pub authority: Pubkey,
+    pub tier_activation_delay: i64,
+    pub tier_grace_period: i64,
+    pub claim_window_anchor: i64,
+    pub claim_window_period: i64,
+    pub claim_window_length: i64,
+    pub claim_blackout_start: i64,
+    pub claim_blackout_end: i64,
+    pub credential_mint: Pubkey,
+    pub min_stake_amount: u64,
+    pub early_unstake_penalty_bps: u64,
+    pub streak_bonus_bps_per_month: u64,
+    pub max_streak_bonus_bps: u64,
+    pub penalty_burn_bps: u64,
+    pub penalty_treasury_bps: u64,
+    pub penalty_insurance_bps: u64,
+    pub reward_epoch_duration: i64,
+    pub action_cooldown: i64,
+    pub protocol_fee_bps: u64,
+    pub early_adopter_slots: u64,
+    pub early_adopter_bonus_bps: u64,
+    pub allow_unstake_when_paused: bool,
+    pub target_apr_bps: u64,
+    pub fixed_apr_bps: u64,
+    pub min_claim_interval: i64,
+    pub claim_tax_start_bps: u64,
+    pub claim_tax_decay_period: i64,
+    pub tier_min_lock_durations: [i64; 3],
+    pub boost_source_pool: Pubkey,
+    pub max_cross_boost_bps: u64,
+    pub timestamp: i64,
+}
+
+pub fn handler(ctx: Context<UpdatePoolConfig>, params: UpdatePoolConfigParams) -> Result<()> {
+    let stake_pool = &mut ctx.accounts.stake_pool;
+
+    if let Some(tier_activation_delay) = params.tier_activation_delay {
+        require!(tier_activation_delay >= 0, StakingError::InvalidConfig);
+        stake_pool.tier_activation_delay = tier_activation_delay;
+        msg!("Tier activation delay: {} seconds", tier_activation_delay);
+    }
+
+    if let Some(tier_grace_period) = params.tier_grace_period {
+        require!(tier_grace_period >= 0, StakingError::InvalidConfig);
+        stake_pool.tier_grace_period = tier_grace_period;
+        msg!("Tier grace period: {} seconds", tier_grace_period);
+    }
+
+    if let Some((anchor, period, length)) = params.claim_window {
+        require!(period >= 0, StakingError::InvalidConfig);
+        require!(
+            period == 0 || (length > 0 && length <= period),
+            StakingError::InvalidConfig
+        );
+        stake_pool.claim_window_anchor = anchor;
+        stake_pool.claim_window_period = period;
+        stake_pool.claim_window_length = length;
+        msg!("Claim window: every {}s for {}s from {}", period, length, anchor);
+    }
+
+    if let Some((start, end)) = params.claim_blackout {
+        require!(end >= start, StakingError::InvalidConfig);
+        stake_pool.claim_blackout_start = start;
+        stake_pool.claim_blackout_end = end;
+        msg!("Claim blackout: {} to {}", start, end);
+    }
+
+    if let Some(credential_mint) = params.credential_mint {
+        stake_pool.credential_mint = credential_mint;
+        msg!("Credential mint: {}", credential_mint);
+    }
+
+    if let Some(min_stake_amount) = params.min_stake_amount {
+        stake_pool.min_stake_amount = min_stake_amount;
+        msg!("Minimum stake amount: {}", min_stake_amount);
+    }
+
+    if let Some(early_unstake_penalty_bps) = params.early_unstake_penalty_bps {
+        require!(
+            early_unstake_penalty_bps <= constants::BPS_DENOMINATOR,
+            StakingError::InvalidConfig
+        );
+        stake_pool.early_unstake_penalty_bps = early_unstake_penalty_bps;
+        msg!("Early unstake penalty: {} bps", early_unstake_penalty_bps);
+    }
+
+    if let Some((per_month, max)) = params.streak_bonus {
+        require!(
+            max <= constants::BPS_DENOMINATOR && per_month <= max,
+            StakingError::InvalidConfig
+        );
+        stake_pool.streak_bonus_bps_per_month = per_month;
+        stake_pool.max_streak_bonus_bps = max;
+        msg!("Streak bonus: {} bps per month, max {} bps", per_month, max);
+    }
+
+    if let Some((burn_bps, treasury_bps)) = params.penalty_split {
+        stake_pool.penalty_burn_bps = burn_bps;
+        stake_pool.penalty_treasury_bps = treasury_bps;
+        msg!("Penalty split: {} bps burned, {} bps to treasury", burn_bps, treasury_bps);
+    }
+
+    if let Some(penalty_insurance_bps) = params.penalty_insurance_bps {
+        stake_pool.penalty_insurance_bps = penalty_insurance_bps;
+        msg!("Penalty insurance share: {} bps", penalty_insurance_bps);
+    }
+
+    require!(
+        stake_pool.penalty_burn_bps
+            .saturating_add(stake_pool.penalty_treasury_bps)
+            .saturating_add(stake_pool.penalty_insurance_bps)
+            <= constants::BPS_DENOMINATOR,
+        StakingError::InvalidConfig
+    );
+
+    if let Some(reward_epoch_duration) = params.reward_epoch_duration {
+        require!(reward_epoch_duration >= 0, StakingError::InvalidConfig);
+        // Bring per-second accrual up to date before switching to epochs.
+        // Switching back resumes per-second accrual from the last settled
+        // epoch, so a partial epoch is never lost.
+        if !stake_pool.uses_reward_epochs() {
+            update_rewards(stake_pool, Clock::get()?.unix_timestamp)?;
+        }
+        stake_pool.reward_epoch_duration = reward_epoch_duration;
+        msg!("Reward epoch duration: {} seconds", reward_epoch_duration);
+    }
+
+    if let Some(action_cooldown) = params.action_cooldown {
+        require!(
+            (0..=constants::MAX_ACTION_COOLDOWN).contains(&action_cooldown),
+            StakingError::InvalidConfig
+        );
+        stake_pool.action_cooldown = action_cooldown;
+        msg!("Action cooldown: {} seconds", action_cooldown);
+    }
+
+    if let Some(protocol_fee_bps) = params.protocol_fee_bps {
+        require!(
+            protocol_fee_bps <= constants::MAX_PROTOCOL_FEE_BPS,
+            StakingError::InvalidConfig
+        );
+        stake_pool.protocol_fee_bps = protocol_fee_bps;
+        msg!("Protocol claim fee: {} bps", protocol_fee_bps);
+    }
+
+    if let Some((slots, bonus_bps)) = params.early_adopter {
+        require!(
+            bonus_bps <= constants::MAX_EARLY_ADOPTER_BONUS_BPS,
+            StakingError::InvalidConfig
+        );
+        stake_pool.early_adopter_slots = slots;
+        stake_pool.early_adopter_bonus_bps = bonus_bps;
+        msg!("Early adopter bonus: {} bps for the first {} stakers", bonus_bps, slots);
+    }
+
+    if let Some(allow_unstake_when_paused) = params.allow_unstake_when_paused {
+        stake_pool.allow_unstake_when_paused = allow_unstake_when_paused;
+        msg!(
+            "Pause policy: {}",
+            if allow_unstake_when_paused { "exit-only" } else { "full freeze" }
+        );
+    }
+
+    if let Some(target_apr_bps) = params.target_apr_bps {
+        require!(
+            target_apr_bps <= constants::MAX_TARGET_APR_BPS,
+            StakingError::InvalidConfig
+        );
+        require!(
+            target_apr_bps == 0 || stake_pool.fixed_apr_bps == 0,
+            StakingError::FixedRatePool
+        );
+        stake_pool.target_apr_bps = target_apr_bps;
+        msg!("Target APR: {} bps", target_apr_bps);
+    }
+
+    if let Some(fixed_apr_bps) = params.fixed_apr_bps {
+        // Open positions keep the rate they locked in; switching modes
+        // under them would mix guaranteed and floating payouts
+        require!(stake_pool.total_staked == 0, StakingError::InvalidConfig);
+        require!(
+            fixed_apr_bps <= constants::MAX_TARGET_APR_BPS,
+            StakingError::InvalidConfig
+        );
+        if fixed_apr_bps > 0 {
+            require!(
+                stake_pool.reward_rate == 0
+                    && stake_pool.rate_change_count == 0
+                    && stake_pool.target_apr_bps == 0,
+                StakingError::InvalidConfig
+            );
+        }
+        stake_pool.fixed_apr_bps = fixed_apr_bps;
+        msg!("Fixed APR: {} bps", fixed_apr_bps);
+    }
+
+    if let Some(min_claim_interval) = params.min_claim_interval {
+        require!(
+            (0..=constants::MAX_CLAIM_INTERVAL).contains(&min_claim_interval),
+            StakingError::InvalidConfig
+        );
+        stake_pool.min_claim_interval = min_claim_interval;
+        msg!("Minimum claim interval: {} seconds", min_claim_interval);
+    }
+
+    if let Some((start_bps, decay_period)) = params.claim_tax {
+        require!(
+            start_bps <= constants::MAX_CLAIM_TAX_BPS,
+            StakingError::InvalidConfig
+        );
+        require!(
+            start_bps == 0 || (1..=constants::MAX_LOCK_DURATION).contains(&decay_period),
+            StakingError::InvalidConfig
+        );
+        stake_pool.claim_tax_start_bps = start_bps;
+        stake_pool.claim_tax_decay_period = decay_period;
+        msg!("Claim tax: {} bps decaying over {} seconds", start_bps, decay_period);
+    }
+
+    if let Some(tier_min_lock_durations) = params.tier_min_lock_durations {
+        // Higher tiers may never ask for a shorter lock than lower ones
+        let [holder, premium, vip] = tier_min_lock_durations;
+        require!(
+            holder >= 0 && holder <= premium && premium <= vip && vip <= constants::MAX_LOCK_DURATION,
+            StakingError::InvalidConfig
+        );
+        stake_pool.tier_min_lock_durations = tier_min_lock_durations;
+        msg!("Tier minimum locks: {:?}", tier_min_lock_durations);
+    }
+
+    if let Some((source_pool, max_bonus_bps)) = params.cross_boost {
+        require!(
+            max_bonus_bps <= constants::MAX_CROSS_BOOST_BPS,
+            StakingError::InvalidConfig
+        );
+        require!(
+            (source_pool == Pubkey::default()) == (max_bonus_bps == 0),
+            StakingError::InvalidConfig
+        );
+        require_keys_neq!(source_pool, stake_pool.key(), StakingError::InvalidConfig);
+        stake_pool.boost_source_pool = source_pool;
+        stake_pool.max_cross_boost_bps = max_bonus_bps;
+        msg!("Cross-pool boost: up to {} bps from {}", max_bonus_bps, source_pool);
+    }
+
+    emit!(PoolConfigUpdatedEvent {
+        stake_pool: stake_pool.key(),
+        authority: stake_pool.authority,
+        tier_activation_delay: stake_pool.tier_activation_delay,
+        tier_grace_period: stake_pool.tier_grace_period,
+        claim_window_anchor: stake_pool.claim_window_anchor,
+        claim_window_period: stake_pool.claim_window_period,
+        claim_window_length: stake_pool.claim_window_length,
+        claim_blackout_start: stake_pool.claim_blackout_start,
+        claim_blackout_end: stake_pool.claim_blackout_end,
+        credential_mint: stake_pool.credential_mint,
+        min_stake_amount: stake_pool.min_stake_amount,
+        early_unstake_penalty_bps: stake_pool.early_unstake_penalty_bps,
+        streak_bonus_bps_per_month: stake_pool.streak_bonus_bps_per_month,
+        max_streak_bonus_bps: stake_pool.max_streak_bonus_bps,
+        penalty_burn_bps: stake_pool.penalty_burn_bps,
+        penalty_treasury_bps: stake_pool.penalty_treasury_bps,
+        penalty_insurance_bps: stake_pool.penalty_insurance_bps,
+        reward_epoch_duration: stake_pool.reward_epoch_duration,
+        action_cooldown: stake_pool.action_cooldown,
+        protocol_fee_bps: stake_pool.protocol_fee_bps,
+        early_adopter_slots: stake_pool.early_adopter_slots,
+        early_adopter_bonus_bps: stake_pool.early_adopter_bonus_bps,
+        allow_unstake_when_paused: stake_pool.allow_unstake_when_paused,
+        target_apr_bps: stake_pool.target_apr_bps,
+        fixed_apr_bps: stake_pool.fixed_apr_bps,
+        min_claim_interval: stake_pool.min_claim_interval,
+        claim_tax_start_bps: stake_pool.claim_tax_start_bps,
+        claim_tax_decay_period: stake_pool.claim_tax_decay_period,
+        tier_min_lock_durations: stake_pool.tier_min_lock_durations,
+        boost_source_pool: stake_pool.boost_source_pool,
+        max_cross_boost_bps: stake_pool.max_cross_boost_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
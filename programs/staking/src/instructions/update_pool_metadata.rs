@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{PoolMetadata, StakePool};
+use crate::errors::StakingError;
+
+/// Update a pool's human-readable metadata
+#[derive(Accounts)]
+pub struct UpdatePoolMetadata<'info> {
+    /// Pool authority
+    pub authority: Signer<'info>,
+
+    /// The stake pool
+    #[account(
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_pool.stake_mint.as_ref(),
+            &stake_pool.id_seed()
+        ],
+        bump = stake_pool.bump,
+        has_one = authority @ StakingError::InvalidAuthority
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// The pool's metadata account
+    #[account(
+        mut,
+        seeds = [PoolMetadata::SEED_PREFIX, stake_pool.key().as_ref()],
+        bump = pool_metadata.bump
+    )]
+    pub pool_metadata: Account<'info, PoolMetadata>,
+}
+
+/// Event emitted when a pool's metadata changes
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PoolMetadataUpdatedEvent {
+    pub stake_pool: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub timestamp: i64,
+}
+
+pub fn handler(ctx: Context<UpdatePoolMetadata>, name: String, symbol: String, uri: String) -> Result<()> {
+    ctx.accounts.pool_metadata.set(&name, &symbol, &uri)?;
+
+    emit!(PoolMetadataUpdatedEvent {
+        stake_pool: ctx.accounts.stake_pool.key(),
+        name: name.clone(),
+        symbol: symbol.clone(),
+        uri: uri.clone(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Pool metadata updated");
+    msg!("Name: {}", name);
+    msg!("Symbol: {}", symbol);
+    msg!("URI: {}", uri);
+
+    Ok(())
+}
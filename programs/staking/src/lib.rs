@@ -1,8 +1,20 @@
 use anchor_lang::prelude::*;
 
+#[cfg(feature = "cpi")]
+pub mod cpi_client;
+pub mod credential;
 pub mod errors;
+pub mod hooks;
 pub mod instructions;
+pub mod math;
+pub mod memo;
+pub mod merkle;
 pub mod state;
+pub mod streams;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
+
+pub use staking_math::u256;
 
 use instructions::*;
 use state::*;
@@ -18,22 +30,94 @@ pub mod constants {
     /// Maximum lock duration: 365 days in seconds
     pub const MAX_LOCK_DURATION: i64 = 365 * 24 * 60 * 60; // 31,536,000 seconds
 
-    /// Precision multiplier for accumulated rewards (1e12)
-    pub const PRECISION: u128 = 1_000_000_000_000;
+    /// Precision multiplier for accumulated rewards (1e18)
+    /// Products against this scale are computed with 256-bit intermediates
+    pub const PRECISION: u128 = staking_math::PRECISION;
+
+    /// Accumulator scale of layout v1 pools (1e12), rescaled to `PRECISION`
+    /// when they are migrated
+    pub const LEGACY_PRECISION: u128 = 1_000_000_000_000;
 
     /// Basis points denominator (10000 = 100%)
-    pub const BPS_DENOMINATOR: u64 = 10000;
+    pub const BPS_DENOMINATOR: u64 = staking_math::BPS_DENOMINATOR;
 
     /// Minimum weight multiplier (1x = 10000 bps)
-    pub const MIN_WEIGHT_MULTIPLIER: u64 = 10000;
+    pub const MIN_WEIGHT_MULTIPLIER: u64 = staking_math::MIN_WEIGHT_MULTIPLIER;
 
     /// Maximum weight multiplier (2x = 20000 bps)
-    pub const MAX_WEIGHT_MULTIPLIER: u64 = 20000;
+    pub const MAX_WEIGHT_MULTIPLIER: u64 = staking_math::MAX_WEIGHT_MULTIPLIER;
 
     /// Tier thresholds (in token smallest units, assuming 9 decimals)
-    pub const HOLDER_THRESHOLD: u64 = 1_000_000_000_000;     // 1,000 tokens
-    pub const PREMIUM_THRESHOLD: u64 = 10_000_000_000_000;   // 10,000 tokens
-    pub const VIP_THRESHOLD: u64 = 100_000_000_000_000;      // 100,000 tokens
+    pub const HOLDER_THRESHOLD: u64 = staking_math::HOLDER_THRESHOLD;     // 1,000 tokens
+    pub const PREMIUM_THRESHOLD: u64 = staking_math::PREMIUM_THRESHOLD;   // 10,000 tokens
+    pub const VIP_THRESHOLD: u64 = staking_math::VIP_THRESHOLD;           // 100,000 tokens
+
+    /// Decimals the built-in tier thresholds assume; pools on other mints
+    /// get them rescaled at creation
+    pub const STAKE_MINT_DECIMALS: u8 = staking_math::THRESHOLD_DECIMALS;
+
+    /// Length of a streak month in seconds (30 days)
+    pub const SECONDS_PER_MONTH: i64 = 30 * 24 * 60 * 60;
+
+    /// Unbroken staking needed for each milestone bonus (90, 180 and 365 days)
+    pub const MILESTONE_DURATIONS: [i64; 3] = [
+        90 * 24 * 60 * 60,
+        180 * 24 * 60 * 60,
+        365 * 24 * 60 * 60,
+    ];
+
+    /// Stake-seconds per loyalty point: one whole token (9 decimals) for a day
+    pub const POINT_STAKE_SECONDS: u128 = 1_000_000_000 * 24 * 60 * 60;
+
+    /// Longest action cooldown a pool may configure (1 day)
+    pub const MAX_ACTION_COOLDOWN: i64 = 24 * 60 * 60;
+
+    /// Longest minimum claim interval a pool may configure (30 days)
+    pub const MAX_CLAIM_INTERVAL: i64 = 30 * 24 * 60 * 60;
+
+    /// Highest starting claim tax a pool may charge (50%)
+    pub const MAX_CLAIM_TAX_BPS: u64 = 5_000;
+
+    /// Highest cross-pool boost a farm pool may grant (1.5x on top of the
+    /// base multiplier)
+    pub const MAX_CROSS_BOOST_BPS: u64 = 15_000;
+
+    /// Highest early adopter bonus a pool may grant (0.25x)
+    pub const MAX_EARLY_ADOPTER_BONUS_BPS: u64 = 2_500;
+
+    /// Minimum spacing of `PoolHistory` samples (1 day), so the ring buffer
+    /// covers about three months however often the snapshot is cranked
+    pub const HISTORY_SAMPLE_INTERVAL: i64 = 24 * 60 * 60;
+
+    /// Seconds between APR retargets for per-second pools (1 day)
+    pub const APR_RETARGET_INTERVAL: i64 = 24 * 60 * 60;
+
+    /// Highest target APR a pool may configure (1,000%)
+    pub const MAX_TARGET_APR_BPS: u64 = 100_000;
+
+    /// Seed of the PDA a lending program signs with (via CPI) to lock or
+    /// release a position as collateral
+    pub const COLLATERAL_AUTHORITY_SEED: &[u8] = b"collateral_authority";
+
+    /// Highest protocol fee a pool may take from claims (10%)
+    pub const MAX_PROTOCOL_FEE_BPS: u64 = 1_000;
+
+    /// Compute units kept in reserve per claim processed by `crank_claims`;
+    /// the crank stops early once fewer remain
+    pub const CRANK_CLAIM_COMPUTE_UNITS: u64 = 30_000;
+
+    /// Sanity cap on a pool's reward rate: 1,000,000 tokens per second at 9 decimals
+    pub const MAX_REWARD_RATE: u64 = 1_000_000_000_000_000;
+
+    /// Treasury program's `Treasury` PDA (GhRBp5TpFxTKmEJurUWgko2QVxghTaWCvt6buoX9RxKC),
+    /// which owns the protocol fee accounts. Fixed here so claims compare
+    /// keys instead of deriving it.
+    pub const TREASURY_ADDRESS: anchor_lang::prelude::Pubkey = anchor_lang::prelude::Pubkey::new_from_array([
+        233, 57, 226, 72, 100, 206, 135, 122,
+        35, 160, 136, 19, 73, 122, 58, 133,
+        166, 202, 160, 132, 179, 71, 233, 179,
+        196, 4, 77, 169, 94, 16, 69, 91,
+    ]);
 }
 
 #[program]
@@ -55,7 +139,11 @@ pub mod staking {
     /// * `ctx` - Stake context
     /// * `amount` - Amount of tokens to stake
     /// * `lock_duration` - Lock duration in seconds (must be between min and max)
-    pub fn stake(ctx: Context<Stake>, amount: u64, lock_duration: i64) -> Result<()> {
+    pub fn stake<'info>(
+        ctx: Context<'_, '_, '_, 'info, Stake<'info>>,
+        amount: u64,
+        lock_duration: i64,
+    ) -> Result<()> {
         instructions::stake::handler(ctx, amount, lock_duration)
     }
 
@@ -64,7 +152,10 @@ pub mod staking {
     /// # Arguments
     /// * `ctx` - Unstake context
     /// * `amount` - Amount of tokens to unstake
-    pub fn unstake(ctx: Context<Unstake>, amount: u64) -> Result<()> {
+    pub fn unstake<'info>(
+        ctx: Context<'_, '_, '_, 'info, Unstake<'info>>,
+        amount: u64,
+    ) -> Result<()> {
         instructions::unstake::handler(ctx, amount)
     }
 
@@ -75,251 +166,2776 @@ pub mod staking {
     pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
         instructions::claim_rewards::handler(ctx)
     }
-}
 
-// ============================================================================
-// Helper Functions
-// ============================================================================
+    /// Stake native SOL into a wrapped SOL pool
+    ///
+    /// # Arguments
+    /// * `ctx` - StakeNative context
+    /// * `amount` - Amount of lamports to stake
+    /// * `lock_duration` - Lock duration in seconds (must be between min and max)
+    pub fn stake_native<'info>(
+        ctx: Context<'_, '_, '_, 'info, StakeNative<'info>>,
+        amount: u64,
+        lock_duration: i64,
+    ) -> Result<()> {
+        instructions::stake_native::handler(ctx, amount, lock_duration)
+    }
 
-/// Calculate the staking tier based on staked amount
-///
-/// # Arguments
-/// * `staked_amount` - Total tokens staked by user
-///
-/// # Returns
-/// * `StakingTier` - The user's tier based on their stake
-pub fn calculate_tier(staked_amount: u64) -> StakingTier {
-    if staked_amount >= constants::VIP_THRESHOLD {
-        StakingTier::Vip
-    } else if staked_amount >= constants::PREMIUM_THRESHOLD {
-        StakingTier::Premium
-    } else if staked_amount >= constants::HOLDER_THRESHOLD {
-        StakingTier::Holder
-    } else {
-        StakingTier::None
+    /// Unstake from a wrapped SOL pool, receiving native SOL
+    ///
+    /// # Arguments
+    /// * `ctx` - UnstakeNative context
+    /// * `amount` - Amount of lamports to unstake
+    pub fn unstake_native<'info>(
+        ctx: Context<'_, '_, '_, 'info, UnstakeNative<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::unstake_native::handler(ctx, amount)
     }
-}
 
-/// Get platform fee in basis points for a given tier
-///
-/// # Arguments
-/// * `tier` - The staking tier
-///
-/// # Returns
-/// * `u16` - Fee in basis points (500 = 5%, 400 = 4%, 200 = 2%, 0 = 0%)
-pub fn get_platform_fee(tier: StakingTier) -> u16 {
-    tier.platform_fee_bps()
-}
+    /// Register a hook program notified on every stake/unstake
+    ///
+    /// # Arguments
+    /// * `ctx` - ManageHooks context
+    /// * `hook_program` - Program implementing `on_stake_changed`
+    pub fn add_hook(ctx: Context<ManageHooks>, hook_program: Pubkey) -> Result<()> {
+        instructions::manage_hooks::add_hook_handler(ctx, hook_program)
+    }
 
-/// Get reward multiplier in basis points for a given tier
-///
-/// # Arguments
-/// * `tier` - The staking tier
-///
-/// # Returns
-/// * `u64` - Multiplier in basis points (10000 = 1x, 15000 = 1.5x)
-pub fn get_reward_multiplier(tier: StakingTier) -> u64 {
-    tier.reward_multiplier_bps()
-}
+    /// Remove a previously registered hook program
+    ///
+    /// # Arguments
+    /// * `ctx` - ManageHooks context
+    /// * `hook_program` - Program to remove from the registry
+    pub fn remove_hook(ctx: Context<ManageHooks>, hook_program: Pubkey) -> Result<()> {
+        instructions::manage_hooks::remove_hook_handler(ctx, hook_program)
+    }
 
-/// Calculate weight multiplier based on lock duration
-/// Linear interpolation from 1x (min duration) to 2x (max duration)
-///
-/// # Arguments
-/// * `lock_duration` - Chosen lock duration in seconds
-/// * `min_duration` - Minimum allowed lock duration
-/// * `max_duration` - Maximum allowed lock duration
-///
-/// # Returns
-/// * `u64` - Weight multiplier in basis points (10000 = 1x, 20000 = 2x)
-pub fn calculate_weight_multiplier(
-    lock_duration: i64,
-    min_duration: i64,
-    max_duration: i64,
-) -> u64 {
-    // Clamp duration to valid range
-    let duration = lock_duration.max(min_duration).min(max_duration);
+    /// Update pool configuration (authority only)
+    ///
+    /// # Arguments
+    /// * `ctx` - UpdatePoolConfig context
+    /// * `params` - Fields to change; unset fields are left as-is
+    pub fn update_pool_config(
+        ctx: Context<UpdatePoolConfig>,
+        params: UpdatePoolConfigParams,
+    ) -> Result<()> {
+        instructions::update_pool_config::handler(ctx, params)
+    }
 
-    // Calculate how far through the range we are (0 to 10000)
-    let range = max_duration - min_duration;
-    if range == 0 {
-        return constants::MIN_WEIGHT_MULTIPLIER;
+    /// Remove the lock bonus from a position whose lock has expired
+    /// (permissionless)
+    ///
+    /// # Arguments
+    /// * `ctx` - KickExpired context
+    pub fn kick_expired(ctx: Context<KickExpired>) -> Result<()> {
+        instructions::kick_expired::handler(ctx)
     }
 
-    let progress = duration - min_duration;
-    let progress_bps = ((progress as u128) * 10000 / (range as u128)) as u64;
+    /// Freeze a user's stake, blocking unstake and claim (authority only)
+    ///
+    /// # Arguments
+    /// * `ctx` - SetUserFrozen context
+    pub fn freeze_user_stake(ctx: Context<SetUserFrozen>) -> Result<()> {
+        instructions::freeze_user_stake::freeze_handler(ctx)
+    }
 
-    // Linear interpolation: min_mult + (max_mult - min_mult) * progress / 10000
-    let multiplier_range = constants::MAX_WEIGHT_MULTIPLIER - constants::MIN_WEIGHT_MULTIPLIER;
-    constants::MIN_WEIGHT_MULTIPLIER + (multiplier_range * progress_bps / 10000)
-}
+    /// Lift a compliance freeze from a user's stake (authority only)
+    ///
+    /// # Arguments
+    /// * `ctx` - SetUserFrozen context
+    pub fn thaw_user_stake(ctx: Context<SetUserFrozen>) -> Result<()> {
+        instructions::freeze_user_stake::thaw_handler(ctx)
+    }
 
-/// Update the accumulated rewards per share for a stake pool
-/// Must be called before any stake/unstake/claim operation
-///
-/// # Arguments
-/// * `stake_pool` - Mutable reference to the stake pool
-/// * `current_time` - Current Unix timestamp
-///
-/// # Returns
-/// * `Result<()>` - Success or error
-pub fn update_rewards(stake_pool: &mut StakePool, current_time: i64) -> Result<()> {
-    if stake_pool.total_weighted_stake == 0 {
-        stake_pool.last_reward_time = current_time;
-        return Ok(());
+    /// Create the global protocol treasury (program upgrade authority only)
+    ///
+    /// # Arguments
+    /// * `ctx` - InitializeProtocolTreasury context
+    /// * `pool_creation_fee` - SOL fee in lamports charged per new pool
+    pub fn initialize_protocol_treasury(
+        ctx: Context<InitializeProtocolTreasury>,
+        pool_creation_fee: u64,
+    ) -> Result<()> {
+        instructions::initialize_protocol_treasury::handler(ctx, pool_creation_fee)
     }
 
-    let time_elapsed = current_time
-        .checked_sub(stake_pool.last_reward_time)
-        .ok_or(StakingError::MathOverflow)?;
+    /// Update the pool creation fee (treasury admin only)
+    ///
+    /// # Arguments
+    /// * `ctx` - SetPoolCreationFee context
+    /// * `pool_creation_fee` - SOL fee in lamports charged per new pool
+    pub fn set_pool_creation_fee(
+        ctx: Context<SetPoolCreationFee>,
+        pool_creation_fee: u64,
+    ) -> Result<()> {
+        instructions::set_pool_creation_fee::handler(ctx, pool_creation_fee)
+    }
 
-    if time_elapsed <= 0 {
-        return Ok(());
+    /// Forward collected protocol fees to the treasury program
+    ///
+    /// # Arguments
+    /// * `ctx` - SweepProtocolFees context
+    pub fn sweep_protocol_fees(ctx: Context<SweepProtocolFees>) -> Result<()> {
+        instructions::sweep_protocol_fees::handler(ctx)
     }
 
-    // Calculate new rewards: time_elapsed * reward_rate
-    let new_rewards = (time_elapsed as u128)
-        .checked_mul(stake_pool.reward_rate as u128)
-        .ok_or(StakingError::MathOverflow)?;
+    /// Publish a merkle root for a one-off bonus distribution
+    ///
+    /// # Arguments
+    /// * `ctx` - PublishSnapshotRoot context
+    /// * `merkle_root` - Root over `keccak(user || amount)` leaves
+    /// * `total` - Total tokens deposited for the distribution (may be 0
+    ///   for a voting-power-only snapshot)
+    /// * `voting_power_root` - Optional root over `keccak(user || weighted_stake)` leaves
+    pub fn publish_snapshot_root(
+        ctx: Context<PublishSnapshotRoot>,
+        merkle_root: [u8; 32],
+        total: u64,
+        voting_power_root: Option<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::snapshot::publish_handler(ctx, merkle_root, total, voting_power_root)
+    }
 
-    // Update accumulated reward per share
-    // acc_reward_per_share += (new_rewards * PRECISION) / total_weighted_stake
-    let reward_per_share_increase = new_rewards
-        .checked_mul(constants::PRECISION)
-        .ok_or(StakingError::MathOverflow)?
-        .checked_div(stake_pool.total_weighted_stake as u128)
-        .ok_or(StakingError::MathOverflow)?;
+    /// Claim an allocation from a snapshot distribution
+    ///
+    /// # Arguments
+    /// * `ctx` - ClaimSnapshotReward context
+    /// * `amount` - Allocation recorded in the merkle leaf
+    /// * `proof` - Sibling hashes from the leaf up to the root
+    pub fn claim_snapshot_reward(
+        ctx: Context<ClaimSnapshotReward>,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::snapshot::claim_snapshot_handler(ctx, amount, proof)
+    }
 
-    stake_pool.accumulated_reward_per_share = stake_pool
-        .accumulated_reward_per_share
-        .checked_add(reward_per_share_increase)
-        .ok_or(StakingError::MathOverflow)?;
+    /// Prove a staker's voting power at a snapshot (read-only)
+    ///
+    /// # Arguments
+    /// * `ctx` - VerifySnapshotEntry context
+    /// * `user` - Staker whose entry is being proven
+    /// * `weighted_stake` - Weighted stake recorded in the merkle leaf
+    /// * `proof` - Sibling hashes from the leaf up to the voting power root
+    ///
+    /// # Returns
+    /// * `u64` - The verified weighted stake
+    pub fn verify_snapshot_entry(
+        ctx: Context<VerifySnapshotEntry>,
+        user: Pubkey,
+        weighted_stake: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<u64> {
+        instructions::snapshot::verify_entry_handler(ctx, user, weighted_stake, proof)
+    }
 
-    stake_pool.last_reward_time = current_time;
+    /// Start emitting an extra reward token from a live pool
+    ///
+    /// # Arguments
+    /// * `ctx` - AddRewardToken context
+    /// * `rate` - Reward tokens emitted per second
+    pub fn add_reward_token(ctx: Context<AddRewardToken>, rate: u64) -> Result<()> {
+        instructions::manage_reward_streams::add_stream_handler(ctx, rate)
+    }
 
-    Ok(())
-}
+    /// Stop emitting an extra reward token
+    ///
+    /// # Arguments
+    /// * `ctx` - RemoveRewardToken context
+    /// * `stream_id` - Id of the stream to stop
+    pub fn remove_reward_token(ctx: Context<RemoveRewardToken>, stream_id: u64) -> Result<()> {
+        instructions::manage_reward_streams::remove_stream_handler(ctx, stream_id)
+    }
 
-/// Calculate pending rewards for a user stake
-///
-/// # Arguments
-/// * `user_stake` - Reference to the user's stake account
-/// * `accumulated_reward_per_share` - Current accumulated reward per share from pool
-///
-/// # Returns
-/// * `Result<u64>` - Pending reward amount
-pub fn calculate_pending_rewards(
-    user_stake: &UserStake,
-    accumulated_reward_per_share: u128,
-) -> Result<u64> {
-    if user_stake.weighted_stake == 0 {
-        return Ok(0);
+    /// Claim rewards from an extra reward stream
+    ///
+    /// # Arguments
+    /// * `ctx` - ClaimStreamRewards context
+    /// * `stream_id` - Id of the stream to claim from
+    pub fn claim_stream_rewards(ctx: Context<ClaimStreamRewards>, stream_id: u64) -> Result<()> {
+        instructions::claim_stream_rewards::handler(ctx, stream_id)
     }
 
-    // pending = (weighted_stake * acc_reward_per_share / PRECISION) - reward_debt
-    let accumulated = (user_stake.weighted_stake as u128)
-        .checked_mul(accumulated_reward_per_share)
-        .ok_or(StakingError::MathOverflow)?
-        .checked_div(constants::PRECISION)
-        .ok_or(StakingError::MathOverflow)?;
+    /// Add, update, or remove (zero bonus) a partner token boost
+    ///
+    /// # Arguments
+    /// * `ctx` - SetPartnerBoost context
+    /// * `partner_mint` - Partner token mint
+    /// * `min_amount` - Minimum partner tokens locked to qualify
+    /// * `bonus_bps` - Extra reward multiplier granted in basis points
+    pub fn set_partner_boost(
+        ctx: Context<SetPartnerBoost>,
+        partner_mint: Pubkey,
+        min_amount: u64,
+        bonus_bps: u64,
+    ) -> Result<()> {
+        instructions::partner_boost::set_handler(ctx, partner_mint, min_amount, bonus_bps)
+    }
 
-    let pending = accumulated
-        .checked_sub(user_stake.reward_debt)
-        .unwrap_or(0);
+    /// Lock partner tokens in escrow to boost main pool rewards
+    ///
+    /// # Arguments
+    /// * `ctx` - MovePartnerTokens context
+    /// * `amount` - Partner tokens to lock
+    pub fn lock_partner_tokens(ctx: Context<MovePartnerTokens>, amount: u64) -> Result<()> {
+        instructions::partner_boost::lock_partner_handler(ctx, amount)
+    }
 
-    // Safe to cast as rewards should never exceed u64 in practice
-    Ok(pending.min(u64::MAX as u128) as u64)
-}
+    /// Withdraw partner tokens from escrow
+    ///
+    /// # Arguments
+    /// * `ctx` - MovePartnerTokens context
+    /// * `amount` - Partner tokens to withdraw
+    pub fn unlock_partner_tokens(ctx: Context<MovePartnerTokens>, amount: u64) -> Result<()> {
+        instructions::partner_boost::unlock_partner_handler(ctx, amount)
+    }
 
-// ============================================================================
-// Tests
-// ============================================================================
+    /// Re-fold a position's current tier multiplier into its weight
+    /// (permissionless; also migrates legacy positions)
+    ///
+    /// # Arguments
+    /// * `ctx` - RefreshTier context
+    pub fn refresh_tier(ctx: Context<RefreshTier>) -> Result<()> {
+        instructions::refresh_tier::handler(ctx)
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Unstake before the lock ends, paying a penalty that scales with the
+    /// lock time remaining
+    ///
+    /// # Arguments
+    /// * `ctx` - Unstake context
+    /// * `amount` - Amount to unstake (penalty is withheld from it)
+    pub fn early_unstake<'info>(
+        ctx: Context<'_, '_, '_, 'info, Unstake<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::early_unstake::handler(ctx, amount)
+    }
 
-    #[test]
-    fn test_calculate_tier() {
-        // Less than 1,000 tokens (with 9 decimals)
-        assert_eq!(calculate_tier(999_000_000_000), StakingTier::None);
+    /// Preview the early unstake penalty for a position (read-only)
+    ///
+    /// # Arguments
+    /// * `ctx` - PreviewEarlyUnstakeFee context
+    /// * `amount` - Amount that would be unstaked
+    ///
+    /// # Returns
+    /// * Penalty that would be withheld, via return data
+    pub fn preview_early_unstake_fee(
+        ctx: Context<PreviewEarlyUnstakeFee>,
+        amount: u64,
+    ) -> Result<u64> {
+        instructions::early_unstake::preview_handler(ctx, amount)
+    }
 
-        // Exactly 1,000 tokens
-        assert_eq!(calculate_tier(1_000_000_000_000), StakingTier::Holder);
+    /// Claim owed rewards and unstake in one instruction, settling rewards
+    /// before the stake is reduced so nothing is left behind
+    ///
+    /// # Arguments
+    /// * `ctx` - ClaimAndUnstake context
+    /// * `amount` - Amount to unstake
+    pub fn claim_and_unstake<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimAndUnstake<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::claim_and_unstake::handler(ctx, amount)
+    }
 
-        // Between 1,000 and 10,000
-        assert_eq!(calculate_tier(5_000_000_000_000), StakingTier::Holder);
+    /// Update the pool's name, symbol and metadata URI (authority only)
+    ///
+    /// # Arguments
+    /// * `ctx` - UpdatePoolMetadata context
+    /// * `name` - Display name (max 32 bytes)
+    /// * `symbol` - Symbol (max 10 bytes)
+    /// * `uri` - Off-chain metadata URI (max 128 bytes)
+    pub fn update_pool_metadata(
+        ctx: Context<UpdatePoolMetadata>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        instructions::update_pool_metadata::handler(ctx, name, symbol, uri)
+    }
 
-        // Exactly 10,000 tokens
-        assert_eq!(calculate_tier(10_000_000_000_000), StakingTier::Premium);
+    /// Recover tokens of a foreign mint sent to the pool PDA's associated
+    /// token account (authority only). Stake, reward and stream reward
+    /// mints are refused.
+    ///
+    /// # Arguments
+    /// * `ctx` - RecoverToken context
+    pub fn recover_token(ctx: Context<RecoverToken>) -> Result<()> {
+        instructions::recover_token::handler(ctx)
+    }
 
-        // Between 10,000 and 100,000
-        assert_eq!(calculate_tier(50_000_000_000_000), StakingTier::Premium);
+    /// Create the global tier benefits table read by the launchpad
+    /// (treasury admin only)
+    ///
+    /// # Arguments
+    /// * `ctx` - InitializeTierBenefits context
+    /// * `governance` - Authority allowed to edit the table
+    /// * `benefits` - Benefits for None, Holder, Premium and Vip
+    pub fn initialize_tier_benefits(
+        ctx: Context<InitializeTierBenefits>,
+        governance: Pubkey,
+        benefits: [TierBenefit; 4],
+    ) -> Result<()> {
+        instructions::tier_benefits::initialize_tier_benefits_handler(ctx, governance, benefits)
+    }
 
-        // Exactly 100,000 tokens
-        assert_eq!(calculate_tier(100_000_000_000_000), StakingTier::Vip);
+    /// Replace one tier's launchpad benefits (governance only)
+    ///
+    /// # Arguments
+    /// * `ctx` - UpdateTierBenefits context
+    /// * `tier` - Tier to update
+    /// * `benefit` - New benefits for the tier
+    pub fn set_tier_benefit(
+        ctx: Context<UpdateTierBenefits>,
+        tier: StakingTier,
+        benefit: TierBenefit,
+    ) -> Result<()> {
+        instructions::tier_benefits::set_benefit_handler(ctx, tier, benefit)
+    }
 
-        // More than 100,000
-        assert_eq!(calculate_tier(500_000_000_000_000), StakingTier::Vip);
+    /// Hand the tier benefits table to a new governance authority
+    ///
+    /// # Arguments
+    /// * `ctx` - UpdateTierBenefits context
+    /// * `new_governance` - New governance authority
+    pub fn set_tier_benefits_governance(
+        ctx: Context<UpdateTierBenefits>,
+        new_governance: Pubkey,
+    ) -> Result<()> {
+        instructions::tier_benefits::set_governance_handler(ctx, new_governance)
     }
 
-    #[test]
-    fn test_tier_fees() {
-        assert_eq!(StakingTier::None.platform_fee_bps(), 500);    // 5%
-        assert_eq!(StakingTier::Holder.platform_fee_bps(), 400);  // 4%
-        assert_eq!(StakingTier::Premium.platform_fee_bps(), 200); // 2%
-        assert_eq!(StakingTier::Vip.platform_fee_bps(), 0);       // 0%
+    /// Transfer pool management to a new authority. Setting it to the
+    /// timelock PDA routes all later config changes through the timelock.
+    ///
+    /// # Arguments
+    /// * `ctx` - SetPoolAuthority context
+    /// * `new_authority` - The new pool authority
+    pub fn set_pool_authority(ctx: Context<SetPoolAuthority>, new_authority: Pubkey) -> Result<()> {
+        instructions::set_pool_authority::handler(ctx, new_authority)
     }
 
-    #[test]
-    fn test_tier_multipliers() {
-        assert_eq!(StakingTier::None.reward_multiplier_bps(), 10000);    // 1.0x
-        assert_eq!(StakingTier::Holder.reward_multiplier_bps(), 11000);  // 1.1x
-        assert_eq!(StakingTier::Premium.reward_multiplier_bps(), 12500); // 1.25x
-        assert_eq!(StakingTier::Vip.reward_multiplier_bps(), 15000);     // 1.5x
+    /// Spend loyalty points with a partner (user and partner both sign)
+    ///
+    /// # Arguments
+    /// * `ctx` - SpendPoints context
+    /// * `amount` - Points to spend
+    pub fn spend_points(ctx: Context<SpendPoints>, amount: u64) -> Result<()> {
+        instructions::spend_points::handler(ctx, amount)
     }
 
-    #[test]
-    fn test_weight_multiplier() {
-        let min_duration = constants::MIN_LOCK_DURATION; // 7 days
-        let max_duration = constants::MAX_LOCK_DURATION; // 365 days
+    /// Create the pool's insurance fund (authority only)
+    ///
+    /// # Arguments
+    /// * `ctx` - InitializeInsuranceFund context
+    pub fn initialize_insurance_fund(ctx: Context<InitializeInsuranceFund>) -> Result<()> {
+        instructions::insurance_fund::initialize_handler(ctx)
+    }
 
-        // Minimum duration should give 1x (10000 bps)
-        let mult_min = calculate_weight_multiplier(min_duration, min_duration, max_duration);
-        assert_eq!(mult_min, 10000);
+    /// Top up the reward vault from the insurance fund (authority only)
+    ///
+    /// # Arguments
+    /// * `ctx` - CoverShortfall context
+    /// * `amount` - Tokens to move into the reward vault
+    pub fn cover_shortfall(ctx: Context<CoverShortfall>, amount: u64) -> Result<()> {
+        instructions::insurance_fund::cover_handler(ctx, amount)
+    }
 
-        // Maximum duration should give 2x (20000 bps)
-        let mult_max = calculate_weight_multiplier(max_duration, min_duration, max_duration);
-        assert_eq!(mult_max, 20000);
+    /// Opt a position in or out of keeper auto-claims
+    ///
+    /// # Arguments
+    /// * `ctx` - SetAutoClaim context
+    /// * `enabled` - Whether keepers may claim for this position
+    pub fn set_auto_claim(ctx: Context<SetAutoClaim>, enabled: bool) -> Result<()> {
+        instructions::crank_claims::set_auto_claim_handler(ctx, enabled)
+    }
 
-        // Middle duration should give approximately 1.5x
-        let mid_duration = (min_duration + max_duration) / 2;
-        let mult_mid = calculate_weight_multiplier(mid_duration, min_duration, max_duration);
-        // Should be close to 15000 (1.5x)
-        assert!(mult_mid >= 14900 && mult_mid <= 15100);
+    /// Claim rewards for opted-in positions (permissionless keeper crank)
+    ///
+    /// Remaining accounts are `(user_stake, user_reward_account)` pairs.
+    ///
+    /// # Arguments
+    /// * `ctx` - CrankClaims context
+    pub fn crank_claims<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CrankClaims<'info>>,
+    ) -> Result<()> {
+        instructions::crank_claims::handler(ctx)
+    }
+
+    /// Distribute rewards for completed epochs (permissionless crank)
+    ///
+    /// # Arguments
+    /// * `ctx` - SettleEpoch context
+    pub fn settle_epoch(ctx: Context<SettleEpoch>) -> Result<()> {
+        instructions::settle_epoch::handler(ctx)
+    }
+
+    /// Move tokens donated directly to the stake vault to the reward vault
+    /// (permissionless)
+    ///
+    /// # Arguments
+    /// * `ctx` - SweepSurplus context
+    pub fn sweep_surplus(ctx: Context<SweepSurplus>) -> Result<()> {
+        instructions::sweep_surplus::handler(ctx)
+    }
+
+    /// Reconcile vault balances with pool accounting (permissionless)
+    ///
+    /// # Arguments
+    /// * `ctx` - SyncVaults context
+    pub fn sync_vaults(ctx: Context<SyncVaults>) -> Result<()> {
+        instructions::sync_vaults::handler(ctx)
+    }
+
+    /// Fail if pool accounting invariants don't hold (permissionless)
+    ///
+    /// Remaining accounts are `UserStake` accounts of this pool to check too.
+    ///
+    /// # Arguments
+    /// * `ctx` - AssertInvariants context
+    pub fn assert_invariants<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AssertInvariants<'info>>,
+    ) -> Result<()> {
+        instructions::assert_invariants::handler(ctx)
+    }
+
+    /// Refresh the pool's light-client snapshot and sample its APR/TVL
+    /// history (permissionless crank)
+    ///
+    /// # Arguments
+    /// * `ctx` - RefreshPoolSnapshot context
+    pub fn refresh_pool_snapshot(ctx: Context<RefreshPoolSnapshot>) -> Result<()> {
+        instructions::refresh_pool_snapshot::handler(ctx)
+    }
+
+    /// Create the global program config (program upgrade authority only)
+    ///
+    /// # Arguments
+    /// * `ctx` - InitializeProgramConfig context
+    /// * `params` - Initial platform-wide parameters
+    pub fn initialize_program_config(
+        ctx: Context<InitializeProgramConfig>,
+        params: ProgramConfigParams,
+    ) -> Result<()> {
+        instructions::program_config::initialize_handler(ctx, params)
+    }
+
+    /// Update the global program config (protocol admin only)
+    ///
+    /// # Arguments
+    /// * `ctx` - UpdateProgramConfig context
+    /// * `update` - Fields to change; `None` leaves a field as is
+    pub fn update_program_config(
+        ctx: Context<UpdateProgramConfig>,
+        update: ProgramConfigUpdate,
+    ) -> Result<()> {
+        instructions::program_config::update_handler(ctx, update)
+    }
+
+    /// Schedule a reward rate change (pool authority only)
+    ///
+    /// # Arguments
+    /// * `ctx` - ScheduleRateChange context
+    /// * `new_rate` - Reward rate (tokens per second) from `effective_time` on
+    /// * `effective_time` - Unix timestamp the new rate takes effect
+    pub fn schedule_rate_change(
+        ctx: Context<ScheduleRateChange>,
+        new_rate: u64,
+        effective_time: i64,
+    ) -> Result<()> {
+        instructions::schedule_rate_change::handler(ctx, new_rate, effective_time)
+    }
+
+    /// Preview an early exit for a position (read-only)
+    ///
+    /// # Arguments
+    /// * `ctx` - PreviewEarlyUnstakeFee context
+    /// * `amount` - Amount that would be unstaked
+    ///
+    /// # Returns
+    /// * Penalty, net tokens out and forfeited rewards, via return data
+    pub fn preview_early_exit(
+        ctx: Context<PreviewEarlyUnstakeFee>,
+        amount: u64,
+    ) -> Result<EarlyExitPreview> {
+        instructions::early_unstake::preview_exit_handler(ctx, amount)
+    }
+
+    /// Create the pool's milestone bonus vault (authority only)
+    ///
+    /// # Arguments
+    /// * `ctx` - InitializeMilestoneBonus context
+    /// * `bonus_amounts` - Bonus for the 90, 180 and 365 day milestones
+    pub fn initialize_milestone_bonus(
+        ctx: Context<InitializeMilestoneBonus>,
+        bonus_amounts: [u64; 3],
+    ) -> Result<()> {
+        instructions::milestone_bonus::initialize_handler(ctx, bonus_amounts)
+    }
+
+    /// Change the bonus paid per milestone (authority only)
+    ///
+    /// # Arguments
+    /// * `ctx` - SetMilestoneBonuses context
+    /// * `bonus_amounts` - Bonus for the 90, 180 and 365 day milestones
+    pub fn set_milestone_bonuses(
+        ctx: Context<SetMilestoneBonuses>,
+        bonus_amounts: [u64; 3],
+    ) -> Result<()> {
+        instructions::milestone_bonus::set_bonuses_handler(ctx, bonus_amounts)
+    }
+
+    /// Claim the one-time bonuses for every milestone the position's
+    /// unbroken streak has reached
+    ///
+    /// # Arguments
+    /// * `ctx` - ClaimMilestoneBonus context
+    pub fn claim_milestone_bonus(ctx: Context<ClaimMilestoneBonus>) -> Result<()> {
+        instructions::milestone_bonus::claim_handler(ctx)
+    }
+
+    /// Claim rewards from several pools in one transaction
+    ///
+    /// Remaining accounts are `(stake_pool, pool_stats, user_stake,
+    /// reward_vault, user_reward_account, fee_treasury)` groups.
+    ///
+    /// # Arguments
+    /// * `ctx` - ClaimMulti context
+    pub fn claim_multi<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimMulti<'info>>,
+    ) -> Result<()> {
+        instructions::claim_multi::handler(ctx)
+    }
+
+    /// Reallocate a pool created under the v1 layout to the current size,
+    /// fill the new fields and bump its version (authority only)
+    ///
+    /// # Arguments
+    /// * `ctx` - MigratePool context
+    pub fn migrate_pool_v1_to_v2(ctx: Context<MigratePool>) -> Result<()> {
+        instructions::migrate_pool::handler(ctx)
+    }
+
+    /// Hand the program's upgrade authority to the governance timelock
+    /// (current upgrade authority only)
+    ///
+    /// # Arguments
+    /// * `ctx` - SetUpgradeAuthority context
+    pub fn set_upgrade_authority(ctx: Context<SetUpgradeAuthority>) -> Result<()> {
+        instructions::set_upgrade_authority::handler(ctx)
+    }
+
+    /// Pause or unpause a pool (authority only)
+    ///
+    /// # Arguments
+    /// * `ctx` - SetPoolPaused context
+    /// * `paused` - Whether the pool should be paused
+    pub fn set_pool_paused(ctx: Context<SetPoolPaused>, paused: bool) -> Result<()> {
+        instructions::pause_pool::set_paused_handler(ctx, paused)
+    }
+
+    /// Pause any pool for incident response (program guardian only)
+    ///
+    /// # Arguments
+    /// * `ctx` - GuardianPause context
+    pub fn guardian_pause(ctx: Context<GuardianPause>) -> Result<()> {
+        instructions::pause_pool::guardian_pause_handler(ctx)
+    }
+
+    /// Pause every pool protocol-wide (program admin or guardian)
+    ///
+    /// # Arguments
+    /// * `ctx` - PauseAll context
+    pub fn pause_all(ctx: Context<PauseAll>) -> Result<()> {
+        instructions::pause_pool::pause_all_handler(ctx)
+    }
+
+    /// Lift the protocol-wide pause (program admin only)
+    ///
+    /// # Arguments
+    /// * `ctx` - UnpauseAll context
+    pub fn unpause_all(ctx: Context<UnpauseAll>) -> Result<()> {
+        instructions::pause_pool::unpause_all_handler(ctx)
+    }
+
+    /// Reset an APR-targeted pool's reward rate from its current TVL
+    /// (permissionless crank, once per retarget interval)
+    ///
+    /// # Arguments
+    /// * `ctx` - RetargetRewardRate context
+    pub fn retarget_reward_rate(ctx: Context<RetargetRewardRate>) -> Result<()> {
+        instructions::retarget_reward_rate::handler(ctx)
+    }
+
+    /// Open the pool's fixed-term certificate product and create its
+    /// escrow vaults (authority only)
+    ///
+    /// # Arguments
+    /// * `ctx` - InitializeCertificates context
+    /// * `apr_bps` - APR fixed into new certificates (0 = issuance closed)
+    /// * `min_term` - Shortest certificate term in seconds
+    /// * `max_term` - Longest certificate term in seconds
+    pub fn initialize_certificates(
+        ctx: Context<InitializeCertificates>,
+        apr_bps: u64,
+        min_term: i64,
+        max_term: i64,
+    ) -> Result<()> {
+        instructions::certificates::initialize_certificates_handler(ctx, apr_bps, min_term, max_term)
+    }
+
+    /// Change the terms offered to new certificates (authority only)
+    ///
+    /// # Arguments
+    /// * `ctx` - SetCertificateTerms context
+    /// * `apr_bps` - APR fixed into new certificates (0 = issuance closed)
+    /// * `min_term` - Shortest certificate term in seconds
+    /// * `max_term` - Longest certificate term in seconds
+    pub fn set_certificate_terms(
+        ctx: Context<SetCertificateTerms>,
+        apr_bps: u64,
+        min_term: i64,
+        max_term: i64,
+    ) -> Result<()> {
+        instructions::certificates::set_terms_handler(ctx, apr_bps, min_term, max_term)
+    }
+
+    /// Buy a fixed-term certificate; principal and the fixed payout are
+    /// escrowed until maturity
+    ///
+    /// # Arguments
+    /// * `ctx` - IssueCertificate context
+    /// * `certificate_id` - Id distinguishing this certificate among the user's
+    /// * `principal` - Stake tokens to lock
+    /// * `term` - Term in seconds, within the pool's offered range
+    pub fn issue_certificate(
+        ctx: Context<IssueCertificate>,
+        certificate_id: u64,
+        principal: u64,
+        term: i64,
+    ) -> Result<()> {
+        instructions::certificates::issue_handler(ctx, certificate_id, principal, term)
+    }
+
+    /// Redeem a matured certificate for its principal and payout
+    ///
+    /// # Arguments
+    /// * `ctx` - RedeemCertificate context
+    pub fn redeem_certificate(ctx: Context<RedeemCertificate>) -> Result<()> {
+        instructions::certificates::redeem_handler(ctx)
+    }
+
+    /// Lock a position as collateral for a whitelisted lending program,
+    /// blocking unstake until released. Called by the lending program via
+    /// CPI, signed by the owner and the program's collateral authority PDA.
+    ///
+    /// # Arguments
+    /// * `ctx` - LockPositionAsCollateral context
+    /// * `lending_program` - The whitelisted lending program taking the position
+    pub fn lock_position_as_collateral(
+        ctx: Context<LockPositionAsCollateral>,
+        lending_program: Pubkey,
+    ) -> Result<()> {
+        instructions::collateral::lock_collateral_handler(ctx, lending_program)
+    }
+
+    /// Release a position from collateral (the holding lending program's
+    /// collateral authority only)
+    ///
+    /// # Arguments
+    /// * `ctx` - ReleasePositionCollateral context
+    pub fn release_position_collateral(ctx: Context<ReleasePositionCollateral>) -> Result<()> {
+        instructions::collateral::release_collateral_handler(ctx)
+    }
+
+    /// Refresh a farm position's boost from its owner's weighted stake in
+    /// the pool's boost source pool (permissionless)
+    ///
+    /// # Arguments
+    /// * `ctx` - RefreshCrossBoost context
+    pub fn refresh_cross_boost(ctx: Context<RefreshCrossBoost>) -> Result<()> {
+        instructions::cross_boost::handler(ctx)
+    }
+
+    /// Turn the caller's position into an operator position whose rewards
+    /// are paid into an escrow for its beneficiaries (one-way)
+    ///
+    /// # Arguments
+    /// * `ctx` - EnableOperatorMode context
+    pub fn enable_operator_mode(ctx: Context<EnableOperatorMode>) -> Result<()> {
+        instructions::operator::enable_handler(ctx)
+    }
+
+    /// Publish the merkle root of an operator position's sub-accounts
+    /// (operator only)
+    ///
+    /// # Arguments
+    /// * `ctx` - PublishOperatorRoot context
+    /// * `merkle_root` - Root over each beneficiary's balance and cumulative rewards
+    /// * `total_balance` - Sum of sub-balances; must equal the staked amount
+    /// * `total_rewards` - Sum of cumulative rewards; at most what was collected
+    pub fn publish_operator_root(
+        ctx: Context<PublishOperatorRoot>,
+        merkle_root: [u8; 32],
+        total_balance: u64,
+        total_rewards: u64,
+    ) -> Result<()> {
+        instructions::operator::publish_root_handler(ctx, merkle_root, total_balance, total_rewards)
+    }
+
+    /// Pay an operator position's rewards into its escrow (permissionless)
+    ///
+    /// # Arguments
+    /// * `ctx` - CollectOperatorRewards context
+    pub fn collect_operator_rewards(ctx: Context<CollectOperatorRewards>) -> Result<()> {
+        instructions::operator::collect_handler(ctx)
+    }
+
+    /// Claim a beneficiary's rewards from an operator position's escrow
+    ///
+    /// # Arguments
+    /// * `ctx` - ClaimOperatorRewards context
+    /// * `balance` - Beneficiary's sub-balance recorded in the leaf
+    /// * `cumulative_rewards` - Beneficiary's cumulative rewards recorded in the leaf
+    /// * `proof` - Sibling hashes from the leaf up to the root
+    pub fn claim_operator_rewards(
+        ctx: Context<ClaimOperatorRewards>,
+        balance: u64,
+        cumulative_rewards: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::operator::operator_claim_handler(ctx, balance, cumulative_rewards, proof)
+    }
+
+    /// Cancel a frozen account's unclaimed rewards (guardian, through the
+    /// governance timelock)
+    ///
+    /// # Arguments
+    /// * `ctx` - ClawbackRewards context
+    pub fn clawback_rewards(ctx: Context<ClawbackRewards>) -> Result<()> {
+        instructions::clawback_rewards::clawback_handler(ctx)
+    }
+
+    /// Reallocate a position created under the original layout to the
+    /// current `UserStake` size (permissionless, after its pool is migrated)
+    ///
+    /// # Arguments
+    /// * `ctx` - MigrateUserStake context
+    pub fn migrate_user_stake(ctx: Context<MigrateUserStake>) -> Result<()> {
+        instructions::migrate_user_stake::handler(ctx)
+    }
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Calculate the staking tier based on staked amount
+///
+/// # Arguments
+/// * `staked_amount` - Total tokens staked by user
+///
+/// # Returns
+/// * `StakingTier` - The user's tier based on their stake
+pub fn calculate_tier(staked_amount: u64) -> StakingTier {
+    staking_math::calculate_tier(staked_amount).into()
+}
+
+/// Calculate the staking tier from the staked amount and lock duration
+///
+/// # Arguments
+/// * `amount` - Total tokens staked by user
+/// * `lock_duration` - The position's lock duration in seconds
+/// * `config` - Amount and minimum lock requirements per tier
+///
+/// # Returns
+/// * `StakingTier` - The highest tier whose requirements are both met
+pub fn calculate_tier_v2(amount: u64, lock_duration: i64, config: &staking_math::TierConfig) -> StakingTier {
+    staking_math::calculate_tier_v2(amount, lock_duration, config).into()
+}
+
+/// Get platform fee in basis points for a given tier
+///
+/// # Arguments
+/// * `tier` - The staking tier
+///
+/// # Returns
+/// * `u16` - Fee in basis points (500 = 5%, 400 = 4%, 200 = 2%, 0 = 0%)
+pub fn get_platform_fee(tier: StakingTier) -> u16 {
+    tier.platform_fee_bps()
+}
+
+/// Get reward multiplier in basis points for a given tier
+///
+/// # Arguments
+/// * `tier` - The staking tier
+///
+/// # Returns
+/// * `u64` - Multiplier in basis points (10000 = 1x, 15000 = 1.5x)
+pub fn get_reward_multiplier(tier: StakingTier) -> u64 {
+    tier.reward_multiplier_bps()
+}
+
+/// Calculate weight multiplier based on lock duration
+/// Linear interpolation from 1x (min duration) to 2x (max duration)
+///
+/// # Arguments
+/// * `lock_duration` - Chosen lock duration in seconds
+/// * `min_duration` - Minimum allowed lock duration
+/// * `max_duration` - Maximum allowed lock duration
+///
+/// # Returns
+/// * `u64` - Weight multiplier in basis points (10000 = 1x, 20000 = 2x)
+pub fn calculate_weight_multiplier(
+    lock_duration: i64,
+    min_duration: i64,
+    max_duration: i64,
+) -> u64 {
+    staking_math::calculate_weight_multiplier(lock_duration, min_duration, max_duration)
+}
+
+/// Update the accumulated rewards per share for a stake pool
+/// Must be called before any stake/unstake/claim operation. A no-op for
+/// epoch-mode pools, whose rewards only move in `settle_reward_epochs`.
+///
+/// # Arguments
+/// * `stake_pool` - Mutable reference to the stake pool
+/// * `current_time` - Current Unix timestamp
+///
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn update_rewards(stake_pool: &mut StakePool, current_time: i64) -> Result<()> {
+    if stake_pool.uses_reward_epochs() {
+        return Ok(());
+    }
+
+    accrue_rewards_until(stake_pool, current_time)
+}
+
+/// Accrue rewards from `last_reward_time` up to `end_time`, switching to
+/// each scheduled reward rate exactly at its effective time
+fn accrue_rewards_until(stake_pool: &mut StakePool, end_time: i64) -> Result<()> {
+    while let Some(change) = stake_pool.due_rate_change(end_time) {
+        accrue_segment(stake_pool, change.effective_time)?;
+        stake_pool.apply_next_rate_change();
+    }
+    accrue_segment(stake_pool, end_time)
+}
+
+/// Accrue rewards at the current rate up to `until`. With nothing staked
+/// the time passes without emitting anything.
+fn accrue_segment(stake_pool: &mut StakePool, until: i64) -> Result<()> {
+    let time_elapsed = until.saturating_sub(stake_pool.last_reward_time);
+    if time_elapsed <= 0 {
+        return Ok(());
+    }
+
+    stake_pool.record_emission(time_elapsed);
+    stake_pool.accumulated_reward_per_share = staking_math::update_reward_per_share(
+        stake_pool.accumulated_reward_per_share,
+        stake_pool.total_weighted_stake,
+        stake_pool.reward_rate,
+        time_elapsed,
+    )
+    .ok_or(StakingError::MathOverflow)?;
+
+    stake_pool.last_reward_time = until;
+
+    Ok(())
+}
+
+/// Distribute rewards for every reward epoch completed since the last
+/// settlement across the weight staked at settlement time. Epochs with no
+/// stake emit nothing.
+///
+/// # Arguments
+/// * `stake_pool` - Mutable reference to an epoch-mode stake pool
+/// * `current_time` - Current Unix timestamp
+///
+/// # Returns
+/// * `Result<u64>` - Number of epochs settled
+pub fn settle_reward_epochs(stake_pool: &mut StakePool, current_time: i64) -> Result<u64> {
+    require!(stake_pool.uses_reward_epochs(), StakingError::NotEpochMode);
+
+    let elapsed = current_time.saturating_sub(stake_pool.last_reward_time);
+    let epochs = (elapsed / stake_pool.reward_epoch_duration) as u64;
+    require!(epochs > 0, StakingError::EpochNotEnded);
+
+    let settled_seconds = (epochs as i64)
+        .checked_mul(stake_pool.reward_epoch_duration)
+        .ok_or(StakingError::MathOverflow)?;
+
+    // Epochs with no stake leave the accumulator unchanged
+    let settled_until = stake_pool.last_reward_time
+        .checked_add(settled_seconds)
+        .ok_or(StakingError::MathOverflow)?;
+    accrue_rewards_until(stake_pool, settled_until)?;
+    stake_pool.reward_epoch = stake_pool.reward_epoch
+        .checked_add(epochs)
+        .ok_or(StakingError::MathOverflow)?;
+
+    Ok(epochs)
+}
+
+/// Calculate pending rewards for a user stake
+///
+/// # Arguments
+/// * `user_stake` - Reference to the user's stake account
+/// * `accumulated_reward_per_share` - Current accumulated reward per share from pool
+///
+/// # Returns
+/// * `Result<u64>` - Pending reward amount
+pub fn calculate_pending_rewards(
+    user_stake: &UserStake,
+    accumulated_reward_per_share: u128,
+) -> Result<u64> {
+    // pending = (weighted_stake * acc_reward_per_share / PRECISION) - reward_debt
+    staking_math::calculate_pending_rewards(
+        user_stake.weighted_stake,
+        user_stake.reward_debt,
+        accumulated_reward_per_share,
+    )
+    .ok_or_else(|| StakingError::MathOverflow.into())
+}
+
+/// Settle a user's pending rewards into `owed_rewards`
+/// Applies the user's effective tier multiplier plus any partner boost, then resets `reward_debt` to
+/// the current accumulator. Fixed-rate positions also accrue their
+/// guaranteed rewards up to `now`. Call after `update_rewards` and before
+/// changing `weighted_stake` or `staked_amount` so no accrued rewards are lost.
+///
+/// # Arguments
+/// * `user_stake` - Mutable reference to the user's stake account
+/// * `accumulated_reward_per_share` - Current accumulated reward per share from pool
+/// * `now` - Current Unix timestamp (for the effective tier)
+///
+/// # Returns
+/// * `Result<u64>` - Amount newly added to `owed_rewards`
+pub fn settle_rewards(
+    user_stake: &mut UserStake,
+    accumulated_reward_per_share: u128,
+    now: i64,
+) -> Result<u64> {
+    let pending = calculate_pending_rewards(user_stake, accumulated_reward_per_share)?;
+
+    // Legacy positions still earn their multiplier at settlement, then are
+    // migrated to a 1x folded weight; `rebase_weight` applies the real one
+    let settled = if user_stake.is_legacy_weight() {
+        let settled = math::apply_bps(pending, user_stake.reward_multiplier_bps(now))?;
+        user_stake.lock_weighted_stake = user_stake.weighted_stake;
+        user_stake.applied_multiplier_bps = constants::BPS_DENOMINATOR;
+        settled
+    } else {
+        pending
+    };
+
+    user_stake.owed_rewards = user_stake.owed_rewards
+        .checked_add(settled)
+        .ok_or(StakingError::MathOverflow)?;
+    user_stake.reward_debt = calculate_reward_debt(
+        user_stake.weighted_stake,
+        accumulated_reward_per_share,
+    )?;
+
+    let fixed = user_stake.accrue_fixed_rewards(now)?;
+
+    settled.checked_add(fixed).ok_or_else(|| StakingError::MathOverflow.into())
+}
+
+/// Fold the user's current reward multiplier into `weighted_stake`
+/// `weighted_stake = lock_weighted_stake * (tier + partner boost) / 10000`.
+/// Moves the difference into the pool total and resets `reward_debt`, so
+/// payouts never exceed what `reward_rate` emits. Call after `update_rewards`
+/// and `settle_rewards` so nothing already accrued is repriced.
+///
+/// # Arguments
+/// * `stake_pool` - Mutable reference to the stake pool
+/// * `user_stake` - Mutable reference to the user's stake account
+/// * `now` - Current Unix timestamp (for the effective tier)
+///
+/// # Returns
+/// * `Result<u64>` - The new weighted stake
+pub fn rebase_weight(stake_pool: &mut StakePool, user_stake: &mut UserStake, now: i64) -> Result<u64> {
+    let multiplier = stake_pool.position_multiplier_bps(user_stake, now);
+    let new_weighted = math::apply_bps(user_stake.lock_weighted_stake, multiplier)?;
+
+    stake_pool.total_weighted_stake = stake_pool.total_weighted_stake
+        .checked_sub(user_stake.weighted_stake)
+        .ok_or(StakingError::MathOverflow)?
+        .checked_add(new_weighted)
+        .ok_or(StakingError::MathOverflow)?;
+
+    user_stake.weighted_stake = new_weighted;
+    user_stake.applied_multiplier_bps = multiplier;
+    user_stake.reward_debt = calculate_reward_debt(
+        new_weighted,
+        stake_pool.accumulated_reward_per_share,
+    )?;
+
+    Ok(new_weighted)
+}
+
+/// Hand `amount` of reward tokens already in the reward vault to every
+/// staker except `user_stake`, whose debt absorbs its own share of the
+/// accumulator bump. Call after `settle_rewards`.
+///
+/// # Arguments
+/// * `stake_pool` - Mutable reference to the stake pool
+/// * `user_stake` - Mutable reference to the excluded stake account
+/// * `amount` - Reward tokens to distribute
+///
+/// # Returns
+/// * `Result<bool>` - Whether anyone else was staked to receive it
+pub fn distribute_to_other_stakers(
+    stake_pool: &mut StakePool,
+    user_stake: &mut UserStake,
+    amount: u64,
+) -> Result<bool> {
+    let debt_before = calculate_reward_debt(
+        user_stake.weighted_stake,
+        stake_pool.accumulated_reward_per_share,
+    )?;
+    let distributed = stake_pool.distribute_to_stakers(amount, user_stake.weighted_stake)?;
+    let debt_after = calculate_reward_debt(
+        user_stake.weighted_stake,
+        stake_pool.accumulated_reward_per_share,
+    )?;
+    user_stake.reward_debt = user_stake.reward_debt
+        .checked_add(debt_after - debt_before)
+        .ok_or(StakingError::MathOverflow)?;
+    Ok(distributed)
+}
+
+/// Calculate the penalty for unstaking before the lock ends
+/// penalty = amount * base_penalty_bps / 10000 * remaining_lock / lock_duration
+/// so exiting on day one costs the full penalty and exiting a day early
+/// costs almost nothing.
+///
+/// # Arguments
+/// * `amount` - Amount being unstaked
+/// * `base_penalty_bps` - Pool's early unstake penalty in basis points
+/// * `lock_end_time` - When the position's lock ends
+/// * `lock_duration` - Length of the position's lock in seconds
+/// * `now` - Current Unix timestamp
+///
+/// # Returns
+/// * `Result<u64>` - Penalty withheld from the unstaked amount
+pub fn calculate_early_unstake_penalty(
+    amount: u64,
+    base_penalty_bps: u64,
+    lock_end_time: i64,
+    lock_duration: i64,
+    now: i64,
+) -> Result<u64> {
+    if lock_duration <= 0 || now >= lock_end_time {
+        return Ok(0);
+    }
+
+    let remaining = (lock_end_time - now).min(lock_duration) as u64;
+    let max_penalty = math::apply_bps(amount, base_penalty_bps)?;
+
+    math::to_u64(math::proportional(max_penalty as u128, remaining, lock_duration as u64)?)
+}
+
+/// How an early unstake penalty is distributed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PenaltySplit {
+    pub burned: u64,
+    pub to_treasury: u64,
+    pub to_insurance: u64,
+    pub retained: u64,
+}
+
+/// Split a penalty into burned, treasury, insurance and retained shares.
+/// Rounding dust stays with the retained share.
+///
+/// # Arguments
+/// * `penalty` - Total penalty withheld
+/// * `burn_bps` - Share burned in basis points
+/// * `treasury_bps` - Share sent to the treasury in basis points
+/// * `insurance_bps` - Share sent to the insurance fund in basis points
+///
+/// # Returns
+/// * `Result<PenaltySplit>` - Amount for each destination
+pub fn calculate_penalty_split(
+    penalty: u64,
+    burn_bps: u64,
+    treasury_bps: u64,
+    insurance_bps: u64,
+) -> Result<PenaltySplit> {
+    let burned = math::apply_bps(penalty, burn_bps)?;
+    let to_treasury = math::apply_bps(penalty, treasury_bps)?;
+    let to_insurance = math::apply_bps(penalty, insurance_bps)?;
+    let retained = penalty
+        .checked_sub(burned)
+        .and_then(|rest| rest.checked_sub(to_treasury))
+        .and_then(|rest| rest.checked_sub(to_insurance))
+        .ok_or(StakingError::MathOverflow)?;
+    Ok(PenaltySplit { burned, to_treasury, to_insurance, retained })
+}
+
+/// Calculate the reward debt for a weighted stake at the current accumulator
+/// reward_debt = weighted_stake * accumulated_reward_per_share / PRECISION
+///
+/// # Arguments
+/// * `weighted_stake` - Weighted stake amount
+/// * `accumulated_reward_per_share` - Current accumulated reward per share from pool
+///
+/// # Returns
+/// * `Result<u128>` - Rewards accumulated by this weighted stake to date
+pub fn calculate_reward_debt(
+    weighted_stake: u64,
+    accumulated_reward_per_share: u128,
+) -> Result<u128> {
+    staking_math::calculate_reward_debt(weighted_stake, accumulated_reward_per_share)
+        .ok_or_else(|| StakingError::MathOverflow.into())
+}
+
+/// Calculate the weighted stake and reward debt to remove when unstaking
+///
+/// Partial unstakes remove a proportional share, rounded down. When the
+/// whole position is withdrawn the remaining `weighted_stake` and
+/// `reward_debt` are removed in full, so rounding dust from earlier partial
+/// unstakes is swept out of the user and pool totals instead of lingering.
+///
+/// # Arguments
+/// * `amount` - Amount of tokens being unstaked
+/// * `staked_amount` - User's staked amount before the unstake
+/// * `weighted_stake` - User's weighted stake before the unstake
+/// * `reward_debt` - User's reward debt before the unstake
+///
+/// # Returns
+/// * `Result<(u64, u128)>` - (weighted stake to remove, reward debt to remove)
+pub fn calculate_unstake_removal(
+    amount: u64,
+    staked_amount: u64,
+    weighted_stake: u64,
+    reward_debt: u128,
+) -> Result<(u64, u128)> {
+    require!(amount <= staked_amount, StakingError::InsufficientStake);
+
+    if amount == staked_amount {
+        return Ok((weighted_stake, reward_debt));
+    }
+
+    // weighted_to_remove = (amount / staked_amount) * weighted_stake
+    let weighted_to_remove = math::to_u64(math::proportional(
+        weighted_stake as u128,
+        amount,
+        staked_amount,
+    )?)?;
+
+    // debt_to_remove = (amount / staked_amount) * reward_debt
+    let debt_to_remove = math::proportional(reward_debt, amount, staked_amount)?;
+
+    Ok((weighted_to_remove, debt_to_remove))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calculate_tier() {
+        // Less than 1,000 tokens (with 9 decimals)
+        assert_eq!(calculate_tier(999_000_000_000), StakingTier::None);
+
+        // Exactly 1,000 tokens
+        assert_eq!(calculate_tier(1_000_000_000_000), StakingTier::Holder);
+
+        // Between 1,000 and 10,000
+        assert_eq!(calculate_tier(5_000_000_000_000), StakingTier::Holder);
+
+        // Exactly 10,000 tokens
+        assert_eq!(calculate_tier(10_000_000_000_000), StakingTier::Premium);
+
+        // Between 10,000 and 100,000
+        assert_eq!(calculate_tier(50_000_000_000_000), StakingTier::Premium);
+
+        // Exactly 100,000 tokens
+        assert_eq!(calculate_tier(100_000_000_000_000), StakingTier::Vip);
+
+        // More than 100,000
+        assert_eq!(calculate_tier(500_000_000_000_000), StakingTier::Vip);
+    }
+
+    #[test]
+    fn test_tier_fees() {
+        assert_eq!(StakingTier::None.platform_fee_bps(), 500);    // 5%
+        assert_eq!(StakingTier::Holder.platform_fee_bps(), 400);  // 4%
+        assert_eq!(StakingTier::Premium.platform_fee_bps(), 200); // 2%
+        assert_eq!(StakingTier::Vip.platform_fee_bps(), 0);       // 0%
+    }
+
+    #[test]
+    fn test_tier_multipliers() {
+        assert_eq!(StakingTier::None.reward_multiplier_bps(), 10000);    // 1.0x
+        assert_eq!(StakingTier::Holder.reward_multiplier_bps(), 11000);  // 1.1x
+        assert_eq!(StakingTier::Premium.reward_multiplier_bps(), 12500); // 1.25x
+        assert_eq!(StakingTier::Vip.reward_multiplier_bps(), 15000);     // 1.5x
+    }
+
+    #[test]
+    fn test_weight_multiplier() {
+        let min_duration = constants::MIN_LOCK_DURATION; // 7 days
+        let max_duration = constants::MAX_LOCK_DURATION; // 365 days
+
+        // Minimum duration should give 1x (10000 bps)
+        let mult_min = calculate_weight_multiplier(min_duration, min_duration, max_duration);
+        assert_eq!(mult_min, 10000);
+
+        // Maximum duration should give 2x (20000 bps)
+        let mult_max = calculate_weight_multiplier(max_duration, min_duration, max_duration);
+        assert_eq!(mult_max, 20000);
+
+        // Middle duration should give approximately 1.5x
+        let mid_duration = (min_duration + max_duration) / 2;
+        let mult_mid = calculate_weight_multiplier(mid_duration, min_duration, max_duration);
+        // Should be close to 15000 (1.5x)
+        assert!(mult_mid >= 14900 && mult_mid <= 15100);
 
         // Below minimum should be clamped to 1x
         let mult_below = calculate_weight_multiplier(0, min_duration, max_duration);
         assert_eq!(mult_below, 10000);
 
-        // Above maximum should be clamped to 2x
-        let mult_above = calculate_weight_multiplier(max_duration * 2, min_duration, max_duration);
-        assert_eq!(mult_above, 20000);
+        // Above maximum should be clamped to 2x
+        let mult_above = calculate_weight_multiplier(max_duration * 2, min_duration, max_duration);
+        assert_eq!(mult_above, 20000);
+    }
+
+    #[test]
+    fn test_get_platform_fee() {
+        assert_eq!(get_platform_fee(StakingTier::None), 500);
+        assert_eq!(get_platform_fee(StakingTier::Holder), 400);
+        assert_eq!(get_platform_fee(StakingTier::Premium), 200);
+        assert_eq!(get_platform_fee(StakingTier::Vip), 0);
+    }
+
+    #[test]
+    fn test_get_reward_multiplier() {
+        assert_eq!(get_reward_multiplier(StakingTier::None), 10000);
+        assert_eq!(get_reward_multiplier(StakingTier::Holder), 11000);
+        assert_eq!(get_reward_multiplier(StakingTier::Premium), 12500);
+        assert_eq!(get_reward_multiplier(StakingTier::Vip), 15000);
+    }
+
+    #[test]
+    fn test_available_rewards_excludes_principal_in_shared_vault() {
+        let mut pool = StakePool {
+            total_staked: 1_000,
+            ..Default::default()
+        };
+
+        // Separate reward vault: full balance is distributable
+        assert_eq!(pool.available_rewards(400), 400);
+
+        // Shared vault: only the surplus above staked principal
+        pool.shared_vault = true;
+        assert_eq!(pool.available_rewards(1_400), 400);
+        assert_eq!(pool.available_rewards(1_000), 0);
+        assert_eq!(pool.available_rewards(900), 0);
+    }
+
+    #[test]
+    fn test_unstake_removal_full_exit_sweeps_dust() {
+        // Weighted stake and debt that don't divide evenly by staked amount
+        let (weighted, debt) = calculate_unstake_removal(3, 3, 10, 7).unwrap();
+        assert_eq!(weighted, 10);
+        assert_eq!(debt, 7);
+    }
+
+    #[test]
+    fn test_unstake_removal_partial_rounds_down() {
+        // 1/3 of 10 = 3.33 -> 3, 1/3 of 7 = 2.33 -> 2
+        let (weighted, debt) = calculate_unstake_removal(1, 3, 10, 7).unwrap();
+        assert_eq!(weighted, 3);
+        assert_eq!(debt, 2);
+
+        // Tiny amount against a large position rounds to zero weight
+        let (weighted, debt) = calculate_unstake_removal(1, 1_000_000, 999_999, 0).unwrap();
+        assert_eq!(weighted, 0);
+        assert_eq!(debt, 0);
+    }
+
+    #[test]
+    fn test_unstake_removal_sequence_leaves_no_dust() {
+        let mut staked: u64 = 1_000_003;
+        let mut weighted: u64 = 1_750_007;
+        let mut debt: u128 = 123_456_789;
+        let mut total_weighted_removed: u64 = 0;
+        let mut total_debt_removed: u128 = 0;
+
+        for amount in [7, 333_333, 1, 250_000, 99_999] {
+            let (w, d) = calculate_unstake_removal(amount, staked, weighted, debt).unwrap();
+            staked -= amount;
+            weighted -= w;
+            debt -= d;
+            total_weighted_removed += w;
+            total_debt_removed += d;
+        }
+
+        // Final full exit takes whatever remains
+        let (w, d) = calculate_unstake_removal(staked, staked, weighted, debt).unwrap();
+        total_weighted_removed += w;
+        total_debt_removed += d;
+
+        assert_eq!(weighted - w, 0);
+        assert_eq!(debt - d, 0);
+        assert_eq!(total_weighted_removed, 1_750_007);
+        assert_eq!(total_debt_removed, 123_456_789);
+    }
+
+    #[test]
+    fn test_unstake_removal_rejects_excess_amount() {
+        assert!(calculate_unstake_removal(4, 3, 10, 7).is_err());
+    }
+
+    #[test]
+    fn test_accrual_precision_for_large_pools() {
+        // 100M tokens (9 decimals) of weighted stake earning 1 unit per second
+        let mut pool = StakePool {
+            total_weighted_stake: 100_000_000_000_000_000,
+            reward_rate: 1,
+            last_reward_time: 0,
+            ..Default::default()
+        };
+        update_rewards(&mut pool, 1).unwrap();
+        assert!(pool.accumulated_reward_per_share > 0);
+
+        // After 1 day the whole pool should have earned ~86,400 units
+        update_rewards(&mut pool, 86_400).unwrap();
+        let user = UserStake {
+            weighted_stake: 100_000_000_000_000_000,
+            ..Default::default()
+        };
+        let pending = calculate_pending_rewards(&user, pool.accumulated_reward_per_share).unwrap();
+        assert_eq!(pending, 86_400);
+    }
+
+    #[test]
+    fn test_reward_debt_wide_product() {
+        // weighted_stake * acc overflows u128 without a 256-bit intermediate
+        let acc = u128::MAX / 1_000;
+        let debt = calculate_reward_debt(u64::MAX, acc).unwrap();
+        assert_eq!(debt, u256::mul_div(u64::MAX as u128, acc, constants::PRECISION).unwrap());
+        assert!(debt > 0);
+    }
+
+    #[test]
+    fn test_action_history_ring_buffer() {
+        let mut user = UserStake::default();
+        assert_eq!(user.recent_actions().count(), 0);
+
+        user.record_action(ActionKind::Stake, 100, 1);
+        user.record_action(ActionKind::Claim, 5, 2);
+        let actions: Vec<_> = user.recent_actions().collect();
+        assert_eq!(actions.len(), 2);
+        assert_eq!(actions[0].kind, ActionKind::Stake);
+        assert_eq!(actions[1].kind, ActionKind::Claim);
+
+        // Overflow the buffer: oldest entries are dropped, order is preserved
+        for i in 0..UserStake::HISTORY_SIZE as i64 {
+            user.record_action(ActionKind::Unstake, i as u64, 10 + i);
+        }
+        let actions: Vec<_> = user.recent_actions().collect();
+        assert_eq!(actions.len(), UserStake::HISTORY_SIZE);
+        assert!(actions.iter().all(|a| a.kind == ActionKind::Unstake));
+        assert_eq!(actions.first().unwrap().timestamp, 10);
+        assert_eq!(actions.last().unwrap().timestamp, 10 + UserStake::HISTORY_SIZE as i64 - 1);
+    }
+
+    #[test]
+    fn test_pool_history_sampling() {
+        let day = constants::HISTORY_SAMPLE_INTERVAL;
+        let sample = |timestamp: i64| HistorySample { timestamp, apr_bps: 1_000, tvl: timestamp as u64 };
+        let mut history = PoolHistory::default();
+        assert_eq!(history.recent_samples().count(), 0);
+
+        assert!(history.record(sample(day)));
+        // Cranking again within the interval doesn't add a point
+        assert!(!history.record(sample(2 * day - 1)));
+        assert!(history.record(sample(2 * day)));
+        assert_eq!(history.recent_samples().count(), 2);
+
+        // Overflow the buffer: oldest samples are dropped, order is preserved
+        for i in 0..PoolHistory::SIZE as i64 {
+            assert!(history.record(sample((3 + i) * day)));
+        }
+        let samples: Vec<_> = history.recent_samples().collect();
+        assert_eq!(samples.len(), PoolHistory::SIZE);
+        assert_eq!(samples.first().unwrap().timestamp, 3 * day);
+        assert_eq!(samples.last().unwrap().timestamp, (2 + PoolHistory::SIZE as i64) * day);
+        assert!(samples.windows(2).all(|pair| pair[0].timestamp < pair[1].timestamp));
+    }
+
+    #[test]
+    fn test_pool_stats_tracking() {
+        let mut stats = PoolStats::default();
+
+        stats.record_stake(100, true, 100);
+        stats.record_stake(50, false, 150);
+        stats.record_stake(10, true, 60);
+        stats.record_claim(7);
+        stats.record_claim(3);
+
+        assert_eq!(stats.unique_stakers, 2);
+        assert_eq!(stats.cumulative_staked_volume, 160);
+        assert_eq!(stats.peak_tvl, 150);
+        assert_eq!(stats.total_rewards_distributed, 10);
+    }
+
+    #[test]
+    fn test_tier_activation_delay() {
+        let delay = 3 * 24 * 60 * 60;
+        let mut user = UserStake {
+            staked_amount: constants::HOLDER_THRESHOLD,
+            ..Default::default()
+        };
+        assert_eq!(user.effective_tier(0), StakingTier::Holder);
+
+        // Upgrade to VIP at t=100: Holder benefits until the delay passes
+        let previous = user.effective_tier(100);
+        user.staked_amount = constants::VIP_THRESHOLD;
+        user.schedule_tier_activation(previous, delay, 100);
+        assert_eq!(user.effective_tier(100), StakingTier::Holder);
+        assert_eq!(user.effective_tier(100 + delay - 1), StakingTier::Holder);
+        assert_eq!(user.effective_tier(100 + delay), StakingTier::Vip);
+
+        // Dropping below the pending tier applies immediately
+        user.staked_amount = constants::PREMIUM_THRESHOLD;
+        user.tier_activation_time = 200 + delay;
+        assert_eq!(user.effective_tier(200), StakingTier::Holder);
+        user.staked_amount = 0;
+        assert_eq!(user.effective_tier(200), StakingTier::None);
+    }
+
+    #[test]
+    fn test_tier_activation_without_delay() {
+        let mut user = UserStake::default();
+        let previous = user.effective_tier(0);
+        user.staked_amount = constants::VIP_THRESHOLD;
+        user.schedule_tier_activation(previous, 0, 0);
+        assert_eq!(user.effective_tier(0), StakingTier::Vip);
+    }
+
+    #[test]
+    fn test_tier_grace_period_after_partial_unstake() {
+        let grace = 7 * 24 * 60 * 60;
+        let mut user = UserStake {
+            staked_amount: constants::PREMIUM_THRESHOLD,
+            ..Default::default()
+        };
+
+        // Partial unstake below the Premium threshold keeps Premium during grace
+        let previous = user.effective_tier(1_000);
+        user.staked_amount = constants::PREMIUM_THRESHOLD - 1;
+        user.start_tier_grace(previous, grace, 1_000);
+        assert_eq!(user.effective_tier(1_000), StakingTier::Premium);
+        assert_eq!(user.effective_tier(1_000 + grace - 1), StakingTier::Premium);
+        assert_eq!(user.effective_tier(1_000 + grace), StakingTier::Holder);
+
+        // A second unstake during grace does not extend the window
+        let previous = user.effective_tier(2_000);
+        user.staked_amount = constants::HOLDER_THRESHOLD - 1;
+        user.start_tier_grace(previous, grace, 2_000);
+        assert_eq!(user.tier_grace_until, 1_000 + grace);
+        assert_eq!(user.effective_tier(2_000), StakingTier::Premium);
+
+        // Full exit ends any grace immediately
+        user.staked_amount = 0;
+        assert_eq!(user.effective_tier(2_000), StakingTier::None);
+    }
+
+    #[test]
+    fn test_settle_rewards_preserves_pending() {
+        let acc = 5 * constants::PRECISION;
+        let mut user = UserStake {
+            staked_amount: constants::HOLDER_THRESHOLD,
+            weighted_stake: 100,
+            reward_debt: 200,
+            ..Default::default()
+        };
+
+        // pending = 100 * 5 - 200 = 300, Holder multiplier 1.1x = 330
+        let settled = settle_rewards(&mut user, acc, 0).unwrap();
+        assert_eq!(settled, 330);
+        assert_eq!(user.owed_rewards, 330);
+        assert_eq!(user.reward_debt, 500);
+        assert_eq!(calculate_pending_rewards(&user, acc).unwrap(), 0);
+
+        // Weight can now change without losing what was earned
+        user.weighted_stake = 50;
+        user.reward_debt = calculate_reward_debt(50, acc).unwrap();
+        assert_eq!(settle_rewards(&mut user, acc, 0).unwrap(), 0);
+        assert_eq!(user.owed_rewards, 330);
+    }
+
+    #[test]
+    fn test_claim_window_and_blackout() {
+        let day = 24 * 60 * 60;
+        let mut pool = StakePool::default();
+        assert!(pool.is_claim_window_open(0));
+
+        // Claims open for the first 3 days of every 30-day cycle
+        pool.claim_window_anchor = 1_000;
+        pool.claim_window_period = 30 * day;
+        pool.claim_window_length = 3 * day;
+        assert!(!pool.is_claim_window_open(999));
+        assert!(pool.is_claim_window_open(1_000));
+        assert!(pool.is_claim_window_open(1_000 + 3 * day - 1));
+        assert!(!pool.is_claim_window_open(1_000 + 3 * day));
+        assert!(pool.is_claim_window_open(1_000 + 30 * day + day));
+
+        // Blackout overrides an open window
+        pool.claim_blackout_start = 1_000 + 30 * day;
+        pool.claim_blackout_end = 1_000 + 31 * day;
+        assert!(!pool.is_claim_window_open(1_000 + 30 * day));
+        assert!(pool.is_claim_window_open(1_000 + 31 * day));
+    }
+
+    #[test]
+    fn test_reward_stream_accrual_and_replacement() {
+        let mut streams = RewardStreams::default();
+        streams.streams[0] = RewardStream {
+            stream_id: 1,
+            reward_rate: 10,
+            active: true,
+            ..Default::default()
+        };
+
+        // 100 seconds at 10/s over 1,000 weight: 1 token per weight unit
+        streams.update_all(1_000, 100).unwrap();
+        assert_eq!(streams.streams[0].accumulated_reward_per_share, constants::PRECISION);
+
+        // A user holding 250 weight since the stream started earns 250
+        let mut user = UserRewardStreams::default();
+        user.settle(&streams, 250).unwrap();
+        assert_eq!(user.entries[0].stream_id, 1);
+        assert_eq!(user.entries[0].owed_rewards, 250);
+        user.reset_debts(&streams, 500).unwrap();
+
+        // Stopped streams stop accruing
+        streams.streams[0].active = false;
+        streams.update_all(1_000, 200).unwrap();
+        user.settle(&streams, 500).unwrap();
+        assert_eq!(user.entries[0].owed_rewards, 250);
+
+        // Replacing the slot resets the stale entry
+        streams.streams[0] = RewardStream {
+            stream_id: 2,
+            reward_rate: 10,
+            active: true,
+            last_update_time: 200,
+            ..Default::default()
+        };
+        user.settle(&streams, 500).unwrap();
+        assert_eq!(user.entries[0].stream_id, 2);
+        assert_eq!(user.entries[0].owed_rewards, 0);
+    }
+
+    #[test]
+    fn test_partner_boost_stacks_on_tier_multiplier() {
+        let mut table = BoostTable::default();
+        let partner = Pubkey::new_unique();
+        table.entries[1] = PartnerBoostConfig {
+            partner_mint: partner,
+            min_amount: 500,
+            bonus_bps: 1_000,
+        };
+        assert_eq!(table.bonus_for(&partner, 499), 0);
+        assert_eq!(table.bonus_for(&partner, 500), 1_000);
+        assert_eq!(table.bonus_for(&Pubkey::new_unique(), 10_000), 0);
+        assert_eq!(table.bonus_for(&Pubkey::default(), 10_000), 0);
+
+        // Holder tier (1.1x) plus a 0.1x partner boost = 1.2x
+        let acc = 5 * constants::PRECISION;
+        let mut user = UserStake {
+            staked_amount: constants::HOLDER_THRESHOLD,
+            weighted_stake: 100,
+            reward_debt: 200,
+            partner_boost_bps: table.bonus_for(&partner, 500),
+            ..Default::default()
+        };
+        assert_eq!(settle_rewards(&mut user, acc, 0).unwrap(), 360);
+    }
+
+    #[test]
+    fn test_multiplier_folded_into_weight_stays_within_budget() {
+        let mut pool = StakePool {
+            total_weighted_stake: 0,
+            ..Default::default()
+        };
+
+        // A VIP (1.5x) and a non-tier staker with equal lock weight
+        let mut vip = UserStake {
+            staked_amount: constants::VIP_THRESHOLD,
+            lock_weighted_stake: 1_000,
+            applied_multiplier_bps: constants::BPS_DENOMINATOR,
+            ..Default::default()
+        };
+        let mut small = UserStake {
+            staked_amount: 1,
+            lock_weighted_stake: 1_000,
+            applied_multiplier_bps: constants::BPS_DENOMINATOR,
+            ..Default::default()
+        };
+        assert_eq!(rebase_weight(&mut pool, &mut vip, 0).unwrap(), 1_500);
+        assert_eq!(rebase_weight(&mut pool, &mut small, 0).unwrap(), 1_000);
+        assert_eq!(pool.total_weighted_stake, 2_500);
+        assert_eq!(vip.applied_multiplier_bps, 15_000);
+
+        // 2,500 emitted tokens split 1,500 / 1,000: nothing over budget
+        let acc = constants::PRECISION;
+        assert_eq!(settle_rewards(&mut vip, acc, 0).unwrap(), 1_500);
+        assert_eq!(settle_rewards(&mut small, acc, 0).unwrap(), 1_000);
+
+        // Dropping out of VIP re-bases the pool total
+        pool.accumulated_reward_per_share = acc;
+        vip.staked_amount = 1;
+        rebase_weight(&mut pool, &mut vip, 0).unwrap();
+        assert_eq!(pool.total_weighted_stake, 2_000);
+        assert_eq!(calculate_pending_rewards(&vip, acc).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_legacy_position_migrates_on_settle() {
+        let acc = 5 * constants::PRECISION;
+        let mut user = UserStake {
+            staked_amount: constants::HOLDER_THRESHOLD,
+            weighted_stake: 100,
+            reward_debt: 200,
+            ..Default::default()
+        };
+        assert!(user.is_legacy_weight());
+
+        // Legacy accruals keep the old claim-time multiplier once
+        assert_eq!(settle_rewards(&mut user, acc, 0).unwrap(), 330);
+        assert!(!user.is_legacy_weight());
+        assert_eq!(user.lock_weighted_stake, 100);
+
+        let mut pool = StakePool {
+            total_weighted_stake: 100,
+            accumulated_reward_per_share: acc,
+            ..Default::default()
+        };
+        assert_eq!(rebase_weight(&mut pool, &mut user, 0).unwrap(), 110);
+        assert_eq!(pool.total_weighted_stake, 110);
     }
 
     #[test]
-    fn test_get_platform_fee() {
-        assert_eq!(get_platform_fee(StakingTier::None), 500);
-        assert_eq!(get_platform_fee(StakingTier::Holder), 400);
-        assert_eq!(get_platform_fee(StakingTier::Premium), 200);
-        assert_eq!(get_platform_fee(StakingTier::Vip), 0);
+    fn test_early_unstake_penalty_scales_with_remaining_lock() {
+        let day = 24 * 60 * 60;
+        let lock = 100 * day;
+        let end = lock;
+
+        // Day one: full 10% penalty
+        assert_eq!(calculate_early_unstake_penalty(1_000_000, 1_000, end, lock, 0).unwrap(), 100_000);
+        // Halfway: half the penalty
+        assert_eq!(calculate_early_unstake_penalty(1_000_000, 1_000, end, lock, 50 * day).unwrap(), 50_000);
+        // One day early: 1% of the penalty
+        assert_eq!(calculate_early_unstake_penalty(1_000_000, 1_000, end, lock, 99 * day).unwrap(), 1_000);
+        // Unlocked or lockless positions pay nothing
+        assert_eq!(calculate_early_unstake_penalty(1_000_000, 1_000, end, lock, end).unwrap(), 0);
+        assert_eq!(calculate_early_unstake_penalty(1_000_000, 1_000, 0, 0, 0).unwrap(), 0);
     }
 
     #[test]
-    fn test_get_reward_multiplier() {
-        assert_eq!(get_reward_multiplier(StakingTier::None), 10000);
-        assert_eq!(get_reward_multiplier(StakingTier::Holder), 11000);
-        assert_eq!(get_reward_multiplier(StakingTier::Premium), 12500);
-        assert_eq!(get_reward_multiplier(StakingTier::Vip), 15000);
+    fn test_pool_metadata_fixed_bytes() {
+        let name: [u8; 32] = state::fixed_bytes("KR8TIV Flex").unwrap();
+        assert_eq!(&name[..11], b"KR8TIV Flex");
+        assert!(name[11..].iter().all(|b| *b == 0));
+
+        let exact: [u8; 10] = state::fixed_bytes("ABCDEFGHIJ").unwrap();
+        assert_eq!(&exact, b"ABCDEFGHIJ");
+
+        assert!(state::fixed_bytes::<10>("ABCDEFGHIJK").is_err());
+    }
+
+    #[test]
+    fn test_protected_mints() {
+        let stake_mint = Pubkey::new_unique();
+        let reward_mint = Pubkey::new_unique();
+        let stream_mint = Pubkey::new_unique();
+        let foreign_mint = Pubkey::new_unique();
+
+        let pool = StakePool { stake_mint, reward_mint, ..Default::default() };
+        assert!(pool.is_pool_mint(&stake_mint));
+        assert!(pool.is_pool_mint(&reward_mint));
+        assert!(!pool.is_pool_mint(&foreign_mint));
+
+        let mut streams = RewardStreams::default();
+        streams.streams[1].stream_id = 1;
+        streams.streams[1].mint = stream_mint;
+        assert!(streams.has_mint(&stream_mint));
+        assert!(!streams.has_mint(&foreign_mint));
+    }
+
+    #[test]
+    fn test_validate_initialize_params() {
+        let params = |reward_rate: u64, min: i64, max: i64| InitializeParams {
+            pool_id: 0,
+            reward_rate,
+            min_lock_duration: min,
+            max_lock_duration: max,
+            name: String::new(),
+            symbol: String::new(),
+            uri: String::new(),
+            allow_freezable: false,
+        };
+        let min = constants::MIN_LOCK_DURATION;
+        let max = constants::MAX_LOCK_DURATION;
+
+        assert!(params(1_000, min, max).validate(9).is_ok());
+        assert!(params(constants::MAX_REWARD_RATE, 1, 1).validate(9).is_ok());
+
+        assert!(params(constants::MAX_REWARD_RATE + 1, min, max).validate(9).is_err());
+        assert!(params(1_000, 0, max).validate(9).is_err());
+        assert!(params(1_000, 0, 0).validate(9).is_err());
+        assert!(params(1_000, max, min).validate(9).is_err());
+
+        // Other decimals are fine as long as the tier thresholds fit
+        assert!(params(1_000, min, max).validate(6).is_ok());
+        assert!(params(1_000, min, max).validate(15).is_err());
+    }
+
+    #[test]
+    fn test_tier_benefits_lookup_and_validation() {
+        let mut table = TierBenefits::default();
+        table.benefits[StakingTier::Vip as usize] = TierBenefit {
+            allocation_multiplier_bps: 30_000,
+            fee_discount_bps: 500,
+            cooldown_exempt: true,
+        };
+        assert_eq!(table.for_tier(StakingTier::Vip).allocation_multiplier_bps, 30_000);
+        assert!(table.for_tier(StakingTier::Vip).cooldown_exempt);
+        assert!(!table.for_tier(StakingTier::Holder).cooldown_exempt);
+        assert_eq!(table.allocation_multipliers_bps(), [0, 0, 0, 30_000]);
+
+        assert!(table.for_tier(StakingTier::Vip).validate().is_ok());
+        // Zero, oversized multipliers and >100% discounts are rejected
+        assert!(TierBenefit::default().validate().is_err());
+        let too_big = TierBenefit { allocation_multiplier_bps: 100_001, ..Default::default() };
+        assert!(too_big.validate().is_err());
+        let bad_discount = TierBenefit {
+            allocation_multiplier_bps: 10_000,
+            fee_discount_bps: 10_001,
+            cooldown_exempt: false,
+        };
+        assert!(bad_discount.validate().is_err());
+    }
+
+    #[test]
+    fn test_loyalty_points_accrue_per_token_day() {
+        let day = 24 * 60 * 60;
+        let mut user_stake = UserStake::default();
+
+        // The first touch only starts the clock
+        user_stake.accrue_points(1_000);
+        assert_eq!(user_stake.total_points(), 0);
+
+        // 1,000 tokens for two days = 2,000 points
+        user_stake.staked_amount = 1_000 * 1_000_000_000;
+        user_stake.accrue_points(1_000 + 2 * day);
+        assert_eq!(user_stake.total_points(), 2_000);
+
+        // Half a token for a day rounds down but isn't lost
+        user_stake.staked_amount = 500_000_000;
+        user_stake.accrue_points(1_000 + 3 * day);
+        assert_eq!(user_stake.total_points(), 2_000);
+        user_stake.accrue_points(1_000 + 4 * day);
+        assert_eq!(user_stake.total_points(), 2_001);
+
+        user_stake.points_spent = 1_500;
+        assert_eq!(user_stake.available_points(), 501);
+    }
+
+    #[test]
+    fn test_streak_bonus_grows_monthly_and_caps() {
+        let month = constants::SECONDS_PER_MONTH;
+        let pool = StakePool {
+            streak_bonus_bps_per_month: 100,
+            max_streak_bonus_bps: 500,
+            ..Default::default()
+        };
+
+        // No streak, or less than a full month, earns nothing
+        assert_eq!(pool.streak_bonus_bps(0, 10 * month), 0);
+        assert_eq!(pool.streak_bonus_bps(1_000, 1_000 + month - 1), 0);
+
+        assert_eq!(pool.streak_bonus_bps(1_000, 1_000 + 3 * month), 300);
+        assert_eq!(pool.streak_bonus_bps(1_000, 1_000 + 24 * month), 500);
+
+        // The bonus stacks on top of the tier multiplier
+        let user_stake = UserStake {
+            staked_amount: 1,
+            unbroken_since: 1_000,
+            ..Default::default()
+        };
+        assert_eq!(
+            pool.position_multiplier_bps(&user_stake, 1_000 + 2 * month),
+            user_stake.reward_multiplier_bps(1_000 + 2 * month) + 200
+        );
+    }
+
+    #[test]
+    fn test_penalty_split() {
+        // 25% burned, 25% to treasury, 10% insured, the rest retained
+        assert_eq!(
+            calculate_penalty_split(1_000, 2_500, 2_500, 1_000).unwrap(),
+            PenaltySplit { burned: 250, to_treasury: 250, to_insurance: 100, retained: 400 }
+        );
+
+        // Rounding dust stays with the retained share
+        assert_eq!(
+            calculate_penalty_split(7, 5_000, 5_000, 0).unwrap(),
+            PenaltySplit { burned: 3, to_treasury: 3, to_insurance: 0, retained: 1 }
+        );
+
+        // No split keeps everything in the vault
+        assert_eq!(calculate_penalty_split(1_000, 0, 0, 0).unwrap().retained, 1_000);
+    }
+
+    #[test]
+    fn test_memo_tag_format() {
+        let pool = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+        assert_eq!(
+            memo::memo_tag(memo::MemoAction::Stake, &pool, &user),
+            format!("stake:{}:{}", pool, user)
+        );
+        assert!(memo::memo_tag(memo::MemoAction::Unstake, &pool, &user).starts_with("unstake:"));
+        assert!(memo::memo_tag(memo::MemoAction::Claim, &pool, &user).starts_with("claim:"));
+    }
+
+    #[test]
+    fn test_epoch_mode_rewards() {
+        let day = 24 * 60 * 60;
+        let mut pool = StakePool {
+            reward_rate: 10,
+            total_weighted_stake: 1_000,
+            last_reward_time: 0,
+            reward_epoch_duration: day,
+            ..Default::default()
+        };
+
+        // Per-second accrual is switched off
+        update_rewards(&mut pool, day / 2).unwrap();
+        assert_eq!(pool.accumulated_reward_per_share, 0);
+
+        // Nothing to settle mid-epoch
+        assert!(settle_reward_epochs(&mut pool, day - 1).is_err());
+
+        // Two and a half days settle two full epochs
+        assert_eq!(settle_reward_epochs(&mut pool, 2 * day + day / 2).unwrap(), 2);
+        assert_eq!(pool.reward_epoch, 2);
+        assert_eq!(pool.last_reward_time, 2 * day);
+        let expected = (2 * day as u128) * 10 * constants::PRECISION / 1_000;
+        assert_eq!(pool.accumulated_reward_per_share, expected);
+
+        // Per-second pools can't be settled
+        pool.reward_epoch_duration = 0;
+        assert!(settle_reward_epochs(&mut pool, 10 * day).is_err());
+    }
+
+    #[test]
+    fn test_action_cooldown() {
+        let mut user_stake = UserStake::default();
+
+        // A fresh position is never in cooldown
+        assert!(user_stake.cooldown_elapsed(60, 1_000));
+
+        user_stake.last_action_time = 1_000;
+        assert!(!user_stake.cooldown_elapsed(60, 1_059));
+        assert!(user_stake.cooldown_elapsed(60, 1_060));
+
+        // No cooldown configured
+        assert!(user_stake.cooldown_elapsed(0, 1_000));
+    }
+
+    #[test]
+    fn test_vault_surplus_protects_principal() {
+        let mut pool = StakePool {
+            total_staked: 1_000,
+            vault_balance: 1_100,
+            ..Default::default()
+        };
+
+        // Retained penalties are accounted for; only donations are surplus
+        assert_eq!(pool.vault_surplus(1_100), 0);
+        assert_eq!(pool.vault_surplus(1_150), 50);
+        assert_eq!(pool.vault_surplus(900), 0);
+
+        // Legacy pools without a tracked balance still protect principal
+        pool.vault_balance = 0;
+        assert_eq!(pool.vault_surplus(1_000), 0);
+        assert_eq!(pool.vault_surplus(1_050), 50);
+    }
+
+    #[test]
+    fn test_reward_liability_tracks_emission() {
+        let mut pool = StakePool {
+            reward_rate: 5,
+            total_weighted_stake: 0,
+            last_reward_time: 0,
+            ..Default::default()
+        };
+
+        // Nothing is owed while the pool is empty
+        update_rewards(&mut pool, 100).unwrap();
+        assert_eq!(pool.reward_liability, 0);
+
+        pool.total_weighted_stake = 1_000;
+        update_rewards(&mut pool, 200).unwrap();
+        assert_eq!(pool.reward_liability, 500);
+
+        assert_eq!(pool.expected_stake_vault_balance(), 0);
+        pool.total_staked = 1_000;
+        assert_eq!(pool.expected_stake_vault_balance(), 1_000);
+    }
+
+    #[test]
+    fn test_invariant_checks() {
+        let mut pool = StakePool {
+            total_staked: 1_000,
+            total_weighted_stake: 1_500,
+            accumulated_reward_per_share: constants::PRECISION,
+            ..Default::default()
+        };
+        assert!(pool.check_invariants(1_000).is_ok());
+        assert!(pool.check_invariants(999).is_err());
+
+        let mut user_stake = UserStake {
+            staked_amount: 500,
+            weighted_stake: 750,
+            reward_debt: 750,
+            ..Default::default()
+        };
+        assert!(user_stake.check_invariants(&pool).is_ok());
+
+        // Debt can't exceed what the weight has accumulated
+        user_stake.reward_debt = 751;
+        assert!(user_stake.check_invariants(&pool).is_err());
+
+        pool.total_weighted_stake = 999;
+        assert!(pool.check_invariants(1_000).is_err());
+    }
+
+    #[test]
+    fn test_user_stake_bind_keeps_identity() {
+        let owner = Pubkey::new_unique();
+        let pool = Pubkey::new_unique();
+        let mut user_stake = UserStake::default();
+        assert!(!user_stake.is_initialized());
+
+        user_stake.bind(owner, pool, 254, 100).unwrap();
+        assert!(user_stake.initialized);
+        assert_eq!(user_stake.stake_start_time, 100);
+
+        // Re-staking after a full exit keeps the original binding
+        user_stake.bind(owner, pool, 1, 500).unwrap();
+        assert_eq!(user_stake.stake_start_time, 100);
+        assert_eq!(user_stake.bump, 254);
+
+        assert!(user_stake.bind(Pubkey::new_unique(), pool, 254, 600).is_err());
+        assert!(user_stake.bind(owner, Pubkey::new_unique(), 254, 600).is_err());
+
+        // Accounts from before the flag count as initialized once owned
+        let legacy = UserStake { owner, ..Default::default() };
+        assert!(legacy.is_initialized());
+    }
+
+    #[test]
+    fn test_pool_snapshot_refresh() {
+        let pool = StakePool {
+            total_staked: 1_000_000,
+            total_weighted_stake: 1_500_000,
+            reward_rate: 1,
+            paused: true,
+            ..Default::default()
+        };
+        let stats = PoolStats {
+            unique_stakers: 7,
+            total_rewards_distributed: 42,
+            ..Default::default()
+        };
+        let mut snapshot = PoolSnapshot::default();
+        snapshot.refresh(&pool, &stats, 1_000);
+
+        assert_eq!(snapshot.total_staked, 1_000_000);
+        // 31_536_000 tokens a year over 1_000_000 staked
+        assert_eq!(snapshot.apr_bps, 315_360);
+        assert_eq!(snapshot.unique_stakers, 7);
+        assert_eq!(snapshot.total_rewards_distributed, 42);
+        assert!(snapshot.paused);
+        assert_eq!(snapshot.last_update, 1_000);
+
+        // Empty pools report no APR rather than failing
+        snapshot.refresh(&StakePool::default(), &stats, 2_000);
+        assert_eq!(snapshot.apr_bps, 0);
+    }
+
+    #[test]
+    fn test_program_config_validation() {
+        let mut config = ProgramConfig {
+            tier_thresholds: ProgramConfig::DEFAULT_TIER_THRESHOLDS,
+            feature_flags: ProgramConfig::ALL_FEATURES,
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+        assert!(config.is_enabled(ProgramConfig::FEATURE_POOL_CREATION));
+
+        config.feature_flags = ProgramConfig::FEATURE_NATIVE_POOLS;
+        assert!(!config.is_enabled(ProgramConfig::FEATURE_POOL_CREATION));
+        assert!(!config.is_enabled(ProgramConfig::ALL_FEATURES));
+
+        // Unknown feature bits are rejected
+        config.feature_flags = 1 << 63;
+        assert!(config.validate().is_err());
+
+        config.feature_flags = 0;
+        config.tier_thresholds = [10, 10, 20];
+        assert!(config.validate().is_err());
+        config.tier_thresholds = [0, 10, 20];
+        assert!(config.validate().is_err());
+
+        // No guardian until one is assigned
+        let guardian = Pubkey::new_unique();
+        assert!(!config.is_guardian(&Pubkey::default()));
+        assert!(!config.is_guardian(&guardian));
+        config.guardian = guardian;
+        assert!(config.is_guardian(&guardian));
+        assert!(!config.is_guardian(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_claim_fee_discounted_by_tier() {
+        let pool = StakePool {
+            protocol_fee_bps: 1_000,
+            ..Default::default()
+        };
+        assert_eq!(pool.claim_fee_bps(StakingTier::None), 1_000);
+        assert_eq!(pool.claim_fee_bps(StakingTier::Holder), 800);
+        assert_eq!(pool.claim_fee_bps(StakingTier::Premium), 400);
+        assert_eq!(pool.claim_fee_bps(StakingTier::Vip), 0);
+
+        assert_eq!(StakePool::default().claim_fee_bps(StakingTier::None), 0);
+    }
+
+    #[test]
+    fn test_snapshot_voting_power_proof() {
+        use crate::merkle::{hash_pair, snapshot_leaf};
+
+        let alice = Pubkey::new_unique();
+        let bob = Pubkey::new_unique();
+        let alice_leaf = snapshot_leaf(&alice, 1_500);
+        let bob_leaf = snapshot_leaf(&bob, 2_000);
+
+        let mut snapshot = SnapshotDistribution::default();
+        assert!(!snapshot.verify_voting_power(&alice, 1_500, &[bob_leaf]));
+
+        snapshot.voting_power_root = hash_pair(&alice_leaf, &bob_leaf);
+        assert!(snapshot.has_voting_power());
+        assert!(snapshot.verify_voting_power(&alice, 1_500, &[bob_leaf]));
+        assert!(snapshot.verify_voting_power(&bob, 2_000, &[alice_leaf]));
+        assert!(!snapshot.verify_voting_power(&alice, 2_000, &[bob_leaf]));
+    }
+
+    #[test]
+    fn test_scheduled_rate_change_integrates_piecewise() {
+        let mut pool = StakePool {
+            total_weighted_stake: 1_000,
+            reward_rate: 10,
+            ..Default::default()
+        };
+        pool.queue_rate_change(RateChange { rate: 30, effective_time: 80 }).unwrap();
+        pool.queue_rate_change(RateChange { rate: 20, effective_time: 50 }).unwrap();
+        assert_eq!(pool.rate_changes[0].effective_time, 50);
+
+        // 50s at 10/s, then 30s at 20/s; the change at 80 isn't due yet
+        update_rewards(&mut pool, 80).unwrap();
+        assert_eq!(pool.reward_rate, 30);
+        assert_eq!(pool.rate_change_count, 0);
+        assert_eq!(pool.reward_liability, 500 + 600);
+        assert_eq!(pool.accumulated_reward_per_share, 1_100 * constants::PRECISION / 1_000);
+
+        update_rewards(&mut pool, 100).unwrap();
+        assert_eq!(pool.reward_liability, 1_100 + 600);
+
+        for i in 0..StakePool::MAX_RATE_CHANGES as i64 {
+            pool.queue_rate_change(RateChange { rate: 1, effective_time: 200 + i }).unwrap();
+        }
+        assert!(pool.queue_rate_change(RateChange { rate: 1, effective_time: 300 }).is_err());
+    }
+
+    #[test]
+    fn test_preview_early_exit() {
+        use crate::instructions::early_unstake::preview_early_exit;
+
+        let pool = StakePool {
+            early_unstake_penalty_bps: 1_000,
+            ..Default::default()
+        };
+        let user_stake = UserStake {
+            staked_amount: 10_000,
+            lock_duration: 100,
+            lock_end_time: 100,
+            ..Default::default()
+        };
+
+        // Half the lock remaining: half the 10% penalty
+        let preview = preview_early_exit(&pool, &user_stake, 10_000, 50).unwrap();
+        assert_eq!(preview.penalty, 500);
+        assert_eq!(preview.net_out, 9_500);
+        assert_eq!(preview.forfeited_rewards, 0);
+
+        let after_lock = preview_early_exit(&pool, &user_stake, 4_000, 100).unwrap();
+        assert_eq!(after_lock.penalty, 0);
+        assert_eq!(after_lock.net_out, 4_000);
+
+        assert!(preview_early_exit(&pool, &user_stake, 10_001, 50).is_err());
+    }
+
+    #[test]
+    fn test_milestones_follow_unbroken_streak() {
+        let day = 24 * 60 * 60;
+        let mut user = UserStake {
+            staked_amount: 1_000,
+            unbroken_since: 1_000,
+            ..Default::default()
+        };
+
+        assert_eq!(user.reached_milestones(1_000 + 89 * day), 0);
+        assert_eq!(user.reached_milestones(1_000 + 90 * day), 0b001);
+        assert_eq!(user.reached_milestones(1_000 + 200 * day), 0b011);
+        assert_eq!(user.reached_milestones(1_000 + 365 * day), 0b111);
+
+        // Paid milestones are never claimable again, even on a new streak
+        user.claimed_milestones = 0b001;
+        assert_eq!(user.claimable_milestones(1_000 + 200 * day), 0b010);
+        user.unbroken_since = 1_000 + 300 * day;
+        assert_eq!(user.claimable_milestones(1_000 + 480 * day), 0b010);
+
+        // No streak, no milestones
+        user.staked_amount = 0;
+        assert_eq!(user.reached_milestones(1_000 + 400 * day), 0);
+    }
+
+    #[test]
+    fn test_milestone_bonus_sums_flagged_amounts() {
+        let bonus = MilestoneBonus {
+            bonus_amounts: [100, 250, 1_000],
+            ..Default::default()
+        };
+        assert_eq!(bonus.bonus_for(0), Some(0));
+        assert_eq!(bonus.bonus_for(0b010), Some(250));
+        assert_eq!(bonus.bonus_for(0b101), Some(1_100));
+
+        let overflowing = MilestoneBonus {
+            bonus_amounts: [u64::MAX, 1, 0],
+            ..Default::default()
+        };
+        assert_eq!(overflowing.bonus_for(0b011), None);
+    }
+
+    #[test]
+    fn test_early_adopter_bonus_for_first_stakers() {
+        let pool = StakePool {
+            early_adopter_slots: 2,
+            early_adopter_bonus_bps: 500,
+            ..Default::default()
+        };
+        let mut stats = PoolStats::default();
+
+        assert_eq!(stats.early_adopter_bonus_bps(&pool), 500);
+        stats.record_stake(100, true, 100);
+        assert_eq!(stats.early_adopter_bonus_bps(&pool), 500);
+        stats.record_stake(100, true, 200);
+        assert_eq!(stats.early_adopter_bonus_bps(&pool), 0);
+
+        // Disabled when no slots are configured
+        assert_eq!(PoolStats::default().early_adopter_bonus_bps(&StakePool::default()), 0);
+
+        // The bonus stacks on the tier multiplier: Holder 1.1x + 0.05x
+        let user = UserStake {
+            staked_amount: constants::HOLDER_THRESHOLD,
+            early_adopter_bonus_bps: 500,
+            ..Default::default()
+        };
+        assert_eq!(user.reward_multiplier_bps(0), 11_500);
+    }
+
+    #[test]
+    fn test_pool_migration_backfills_vault_balance() {
+        let mut pool = StakePool {
+            total_staked: 1_000,
+            ..Default::default()
+        };
+        assert_eq!(pool.version, 0);
+
+        pool.migrate_to_v2().unwrap();
+        assert_eq!(pool.version, StakePool::CURRENT_VERSION);
+        assert_eq!(pool.vault_balance, 1_000);
+
+        // Tracked balances above principal (retained penalties) are kept
+        let mut pool = StakePool {
+            total_staked: 1_000,
+            vault_balance: 1_200,
+            ..Default::default()
+        };
+        pool.migrate_to_v2().unwrap();
+        assert_eq!(pool.vault_balance, 1_200);
+        assert!(pool.allow_unstake_when_paused);
+    }
+
+    #[test]
+    fn test_unstake_pause_policy() {
+        let mut pool = StakePool::default();
+        assert!(pool.unstake_allowed(false));
+
+        // Full freeze
+        pool.paused = true;
+        assert!(!pool.unstake_allowed(false));
+
+        // Exit-only
+        pool.allow_unstake_when_paused = true;
+        assert!(pool.unstake_allowed(false));
+    }
+
+    #[test]
+    fn test_protocol_pause_overrides_pool_flag() {
+        let mut pool = StakePool::default();
+        assert!(!pool.is_paused(false));
+        assert!(pool.is_paused(true));
+        assert!(!pool.unstake_allowed(true));
+
+        // The pool's exit-only policy still applies under a global pause
+        pool.allow_unstake_when_paused = true;
+        assert!(pool.unstake_allowed(true));
+
+        // Clearing the global pause leaves a pool-level pause in place
+        pool.paused = true;
+        assert!(pool.is_paused(false));
+    }
+
+    #[test]
+    fn test_apr_retarget_interval() {
+        let mut pool = StakePool::default();
+        assert_eq!(pool.apr_retarget_interval(), constants::APR_RETARGET_INTERVAL);
+
+        pool.reward_epoch_duration = 7 * 24 * 60 * 60;
+        assert_eq!(pool.apr_retarget_interval(), 7 * 24 * 60 * 60);
+    }
+
+    #[test]
+    fn test_fixed_rewards_accrue_to_lock_end() {
+        let year = staking_math::SECONDS_PER_YEAR as i64;
+        // 1,000 at 10% for a year guarantees 100
+        let mut user = UserStake {
+            staked_amount: 1_000,
+            lock_end_time: year,
+            fixed_apr_bps: 1_000,
+            fixed_reward_reserved: 100,
+            ..Default::default()
+        };
+
+        // Each third rounds down to 33
+        assert_eq!(settle_rewards(&mut user, 0, year / 3).unwrap(), 33);
+        assert_eq!(settle_rewards(&mut user, 0, 2 * year / 3).unwrap(), 33);
+        assert_eq!(user.fixed_reward_reserved, 34);
+
+        // The lock end sweeps the rest, and nothing accrues after it
+        assert_eq!(settle_rewards(&mut user, 0, year + 1_000).unwrap(), 34);
+        assert_eq!(user.owed_rewards, 100);
+        assert_eq!(user.fixed_reward_reserved, 0);
+        assert_eq!(user.fixed_accrued_until, year);
+        assert_eq!(settle_rewards(&mut user, 0, 2 * year).unwrap(), 0);
+
+        // Floating positions accrue nothing fixed
+        let mut floating = UserStake {
+            staked_amount: 1_000,
+            lock_end_time: year,
+            fixed_reward_reserved: 100,
+            ..Default::default()
+        };
+        assert_eq!(floating.accrue_fixed_rewards(year).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_certificate_payout_and_backing() {
+        let year = staking_math::SECONDS_PER_YEAR as i64;
+        let terms = CertificateTerms {
+            apr_bps: 800,
+            min_term: 30 * 24 * 60 * 60,
+            max_term: year,
+            ..Default::default()
+        };
+        assert!(terms.accepts_term(year));
+        assert!(!terms.accepts_term(year + 1));
+        assert!(!terms.accepts_term(0));
+
+        // 8% on 10,000 for a year, and for half of one
+        assert_eq!(terms.payout_for(10_000, year), Some(800));
+        assert_eq!(terms.payout_for(10_000, year / 2), Some(400));
+        assert_eq!(terms.payout_for(10_000, -1), None);
+
+        let certificate = Certificate { maturity_time: year, ..Default::default() };
+        assert!(!certificate.is_mature(year - 1));
+        assert!(certificate.is_mature(year));
+
+        // Emitted-but-unpaid and fixed-rate rewards can't back a certificate
+        let pool = StakePool {
+            reward_liability: 300,
+            fixed_reward_reserved: 200,
+            ..Default::default()
+        };
+        assert_eq!(pool.unreserved_rewards(1_000), 500);
+        assert_eq!(pool.unreserved_rewards(400), 0);
+    }
+
+    #[test]
+    fn test_lending_program_whitelist() {
+        let lender = Pubkey::new_unique();
+        let mut config = ProgramConfig::default();
+        assert!(!config.is_lending_program(&lender));
+        // Empty slots never match
+        assert!(!config.is_lending_program(&Pubkey::default()));
+
+        config.lending_programs[1] = lender;
+        assert!(config.is_lending_program(&lender));
+        assert!(!config.is_lending_program(&Pubkey::new_unique()));
+
+        let mut user = UserStake::default();
+        assert!(!user.is_collateralized());
+        user.collateral_program = lender;
+        assert!(user.is_collateralized());
+    }
+
+    #[test]
+    fn test_claim_interval() {
+        let mut user = UserStake::default();
+        // Never claimed
+        assert!(user.claim_interval_elapsed(3_600, 100));
+
+        user.last_claim_time = 1_000;
+        assert!(!user.claim_interval_elapsed(3_600, 4_599));
+        assert!(user.claim_interval_elapsed(3_600, 4_600));
+        // Disabled
+        assert!(user.claim_interval_elapsed(0, 1_000));
+    }
+
+    #[test]
+    fn test_claim_tax_decays_and_goes_to_other_stakers() {
+        let day = 24 * 60 * 60;
+        let mut pool = StakePool {
+            claim_tax_start_bps: 5_000,
+            claim_tax_decay_period: 10 * day,
+            total_weighted_stake: 1_000,
+            ..Default::default()
+        };
+
+        // 50% at open, 25% halfway, nothing once the period has run
+        assert_eq!(pool.claim_tax_bps(day, day), 5_000);
+        assert_eq!(pool.claim_tax_bps(day, 6 * day), 2_500);
+        assert_eq!(pool.claim_tax_bps(day, 11 * day), 0);
+        // Positions opened before the tax existed are exempt
+        assert_eq!(pool.claim_tax_bps(0, day), 0);
+
+        // A claimant holding 200 of the 1,000 weight pays 400 in tax; the
+        // other 800 weight shares all of it
+        assert!(pool.distribute_to_stakers(400, 200).unwrap());
+        assert_eq!(pool.accumulated_reward_per_share, constants::PRECISION / 2);
+        assert_eq!(pool.reward_liability, 400);
+        let others = UserStake { weighted_stake: 800, ..Default::default() };
+        assert_eq!(calculate_pending_rewards(&others, pool.accumulated_reward_per_share).unwrap(), 400);
+
+        // With nobody else staked it isn't distributed
+        assert!(!pool.distribute_to_stakers(400, 1_000).unwrap());
+        assert_eq!(pool.reward_liability, 400);
+    }
+
+    #[test]
+    fn test_tier_requires_min_lock() {
+        let day = 24 * 60 * 60;
+        let mut user = UserStake {
+            staked_amount: constants::VIP_THRESHOLD,
+            lock_duration: 7 * day,
+            ..Default::default()
+        };
+        // No requirements: the amount alone decides
+        assert_eq!(user.current_tier(), StakingTier::Vip);
+
+        // VIP needs a 90-day lock, Premium 30 days
+        user.tier_min_lock_durations = [0, 30 * day, 90 * day];
+        assert_eq!(user.current_tier(), StakingTier::Holder);
+        assert_eq!(user.effective_tier(0), StakingTier::Holder);
+
+        user.lock_duration = 365 * day;
+        assert_eq!(user.current_tier(), StakingTier::Vip);
+    }
+
+    #[test]
+    fn test_tier_thresholds_follow_mint_decimals() {
+        // 1,000 tokens of a 6-decimal mint
+        let mut user = UserStake {
+            staked_amount: 1_000_000_000,
+            ..Default::default()
+        };
+        assert_eq!(user.current_tier(), StakingTier::None);
+
+        user.tier_thresholds = staking_math::TierConfig::for_decimals(6).unwrap().min_amounts;
+        assert_eq!(user.current_tier(), StakingTier::Holder);
+        user.staked_amount = 100_000_000_000;
+        assert_eq!(user.current_tier(), StakingTier::Vip);
+    }
+
+    #[test]
+    fn test_cross_pool_boost() {
+        let source = Pubkey::new_unique();
+        let mut pool = StakePool {
+            boost_source_pool: source,
+            max_cross_boost_bps: 10_000,
+            total_staked: 1_000,
+            ..Default::default()
+        };
+        // 10% of the source pool's weight, 20% of the farm: half the bonus
+        let mut user = UserStake {
+            staked_amount: 200,
+            boost_source_pool: source,
+            boost_source_weight: 100,
+            boost_source_total_weight: 1_000,
+            ..Default::default()
+        };
+        assert_eq!(pool.cross_boost_bps(&user), 5_000);
+        let base = user.reward_multiplier_bps(0);
+        assert_eq!(pool.position_multiplier_bps(&user, 0), base + 5_000);
+
+        // A snapshot against a previous source pool earns nothing
+        pool.boost_source_pool = Pubkey::new_unique();
+        assert_eq!(pool.cross_boost_bps(&user), 0);
+        pool.boost_source_pool = Pubkey::default();
+        user.boost_source_pool = Pubkey::default();
+        assert_eq!(pool.cross_boost_bps(&user), 0);
+    }
+
+    #[test]
+    fn test_operator_root_publishing() {
+        use crate::merkle::{hash_pair, operator_leaf};
+
+        let mut book = OperatorBook {
+            total_collected: 100,
+            ..Default::default()
+        };
+        let alice = Pubkey::new_unique();
+        let bob = Pubkey::new_unique();
+        let alice_leaf = operator_leaf(&alice, 600, 60);
+        let bob_leaf = operator_leaf(&bob, 400, 40);
+        let root = hash_pair(&alice_leaf, &bob_leaf);
+
+        // No root yet
+        assert!(!book.verify_entry(&alice, 600, 60, &[bob_leaf]));
+
+        // Sub-balances must add up to the position
+        assert!(book.publish_root(root, 999, 100, 1_000, 1).is_err());
+        // Rewards can't be allocated before they are collected
+        assert!(book.publish_root(root, 1_000, 101, 1_000, 1).is_err());
+
+        book.publish_root(root, 1_000, 100, 1_000, 1).unwrap();
+        assert_eq!(book.root_version, 1);
+        assert!(book.verify_entry(&alice, 600, 60, &[bob_leaf]));
+        assert!(!book.verify_entry(&alice, 600, 61, &[bob_leaf]));
+
+        // Cumulative rewards never shrink
+        assert!(book.publish_root(root, 1_000, 99, 1_000, 2).is_err());
+    }
+
+    #[test]
+    fn test_operator_position_flag() {
+        let mut user = UserStake::default();
+        assert!(!user.is_operator());
+        user.operator_escrow = Pubkey::new_unique();
+        assert!(user.is_operator());
+    }
+
+    #[test]
+    fn test_cancel_owed_rewards_releases_liability() {
+        let mut pool = StakePool {
+            reward_liability: 1_000,
+            fixed_reward_reserved: 500,
+            ..Default::default()
+        };
+        let mut user = UserStake {
+            owed_rewards: 300,
+            ..Default::default()
+        };
+
+        assert_eq!(pool.cancel_owed_rewards(&mut user), 300);
+        assert_eq!(user.owed_rewards, 0);
+        assert_eq!(pool.reward_liability, 700);
+        assert_eq!(pool.fixed_reward_reserved, 500);
+        assert_eq!(pool.unreserved_rewards(1_500), 300);
+
+        // Fixed-rate positions also release their reservation
+        user.fixed_apr_bps = 1_000;
+        user.owed_rewards = 200;
+        assert_eq!(pool.cancel_owed_rewards(&mut user), 200);
+        assert_eq!(pool.reward_liability, 500);
+        assert_eq!(pool.fixed_reward_reserved, 300);
+
+        assert_eq!(pool.cancel_owed_rewards(&mut user), 0);
+    }
+
+    #[test]
+    fn test_retained_penalty_goes_to_remaining_stakers() {
+        // The exiting position's unstaked weight is already removed; it
+        // keeps 500 of its weight next to another staker's 1,000
+        let mut pool = StakePool {
+            total_weighted_stake: 1_500,
+            total_staked: 1_500,
+            vault_balance: 1_500,
+            ..Default::default()
+        };
+        let mut exiting = UserStake { weighted_stake: 500, ..Default::default() };
+        let remaining = UserStake { weighted_stake: 1_000, ..Default::default() };
+
+        assert!(distribute_to_other_stakers(&mut pool, &mut exiting, 300).unwrap());
+        assert_eq!(calculate_pending_rewards(&remaining, pool.accumulated_reward_per_share).unwrap(), 300);
+        assert_eq!(calculate_pending_rewards(&exiting, pool.accumulated_reward_per_share).unwrap(), 0);
+        assert_eq!(pool.reward_liability, 300);
+        // Paid out as rewards, not held back as principal
+        assert_eq!(pool.expected_stake_vault_balance(), 1_500);
+
+        // A full exit leaves nobody to receive it
+        let mut last = UserStake { weighted_stake: 1_500, ..Default::default() };
+        assert!(!distribute_to_other_stakers(&mut pool, &mut last, 300).unwrap());
+    }
+
+    #[test]
+    fn test_treasury_address_matches_pda() {
+        let (expected, _) = Pubkey::find_program_address(
+            &[treasury::state::Treasury::SEED_PREFIX],
+            &treasury::ID,
+        );
+        assert_eq!(constants::TREASURY_ADDRESS, expected);
+    }
+
+    /// A position serialized under the original layout: fields through
+    /// `bump`, then 32 reserved bytes
+    fn legacy_user_stake_data(
+        owner: &Pubkey,
+        stake_pool: &Pubkey,
+        staked_amount: u64,
+        weighted_stake: u64,
+        reward_debt: u128,
+    ) -> Vec<u8> {
+        use anchor_lang::Discriminator;
+
+        let mut data = UserStake::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(owner.as_ref());
+        data.extend_from_slice(stake_pool.as_ref());
+        data.extend_from_slice(&staked_amount.to_le_bytes());
+        data.extend_from_slice(&weighted_stake.to_le_bytes());
+        data.extend_from_slice(&9_000i64.to_le_bytes()); // lock_end_time
+        data.extend_from_slice(&3_000i64.to_le_bytes()); // lock_duration
+        data.extend_from_slice(&reward_debt.to_le_bytes());
+        data.extend_from_slice(&7u64.to_le_bytes());     // total_claimed
+        data.extend_from_slice(&6_000i64.to_le_bytes()); // stake_start_time
+        data.push(254);                                  // bump
+        data.extend_from_slice(&[0u8; 32]);              // _reserved
+        data
+    }
+
+    #[test]
+    fn test_legacy_user_stake_layout_migrates() {
+        let owner = Pubkey::new_unique();
+        let stake_pool = Pubkey::new_unique();
+        let mut data = legacy_user_stake_data(&owner, &stake_pool, 1_000, 1_500, 42);
+        assert!(UserStake::try_deserialize(&mut &data[..]).is_err());
+
+        // The migration zero-fills the extension
+        data.resize(UserStake::LEN, 0);
+        let user = UserStake::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(user.owner, owner);
+        assert_eq!(user.stake_pool, stake_pool);
+        assert_eq!(user.staked_amount, 1_000);
+        assert_eq!(user.weighted_stake, 1_500);
+        assert_eq!(user.total_claimed, 7);
+        assert_eq!(user.bump, 254);
+        assert_eq!(user.history[0].kind, ActionKind::Empty);
+        assert!(user.is_initialized());
+        assert!(user.is_legacy_weight());
+    }
+
+    #[test]
+    fn test_first_pool_keeps_legacy_address() {
+        let stake_mint = Pubkey::new_unique();
+        let pool_address = |pool_id: u64| {
+            Pubkey::find_program_address(
+                &[StakePool::SEED_PREFIX, stake_mint.as_ref(), &StakePool::pool_id_seed(pool_id)],
+                &crate::ID,
+            )
+            .0
+        };
+        let (legacy, _) = Pubkey::find_program_address(
+            &[StakePool::SEED_PREFIX, stake_mint.as_ref()],
+            &crate::ID,
+        );
+        assert_eq!(pool_address(0), legacy);
+
+        // Later pools of the same mint get their own addresses
+        let (second, _) = Pubkey::find_program_address(
+            &[StakePool::SEED_PREFIX, stake_mint.as_ref(), &1u64.to_le_bytes()],
+            &crate::ID,
+        );
+        assert_eq!(pool_address(1), second);
+        assert_ne!(second, legacy);
+    }
+
+    /// A pool serialized under the original layout: fields through `bump`,
+    /// then 64 reserved bytes
+    fn legacy_pool_data(stake_mint: &Pubkey, bump: u8, accumulated_reward_per_share: u128) -> Vec<u8> {
+        use anchor_lang::Discriminator;
+
+        let mut data = StakePool::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(Pubkey::new_unique().as_ref()); // authority
+        data.extend_from_slice(stake_mint.as_ref());
+        data.extend_from_slice(Pubkey::new_unique().as_ref()); // reward_mint
+        data.extend_from_slice(Pubkey::new_unique().as_ref()); // stake_vault
+        data.extend_from_slice(Pubkey::new_unique().as_ref()); // reward_vault
+        data.extend_from_slice(&0u64.to_le_bytes());           // total_staked
+        data.extend_from_slice(&0u64.to_le_bytes());           // total_weighted_stake
+        data.extend_from_slice(&10u64.to_le_bytes());          // reward_rate
+        data.extend_from_slice(&accumulated_reward_per_share.to_le_bytes());
+        data.extend_from_slice(&0i64.to_le_bytes());           // last_reward_time
+        data.extend_from_slice(&constants::MIN_LOCK_DURATION.to_le_bytes());
+        data.extend_from_slice(&constants::MAX_LOCK_DURATION.to_le_bytes());
+        data.push(0);                                           // paused
+        data.push(bump);
+        data.extend_from_slice(&[0u8; 64]);                    // _reserved
+        data
+    }
+
+    /// Run `migrate_pool_v1_to_v2`'s data changes: zero-fill to the current
+    /// size, then migrate the fields
+    fn migrate_legacy_pool_data(data: &mut Vec<u8>) {
+        assert!(StakePool::try_deserialize(&mut &data[..]).is_err());
+        data.resize(StakePool::LEN, 0);
+        let mut pool = StakePool::try_deserialize(&mut &data[..]).unwrap();
+        pool.migrate_to_v2().unwrap();
+        pool.try_serialize(&mut &mut data[..]).unwrap();
+    }
+
+    #[test]
+    fn test_migrated_legacy_pool_stakes_claims_and_unstakes() {
+        use anchor_lang::Discriminator;
+
+        let stake_mint = Pubkey::new_unique();
+        let (pool_key, pool_bump) = Pubkey::find_program_address(
+            &[StakePool::SEED_PREFIX, stake_mint.as_ref()],
+            &crate::ID,
+        );
+        let mut pool_data = legacy_pool_data(&stake_mint, pool_bump, 0);
+        migrate_legacy_pool_data(&mut pool_data);
+
+        let program_id = crate::ID;
+        let mut pool_lamports = 0;
+        let pool_info = AccountInfo::new(
+            &pool_key, false, true, &mut pool_lamports, &mut pool_data, &program_id, false, 0,
+        );
+        let mut stake_pool = Account::<StakePool>::try_from(&pool_info).unwrap();
+        assert_eq!(stake_pool.version, StakePool::CURRENT_VERSION);
+
+        // The seeds constraint every instruction checks accepts the legacy address
+        assert_eq!(
+            Pubkey::create_program_address(
+                &[StakePool::SEED_PREFIX, stake_mint.as_ref(), &stake_pool.id_seed(), &[stake_pool.bump]],
+                &crate::ID,
+            )
+            .unwrap(),
+            pool_key
+        );
+
+        let user = Pubkey::new_unique();
+        let (user_stake_key, user_stake_bump) = Pubkey::find_program_address(
+            &[UserStake::SEED_PREFIX, pool_key.as_ref(), user.as_ref()],
+            &crate::ID,
+        );
+        let mut user_data = UserStake::DISCRIMINATOR.to_vec();
+        user_data.resize(UserStake::LEN, 0);
+        let mut user_lamports = 0;
+        let user_info = AccountInfo::new(
+            &user_stake_key, false, true, &mut user_lamports, &mut user_data, &program_id, false, 0,
+        );
+        let mut user_stake = Account::<UserStake>::try_from(&user_info).unwrap();
+        let mut pool_stats = PoolStats::default();
+
+        // Stake
+        let start = 1_000;
+        instructions::stake::process_stake(
+            instructions::stake::StakeAccounts {
+                stake_pool: &mut stake_pool,
+                pool_stats: &mut pool_stats,
+                user_stake: &mut user_stake,
+                user,
+                user_stake_bump,
+            },
+            None,
+            false,
+            1_000_000,
+            constants::MIN_LOCK_DURATION,
+            start,
+        )
+        .unwrap();
+        assert_eq!(stake_pool.total_staked, 1_000_000);
+        assert_eq!(user_stake.stake_pool, pool_key);
+
+        // Claim: the sole staker earns the whole emission
+        let later = start + 100;
+        update_rewards(&mut stake_pool, later).unwrap();
+        settle_rewards(&mut user_stake, stake_pool.accumulated_reward_per_share, later).unwrap();
+        assert!(user_stake.owed_rewards > 990 && user_stake.owed_rewards <= 1_000);
+
+        // Unstake once the lock ends
+        let unlocked = start + constants::MIN_LOCK_DURATION;
+        instructions::unstake::process_unstake(
+            &mut stake_pool,
+            &mut user_stake,
+            false,
+            1_000_000,
+            unlocked,
+            true,
+        )
+        .unwrap();
+        assert_eq!(stake_pool.total_staked, 0);
+        assert_eq!(user_stake.staked_amount, 0);
+    }
+
+    #[test]
+    fn test_legacy_accrual_settles_under_new_precision() {
+        // Accrued at the v1 scale: 5 tokens per unit of weight so far, and
+        // the position joined at 2 (debt 1,000 * 2)
+        let stake_mint = Pubkey::new_unique();
+        let mut pool_data = legacy_pool_data(&stake_mint, 255, 5 * constants::LEGACY_PRECISION);
+        migrate_legacy_pool_data(&mut pool_data);
+        let mut pool = StakePool::try_deserialize(&mut &pool_data[..]).unwrap();
+        assert_eq!(pool.accumulated_reward_per_share, 5 * constants::PRECISION);
+
+        let mut user_data = legacy_user_stake_data(&Pubkey::new_unique(), &Pubkey::new_unique(), 1_000, 1_000, 2_000);
+        user_data.resize(UserStake::LEN, 0);
+        let mut user = UserStake::try_deserialize(&mut &user_data[..]).unwrap();
+        assert_eq!(user.reward_debt, 2_000);
+        assert_eq!(calculate_pending_rewards(&user, pool.accumulated_reward_per_share).unwrap(), 3_000);
+
+        // Accrual continues at the new scale: 100s at 10/s to 1,000 weight
+        pool.total_weighted_stake = 1_000;
+        update_rewards(&mut pool, 100).unwrap();
+        settle_rewards(&mut user, pool.accumulated_reward_per_share, 100).unwrap();
+        assert_eq!(user.owed_rewards, 4_000);
     }
 }
@@ -0,0 +1,98 @@
+//! Checked arithmetic primitives shared by all instructions.
+//!
+//! Every helper returns `StakingError::MathOverflow` instead of panicking or
+//! wrapping, so handlers can compose them with `?`.
+
+use anchor_lang::prelude::*;
+
+use crate::constants::BPS_DENOMINATOR;
+use crate::errors::StakingError;
+use crate::u256;
+
+/// Compute `a * b / denominator` using a 256-bit intermediate, rounding down
+///
+/// # Returns
+/// * `Result<u128>` - Quotient, or `MathOverflow` on zero denominator or overflow
+pub fn mul_div_u128(a: u128, b: u128, denominator: u128) -> Result<u128> {
+    u256::mul_div(a, b, denominator).ok_or_else(|| StakingError::MathOverflow.into())
+}
+
+/// Apply a basis-point factor to an amount: `amount * bps / 10000`, rounding down
+///
+/// `bps` may exceed 10000 (multipliers such as 15000 = 1.5x).
+///
+/// # Returns
+/// * `Result<u64>` - Scaled amount, or `MathOverflow` if it does not fit in `u64`
+pub fn apply_bps(amount: u64, bps: u64) -> Result<u64> {
+    to_u64(mul_div_u128(amount as u128, bps as u128, BPS_DENOMINATOR as u128)?)
+}
+
+/// Scale `value` by the ratio `numerator / denominator`, rounding down
+///
+/// Used for pro-rata splits such as removing a share of weighted stake or
+/// reward debt on a partial unstake.
+///
+/// # Returns
+/// * `Result<u128>` - Proportional share, or `MathOverflow` on zero denominator
+pub fn proportional(value: u128, numerator: u64, denominator: u64) -> Result<u128> {
+    mul_div_u128(value, numerator as u128, denominator as u128)
+}
+
+/// Narrow a `u128` to `u64`
+///
+/// # Returns
+/// * `Result<u64>` - The value, or `MathOverflow` if it exceeds `u64::MAX`
+pub fn to_u64(value: u128) -> Result<u64> {
+    u64::try_from(value).map_err(|_| StakingError::MathOverflow.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mul_div_u128() {
+        assert_eq!(mul_div_u128(6, 7, 3).unwrap(), 14);
+        assert_eq!(mul_div_u128(7, 1, 2).unwrap(), 3);
+        assert_eq!(mul_div_u128(0, 5, 1).unwrap(), 0);
+        assert_eq!(mul_div_u128(u128::MAX, 3, 3).unwrap(), u128::MAX);
+        assert!(mul_div_u128(1, 1, 0).is_err());
+        assert!(mul_div_u128(u128::MAX, 2, 1).is_err());
+    }
+
+    #[test]
+    fn test_apply_bps() {
+        assert_eq!(apply_bps(1_000, 10_000).unwrap(), 1_000);
+        assert_eq!(apply_bps(1_000, 15_000).unwrap(), 1_500);
+        assert_eq!(apply_bps(1_000, 500).unwrap(), 50);
+        assert_eq!(apply_bps(1_000, 0).unwrap(), 0);
+        assert_eq!(apply_bps(0, 20_000).unwrap(), 0);
+        // Rounds down: 3 * 1 / 10000 = 0.0003
+        assert_eq!(apply_bps(3, 1).unwrap(), 0);
+        assert_eq!(apply_bps(9_999, 1).unwrap(), 0);
+        assert_eq!(apply_bps(10_001, 1).unwrap(), 1);
+        // Full u64 range is fine at or below 1x
+        assert_eq!(apply_bps(u64::MAX, 10_000).unwrap(), u64::MAX);
+        assert_eq!(apply_bps(u64::MAX, 5_000).unwrap(), u64::MAX / 2);
+        // Result exceeding u64 is rejected
+        assert!(apply_bps(u64::MAX, 20_000).is_err());
+    }
+
+    #[test]
+    fn test_proportional() {
+        assert_eq!(proportional(10, 1, 3).unwrap(), 3);
+        assert_eq!(proportional(10, 3, 3).unwrap(), 10);
+        assert_eq!(proportional(10, 0, 3).unwrap(), 0);
+        assert_eq!(proportional(u128::MAX, u64::MAX, u64::MAX).unwrap(), u128::MAX);
+        assert_eq!(proportional(u128::MAX, 1, 2).unwrap(), u128::MAX / 2);
+        assert!(proportional(10, 1, 0).is_err());
+        assert!(proportional(u128::MAX, 2, 1).is_err());
+    }
+
+    #[test]
+    fn test_to_u64() {
+        assert_eq!(to_u64(0).unwrap(), 0);
+        assert_eq!(to_u64(u64::MAX as u128).unwrap(), u64::MAX);
+        assert!(to_u64(u64::MAX as u128 + 1).is_err());
+    }
+}
@@ -0,0 +1,46 @@
+//! SPL Memo tags attached to user-facing token transfers.
+//!
+//! Exchanges and accounting tools that only parse transfers can attribute a
+//! flow to its pool and user from a compact `{action}:{pool}:{user}` memo.
+
+use anchor_lang::prelude::*;
+use anchor_spl::memo::{build_memo, BuildMemo, Memo};
+
+/// Which operation moved the tokens
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MemoAction {
+    Stake,
+    Unstake,
+    Claim,
+    Bonus,
+}
+
+impl MemoAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MemoAction::Stake => "stake",
+            MemoAction::Unstake => "unstake",
+            MemoAction::Claim => "claim",
+            MemoAction::Bonus => "bonus",
+        }
+    }
+}
+
+/// Build the memo text for a transfer
+pub fn memo_tag(action: MemoAction, stake_pool: &Pubkey, user: &Pubkey) -> String {
+    format!("{}:{}:{}", action.as_str(), stake_pool, user)
+}
+
+/// CPI into the memo program with the transfer's tag
+pub fn tag_transfer<'info>(
+    memo_program: &Program<'info, Memo>,
+    action: MemoAction,
+    stake_pool: &Pubkey,
+    user: &Pubkey,
+) -> Result<()> {
+    let memo = memo_tag(action, stake_pool, user);
+    build_memo(
+        CpiContext::new(memo_program.to_account_info(), BuildMemo {}),
+        memo.as_bytes(),
+    )
+}
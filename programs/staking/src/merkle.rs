@@ -0,0 +1,81 @@
+//! Merkle proof verification for snapshot distributions, voting power and
+//! operator sub-accounts.
+//!
+//! Leaves are `keccak(user || amount_le)`, with the weighted stake as the
+//! amount for voting power roots; operator roots use
+//! `keccak(beneficiary || balance_le || cumulative_rewards_le)`. Interior
+//! nodes hash the two children in sorted order so proofs don't need
+//! left/right flags.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak::hashv;
+
+/// Leaf hash for a `(user, amount)` allocation
+pub fn snapshot_leaf(user: &Pubkey, amount: u64) -> [u8; 32] {
+    hashv(&[user.as_ref(), &amount.to_le_bytes()]).to_bytes()
+}
+
+/// Leaf hash for a beneficiary's sub-balance and cumulative rewards in an
+/// operator position
+pub fn operator_leaf(beneficiary: &Pubkey, balance: u64, cumulative_rewards: u64) -> [u8; 32] {
+    hashv(&[beneficiary.as_ref(), &balance.to_le_bytes(), &cumulative_rewards.to_le_bytes()]).to_bytes()
+}
+
+/// Hash two sibling nodes in sorted order
+pub fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    if a <= b {
+        hashv(&[a, b]).to_bytes()
+    } else {
+        hashv(&[b, a]).to_bytes()
+    }
+}
+
+/// Whether `proof` links `leaf` to `root`
+pub fn verify_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| hash_pair(&node, sibling));
+    computed == *root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_proof_four_leaves() {
+        let users: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let leaves: Vec<[u8; 32]> = users
+            .iter()
+            .enumerate()
+            .map(|(i, user)| snapshot_leaf(user, (i as u64 + 1) * 100))
+            .collect();
+
+        let left = hash_pair(&leaves[0], &leaves[1]);
+        let right = hash_pair(&leaves[2], &leaves[3]);
+        let root = hash_pair(&left, &right);
+
+        assert!(verify_proof(&[leaves[1], right], &root, leaves[0]));
+        assert!(verify_proof(&[leaves[2], left], &root, leaves[3]));
+
+        // Wrong amount or wrong sibling fails
+        assert!(!verify_proof(&[leaves[1], right], &root, snapshot_leaf(&users[0], 101)));
+        assert!(!verify_proof(&[leaves[2], right], &root, leaves[0]));
+    }
+
+    #[test]
+    fn test_operator_leaf_commits_to_both_amounts() {
+        let beneficiary = Pubkey::new_unique();
+        let leaf = operator_leaf(&beneficiary, 1_000, 50);
+        let sibling = operator_leaf(&Pubkey::new_unique(), 2_000, 0);
+        let root = hash_pair(&leaf, &sibling);
+
+        assert!(verify_proof(&[sibling], &root, leaf));
+        assert!(!verify_proof(&[sibling], &root, operator_leaf(&beneficiary, 1_000, 51)));
+        assert!(!verify_proof(&[sibling], &root, operator_leaf(&beneficiary, 1_001, 50)));
+    }
+
+    #[test]
+    fn test_single_leaf_tree() {
+        let leaf = snapshot_leaf(&Pubkey::new_unique(), 42);
+        assert!(verify_proof(&[], &leaf, leaf));
+    }
+}
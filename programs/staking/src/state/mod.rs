@@ -1,7 +1,12 @@
 use anchor_lang::prelude::*;
 
-/// Staking tier based on amount staked
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+use crate::errors::StakingError;
+use crate::{calculate_reward_debt, constants, math};
+use crate::merkle::{operator_leaf, snapshot_leaf, verify_proof};
+
+/// Staking tier based on amount staked (ordered from lowest to highest)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StakingTier {
     /// No tier - less than 1,000 tokens (5% platform fee)
     None,
@@ -19,6 +24,28 @@ impl Default for StakingTier {
     }
 }
 
+impl From<staking_math::Tier> for StakingTier {
+    fn from(tier: staking_math::Tier) -> Self {
+        match tier {
+            staking_math::Tier::None => StakingTier::None,
+            staking_math::Tier::Holder => StakingTier::Holder,
+            staking_math::Tier::Premium => StakingTier::Premium,
+            staking_math::Tier::Vip => StakingTier::Vip,
+        }
+    }
+}
+
+impl From<StakingTier> for staking_math::Tier {
+    fn from(tier: StakingTier) -> Self {
+        match tier {
+            StakingTier::None => staking_math::Tier::None,
+            StakingTier::Holder => staking_math::Tier::Holder,
+            StakingTier::Premium => staking_math::Tier::Premium,
+            StakingTier::Vip => staking_math::Tier::Vip,
+        }
+    }
+}
+
 impl StakingTier {
     /// Get the platform fee percentage (in basis points) for this tier
     /// 500 = 5%, 400 = 4%, 200 = 2%, 0 = 0%
@@ -43,9 +70,61 @@ impl StakingTier {
     }
 }
 
+/// Kind of user action recorded in the on-chain history
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ActionKind {
+    /// Unused history slot
+    #[default]
+    Empty,
+    /// Tokens staked
+    Stake,
+    /// Tokens unstaked
+    Unstake,
+    /// Rewards claimed
+    Claim,
+}
+
+/// A single entry in a user's action history
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ActionRecord {
+    /// What the user did
+    pub kind: ActionKind,
+    /// Token amount involved
+    pub amount: u64,
+    /// Unix timestamp of the action
+    pub timestamp: i64,
+}
+
+impl ActionRecord {
+    pub const LEN: usize = 1 + 8 + 8;
+}
+
+/// A reward rate change scheduled to take effect at a fixed time
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RateChange {
+    /// Reward rate (tokens per second) from `effective_time` on
+    pub rate: u64,
+    /// Unix timestamp the new rate starts accruing
+    pub effective_time: i64,
+}
+
+impl RateChange {
+    pub const LEN: usize = 8 + 8;
+}
+
+/// Zeroed reserved bytes, for serde on arrays longer than serde supports
+#[cfg(feature = "serde")]
+fn reserved_bytes<const N: usize>() -> [u8; N] {
+    [0; N]
+}
+
 /// Stake pool configuration and state
 #[account]
 #[derive(Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StakePool {
     /// Authority that can manage this pool
     pub authority: Pubkey,
@@ -71,7 +150,7 @@ pub struct StakePool {
     /// Reward rate per second (in token smallest units)
     pub reward_rate: u64,
 
-    /// Accumulated reward per share (scaled by 1e12 for precision)
+    /// Accumulated reward per share (scaled by PRECISION = 1e18)
     pub accumulated_reward_per_share: u128,
 
     /// Last timestamp when rewards were updated
@@ -89,7 +168,170 @@ pub struct StakePool {
     /// Bump seed for PDA derivation
     pub bump: u8,
 
+    /// Whether the stake mint is wrapped SOL (enables native stake/unstake)
+    pub is_native: bool,
+
+    /// Whether rewards are held in the stake vault (stake_mint == reward_mint)
+    pub shared_vault: bool,
+
+    /// Whether stake/unstake must notify the programs in the hook registry
+    pub hooks_enabled: bool,
+
+    /// Seconds a tier upgrade must be held before its benefits apply
+    pub tier_activation_delay: i64,
+
+    /// Seconds a user keeps their prior tier after a partial unstake drops it
+    pub tier_grace_period: i64,
+
+    /// Start of the first claim window cycle (Unix timestamp)
+    pub claim_window_anchor: i64,
+
+    /// Length of each claim window cycle in seconds (0 = claims always open)
+    pub claim_window_period: i64,
+
+    /// Seconds at the start of each cycle during which claims are allowed
+    pub claim_window_length: i64,
+
+    /// Start of a claim blackout period (Unix timestamp, inclusive)
+    pub claim_blackout_start: i64,
+
+    /// End of a claim blackout period (Unix timestamp, exclusive)
+    pub claim_blackout_end: i64,
+
+    /// Soulbound credential mint stakers must hold (default = no gate)
+    pub credential_mint: Pubkey,
+
+    /// Minimum amount accepted per stake deposit
+    pub min_stake_amount: u64,
+
+    /// Pool identifier (PDA seed), allowing several pools per stake mint
+    pub pool_id: u64,
+
+    /// Number of snapshot distributions published (next snapshot id)
+    pub snapshot_count: u64,
+
+    /// Occupied extra reward stream slots (streams accounts required when > 0)
+    pub reward_stream_count: u8,
+
+    /// Penalty (bps) for unstaking on the first day of a lock, scaled down
+    /// linearly with the lock time remaining (0 = early unstake disabled)
+    pub early_unstake_penalty_bps: u64,
+
+    /// Bonus multiplier (bps) earned per full month of unbroken staking
+    pub streak_bonus_bps_per_month: u64,
+
+    /// Cap on the streak bonus (bps)
+    pub max_streak_bonus_bps: u64,
+
+    /// Share (bps) of each early unstake penalty burned
+    pub penalty_burn_bps: u64,
+
+    /// Share (bps) of each early unstake penalty sent to the protocol treasury
+    pub penalty_treasury_bps: u64,
+
+    /// Share (bps) of each early unstake penalty sent to the insurance fund;
+    /// the remainder is retained in the stake vault for remaining stakers
+    pub penalty_insurance_bps: u64,
+
+    /// Reward epoch length in seconds (0 = per-second accrual). In epoch
+    /// mode rewards are only distributed by `settle_epoch`, and
+    /// `last_reward_time` marks the end of the last settled epoch.
+    pub reward_epoch_duration: i64,
+
+    /// Number of reward epochs settled so far
+    pub reward_epoch: u64,
+
+    /// Minimum seconds between a user's consecutive stake/unstake operations
+    pub action_cooldown: i64,
+
+    /// Stake vault balance expected from stakes, unstakes and retained
+    /// penalties; anything above it is a direct donation
+    pub vault_balance: u64,
+
+    /// Rewards emitted to stakers but not yet paid out
+    pub reward_liability: u64,
+
+    /// Reward tokens beyond `reward_liability` as of the last `sync_vaults`
+    pub distributable_rewards: u64,
+
+    /// Protocol fee (bps) taken from claimed rewards before the holder's
+    /// tier discount
+    pub protocol_fee_bps: u64,
+
+    /// Total reward tokens paid to the treasury as claim fees
+    pub protocol_fees_collected: u64,
+
+    /// Scheduled reward rate changes, earliest first (only the first
+    /// `rate_change_count` are valid)
+    pub rate_changes: [RateChange; StakePool::MAX_RATE_CHANGES],
+
+    /// Number of pending rate changes
+    pub rate_change_count: u8,
+
+    /// Number of distinct stakers, in order of arrival, who receive the
+    /// early adopter bonus (0 = disabled)
+    pub early_adopter_slots: u64,
+
+    /// Permanent reward multiplier bonus (bps) granted to early adopters
+    pub early_adopter_bonus_bps: u64,
+
+    /// Account layout version (0 = created before versioning, layout v1)
+    pub version: u8,
+
+    /// Whether stakers may still unstake while the pool is paused
+    /// ("exit-only" pause) rather than being frozen in place
+    pub allow_unstake_when_paused: bool,
+
+    /// Target APR (bps) the reward rate is periodically retargeted to
+    /// from `total_staked` (0 = fixed reward rate)
+    pub target_apr_bps: u64,
+
+    /// Unix timestamp of the last APR retarget
+    pub last_apr_retarget: i64,
+
+    /// Guaranteed APR (bps) locked into each new position at stake time
+    /// (0 = rewards float with `reward_rate`)
+    pub fixed_apr_bps: u64,
+
+    /// Reward tokens set aside for fixed-rate positions: their remaining
+    /// guaranteed rewards plus whatever has accrued but not been paid
+    pub fixed_reward_reserved: u64,
+
+    /// Minimum seconds between a user's reward claims (0 disables)
+    pub min_claim_interval: i64,
+
+    /// Claim tax (bps) charged right after a position opens, decaying
+    /// linearly to zero over `claim_tax_decay_period` (0 disables)
+    pub claim_tax_start_bps: u64,
+
+    /// Seconds over which the claim tax decays to zero
+    pub claim_tax_decay_period: i64,
+
+    /// Minimum lock duration (seconds) for Holder, Premium and VIP on top
+    /// of the amount thresholds (0 = no requirement)
+    pub tier_min_lock_durations: [i64; 3],
+
+    /// Holder, Premium and VIP amount thresholds in the stake mint's own
+    /// units, scaled from the built-in table at creation (all zero on pools
+    /// created earlier, which use the built-in table)
+    pub tier_thresholds: [u64; 3],
+
+    /// Decimals of the stake mint, recorded at creation (0 on pools created
+    /// earlier, which all use 9-decimal stake mints)
+    pub stake_mint_decimals: u8,
+
+    /// Decimals of the reward mint, recorded at creation
+    pub reward_mint_decimals: u8,
+
+    /// Pool whose weighted stake boosts positions here (default = none)
+    pub boost_source_pool: Pubkey,
+
+    /// Largest cross-pool boost (bps) a position can earn from its
+    /// owner's weight in `boost_source_pool`
+    pub max_cross_boost_bps: u64,
+
     /// Reserved space for future upgrades
+    #[cfg_attr(feature = "serde", serde(skip, default = "reserved_bytes"))]
     pub _reserved: [u8; 64],
 }
 
@@ -109,42 +351,541 @@ impl StakePool {
         8 +   // max_lock_duration
         1 +   // paused
         1 +   // bump
+        1 +   // is_native
+        1 +   // shared_vault
+        1 +   // hooks_enabled
+        8 +   // tier_activation_delay
+        8 +   // tier_grace_period
+        8 +   // claim_window_anchor
+        8 +   // claim_window_period
+        8 +   // claim_window_length
+        8 +   // claim_blackout_start
+        8 +   // claim_blackout_end
+        32 +  // credential_mint
+        8 +   // min_stake_amount
+        8 +   // pool_id
+        8 +   // snapshot_count
+        1 +   // reward_stream_count
+        8 +   // early_unstake_penalty_bps
+        8 +   // streak_bonus_bps_per_month
+        8 +   // max_streak_bonus_bps
+        8 +   // penalty_burn_bps
+        8 +   // penalty_treasury_bps
+        8 +   // penalty_insurance_bps
+        8 +   // reward_epoch_duration
+        8 +   // reward_epoch
+        8 +   // action_cooldown
+        8 +   // vault_balance
+        8 +   // reward_liability
+        8 +   // distributable_rewards
+        8 +   // protocol_fee_bps
+        8 +   // protocol_fees_collected
+        RateChange::LEN * StakePool::MAX_RATE_CHANGES + // rate_changes
+        1 +   // rate_change_count
+        8 +   // early_adopter_slots
+        8 +   // early_adopter_bonus_bps
+        1 +   // version
+        1 +   // allow_unstake_when_paused
+        8 +   // target_apr_bps
+        8 +   // last_apr_retarget
+        8 +   // fixed_apr_bps
+        8 +   // fixed_reward_reserved
+        8 +   // min_claim_interval
+        8 +   // claim_tax_start_bps
+        8 +   // claim_tax_decay_period
+        8 * 3 + // tier_min_lock_durations
+        8 * 3 + // tier_thresholds
+        1 +   // stake_mint_decimals
+        1 +   // reward_mint_decimals
+        32 +  // boost_source_pool
+        8 +   // max_cross_boost_bps
         64;   // _reserved
 
     pub const SEED_PREFIX: &'static [u8] = b"stake_pool";
+
+    /// Pool id seed of a pool's PDA. Pool 0 uses an empty seed, so the first
+    /// pool of each mint keeps the original `[SEED_PREFIX, stake_mint]`
+    /// address that pools created before pool ids still live at.
+    pub fn pool_id_seed(pool_id: u64) -> Vec<u8> {
+        if pool_id == 0 {
+            Vec::new()
+        } else {
+            pool_id.to_le_bytes().to_vec()
+        }
+    }
+
+    /// This pool's pool id seed
+    pub fn id_seed(&self) -> Vec<u8> {
+        Self::pool_id_seed(self.pool_id)
+    }
+
+    /// Layout version written by `initialize` and `migrate_pool_v1_to_v2`
+    pub const CURRENT_VERSION: u8 = 2;
+
+    /// Fill fields added since layout v1 with values consistent with the
+    /// pool's existing accounting, then mark it current. Fields read as
+    /// zero from the old reserved space otherwise keep their zero default.
+    ///
+    /// The accumulator moves from the v1 scale to `PRECISION`. Positions
+    /// need no rescale: `reward_debt` is kept in token units.
+    pub fn migrate_to_v2(&mut self) -> Result<()> {
+        self.accumulated_reward_per_share = self.accumulated_reward_per_share
+            .checked_mul(constants::PRECISION / constants::LEGACY_PRECISION)
+            .ok_or(StakingError::MathOverflow)?;
+        self.vault_balance = self.vault_balance.max(self.total_staked);
+        // Legacy pools never blocked exits while paused
+        self.allow_unstake_when_paused = true;
+        self.version = Self::CURRENT_VERSION;
+        Ok(())
+    }
+
+    /// Seconds between APR retargets: one reward epoch, or a day for
+    /// per-second pools
+    pub fn apr_retarget_interval(&self) -> i64 {
+        if self.uses_reward_epochs() {
+            self.reward_epoch_duration
+        } else {
+            constants::APR_RETARGET_INTERVAL
+        }
+    }
+
+    /// Whether the pool is paused, by its own flag or protocol-wide
+    pub fn is_paused(&self, protocol_paused: bool) -> bool {
+        self.paused || protocol_paused
+    }
+
+    /// Whether unstaking is open under the pool's pause policy, which also
+    /// governs a protocol-wide pause
+    pub fn unstake_allowed(&self, protocol_paused: bool) -> bool {
+        !self.is_paused(protocol_paused) || self.allow_unstake_when_paused
+    }
+
+    /// Most reward rate changes that can be pending at once
+    pub const MAX_RATE_CHANGES: usize = 4;
+
+    /// Queue a rate change, keeping the queue ordered by effective time
+    pub fn queue_rate_change(&mut self, change: RateChange) -> Result<()> {
+        let count = self.rate_change_count as usize;
+        require!(count < Self::MAX_RATE_CHANGES, StakingError::RateChangeQueueFull);
+
+        let position = self.rate_changes[..count]
+            .iter()
+            .position(|queued| queued.effective_time > change.effective_time)
+            .unwrap_or(count);
+        self.rate_changes.copy_within(position..count, position + 1);
+        self.rate_changes[position] = change;
+        self.rate_change_count += 1;
+        Ok(())
+    }
+
+    /// The earliest pending rate change if it takes effect by `until`
+    pub fn due_rate_change(&self, until: i64) -> Option<RateChange> {
+        let next = self.rate_changes[0];
+        (self.rate_change_count > 0 && next.effective_time <= until).then_some(next)
+    }
+
+    /// Switch to the earliest pending rate and drop it from the queue
+    pub fn apply_next_rate_change(&mut self) {
+        let count = self.rate_change_count as usize;
+        if count == 0 {
+            return;
+        }
+        self.reward_rate = self.rate_changes[0].rate;
+        self.rate_changes.copy_within(1..count, 0);
+        self.rate_changes[count - 1] = RateChange::default();
+        self.rate_change_count -= 1;
+    }
+
+    /// Claim fee (bps) for a holder of `tier`: the protocol fee scaled by
+    /// the tier's platform fee relative to the untiered fee, so higher tiers
+    /// pay less and VIPs pay nothing
+    pub fn claim_fee_bps(&self, tier: StakingTier) -> u64 {
+        self.protocol_fee_bps
+            .saturating_mul(tier.platform_fee_bps() as u64)
+            / StakingTier::None.platform_fee_bps() as u64
+    }
+
+    /// Claim tax (bps) for a position opened at `opened_at`: the starting
+    /// tax scaled by the share of the decay period still to run. Positions
+    /// opened before the tax existed (`opened_at == 0`) pay none.
+    pub fn claim_tax_bps(&self, opened_at: i64, now: i64) -> u64 {
+        if self.claim_tax_start_bps == 0 || self.claim_tax_decay_period <= 0 || opened_at == 0 {
+            return 0;
+        }
+        let remaining = self.claim_tax_decay_period.saturating_sub(now.saturating_sub(opened_at));
+        if remaining <= 0 {
+            return 0;
+        }
+        (self.claim_tax_start_bps as u128 * remaining as u128 / self.claim_tax_decay_period as u128) as u64
+    }
+
+    /// Hand `amount` of reward tokens already in the reward vault to every
+    /// staker except `excluded_weight` (the claimant) by raising the
+    /// accumulator over the remaining weight
+    ///
+    /// # Returns
+    /// * `Result<bool>` - Whether anyone else was staked to receive it
+    pub fn distribute_to_stakers(&mut self, amount: u64, excluded_weight: u64) -> Result<bool> {
+        let recipients_weight = self.total_weighted_stake.saturating_sub(excluded_weight);
+        if amount == 0 || recipients_weight == 0 {
+            return Ok(false);
+        }
+        let increase = math::mul_div_u128(
+            amount as u128,
+            constants::PRECISION,
+            recipients_weight as u128,
+        )?;
+        self.accumulated_reward_per_share = self.accumulated_reward_per_share
+            .checked_add(increase)
+            .ok_or(StakingError::MathOverflow)?;
+        self.reward_liability = self.reward_liability.saturating_add(amount);
+        Ok(true)
+    }
+
+    /// Whether stakers must hold the pool's credential token
+    pub fn requires_credential(&self) -> bool {
+        self.credential_mint != Pubkey::default()
+    }
+
+    /// Whether claims are open at `now`
+    /// Claims must fall inside the periodic claim window (if configured)
+    /// and outside the blackout period.
+    pub fn is_claim_window_open(&self, now: i64) -> bool {
+        if now >= self.claim_blackout_start && now < self.claim_blackout_end {
+            return false;
+        }
+
+        if self.claim_window_period > 0 {
+            if now < self.claim_window_anchor {
+                return false;
+            }
+            let offset = (now - self.claim_window_anchor) % self.claim_window_period;
+            return offset < self.claim_window_length;
+        }
+
+        true
+    }
+
+    /// Reward tokens available for payout given the reward vault balance.
+    /// For single-vault pools the staked principal is excluded so rewards
+    /// can never be paid out of user deposits.
+    pub fn available_rewards(&self, reward_vault_balance: u64) -> u64 {
+        if self.shared_vault {
+            reward_vault_balance.saturating_sub(self.total_staked)
+        } else {
+            reward_vault_balance
+        }
+    }
+
+    /// Reward tokens not yet promised to anyone: the available balance less
+    /// emitted-but-unpaid rewards and fixed-rate reservations
+    pub fn unreserved_rewards(&self, reward_vault_balance: u64) -> u64 {
+        self.available_rewards(reward_vault_balance)
+            .saturating_sub(self.reward_liability)
+            .saturating_sub(self.fixed_reward_reserved)
+    }
+
+    /// Cancel a position's owed rewards, releasing them from the pool's
+    /// liability so they stay in the reward vault for other stakers
+    ///
+    /// # Returns
+    /// * `u64` - Amount cancelled
+    pub fn cancel_owed_rewards(&mut self, user_stake: &mut UserStake) -> u64 {
+        let amount = user_stake.owed_rewards;
+        user_stake.owed_rewards = 0;
+        // Pools created before liability tracking may under-count it
+        self.reward_liability = self.reward_liability.saturating_sub(amount);
+        if user_stake.fixed_apr_bps > 0 {
+            self.fixed_reward_reserved = self.fixed_reward_reserved.saturating_sub(amount);
+        }
+        amount
+    }
+
+    /// Streak bonus (bps) for a position staked without interruption since
+    /// `unbroken_since` (0 = no streak)
+    pub fn streak_bonus_bps(&self, unbroken_since: i64, now: i64) -> u64 {
+        if unbroken_since == 0 || now <= unbroken_since {
+            return 0;
+        }
+        let months = ((now - unbroken_since) / constants::SECONDS_PER_MONTH) as u64;
+        months
+            .saturating_mul(self.streak_bonus_bps_per_month)
+            .min(self.max_streak_bonus_bps)
+    }
+
+    /// Full reward multiplier (bps) a position should carry at `now`:
+    /// tier, partner boost, streak bonus and cross-pool boost
+    pub fn position_multiplier_bps(&self, user_stake: &UserStake, now: i64) -> u64 {
+        user_stake
+            .reward_multiplier_bps(now)
+            .saturating_add(self.streak_bonus_bps(user_stake.unbroken_since, now))
+            .saturating_add(self.cross_boost_bps(user_stake))
+    }
+
+    /// Boost (bps) a position earns from its owner's last refreshed weight
+    /// in the boost source pool, against its current share of this pool.
+    /// Snapshots taken against a previous source pool earn nothing.
+    pub fn cross_boost_bps(&self, user_stake: &UserStake) -> u64 {
+        if self.boost_source_pool == Pubkey::default()
+            || user_stake.boost_source_pool != self.boost_source_pool
+        {
+            return 0;
+        }
+        staking_math::cross_pool_boost_bps(
+            user_stake.boost_source_weight,
+            user_stake.boost_source_total_weight,
+            user_stake.staked_amount,
+            self.total_staked,
+            self.max_cross_boost_bps,
+        )
+        .unwrap_or(0)
+    }
+
+    /// Tokens in the stake vault beyond what pool accounting expects.
+    /// Principal is always protected, even for pools created before
+    /// `vault_balance` was tracked.
+    pub fn vault_surplus(&self, stake_vault_balance: u64) -> u64 {
+        stake_vault_balance.saturating_sub(self.expected_stake_vault_balance())
+    }
+
+    /// Add `elapsed` seconds of emission to the outstanding reward liability.
+    /// Nothing is emitted while the pool is empty.
+    pub fn record_emission(&mut self, elapsed: i64) {
+        if self.total_weighted_stake == 0 || elapsed <= 0 {
+            return;
+        }
+        let emitted = (elapsed as u64).saturating_mul(self.reward_rate);
+        self.reward_liability = self.reward_liability.saturating_add(emitted);
+    }
+
+    /// Principal the stake vault must hold
+    pub fn expected_stake_vault_balance(&self) -> u64 {
+        self.vault_balance.max(self.total_staked)
+    }
+
+    /// Check pool-level accounting invariants against the stake vault balance
+    ///
+    /// # Returns
+    /// * `std::result::Result<(), &'static str>` - The first violated invariant
+    pub fn check_invariants(&self, stake_vault_balance: u64) -> std::result::Result<(), &'static str> {
+        if self.total_weighted_stake < self.total_staked {
+            return Err("total_weighted_stake < total_staked");
+        }
+        if stake_vault_balance < self.total_staked {
+            return Err("stake_vault.amount < total_staked");
+        }
+        if self.total_staked == 0 && self.total_weighted_stake != 0 {
+            return Err("weight left in an empty pool");
+        }
+        Ok(())
+    }
+
+    /// Whether rewards are distributed per epoch rather than per second
+    pub fn uses_reward_epochs(&self) -> bool {
+        self.reward_epoch_duration > 0
+    }
+
+    /// Whether `mint` is the pool's stake or reward mint
+    pub fn is_pool_mint(&self, mint: &Pubkey) -> bool {
+        *mint == self.stake_mint || *mint == self.reward_mint
+    }
 }
 
-/// Individual user stake account
+/// Global treasury collecting protocol fees (lamports held on the PDA)
 #[account]
 #[derive(Default)]
-pub struct UserStake {
-    /// Owner of this stake
-    pub owner: Pubkey,
+pub struct ProtocolTreasury {
+    /// Admin allowed to change protocol fees
+    pub admin: Pubkey,
 
-    /// The stake pool this belongs to
+    /// SOL fee (in lamports) charged to create a stake pool
+    pub pool_creation_fee: u64,
+
+    /// Total lamports collected in fees
+    pub total_fees_collected: u64,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+
+    /// Reserved space for future upgrades
+    pub _reserved: [u8; 32],
+}
+
+impl ProtocolTreasury {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // admin
+        8 +   // pool_creation_fee
+        8 +   // total_fees_collected
+        1 +   // bump
+        32;   // _reserved
+
+    pub const SEED_PREFIX: &'static [u8] = b"protocol_treasury";
+}
+
+/// Global program configuration, created once and shared by every pool so
+/// platform-wide parameters live in one place
+#[account]
+#[derive(Default)]
+pub struct ProgramConfig {
+    /// Protocol admin allowed to update the config
+    pub admin: Pubkey,
+
+    /// Default treasury receiving protocol fees
+    pub fee_treasury: Pubkey,
+
+    /// Default tier thresholds (Holder, Premium, Vip) in stake token units
+    pub tier_thresholds: [u64; 3],
+
+    /// SOL fee (in lamports) charged to create a stake pool
+    pub pool_creation_fee: u64,
+
+    /// Platform feature switches (`ProgramConfig::FEATURE_*` bits)
+    pub feature_flags: u64,
+
+    /// Low-privilege incident key that may pause any pool, and nothing
+    /// else (default = no guardian)
+    pub guardian: Pubkey,
+
+    /// Lending programs allowed to lock positions as collateral (unused
+    /// slots are `Pubkey::default()`)
+    pub lending_programs: [Pubkey; ProgramConfig::MAX_LENDING_PROGRAMS],
+
+    /// Protocol-wide pause, applied on top of every pool's own flag
+    pub paused: bool,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+
+    /// Reserved space for future upgrades
+    pub _reserved: [u8; 32],
+}
+
+impl ProgramConfig {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // admin
+        32 +  // fee_treasury
+        8 * 3 + // tier_thresholds
+        8 +   // pool_creation_fee
+        8 +   // feature_flags
+        32 +  // guardian
+        32 * ProgramConfig::MAX_LENDING_PROGRAMS + // lending_programs
+        1 +   // paused
+        1 +   // bump
+        32;   // _reserved
+
+    pub const SEED_PREFIX: &'static [u8] = b"program_config";
+
+    /// Lending programs that can be whitelisted at once
+    pub const MAX_LENDING_PROGRAMS: usize = 4;
+
+    /// New pools may be created
+    pub const FEATURE_POOL_CREATION: u64 = 1 << 0;
+    /// New pools may stake native SOL
+    pub const FEATURE_NATIVE_POOLS: u64 = 1 << 1;
+    /// Every feature above
+    pub const ALL_FEATURES: u64 = Self::FEATURE_POOL_CREATION | Self::FEATURE_NATIVE_POOLS;
+
+    /// The built-in tier thresholds
+    pub const DEFAULT_TIER_THRESHOLDS: [u64; 3] = [
+        constants::HOLDER_THRESHOLD,
+        constants::PREMIUM_THRESHOLD,
+        constants::VIP_THRESHOLD,
+    ];
+
+    /// Whether every bit of `feature` is switched on
+    pub fn is_enabled(&self, feature: u64) -> bool {
+        self.feature_flags & feature == feature
+    }
+
+    /// Whether `key` is the configured guardian
+    pub fn is_guardian(&self, key: &Pubkey) -> bool {
+        self.guardian != Pubkey::default() && self.guardian == *key
+    }
+
+    /// Whether the protocol is paused, given the program config PDA's
+    /// account. Deployments that never created the config can't be paused
+    /// globally. The caller must have checked the account's address.
+    pub fn protocol_paused(config_info: &AccountInfo) -> Result<bool> {
+        if config_info.data_is_empty() {
+            return Ok(false);
+        }
+        let config = ProgramConfig::try_deserialize(&mut &config_info.try_borrow_data()?[..])?;
+        Ok(config.paused)
+    }
+
+    /// Whether `program` is a whitelisted lending program
+    pub fn is_lending_program(&self, program: &Pubkey) -> bool {
+        *program != Pubkey::default() && self.lending_programs.contains(program)
+    }
+
+    /// Reject unknown feature bits and non-increasing tier thresholds
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.feature_flags & !Self::ALL_FEATURES == 0,
+            StakingError::InvalidConfig
+        );
+        let [holder, premium, vip] = self.tier_thresholds;
+        require!(
+            holder > 0 && holder < premium && premium < vip,
+            StakingError::InvalidConfig
+        );
+        Ok(())
+    }
+}
+
+/// Per-pool insurance fund, seeded by a share of early unstake penalties and
+/// used to top up the reward vault when claims would otherwise be short
+#[account]
+#[derive(Default)]
+pub struct InsuranceFund {
+    /// The stake pool this fund insures
     pub stake_pool: Pubkey,
 
-    /// Amount of tokens staked (raw, not weighted)
-    pub staked_amount: u64,
+    /// Token account (stake mint) holding the fund
+    pub vault: Pubkey,
 
-    /// Weighted stake amount (includes lock duration multiplier)
-    pub weighted_stake: u64,
+    /// Total tokens paid into the fund
+    pub total_deposited: u64,
 
-    /// Unix timestamp when lock period ends
-    pub lock_end_time: i64,
+    /// Total tokens released to cover reward shortfalls
+    pub total_covered: u64,
 
-    /// Lock duration chosen (for recalculating on additional stakes)
-    pub lock_duration: i64,
+    /// Bump seed for PDA derivation
+    pub bump: u8,
 
-    /// Reward debt for calculating pending rewards
-    /// reward_debt = weighted_stake * accumulated_reward_per_share at time of stake
-    pub reward_debt: u128,
+    /// Reserved space for future upgrades
+    pub _reserved: [u8; 32],
+}
 
-    /// Total rewards claimed by this user
-    pub total_claimed: u64,
+impl InsuranceFund {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // stake_pool
+        32 +  // vault
+        8 +   // total_deposited
+        8 +   // total_covered
+        1 +   // bump
+        32;   // _reserved
 
-    /// Timestamp of first stake
-    pub stake_start_time: i64,
+    pub const SEED_PREFIX: &'static [u8] = b"insurance_fund";
+    pub const VAULT_SEED_PREFIX: &'static [u8] = b"insurance_vault";
+}
+
+/// Per-pool vault of one-time bonuses paid when a position's unbroken
+/// streak reaches each of `constants::MILESTONE_DURATIONS`
+#[account]
+#[derive(Default)]
+pub struct MilestoneBonus {
+    /// The stake pool paying the bonuses
+    pub stake_pool: Pubkey,
+
+    /// Token account (reward mint) holding the bonus funds
+    pub vault: Pubkey,
+
+    /// Bonus paid for each milestone, in reward tokens
+    pub bonus_amounts: [u64; 3],
+
+    /// Total bonuses paid out
+    pub total_paid: u64,
 
     /// Bump seed for PDA derivation
     pub bump: u8,
@@ -153,19 +894,1499 @@ pub struct UserStake {
     pub _reserved: [u8; 32],
 }
 
-impl UserStake {
+impl MilestoneBonus {
     pub const LEN: usize = 8 +  // discriminator
-        32 +  // owner
         32 +  // stake_pool
-        8 +   // staked_amount
-        8 +   // weighted_stake
-        8 +   // lock_end_time
-        8 +   // lock_duration
-        16 +  // reward_debt
-        8 +   // total_claimed
-        8 +   // stake_start_time
+        32 +  // vault
+        8 * 3 + // bonus_amounts
+        8 +   // total_paid
         1 +   // bump
         32;   // _reserved
 
-    pub const SEED_PREFIX: &'static [u8] = b"user_stake";
+    pub const SEED_PREFIX: &'static [u8] = b"milestone_bonus";
+    pub const VAULT_SEED_PREFIX: &'static [u8] = b"milestone_vault";
+
+    /// Total bonus for a set of milestone bitflags
+    pub fn bonus_for(&self, milestones: u8) -> Option<u64> {
+        self.bonus_amounts
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| milestones & (1 << i) != 0)
+            .try_fold(0u64, |total, (_, amount)| total.checked_add(*amount))
+    }
+}
+
+/// A pool's fixed-term certificate product: the terms offered to new
+/// certificates and the vaults escrowing their principal and payouts
+#[account]
+#[derive(Default)]
+pub struct CertificateTerms {
+    /// The stake pool offering certificates
+    pub stake_pool: Pubkey,
+
+    /// Token account (stake mint) escrowing certificate principal
+    pub principal_vault: Pubkey,
+
+    /// Token account (reward mint) escrowing certificate payouts
+    pub payout_vault: Pubkey,
+
+    /// APR (bps) fixed into each new certificate (0 = issuance closed)
+    pub apr_bps: u64,
+
+    /// Shortest term (seconds) a certificate may be issued for
+    pub min_term: i64,
+
+    /// Longest term (seconds) a certificate may be issued for
+    pub max_term: i64,
+
+    /// Principal held for certificates not yet redeemed
+    pub outstanding_principal: u64,
+
+    /// Payouts held for certificates not yet redeemed
+    pub outstanding_payout: u64,
+
+    /// Total certificates ever issued
+    pub certificates_issued: u64,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+
+    /// Reserved space for future upgrades
+    pub _reserved: [u8; 32],
+}
+
+impl CertificateTerms {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // stake_pool
+        32 +  // principal_vault
+        32 +  // payout_vault
+        8 +   // apr_bps
+        8 +   // min_term
+        8 +   // max_term
+        8 +   // outstanding_principal
+        8 +   // outstanding_payout
+        8 +   // certificates_issued
+        1 +   // bump
+        32;   // _reserved
+
+    pub const SEED_PREFIX: &'static [u8] = b"certificate_terms";
+    pub const PRINCIPAL_VAULT_SEED_PREFIX: &'static [u8] = b"certificate_principal";
+    pub const PAYOUT_VAULT_SEED_PREFIX: &'static [u8] = b"certificate_payout";
+
+    /// Whether `term` is one new certificates may be issued for
+    pub fn accepts_term(&self, term: i64) -> bool {
+        term >= self.min_term && term <= self.max_term
+    }
+
+    /// Payout fixed into a certificate for `principal` over `term` seconds
+    pub fn payout_for(&self, principal: u64, term: i64) -> Option<u64> {
+        staking_math::fixed_rate_rewards(principal, self.apr_bps, u64::try_from(term).ok()?)
+    }
+}
+
+/// A fixed-term certificate: principal and payout are escrowed at issue and
+/// released together at maturity, with nothing accruing in between
+#[account]
+#[derive(Default)]
+pub struct Certificate {
+    /// Certificate holder
+    pub owner: Pubkey,
+
+    /// The stake pool that issued it
+    pub stake_pool: Pubkey,
+
+    /// Holder-chosen id, unique per holder and pool
+    pub certificate_id: u64,
+
+    /// Stake tokens escrowed
+    pub principal: u64,
+
+    /// Reward tokens paid at maturity
+    pub payout: u64,
+
+    /// APR (bps) the payout was computed at
+    pub apr_bps: u64,
+
+    /// Unix timestamp the certificate was issued
+    pub issued_at: i64,
+
+    /// Unix timestamp from which it can be redeemed
+    pub maturity_time: i64,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl Certificate {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // owner
+        32 +  // stake_pool
+        8 +   // certificate_id
+        8 +   // principal
+        8 +   // payout
+        8 +   // apr_bps
+        8 +   // issued_at
+        8 +   // maturity_time
+        1;    // bump
+
+    pub const SEED_PREFIX: &'static [u8] = b"certificate";
+
+    /// Whether the certificate can be redeemed at `now`
+    pub fn is_mature(&self, now: i64) -> bool {
+        now >= self.maturity_time
+    }
+}
+
+/// Launchpad benefits granted to one staking tier
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct TierBenefit {
+    /// Sale allocation multiplier (bps, 10000 = 1x)
+    pub allocation_multiplier_bps: u64,
+
+    /// Discount off the launchpad fee (bps)
+    pub fee_discount_bps: u16,
+
+    /// Whether the tier skips the sale participation cooldown
+    pub cooldown_exempt: bool,
+}
+
+impl TierBenefit {
+    pub const LEN: usize = 8 + 2 + 1;
+
+    /// Reject out-of-range values
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.allocation_multiplier_bps > 0
+                && self.allocation_multiplier_bps <= Self::MAX_ALLOCATION_MULTIPLIER_BPS,
+            StakingError::InvalidConfig
+        );
+        require!(
+            self.fee_discount_bps as u64 <= constants::BPS_DENOMINATOR,
+            StakingError::InvalidConfig
+        );
+        Ok(())
+    }
+
+    /// Upper bound on the allocation multiplier (10x)
+    pub const MAX_ALLOCATION_MULTIPLIER_BPS: u64 = 100_000;
+}
+
+/// Canonical tier benefit table read by the launchpad sale program
+#[account]
+#[derive(Default)]
+pub struct TierBenefits {
+    /// Governance authority allowed to edit the table
+    pub governance: Pubkey,
+
+    /// Benefits indexed by `StakingTier` (None, Holder, Premium, Vip)
+    pub benefits: [TierBenefit; 4],
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+
+    /// Reserved space for future upgrades
+    pub _reserved: [u8; 32],
+}
+
+impl TierBenefits {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // governance
+        TierBenefit::LEN * 4 + // benefits
+        1 +   // bump
+        32;   // _reserved
+
+    pub const SEED_PREFIX: &'static [u8] = b"tier_benefits";
+
+    /// Benefits for `tier`
+    pub fn for_tier(&self, tier: StakingTier) -> &TierBenefit {
+        &self.benefits[tier as usize]
+    }
+
+    /// Allocation multipliers in the layout `staking_math::SaleConfig` expects
+    pub fn allocation_multipliers_bps(&self) -> [u64; 4] {
+        self.benefits.map(|benefit| benefit.allocation_multiplier_bps)
+    }
+}
+
+/// Global counter of pools created, used to index `RegistryEntry` accounts
+#[account]
+#[derive(Default)]
+pub struct PoolRegistry {
+    /// Number of pools registered (next registry index)
+    pub pool_count: u64,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl PoolRegistry {
+    pub const LEN: usize = 8 +  // discriminator
+        8 +   // pool_count
+        1;    // bump
+
+    pub const SEED_PREFIX: &'static [u8] = b"pool_registry";
+}
+
+/// One pool's entry in the registry, addressable by its index so frontends
+/// can page through pools (0..pool_count) without getProgramAccounts
+#[account]
+#[derive(Default)]
+pub struct RegistryEntry {
+    /// Position in the registry
+    pub index: u64,
+
+    /// The registered stake pool
+    pub stake_pool: Pubkey,
+
+    /// The pool's stake mint
+    pub stake_mint: Pubkey,
+
+    /// The pool's reward mint
+    pub reward_mint: Pubkey,
+
+    /// The pool's authority at creation
+    pub authority: Pubkey,
+
+    /// Creation timestamp
+    pub created_at: i64,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl RegistryEntry {
+    pub const LEN: usize = 8 +  // discriminator
+        8 +   // index
+        32 +  // stake_pool
+        32 +  // stake_mint
+        32 +  // reward_mint
+        32 +  // authority
+        8 +   // created_at
+        1;    // bump
+
+    pub const SEED_PREFIX: &'static [u8] = b"registry_entry";
+}
+
+/// Human-readable pool info for wallets and aggregators
+/// Strings are UTF-8, zero-padded to the fixed field length.
+#[account]
+pub struct PoolMetadata {
+    /// The stake pool this metadata describes
+    pub stake_pool: Pubkey,
+
+    /// Display name
+    pub name: [u8; 32],
+
+    /// Ticker-style symbol
+    pub symbol: [u8; 10],
+
+    /// Off-chain metadata URI (JSON, image, etc.)
+    pub uri: [u8; 128],
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl PoolMetadata {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // stake_pool
+        32 +  // name
+        10 +  // symbol
+        128 + // uri
+        1;    // bump
+
+    pub const SEED_PREFIX: &'static [u8] = b"pool_metadata";
+
+    /// Overwrite the metadata strings, rejecting any that don't fit
+    pub fn set(&mut self, name: &str, symbol: &str, uri: &str) -> Result<()> {
+        self.name = fixed_bytes(name)?;
+        self.symbol = fixed_bytes(symbol)?;
+        self.uri = fixed_bytes(uri)?;
+        Ok(())
+    }
+}
+
+/// Copy a string into a zero-padded fixed-size buffer
+pub fn fixed_bytes<const N: usize>(value: &str) -> Result<[u8; N]> {
+    require!(value.len() <= N, StakingError::MetadataTooLong);
+    let mut out = [0u8; N];
+    out[..value.len()].copy_from_slice(value.as_bytes());
+    Ok(out)
+}
+
+/// A partner token that earns a reward boost when locked
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct PartnerBoostConfig {
+    /// Partner token mint (default = empty slot)
+    pub partner_mint: Pubkey,
+
+    /// Minimum partner tokens locked to qualify
+    pub min_amount: u64,
+
+    /// Extra reward multiplier granted (bps)
+    pub bonus_bps: u64,
+}
+
+impl PartnerBoostConfig {
+    pub const LEN: usize = 32 +  // partner_mint
+        8 +   // min_amount
+        8;    // bonus_bps
+}
+
+/// Authority-managed table of partner token boosts for a pool
+#[account]
+#[derive(Default)]
+pub struct BoostTable {
+    /// The stake pool this table belongs to
+    pub stake_pool: Pubkey,
+
+    /// Boost entries
+    pub entries: [PartnerBoostConfig; BoostTable::MAX_ENTRIES],
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl BoostTable {
+    pub const MAX_ENTRIES: usize = 4;
+
+    /// Largest bonus a partner token can grant (0.5x)
+    pub const MAX_BONUS_BPS: u64 = 5000;
+
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // stake_pool
+        PartnerBoostConfig::LEN * BoostTable::MAX_ENTRIES + // entries
+        1;    // bump
+
+    pub const SEED_PREFIX: &'static [u8] = b"boost_table";
+
+    /// Configured entry for `partner_mint`
+    pub fn find(&self, partner_mint: &Pubkey) -> Option<&PartnerBoostConfig> {
+        self.entries
+            .iter()
+            .find(|entry| entry.partner_mint != Pubkey::default() && entry.partner_mint == *partner_mint)
+    }
+
+    /// Bonus earned by locking `amount` of `partner_mint`
+    pub fn bonus_for(&self, partner_mint: &Pubkey, amount: u64) -> u64 {
+        match self.find(partner_mint) {
+            Some(entry) if amount >= entry.min_amount => entry.bonus_bps,
+            _ => 0,
+        }
+    }
+}
+
+/// A user's partner tokens held in escrow for a boost
+#[account]
+#[derive(Default)]
+pub struct PartnerEscrow {
+    /// Owner of the escrowed tokens
+    pub owner: Pubkey,
+
+    /// The stake pool being boosted
+    pub stake_pool: Pubkey,
+
+    /// Partner token mint currently escrowed (default = none)
+    pub partner_mint: Pubkey,
+
+    /// Amount of partner tokens escrowed
+    pub amount: u64,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl PartnerEscrow {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // owner
+        32 +  // stake_pool
+        32 +  // partner_mint
+        8 +   // amount
+        1;    // bump
+
+    pub const SEED_PREFIX: &'static [u8] = b"partner_escrow";
+}
+
+/// Maximum number of extra reward tokens a pool can emit
+pub const MAX_REWARD_STREAMS: usize = 3;
+
+/// An extra reward token emitted alongside the pool's main reward
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct RewardStream {
+    /// Unique id of the stream within the pool (0 = empty slot)
+    pub stream_id: u64,
+
+    /// Reward token mint
+    pub mint: Pubkey,
+
+    /// Vault holding the stream's reward tokens
+    pub vault: Pubkey,
+
+    /// Reward tokens emitted per second across all weighted stake
+    pub reward_rate: u64,
+
+    /// Accumulated reward per weighted share (scaled by PRECISION)
+    pub accumulated_reward_per_share: u128,
+
+    /// Last time the accumulator was updated
+    pub last_update_time: i64,
+
+    /// Whether the stream is still emitting
+    pub active: bool,
+}
+
+impl RewardStream {
+    pub const LEN: usize = 8 +  // stream_id
+        32 +  // mint
+        32 +  // vault
+        8 +   // reward_rate
+        16 +  // accumulated_reward_per_share
+        8 +   // last_update_time
+        1;    // active
+
+    /// Whether the slot holds a stream (active or stopped)
+    pub fn is_occupied(&self) -> bool {
+        self.stream_id != 0
+    }
+
+    /// Advance the accumulator to `now` given the pool's total weight
+    pub fn update(&mut self, total_weighted_stake: u64, now: i64) -> Result<()> {
+        if !self.active || total_weighted_stake == 0 || now <= self.last_update_time {
+            self.last_update_time = self.last_update_time.max(now);
+            return Ok(());
+        }
+
+        let elapsed = (now - self.last_update_time) as u128;
+        let new_rewards = elapsed
+            .checked_mul(self.reward_rate as u128)
+            .ok_or(StakingError::MathOverflow)?;
+        let increase = math::mul_div_u128(
+            new_rewards,
+            constants::PRECISION,
+            total_weighted_stake as u128,
+        )?;
+
+        self.accumulated_reward_per_share = self.accumulated_reward_per_share
+            .checked_add(increase)
+            .ok_or(StakingError::MathOverflow)?;
+        self.last_update_time = now;
+
+        Ok(())
+    }
+}
+
+/// Extra reward tokens configured for a pool
+#[account]
+#[derive(Default)]
+pub struct RewardStreams {
+    /// The stake pool these streams belong to
+    pub stake_pool: Pubkey,
+
+    /// Stream slots
+    pub streams: [RewardStream; MAX_REWARD_STREAMS],
+
+    /// Id assigned to the next stream added
+    pub next_stream_id: u64,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl RewardStreams {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // stake_pool
+        RewardStream::LEN * MAX_REWARD_STREAMS + // streams
+        8 +   // next_stream_id
+        1;    // bump
+
+    pub const SEED_PREFIX: &'static [u8] = b"reward_streams";
+
+    /// Whether any occupied stream slot pays out `mint`
+    pub fn has_mint(&self, mint: &Pubkey) -> bool {
+        self.streams
+            .iter()
+            .any(|stream| stream.is_occupied() && stream.mint == *mint)
+    }
+
+    /// Advance every stream to `now`
+    pub fn update_all(&mut self, total_weighted_stake: u64, now: i64) -> Result<()> {
+        for stream in self.streams.iter_mut().filter(|stream| stream.is_occupied()) {
+            stream.update(total_weighted_stake, now)?;
+        }
+        Ok(())
+    }
+}
+
+/// A user's accrual position in one reward stream
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct UserStreamEntry {
+    /// Stream this entry tracks (mismatch with the slot means stale)
+    pub stream_id: u64,
+
+    /// Reward debt against the stream's accumulator
+    pub reward_debt: u128,
+
+    /// Settled, unclaimed stream rewards
+    pub owed_rewards: u64,
+}
+
+impl UserStreamEntry {
+    pub const LEN: usize = 8 +  // stream_id
+        16 +  // reward_debt
+        8;    // owed_rewards
+}
+
+/// A user's positions across a pool's extra reward streams
+#[account]
+#[derive(Default)]
+pub struct UserRewardStreams {
+    /// Owner of the stake
+    pub owner: Pubkey,
+
+    /// The stake pool
+    pub stake_pool: Pubkey,
+
+    /// One entry per stream slot
+    pub entries: [UserStreamEntry; MAX_REWARD_STREAMS],
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl UserRewardStreams {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // owner
+        32 +  // stake_pool
+        UserStreamEntry::LEN * MAX_REWARD_STREAMS + // entries
+        1;    // bump
+
+    pub const SEED_PREFIX: &'static [u8] = b"user_reward_streams";
+
+    /// Move rewards earned at `weighted_stake` into `owed_rewards`.
+    /// Entries left over from a replaced stream are reset first.
+    pub fn settle(&mut self, streams: &RewardStreams, weighted_stake: u64) -> Result<()> {
+        for (entry, stream) in self.entries.iter_mut().zip(streams.streams.iter()) {
+            if entry.stream_id != stream.stream_id {
+                *entry = UserStreamEntry {
+                    stream_id: stream.stream_id,
+                    ..UserStreamEntry::default()
+                };
+            }
+            if !stream.is_occupied() {
+                continue;
+            }
+
+            let accrued = calculate_reward_debt(
+                weighted_stake,
+                stream.accumulated_reward_per_share,
+            )?;
+            let pending = math::to_u64(accrued.saturating_sub(entry.reward_debt))?;
+            entry.owed_rewards = entry.owed_rewards
+                .checked_add(pending)
+                .ok_or(StakingError::MathOverflow)?;
+            entry.reward_debt = accrued;
+        }
+        Ok(())
+    }
+
+    /// Re-base reward debts after the user's weight changed
+    pub fn reset_debts(&mut self, streams: &RewardStreams, weighted_stake: u64) -> Result<()> {
+        for (entry, stream) in self.entries.iter_mut().zip(streams.streams.iter()) {
+            entry.reward_debt = calculate_reward_debt(
+                weighted_stake,
+                stream.accumulated_reward_per_share,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// One-off distribution settled against a published merkle root
+#[account]
+#[derive(Default)]
+pub struct SnapshotDistribution {
+    /// The stake pool this distribution belongs to
+    pub stake_pool: Pubkey,
+
+    /// Sequential id within the pool (PDA seed)
+    pub snapshot_id: u64,
+
+    /// Root over `keccak(user || amount)` leaves
+    pub merkle_root: [u8; 32],
+
+    /// Mint being distributed
+    pub mint: Pubkey,
+
+    /// Vault holding the distribution's tokens
+    pub vault: Pubkey,
+
+    /// Total tokens deposited for the distribution
+    pub total_amount: u64,
+
+    /// Tokens claimed so far
+    pub claimed_amount: u64,
+
+    /// When the root was published
+    pub published_at: i64,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+
+    /// Root over `keccak(user || weighted_stake)` leaves recording voting
+    /// power at the snapshot (all zeroes when not published)
+    pub voting_power_root: [u8; 32],
+
+    /// Pool `total_weighted_stake` when the snapshot was published
+    pub total_weighted_stake: u64,
+}
+
+impl SnapshotDistribution {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // stake_pool
+        8 +   // snapshot_id
+        32 +  // merkle_root
+        32 +  // mint
+        32 +  // vault
+        8 +   // total_amount
+        8 +   // claimed_amount
+        8 +   // published_at
+        1 +   // bump
+        32 +  // voting_power_root
+        8;    // total_weighted_stake
+
+    pub const SEED_PREFIX: &'static [u8] = b"snapshot";
+
+    /// Whether the snapshot carries a voting power root
+    pub fn has_voting_power(&self) -> bool {
+        self.voting_power_root != [0u8; 32]
+    }
+
+    /// Whether `proof` shows `user` held `weighted_stake` at the snapshot
+    pub fn verify_voting_power(&self, user: &Pubkey, weighted_stake: u64, proof: &[[u8; 32]]) -> bool {
+        self.has_voting_power()
+            && verify_proof(proof, &self.voting_power_root, snapshot_leaf(user, weighted_stake))
+    }
+}
+
+/// Receipt marking a user's snapshot allocation as claimed
+#[account]
+#[derive(Default)]
+pub struct SnapshotClaim {
+    /// Amount claimed
+    pub amount: u64,
+
+    /// When the claim happened
+    pub claimed_at: i64,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl SnapshotClaim {
+    pub const LEN: usize = 8 +  // discriminator
+        8 +   // amount
+        8 +   // claimed_at
+        1;    // bump
+
+    pub const SEED_PREFIX: &'static [u8] = b"snapshot_claim";
+}
+
+/// Sub-account ledger of an operator position: a custodian stakes a pooled
+/// amount under one `UserStake` and publishes a merkle root of each
+/// beneficiary's balance and cumulative rewards
+#[account]
+#[derive(Default)]
+pub struct OperatorBook {
+    /// The stake pool
+    pub stake_pool: Pubkey,
+
+    /// The operator's position
+    pub user_stake: Pubkey,
+
+    /// Operator allowed to publish roots (the position owner)
+    pub operator: Pubkey,
+
+    /// Token account (reward mint) holding collected rewards until
+    /// beneficiaries claim them
+    pub escrow: Pubkey,
+
+    /// Root over `operator_leaf(beneficiary, balance, cumulative_rewards)`
+    pub merkle_root: [u8; 32],
+
+    /// Number of roots published so far
+    pub root_version: u64,
+
+    /// Sum of sub-balances in the current root
+    pub total_balance: u64,
+
+    /// Sum of cumulative beneficiary rewards in the current root
+    pub total_rewards: u64,
+
+    /// Rewards collected into the escrow to date
+    pub total_collected: u64,
+
+    /// Rewards claimed by beneficiaries to date
+    pub total_claimed: u64,
+
+    /// Unix timestamp of the last root update
+    pub updated_at: i64,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl OperatorBook {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // stake_pool
+        32 +  // user_stake
+        32 +  // operator
+        32 +  // escrow
+        32 +  // merkle_root
+        8 +   // root_version
+        8 +   // total_balance
+        8 +   // total_rewards
+        8 +   // total_collected
+        8 +   // total_claimed
+        8 +   // updated_at
+        1;    // bump
+
+    pub const SEED_PREFIX: &'static [u8] = b"operator_book";
+
+    /// Publish a new root. Sub-balances must add up to the position and
+    /// cumulative rewards may only grow, up to what has been collected.
+    pub fn publish_root(
+        &mut self,
+        merkle_root: [u8; 32],
+        total_balance: u64,
+        total_rewards: u64,
+        staked_amount: u64,
+        now: i64,
+    ) -> Result<()> {
+        require!(total_balance == staked_amount, StakingError::InvalidOperatorTotals);
+        require!(
+            total_rewards >= self.total_rewards && total_rewards <= self.total_collected,
+            StakingError::InvalidOperatorTotals
+        );
+
+        self.merkle_root = merkle_root;
+        self.total_balance = total_balance;
+        self.total_rewards = total_rewards;
+        self.root_version = self.root_version
+            .checked_add(1)
+            .ok_or(StakingError::MathOverflow)?;
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Whether `proof` shows `beneficiary`'s sub-account in the current root
+    pub fn verify_entry(
+        &self,
+        beneficiary: &Pubkey,
+        balance: u64,
+        cumulative_rewards: u64,
+        proof: &[[u8; 32]],
+    ) -> bool {
+        self.root_version > 0
+            && verify_proof(proof, &self.merkle_root, operator_leaf(beneficiary, balance, cumulative_rewards))
+    }
+}
+
+/// Rewards a beneficiary has claimed from an operator position
+#[account]
+#[derive(Default)]
+pub struct OperatorClaim {
+    /// Cumulative rewards claimed
+    pub claimed: u64,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl OperatorClaim {
+    pub const LEN: usize = 8 +  // discriminator
+        8 +   // claimed
+        1;    // bump
+
+    pub const SEED_PREFIX: &'static [u8] = b"operator_claim";
+}
+
+/// Headline statistics for a stake pool, maintained by the handlers
+#[account]
+#[derive(Default)]
+pub struct PoolStats {
+    /// The stake pool these statistics belong to
+    pub stake_pool: Pubkey,
+
+    /// Number of distinct wallets that have ever staked
+    pub unique_stakers: u64,
+
+    /// Total rewards paid out to stakers
+    pub total_rewards_distributed: u64,
+
+    /// Sum of every stake deposit ever made
+    pub cumulative_staked_volume: u128,
+
+    /// Highest `total_staked` the pool has reached
+    pub peak_tvl: u64,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+
+    /// Reserved space for future upgrades
+    pub _reserved: [u8; 32],
+}
+
+impl PoolStats {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // stake_pool
+        8 +   // unique_stakers
+        8 +   // total_rewards_distributed
+        16 +  // cumulative_staked_volume
+        8 +   // peak_tvl
+        1 +   // bump
+        32;   // _reserved
+
+    pub const SEED_PREFIX: &'static [u8] = b"pool_stats";
+
+    /// Early adopter bonus (bps) for a staker arriving now, before
+    /// `record_stake` counts them
+    pub fn early_adopter_bonus_bps(&self, stake_pool: &StakePool) -> u64 {
+        if self.unique_stakers < stake_pool.early_adopter_slots {
+            stake_pool.early_adopter_bonus_bps
+        } else {
+            0
+        }
+    }
+
+    /// Record a stake deposit and the pool's resulting TVL
+    pub fn record_stake(&mut self, amount: u64, new_staker: bool, total_staked: u64) {
+        if new_staker {
+            self.unique_stakers = self.unique_stakers.saturating_add(1);
+        }
+        self.cumulative_staked_volume = self.cumulative_staked_volume.saturating_add(amount as u128);
+        self.peak_tvl = self.peak_tvl.max(total_staked);
+    }
+
+    /// Record rewards paid out by a claim
+    pub fn record_claim(&mut self, amount: u64) {
+        self.total_rewards_distributed = self.total_rewards_distributed.saturating_add(amount);
+    }
+}
+
+/// Compact, crank-refreshed summary of a pool for light clients, small
+/// enough to render a pool card from one `getAccountInfo`
+#[account]
+#[derive(Default)]
+pub struct PoolSnapshot {
+    /// The stake pool this snapshot summarises
+    pub stake_pool: Pubkey,
+
+    /// Total tokens staked (TVL in stake token units)
+    pub total_staked: u64,
+
+    /// Current reward rate (tokens per second)
+    pub reward_rate: u64,
+
+    /// Base APR (bps) across the pool at the current reward rate
+    pub apr_bps: u64,
+
+    /// Number of distinct wallets that have ever staked
+    pub unique_stakers: u64,
+
+    /// Total rewards paid out to stakers
+    pub total_rewards_distributed: u64,
+
+    /// Whether the pool is paused
+    pub paused: bool,
+
+    /// Unix timestamp of the last refresh
+    pub last_update: i64,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl PoolSnapshot {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // stake_pool
+        8 +   // total_staked
+        8 +   // reward_rate
+        8 +   // apr_bps
+        8 +   // unique_stakers
+        8 +   // total_rewards_distributed
+        1 +   // paused
+        8 +   // last_update
+        1;    // bump
+
+    pub const SEED_PREFIX: &'static [u8] = b"pool_snapshot";
+
+    /// Copy the headline figures from the pool and its statistics
+    pub fn refresh(&mut self, stake_pool: &StakePool, pool_stats: &PoolStats, now: i64) {
+        self.total_staked = stake_pool.total_staked;
+        self.reward_rate = stake_pool.reward_rate;
+        self.apr_bps = staking_math::projected_apr_bps(
+            stake_pool.total_staked,
+            stake_pool.total_weighted_stake,
+            stake_pool.total_weighted_stake,
+            stake_pool.reward_rate,
+        )
+        .unwrap_or(0);
+        self.unique_stakers = pool_stats.unique_stakers;
+        self.total_rewards_distributed = pool_stats.total_rewards_distributed;
+        self.paused = stake_pool.paused;
+        self.last_update = now;
+    }
+}
+
+/// One point of a pool's APR/TVL history
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HistorySample {
+    /// Unix timestamp of the sample (0 for an unused slot)
+    pub timestamp: i64,
+    /// Base APR (bps) at the time
+    pub apr_bps: u64,
+    /// Total tokens staked at the time
+    pub tvl: u64,
+}
+
+impl HistorySample {
+    pub const LEN: usize = 8 + 8 + 8;
+}
+
+/// Ring buffer of recent pool samples appended by the snapshot crank, so
+/// frontends can draw an APR/TVL sparkline without an indexer
+#[account]
+pub struct PoolHistory {
+    /// The stake pool this history belongs to
+    pub stake_pool: Pubkey,
+
+    /// Samples, oldest overwritten first
+    pub samples: [HistorySample; PoolHistory::SIZE],
+
+    /// Index of the next sample slot to write
+    pub head: u8,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl Default for PoolHistory {
+    fn default() -> Self {
+        Self {
+            stake_pool: Pubkey::default(),
+            samples: [HistorySample::default(); PoolHistory::SIZE],
+            head: 0,
+            bump: 0,
+        }
+    }
+}
+
+impl PoolHistory {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // stake_pool
+        HistorySample::LEN * PoolHistory::SIZE + // samples
+        1 +   // head
+        1;    // bump
+
+    pub const SEED_PREFIX: &'static [u8] = b"pool_history";
+
+    /// Number of samples kept in the ring buffer
+    pub const SIZE: usize = 90;
+
+    /// Append a sample unless the previous one is less than
+    /// `HISTORY_SAMPLE_INTERVAL` old
+    ///
+    /// # Returns
+    /// * Whether the sample was recorded
+    pub fn record(&mut self, sample: HistorySample) -> bool {
+        let head = self.head as usize % Self::SIZE;
+        let last = self.samples[(head + Self::SIZE - 1) % Self::SIZE];
+        if last.timestamp != 0
+            && sample.timestamp < last.timestamp.saturating_add(constants::HISTORY_SAMPLE_INTERVAL)
+        {
+            return false;
+        }
+        self.samples[head] = sample;
+        self.head = ((head + 1) % Self::SIZE) as u8;
+        true
+    }
+
+    /// Recorded samples from oldest to newest
+    pub fn recent_samples(&self) -> impl Iterator<Item = &HistorySample> {
+        let head = self.head as usize % Self::SIZE;
+        self.samples[head..]
+            .iter()
+            .chain(self.samples[..head].iter())
+            .filter(|sample| sample.timestamp != 0)
+    }
+}
+
+/// Authority-managed list of programs notified on stake/unstake
+#[account]
+#[derive(Default)]
+pub struct HookRegistry {
+    /// The stake pool this registry belongs to
+    pub stake_pool: Pubkey,
+
+    /// Whitelisted hook programs (only the first `hook_count` are valid)
+    pub hooks: [Pubkey; HookRegistry::MAX_HOOKS],
+
+    /// Number of registered hooks
+    pub hook_count: u8,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl HookRegistry {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // stake_pool
+        32 * HookRegistry::MAX_HOOKS + // hooks
+        1 +   // hook_count
+        1;    // bump
+
+    pub const SEED_PREFIX: &'static [u8] = b"hook_registry";
+
+    /// Maximum number of hook programs per pool
+    pub const MAX_HOOKS: usize = 4;
+
+    /// Registered hook programs
+    pub fn active_hooks(&self) -> &[Pubkey] {
+        &self.hooks[..self.hook_count as usize]
+    }
+}
+
+/// Individual user stake account
+#[account]
+#[derive(Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UserStake {
+    /// Owner of this stake
+    pub owner: Pubkey,
+
+    /// The stake pool this belongs to
+    pub stake_pool: Pubkey,
+
+    /// Amount of tokens staked (raw, not weighted)
+    pub staked_amount: u64,
+
+    /// Weighted stake amount (includes lock duration multiplier)
+    pub weighted_stake: u64,
+
+    /// Unix timestamp when lock period ends
+    pub lock_end_time: i64,
+
+    /// Lock duration chosen (for recalculating on additional stakes)
+    pub lock_duration: i64,
+
+    /// Reward debt for calculating pending rewards
+    /// reward_debt = weighted_stake * accumulated_reward_per_share at time of stake
+    pub reward_debt: u128,
+
+    /// Total rewards claimed by this user
+    pub total_claimed: u64,
+
+    /// Timestamp of first stake
+    pub stake_start_time: i64,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+
+    /// Ring buffer of the most recent actions (oldest overwritten first)
+    pub history: [ActionRecord; UserStake::HISTORY_SIZE],
+
+    /// Index of the next history slot to write
+    pub history_head: u8,
+
+    /// Tier in effect before the most recent upgrade
+    pub previous_tier: StakingTier,
+
+    /// Unix timestamp when the current tier's benefits activate
+    pub tier_activation_time: i64,
+
+    /// Tier retained during the grace window after a partial unstake
+    pub grace_tier: StakingTier,
+
+    /// Unix timestamp until which `grace_tier` still applies
+    pub tier_grace_until: i64,
+
+    /// Rewards earned (tier multiplier applied) but not yet paid out
+    pub owed_rewards: u64,
+
+    /// Compliance hold: blocks unstake and claim for this account only
+    pub frozen: bool,
+
+    /// Extra reward multiplier (bps) earned by locking partner tokens
+    pub partner_boost_bps: u64,
+
+    /// Stake weighted by lock duration only (before reward multipliers)
+    pub lock_weighted_stake: u64,
+
+    /// Reward multiplier (tier + partner boost, bps) folded into
+    /// `weighted_stake`. Zero marks a legacy position that still has its
+    /// multiplier applied at settlement.
+    pub applied_multiplier_bps: u64,
+
+    /// Staked amount integrated over time (token units x seconds), the
+    /// basis for loyalty points
+    pub stake_seconds: u128,
+
+    /// Unix timestamp `stake_seconds` was last brought up to date
+    pub points_last_update: i64,
+
+    /// Loyalty points already spent with partner programs
+    pub points_spent: u64,
+
+    /// Start of the current unbroken staking streak (0 = none); any unstake
+    /// restarts it
+    pub unbroken_since: i64,
+
+    /// Whether the owner lets keepers claim rewards on their behalf
+    pub auto_claim: bool,
+
+    /// Time of the last stake or unstake, for the pool's action cooldown
+    pub last_action_time: i64,
+
+    /// Set once the owner and pool binding are written; the account keeps
+    /// them for its lifetime, including after a full exit
+    pub initialized: bool,
+
+    /// Permanent reward multiplier bonus (bps) granted for being one of the
+    /// pool's first stakers
+    pub early_adopter_bonus_bps: u64,
+
+    /// Bitflags of milestone bonuses already paid (bit i = milestone i)
+    pub claimed_milestones: u8,
+
+    /// Guaranteed APR (bps) snapshotted from the pool when the position
+    /// opened (0 = floating rewards)
+    pub fixed_apr_bps: u64,
+
+    /// Guaranteed rewards not yet accrued, reserved in the pool until
+    /// `lock_end_time`
+    pub fixed_reward_reserved: u64,
+
+    /// Unix timestamp fixed-rate rewards have been accrued up to
+    pub fixed_accrued_until: i64,
+
+    /// Lending program holding the position as collateral; unstaking is
+    /// blocked until it releases it (default = not collateralized)
+    pub collateral_program: Pubkey,
+
+    /// Time of the last reward payout, for the pool's claim interval
+    pub last_claim_time: i64,
+
+    /// When the current position was opened (first stake, or the first
+    /// since a full exit); the pool's claim tax decays from here
+    pub position_opened_at: i64,
+
+    /// The pool's minimum lock per tier as of the position's last stake
+    pub tier_min_lock_durations: [i64; 3],
+
+    /// The pool's tier amount thresholds as of the position's last stake
+    /// (all zero for the built-in table)
+    pub tier_thresholds: [u64; 3],
+
+    /// Source pool of the last cross-pool boost refresh
+    pub boost_source_pool: Pubkey,
+
+    /// Owner's weighted stake in the source pool at the last refresh
+    pub boost_source_weight: u64,
+
+    /// Source pool's total weighted stake at the last refresh
+    pub boost_source_total_weight: u64,
+
+    /// Escrow receiving this position's rewards in operator mode
+    /// (default = a regular position)
+    pub operator_escrow: Pubkey,
+
+    /// Reserved space for future upgrades
+    pub _reserved: [u8; 32],
+}
+
+impl UserStake {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // owner
+        32 +  // stake_pool
+        8 +   // staked_amount
+        8 +   // weighted_stake
+        8 +   // lock_end_time
+        8 +   // lock_duration
+        16 +  // reward_debt
+        8 +   // total_claimed
+        8 +   // stake_start_time
+        1 +   // bump
+        ActionRecord::LEN * UserStake::HISTORY_SIZE + // history
+        1 +   // history_head
+        1 +   // previous_tier
+        8 +   // tier_activation_time
+        1 +   // grace_tier
+        8 +   // tier_grace_until
+        8 +   // owed_rewards
+        1 +   // frozen
+        8 +   // partner_boost_bps
+        8 +   // lock_weighted_stake
+        8 +   // applied_multiplier_bps
+        16 +  // stake_seconds
+        8 +   // points_last_update
+        8 +   // points_spent
+        8 +   // unbroken_since
+        1 +   // auto_claim
+        8 +   // last_action_time
+        1 +   // initialized
+        8 +   // early_adopter_bonus_bps
+        1 +   // claimed_milestones
+        8 +   // fixed_apr_bps
+        8 +   // fixed_reward_reserved
+        8 +   // fixed_accrued_until
+        32 +  // collateral_program
+        8 +   // last_claim_time
+        8 +   // position_opened_at
+        8 * 3 + // tier_min_lock_durations
+        8 * 3 + // tier_thresholds
+        32 +  // boost_source_pool
+        8 +   // boost_source_weight
+        8 +   // boost_source_total_weight
+        32 +  // operator_escrow
+        32;   // _reserved
+
+    pub const SEED_PREFIX: &'static [u8] = b"user_stake";
+
+    /// Number of actions kept in the history ring buffer
+    pub const HISTORY_SIZE: usize = 16;
+
+    /// Append an action to the history, overwriting the oldest entry when full
+    pub fn record_action(&mut self, kind: ActionKind, amount: u64, timestamp: i64) {
+        let head = self.history_head as usize % Self::HISTORY_SIZE;
+        self.history[head] = ActionRecord { kind, amount, timestamp };
+        self.history_head = ((head + 1) % Self::HISTORY_SIZE) as u8;
+    }
+
+    /// Check a position's accounting against its pool
+    ///
+    /// # Returns
+    /// * `std::result::Result<(), &'static str>` - The first violated invariant
+    pub fn check_invariants(&self, stake_pool: &StakePool) -> std::result::Result<(), &'static str> {
+        if self.staked_amount > stake_pool.total_staked {
+            return Err("user staked_amount > pool total_staked");
+        }
+        if self.weighted_stake > stake_pool.total_weighted_stake {
+            return Err("user weighted_stake > pool total_weighted_stake");
+        }
+        let accumulated = crate::calculate_reward_debt(
+            self.weighted_stake,
+            stake_pool.accumulated_reward_per_share,
+        )
+        .map_err(|_| "reward debt overflow")?;
+        if self.reward_debt > accumulated {
+            return Err("reward_debt above accumulated rewards");
+        }
+        Ok(())
+    }
+
+    /// Whether the owner and pool binding have been written
+    /// Accounts created before the flag existed count once they have an owner.
+    pub fn is_initialized(&self) -> bool {
+        self.initialized || self.owner != Pubkey::default()
+    }
+
+    /// Bind a freshly created account to its owner and pool, or check the
+    /// binding of an existing one. Never rewrites identity fields, so
+    /// `stake_start_time` keeps the very first stake across full exits.
+    pub fn bind(&mut self, owner: Pubkey, stake_pool: Pubkey, bump: u8, now: i64) -> Result<()> {
+        if self.is_initialized() {
+            require_keys_eq!(self.owner, owner, StakingError::OwnerMismatch);
+            require_keys_eq!(self.stake_pool, stake_pool, StakingError::StakePoolMismatch);
+        } else {
+            self.owner = owner;
+            self.stake_pool = stake_pool;
+            self.bump = bump;
+            self.stake_start_time = now;
+        }
+        self.initialized = true;
+        Ok(())
+    }
+
+    /// Whether `cooldown` seconds have passed since the last stake or unstake
+    pub fn cooldown_elapsed(&self, cooldown: i64, now: i64) -> bool {
+        self.last_action_time == 0 || now >= self.last_action_time.saturating_add(cooldown)
+    }
+
+    /// Whether `interval` seconds have passed since the last reward payout
+    pub fn claim_interval_elapsed(&self, interval: i64, now: i64) -> bool {
+        self.last_claim_time == 0 || now >= self.last_claim_time.saturating_add(interval)
+    }
+
+    /// Reward multiplier (bps) the position should carry at `now`
+    pub fn reward_multiplier_bps(&self, now: i64) -> u64 {
+        self.effective_tier(now)
+            .reward_multiplier_bps()
+            .saturating_add(self.partner_boost_bps)
+            .saturating_add(self.early_adopter_bonus_bps)
+    }
+
+    /// Accrue loyalty stake-seconds up to `now` at the current staked amount.
+    /// Must run before `staked_amount` changes.
+    pub fn accrue_points(&mut self, now: i64) {
+        if self.points_last_update > 0 && now > self.points_last_update {
+            let elapsed = (now - self.points_last_update) as u128;
+            self.stake_seconds = self.stake_seconds
+                .saturating_add(self.staked_amount as u128 * elapsed);
+        }
+        self.points_last_update = now;
+    }
+
+    /// Points earned to date: one per whole token staked for a day
+    pub fn total_points(&self) -> u64 {
+        u64::try_from(self.stake_seconds / constants::POINT_STAKE_SECONDS).unwrap_or(u64::MAX)
+    }
+
+    /// Points not yet spent
+    pub fn available_points(&self) -> u64 {
+        self.total_points().saturating_sub(self.points_spent)
+    }
+
+    /// Whether the position predates accrual-time multipliers
+    pub fn is_legacy_weight(&self) -> bool {
+        self.applied_multiplier_bps == 0
+    }
+
+    /// Tier the position's amount and lock qualify for, ignoring activation
+    /// delays and grace windows
+    pub fn current_tier(&self) -> StakingTier {
+        let mut config = staking_math::TierConfig {
+            min_lock_durations: self.tier_min_lock_durations,
+            ..Default::default()
+        };
+        if self.tier_thresholds != [0; 3] {
+            config.min_amounts = self.tier_thresholds;
+        }
+        crate::calculate_tier_v2(self.staked_amount, self.lock_duration, &config)
+    }
+
+    /// Tier whose benefits apply at `now`
+    /// A recent upgrade only takes effect once `tier_activation_time` passes;
+    /// until then the lower of the previous and current tier applies.
+    /// During a grace window after a partial unstake, `grace_tier` is kept.
+    pub fn effective_tier(&self, now: i64) -> StakingTier {
+        let current = self.current_tier();
+        let activated = if now >= self.tier_activation_time {
+            current
+        } else {
+            current.min(self.previous_tier)
+        };
+
+        if self.staked_amount > 0 && now < self.tier_grace_until {
+            activated.max(self.grace_tier)
+        } else {
+            activated
+        }
+    }
+
+    /// Start the activation delay if the stake just moved into a higher tier
+    ///
+    /// # Arguments
+    /// * `previous_tier` - Effective tier before the stake changed
+    /// * `delay` - Pool's tier activation delay in seconds
+    /// * `now` - Current Unix timestamp
+    pub fn schedule_tier_activation(&mut self, previous_tier: StakingTier, delay: i64, now: i64) {
+        let new_tier = self.current_tier();
+        if delay > 0 && new_tier > previous_tier {
+            self.previous_tier = previous_tier;
+            self.tier_activation_time = now.saturating_add(delay);
+        }
+    }
+
+    /// Start a grace window if a partial unstake dropped the user's tier
+    /// An already-running grace window is not extended.
+    ///
+    /// # Arguments
+    /// * `previous_tier` - Effective tier before the unstake
+    /// * `grace_period` - Pool's tier grace period in seconds
+    /// * `now` - Current Unix timestamp
+    pub fn start_tier_grace(&mut self, previous_tier: StakingTier, grace_period: i64, now: i64) {
+        let new_tier = self.current_tier();
+        if grace_period > 0
+            && self.staked_amount > 0
+            && new_tier < previous_tier
+            && now >= self.tier_grace_until
+        {
+            self.grace_tier = previous_tier;
+            self.tier_grace_until = now.saturating_add(grace_period);
+        }
+    }
+
+    /// Recorded actions from oldest to newest
+    pub fn recent_actions(&self) -> impl Iterator<Item = &ActionRecord> {
+        let head = self.history_head as usize % Self::HISTORY_SIZE;
+        self.history[head..]
+            .iter()
+            .chain(self.history[..head].iter())
+            .filter(|record| record.kind != ActionKind::Empty)
+    }
+
+    /// Milestones reached by the current unbroken streak, as bitflags
+    pub fn reached_milestones(&self, now: i64) -> u8 {
+        if self.unbroken_since == 0 || self.staked_amount == 0 {
+            return 0;
+        }
+        let held = now.saturating_sub(self.unbroken_since);
+        constants::MILESTONE_DURATIONS
+            .iter()
+            .enumerate()
+            .filter(|(_, duration)| held >= **duration)
+            .fold(0, |flags, (i, _)| flags | 1 << i)
+    }
+
+    /// Reached milestones whose bonus has not been paid yet
+    pub fn claimable_milestones(&self, now: i64) -> u8 {
+        self.reached_milestones(now) & !self.claimed_milestones
+    }
+
+    /// Whether the position is staked by an operator on behalf of
+    /// beneficiaries
+    pub fn is_operator(&self) -> bool {
+        self.operator_escrow != Pubkey::default()
+    }
+
+    /// Whether a lending program holds the position as collateral
+    pub fn is_collateralized(&self) -> bool {
+        self.collateral_program != Pubkey::default()
+    }
+
+    /// Move fixed-rate rewards earned up to `now` (capped at the lock end)
+    /// from the reserve into `owed_rewards`. The lock end sweeps whatever
+    /// is left of the reserve, so rounding never strands any of it.
+    ///
+    /// # Returns
+    /// * `Result<u64>` - Rewards accrued by this call
+    pub fn accrue_fixed_rewards(&mut self, now: i64) -> Result<u64> {
+        if self.fixed_apr_bps == 0 || self.fixed_reward_reserved == 0 {
+            return Ok(0);
+        }
+        let until = now.min(self.lock_end_time);
+        if until <= self.fixed_accrued_until {
+            return Ok(0);
+        }
+
+        let accrued = if until == self.lock_end_time {
+            self.fixed_reward_reserved
+        } else {
+            staking_math::fixed_rate_rewards(
+                self.staked_amount,
+                self.fixed_apr_bps,
+                (until - self.fixed_accrued_until) as u64,
+            )
+            .ok_or(StakingError::MathOverflow)?
+            .min(self.fixed_reward_reserved)
+        };
+
+        self.fixed_reward_reserved -= accrued;
+        self.fixed_accrued_until = until;
+        self.owed_rewards = self.owed_rewards
+            .checked_add(accrued)
+            .ok_or(StakingError::MathOverflow)?;
+        Ok(accrued)
+    }
 }
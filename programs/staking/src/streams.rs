@@ -0,0 +1,51 @@
+//! Extra reward streams: secondary reward tokens added to a live pool.
+//!
+//! Each stream keeps its own accumulator over the pool's weighted stake.
+//! Every handler that changes a user's weight must checkpoint the user's
+//! stream positions, so once a pool has streams the `reward_streams` and
+//! `user_reward_streams` accounts become required.
+
+use anchor_lang::prelude::*;
+
+use crate::errors::StakingError;
+use crate::state::{RewardStreams, StakePool, UserRewardStreams};
+
+/// Weights before and after a stake change
+pub struct WeightChange {
+    pub total_weighted_before: u64,
+    pub user_weighted_before: u64,
+    pub user_weighted_after: u64,
+}
+
+/// Settle a user's stream rewards across a weight change
+///
+/// Call after the pool and user accounting has been updated, passing the
+/// weights captured beforehand. Does nothing for pools without streams.
+pub fn sync_reward_streams(
+    stake_pool: &StakePool,
+    reward_streams: Option<&mut Account<RewardStreams>>,
+    user_reward_streams: Option<&mut Account<UserRewardStreams>>,
+    owner: Pubkey,
+    user_reward_streams_bump: u8,
+    change: WeightChange,
+    now: i64,
+) -> Result<()> {
+    let (streams, user_streams) = match (reward_streams, user_reward_streams) {
+        (Some(streams), Some(user_streams)) => (streams, user_streams),
+        _ if stake_pool.reward_stream_count == 0 => return Ok(()),
+        _ => return err!(StakingError::MissingRewardStreamAccount),
+    };
+
+    // First touch of a freshly created user account
+    if user_streams.owner == Pubkey::default() {
+        user_streams.owner = owner;
+        user_streams.stake_pool = streams.stake_pool;
+        user_streams.bump = user_reward_streams_bump;
+    }
+
+    streams.update_all(change.total_weighted_before, now)?;
+    user_streams.settle(streams, change.user_weighted_before)?;
+    user_streams.reset_debts(streams, change.user_weighted_after)?;
+
+    Ok(())
+}
@@ -0,0 +1,474 @@
+//! Integration test scaffolding for programs that build on staking.
+//!
+//! Enable the `test-utils` feature and build the program first
+//! (`anchor build`), then:
+//!
+//! ```ignore
+//! let mut scenario = StakingScenario::builder()
+//!     .reward_rate(1_000)
+//!     .users(2, 1_000_000_000_000)
+//!     .build()
+//!     .await?;
+//! scenario.stake(0, 500_000_000_000, 7 * 24 * 60 * 60).await?;
+//! scenario.advance_clock(60).await?;
+//! scenario.claim(0).await?;
+//! ```
+
+use anchor_lang::{AccountDeserialize, AccountSerialize, InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token;
+use solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::account::Account;
+use solana_sdk::clock::Clock;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::rent::Rent;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::Transaction;
+
+use crate::constants;
+use crate::cpi_client::{
+    pool_stats_address, program_config_address, stake_pool_address, stake_vault_address, user_stake_address,
+};
+use crate::instructions::InitializeParams;
+use crate::state::{PoolMetadata, PoolRegistry, ProtocolTreasury, RegistryEntry, StakePool, UserStake};
+
+/// Addresses of the scenario's stake pool
+#[derive(Clone, Copy, Debug)]
+pub struct PoolAddresses {
+    pub stake_pool: Pubkey,
+    pub pool_stats: Pubkey,
+    pub stake_vault: Pubkey,
+    pub reward_vault: Pubkey,
+}
+
+/// A funded user with token accounts for both mints
+#[derive(Debug)]
+pub struct ScenarioUser {
+    pub keypair: Keypair,
+    pub stake_account: Pubkey,
+    pub reward_account: Pubkey,
+}
+
+/// Configures and starts a `StakingScenario`
+pub struct StakingScenarioBuilder {
+    reward_rate: u64,
+    min_lock_duration: i64,
+    max_lock_duration: i64,
+    user_count: usize,
+    user_balance: u64,
+    reward_funding: u64,
+}
+
+impl Default for StakingScenarioBuilder {
+    fn default() -> Self {
+        Self {
+            reward_rate: 1_000,
+            min_lock_duration: constants::MIN_LOCK_DURATION,
+            max_lock_duration: constants::MAX_LOCK_DURATION,
+            user_count: 1,
+            user_balance: 1_000_000 * 10u64.pow(constants::STAKE_MINT_DECIMALS as u32),
+            reward_funding: 1_000_000 * 10u64.pow(constants::STAKE_MINT_DECIMALS as u32),
+        }
+    }
+}
+
+impl StakingScenarioBuilder {
+    /// Pool reward rate (tokens per second)
+    pub fn reward_rate(mut self, reward_rate: u64) -> Self {
+        self.reward_rate = reward_rate;
+        self
+    }
+
+    /// Pool lock duration bounds in seconds
+    pub fn lock_bounds(mut self, min_lock_duration: i64, max_lock_duration: i64) -> Self {
+        self.min_lock_duration = min_lock_duration;
+        self.max_lock_duration = max_lock_duration;
+        self
+    }
+
+    /// Number of users and the stake tokens minted to each
+    pub fn users(mut self, count: usize, balance: u64) -> Self {
+        self.user_count = count;
+        self.user_balance = balance;
+        self
+    }
+
+    /// Reward tokens minted into the reward vault
+    pub fn reward_funding(mut self, amount: u64) -> Self {
+        self.reward_funding = amount;
+        self
+    }
+
+    /// Start the test validator, then create the mints, pool and users
+    pub async fn build(self) -> Result<StakingScenario, BanksClientError> {
+        let mut program_test = ProgramTest::new("staking", crate::ID, None);
+        program_test.prefer_bpf(true);
+        program_test.add_account(protocol_treasury_address(), protocol_treasury_account());
+
+        let context = program_test.start_with_context().await;
+        let mut scenario = StakingScenario {
+            context,
+            mint_authority: Keypair::new(),
+            stake_mint: Pubkey::default(),
+            reward_mint: Pubkey::default(),
+            pool: PoolAddresses {
+                stake_pool: Pubkey::default(),
+                pool_stats: Pubkey::default(),
+                stake_vault: Pubkey::default(),
+                reward_vault: Pubkey::default(),
+            },
+            users: Vec::with_capacity(self.user_count),
+        };
+
+        scenario.stake_mint = scenario.create_mint().await?;
+        scenario.reward_mint = scenario.create_mint().await?;
+        scenario.initialize_pool(&self).await?;
+
+        let reward_vault = scenario.pool.reward_vault;
+        let reward_mint = scenario.reward_mint;
+        scenario.mint_to(&reward_mint, &reward_vault, self.reward_funding).await?;
+
+        for _ in 0..self.user_count {
+            scenario.add_user(self.user_balance).await?;
+        }
+
+        Ok(scenario)
+    }
+}
+
+/// A running test validator with a funded stake pool and users
+pub struct StakingScenario {
+    pub context: ProgramTestContext,
+    pub mint_authority: Keypair,
+    pub stake_mint: Pubkey,
+    pub reward_mint: Pubkey,
+    pub pool: PoolAddresses,
+    pub users: Vec<ScenarioUser>,
+}
+
+impl StakingScenario {
+    /// Start configuring a scenario
+    pub fn builder() -> StakingScenarioBuilder {
+        StakingScenarioBuilder::default()
+    }
+
+    /// Create another user holding `balance` stake tokens
+    ///
+    /// # Returns
+    /// * Index of the new user
+    pub async fn add_user(&mut self, balance: u64) -> Result<usize, BanksClientError> {
+        let keypair = Keypair::new();
+        let lamports = 10 * solana_sdk::native_token::LAMPORTS_PER_SOL;
+        let payer = self.context.payer.pubkey();
+        self.process(
+            &[system_instruction::transfer(&payer, &keypair.pubkey(), lamports)],
+            &[],
+        )
+        .await?;
+
+        let (stake_mint, reward_mint) = (self.stake_mint, self.reward_mint);
+        let stake_account = self.create_token_account(&stake_mint, &keypair.pubkey()).await?;
+        let reward_account = self.create_token_account(&reward_mint, &keypair.pubkey()).await?;
+        self.mint_to(&stake_mint, &stake_account, balance).await?;
+
+        self.users.push(ScenarioUser {
+            keypair,
+            stake_account,
+            reward_account,
+        });
+        Ok(self.users.len() - 1)
+    }
+
+    /// Stake `amount` for `user` with the given lock
+    pub async fn stake(&mut self, user: usize, amount: u64, lock_duration: i64) -> Result<(), BanksClientError> {
+        let owner = self.users[user].keypair.pubkey();
+        let accounts = crate::accounts::Stake {
+            user: owner,
+            stake_pool: self.pool.stake_pool,
+            program_config: program_config_address(),
+            pool_stats: self.pool.pool_stats,
+            user_stake: self.user_stake_address(user),
+            user_token_account: self.users[user].stake_account,
+            stake_vault: self.pool.stake_vault,
+            reward_vault: None,
+            credential_account: None,
+            hook_registry: None,
+            reward_streams: None,
+            user_reward_streams: None,
+            token_program: spl_token::ID,
+            memo_program: anchor_spl::memo::ID,
+            system_program: solana_sdk::system_program::ID,
+        };
+        let data = crate::instruction::Stake { amount, lock_duration };
+        self.process_as(user, accounts.to_account_metas(None), data.data()).await
+    }
+
+    /// Unstake `amount` for `user` (the lock must have ended)
+    pub async fn unstake(&mut self, user: usize, amount: u64) -> Result<(), BanksClientError> {
+        let owner = self.users[user].keypair.pubkey();
+        let accounts = crate::accounts::Unstake {
+            user: owner,
+            stake_pool: self.pool.stake_pool,
+            program_config: program_config_address(),
+            user_stake: self.user_stake_address(user),
+            user_token_account: self.users[user].stake_account,
+            stake_vault: self.pool.stake_vault,
+            reward_vault: None,
+            hook_registry: None,
+            reward_streams: None,
+            user_reward_streams: None,
+            stake_mint: None,
+            penalty_treasury: None,
+            insurance_fund: None,
+            insurance_vault: None,
+            token_program: spl_token::ID,
+            memo_program: anchor_spl::memo::ID,
+            system_program: solana_sdk::system_program::ID,
+        };
+        let data = crate::instruction::Unstake { amount };
+        self.process_as(user, accounts.to_account_metas(None), data.data()).await
+    }
+
+    /// Claim `user`'s pending rewards
+    pub async fn claim(&mut self, user: usize) -> Result<(), BanksClientError> {
+        let owner = self.users[user].keypair.pubkey();
+        let accounts = crate::accounts::ClaimRewards {
+            user: owner,
+            stake_pool: self.pool.stake_pool,
+            pool_stats: self.pool.pool_stats,
+            user_stake: self.user_stake_address(user),
+            user_reward_account: self.users[user].reward_account,
+            reward_vault: self.pool.reward_vault,
+            fee_treasury: None,
+            token_program: spl_token::ID,
+            memo_program: anchor_spl::memo::ID,
+        };
+        let data = crate::instruction::ClaimRewards {};
+        self.process_as(user, accounts.to_account_metas(None), data.data()).await
+    }
+
+    /// Move the validator clock forward by `seconds`
+    pub async fn advance_clock(&mut self, seconds: i64) -> Result<(), BanksClientError> {
+        let mut clock: Clock = self.context.banks_client.get_sysvar().await?;
+        clock.unix_timestamp += seconds;
+        self.context.set_sysvar(&clock);
+        Ok(())
+    }
+
+    /// Current validator time
+    pub async fn now(&mut self) -> Result<i64, BanksClientError> {
+        let clock: Clock = self.context.banks_client.get_sysvar().await?;
+        Ok(clock.unix_timestamp)
+    }
+
+    /// User stake PDA for `user`
+    pub fn user_stake_address(&self, user: usize) -> Pubkey {
+        user_stake_address(&self.pool.stake_pool, &self.users[user].keypair.pubkey())
+    }
+
+    /// Fetch the pool's current state
+    pub async fn stake_pool(&mut self) -> Result<StakePool, BanksClientError> {
+        let address = self.pool.stake_pool;
+        self.fetch_anchor_account(&address).await
+    }
+
+    /// Fetch `user`'s position
+    pub async fn user_stake(&mut self, user: usize) -> Result<UserStake, BanksClientError> {
+        let address = self.user_stake_address(user);
+        self.fetch_anchor_account(&address).await
+    }
+
+    /// Token balance of `account`
+    pub async fn token_balance(&mut self, account: &Pubkey) -> Result<u64, BanksClientError> {
+        let data = self.account_data(account).await?;
+        let token_account = spl_token::state::Account::unpack(&data)
+            .map_err(|err| decode_error(err.to_string()))?;
+        Ok(token_account.amount)
+    }
+
+    async fn initialize_pool(&mut self, config: &StakingScenarioBuilder) -> Result<(), BanksClientError> {
+        let stake_pool = stake_pool_address(&self.stake_mint, 0);
+        self.pool = PoolAddresses {
+            stake_pool,
+            pool_stats: pool_stats_address(&stake_pool),
+            stake_vault: stake_vault_address(&stake_pool),
+            reward_vault: Pubkey::find_program_address(&[b"reward_vault", stake_pool.as_ref()], &crate::ID).0,
+        };
+
+        let accounts = crate::accounts::Initialize {
+            authority: self.context.payer.pubkey(),
+            stake_pool,
+            pool_stats: self.pool.pool_stats,
+            pool_metadata: pda(&[PoolMetadata::SEED_PREFIX, stake_pool.as_ref()]),
+            pool_registry: pda(&[PoolRegistry::SEED_PREFIX]),
+            registry_entry: pda(&[RegistryEntry::SEED_PREFIX, &0u64.to_le_bytes()]),
+            protocol_treasury: protocol_treasury_address(),
+            program_config: None,
+            stake_mint: self.stake_mint,
+            reward_mint: self.reward_mint,
+            stake_vault: self.pool.stake_vault,
+            reward_vault: Some(self.pool.reward_vault),
+            system_program: solana_sdk::system_program::ID,
+            token_program: spl_token::ID,
+            rent: solana_sdk::sysvar::rent::ID,
+        };
+        let data = crate::instruction::Initialize {
+            params: InitializeParams {
+                pool_id: 0,
+                reward_rate: config.reward_rate,
+                min_lock_duration: config.min_lock_duration,
+                max_lock_duration: config.max_lock_duration,
+                name: "Scenario Pool".to_string(),
+                symbol: "TEST".to_string(),
+                uri: String::new(),
+                allow_freezable: false,
+            },
+        };
+        let instruction = Instruction {
+            program_id: crate::ID,
+            accounts: accounts.to_account_metas(None),
+            data: data.data(),
+        };
+        self.process(&[instruction], &[]).await
+    }
+
+    async fn create_mint(&mut self) -> Result<Pubkey, BanksClientError> {
+        let mint = Keypair::new();
+        let payer = self.context.payer.pubkey();
+        let instructions = [
+            system_instruction::create_account(
+                &payer,
+                &mint.pubkey(),
+                Rent::default().minimum_balance(spl_token::state::Mint::LEN),
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::initialize_mint2(
+                &spl_token::ID,
+                &mint.pubkey(),
+                &self.mint_authority.pubkey(),
+                None,
+                constants::STAKE_MINT_DECIMALS,
+            )
+            .expect("valid mint instruction"),
+        ];
+        self.process(&instructions, &[&mint]).await?;
+        Ok(mint.pubkey())
+    }
+
+    async fn create_token_account(&mut self, mint: &Pubkey, owner: &Pubkey) -> Result<Pubkey, BanksClientError> {
+        let account = Keypair::new();
+        let payer = self.context.payer.pubkey();
+        let instructions = [
+            system_instruction::create_account(
+                &payer,
+                &account.pubkey(),
+                Rent::default().minimum_balance(spl_token::state::Account::LEN),
+                spl_token::state::Account::LEN as u64,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::initialize_account3(&spl_token::ID, &account.pubkey(), mint, owner)
+                .expect("valid token account instruction"),
+        ];
+        self.process(&instructions, &[&account]).await?;
+        Ok(account.pubkey())
+    }
+
+    async fn mint_to(&mut self, mint: &Pubkey, destination: &Pubkey, amount: u64) -> Result<(), BanksClientError> {
+        if amount == 0 {
+            return Ok(());
+        }
+        let instruction = spl_token::instruction::mint_to(
+            &spl_token::ID,
+            mint,
+            destination,
+            &self.mint_authority.pubkey(),
+            &[],
+            amount,
+        )
+        .expect("valid mint_to instruction");
+        let mint_authority = self.mint_authority.insecure_clone();
+        self.process(&[instruction], &[&mint_authority]).await
+    }
+
+    async fn process_as(
+        &mut self,
+        user: usize,
+        accounts: Vec<solana_sdk::instruction::AccountMeta>,
+        data: Vec<u8>,
+    ) -> Result<(), BanksClientError> {
+        let instruction = Instruction {
+            program_id: crate::ID,
+            accounts,
+            data,
+        };
+        let signer = self.users[user].keypair.insecure_clone();
+        self.process(&[instruction], &[&signer]).await
+    }
+
+    /// Send `instructions` paid by the context payer, signed by `signers` too
+    async fn process(&mut self, instructions: &[Instruction], signers: &[&Keypair]) -> Result<(), BanksClientError> {
+        let blockhash = self.context.get_new_latest_blockhash().await?;
+        let mut all_signers: Vec<&Keypair> = vec![&self.context.payer];
+        all_signers.extend_from_slice(signers);
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.context.payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+        self.context.banks_client.process_transaction(transaction).await
+    }
+
+    async fn account_data(&mut self, address: &Pubkey) -> Result<Vec<u8>, BanksClientError> {
+        self.context
+            .banks_client
+            .get_account(*address)
+            .await?
+            .map(|account| account.data)
+            .ok_or(BanksClientError::ClientError("account not found"))
+    }
+
+    async fn fetch_anchor_account<T: AccountDeserialize>(&mut self, address: &Pubkey) -> Result<T, BanksClientError> {
+        let data = self.account_data(address).await?;
+        T::try_deserialize(&mut data.as_slice())
+            .map_err(|err| decode_error(err.to_string()))
+    }
+}
+
+fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &crate::ID).0
+}
+
+fn protocol_treasury_address() -> Pubkey {
+    pda(&[ProtocolTreasury::SEED_PREFIX])
+}
+
+/// Protocol treasury written straight into genesis, since its initializer
+/// requires the program upgrade authority
+fn protocol_treasury_account() -> Account {
+    let (_, bump) = Pubkey::find_program_address(&[ProtocolTreasury::SEED_PREFIX], &crate::ID);
+    let treasury = ProtocolTreasury {
+        bump,
+        ..Default::default()
+    };
+    let mut data = Vec::with_capacity(ProtocolTreasury::LEN);
+    treasury
+        .try_serialize(&mut data)
+        .expect("protocol treasury serializes");
+    data.resize(ProtocolTreasury::LEN, 0);
+
+    Account {
+        lamports: Rent::default().minimum_balance(ProtocolTreasury::LEN),
+        data,
+        owner: crate::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn decode_error(message: String) -> BanksClientError {
+    BanksClientError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, message))
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum TimelockError {
+    #[msg("Invalid authority for this operation")]
+    InvalidAuthority,
+
+    #[msg("Delay is outside the allowed range")]
+    InvalidDelay,
+
+    #[msg("Transaction delay has not elapsed yet")]
+    NotReady,
+
+    #[msg("Transaction grace period has passed")]
+    Expired,
+
+    #[msg("Transaction was already executed")]
+    AlreadyExecuted,
+
+    #[msg("Transaction was cancelled")]
+    AlreadyCancelled,
+
+    #[msg("Transaction has too many accounts")]
+    TooManyAccounts,
+
+    #[msg("Transaction data is too large")]
+    DataTooLarge,
+
+    #[msg("Remaining accounts don't match the queued transaction")]
+    AccountMismatch,
+
+    #[msg("Math overflow occurred")]
+    MathOverflow,
+}
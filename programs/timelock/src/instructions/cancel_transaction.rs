@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{QueuedTransaction, Timelock};
+use crate::errors::TimelockError;
+
+/// Cancel a queued transaction (governance only)
+#[derive(Accounts)]
+pub struct CancelTransaction<'info> {
+    /// Governance authority
+    pub governance: Signer<'info>,
+
+    /// The timelock PDA
+    #[account(
+        seeds = [Timelock::SEED_PREFIX],
+        bump = timelock.bump,
+        has_one = governance @ TimelockError::InvalidAuthority
+    )]
+    pub timelock: Account<'info, Timelock>,
+
+    /// The transaction to cancel
+    #[account(
+        mut,
+        seeds = [
+            QueuedTransaction::SEED_PREFIX,
+            timelock.key().as_ref(),
+            &queued_transaction.id.to_le_bytes()
+        ],
+        bump = queued_transaction.bump,
+        has_one = timelock @ TimelockError::AccountMismatch
+    )]
+    pub queued_transaction: Account<'info, QueuedTransaction>,
+}
+
+/// Event emitted when a queued transaction is cancelled
+#[event]
+pub struct TransactionCancelledEvent {
+    pub id: u64,
+    pub timestamp: i64,
+}
+
+pub fn handler(ctx: Context<CancelTransaction>) -> Result<()> {
+    let queued = &mut ctx.accounts.queued_transaction;
+    require!(!queued.executed, TimelockError::AlreadyExecuted);
+    require!(!queued.cancelled, TimelockError::AlreadyCancelled);
+    queued.cancelled = true;
+
+    emit!(TransactionCancelledEvent {
+        id: queued.id,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Cancelled transaction {}", queued.id);
+
+    Ok(())
+}
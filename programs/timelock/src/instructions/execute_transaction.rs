@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+
+use crate::state::{QueuedTransaction, Timelock};
+use crate::errors::TimelockError;
+
+/// Execute a queued transaction once its delay has elapsed (permissionless)
+///
+/// Remaining accounts: the queued instruction's accounts in order, followed
+/// by the target program.
+#[derive(Accounts)]
+pub struct ExecuteTransaction<'info> {
+    /// Anyone may execute a ready transaction
+    pub executor: Signer<'info>,
+
+    /// The timelock PDA. Not `mut`: the executed instruction may itself
+    /// update the timelock, and Anchor must not write a stale copy back.
+    #[account(
+        seeds = [Timelock::SEED_PREFIX],
+        bump = timelock.bump
+    )]
+    pub timelock: Account<'info, Timelock>,
+
+    /// The transaction to execute
+    #[account(
+        mut,
+        seeds = [
+            QueuedTransaction::SEED_PREFIX,
+            timelock.key().as_ref(),
+            &queued_transaction.id.to_le_bytes()
+        ],
+        bump = queued_transaction.bump,
+        has_one = timelock @ TimelockError::AccountMismatch
+    )]
+    pub queued_transaction: Account<'info, QueuedTransaction>,
+}
+
+/// Event emitted when a queued transaction is executed
+#[event]
+pub struct TransactionExecutedEvent {
+    pub id: u64,
+    pub program_id: Pubkey,
+    pub executor: Pubkey,
+    pub timestamp: i64,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ExecuteTransaction<'info>>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let queued = &mut ctx.accounts.queued_transaction;
+    queued.check_executable(now)?;
+
+    // Remaining accounts must mirror the queued metas, then the program
+    let remaining = ctx.remaining_accounts;
+    require!(
+        remaining.len() == queued.accounts.len() + 1,
+        TimelockError::AccountMismatch
+    );
+    for (meta, info) in queued.accounts.iter().zip(remaining.iter()) {
+        require_keys_eq!(meta.pubkey, info.key(), TimelockError::AccountMismatch);
+    }
+    require_keys_eq!(
+        remaining[queued.accounts.len()].key(),
+        queued.program_id,
+        TimelockError::AccountMismatch
+    );
+
+    // Mark executed before the CPI so the transaction can't be replayed
+    queued.executed = true;
+
+    let ix = Instruction {
+        program_id: queued.program_id,
+        accounts: queued.accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.pubkey,
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data: queued.data.clone(),
+    };
+
+    let bump = ctx.accounts.timelock.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[Timelock::SEED_PREFIX, &[bump]]];
+    invoke_signed(&ix, remaining, signer_seeds)?;
+
+    emit!(TransactionExecutedEvent {
+        id: queued.id,
+        program_id: queued.program_id,
+        executor: ctx.accounts.executor.key(),
+        timestamp: now,
+    });
+
+    msg!("Executed transaction {}", queued.id);
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::program::Timelock as TimelockProgram;
+use crate::state::Timelock;
+use crate::errors::TimelockError;
+
+/// Create the timelock (program upgrade authority only)
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    /// Program upgrade authority
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The timelock PDA
+    #[account(
+        init,
+        payer = payer,
+        space = Timelock::LEN,
+        seeds = [Timelock::SEED_PREFIX],
+        bump
+    )]
+    pub timelock: Account<'info, Timelock>,
+
+    /// This program
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ TimelockError::InvalidAuthority
+    )]
+    pub program: Program<'info, TimelockProgram>,
+
+    /// This program's data account, proving the upgrade authority
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(payer.key()) @ TimelockError::InvalidAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<Initialize>, governance: Pubkey, delay: i64) -> Result<()> {
+    Timelock::validate_delay(delay)?;
+
+    let timelock = &mut ctx.accounts.timelock;
+    timelock.governance = governance;
+    timelock.delay = delay;
+    timelock.transaction_count = 0;
+    timelock.bump = ctx.bumps.timelock;
+
+    msg!("Timelock initialized");
+    msg!("Governance: {}", governance);
+    msg!("Delay: {} seconds", delay);
+
+    Ok(())
+}
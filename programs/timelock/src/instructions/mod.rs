@@ -0,0 +1,31 @@
+pub mod initialize;
+pub mod queue_transaction;
+pub mod execute_transaction;
+pub mod cancel_transaction;
+pub mod update_config;
+
+// Handlers are called by module path; only accounts, params and events
+// are re-exported so the per-module `handler` fns don't collide
+pub use initialize::Initialize;
+pub use queue_transaction::{QueueTransaction, TransactionQueuedEvent};
+pub use execute_transaction::{ExecuteTransaction, TransactionExecutedEvent};
+pub use cancel_transaction::{CancelTransaction, TransactionCancelledEvent};
+pub use update_config::{UpdateConfig, TimelockConfigUpdatedEvent};
+
+// Client account modules generated by `#[derive(Accounts)]`, which
+// `#[program]` expects at the crate root
+pub(crate) use initialize::__client_accounts_initialize;
+pub(crate) use queue_transaction::__client_accounts_queue_transaction;
+pub(crate) use execute_transaction::__client_accounts_execute_transaction;
+pub(crate) use cancel_transaction::__client_accounts_cancel_transaction;
+pub(crate) use update_config::__client_accounts_update_config;
+#[cfg(feature = "cpi")]
+pub(crate) use initialize::__cpi_client_accounts_initialize;
+#[cfg(feature = "cpi")]
+pub(crate) use queue_transaction::__cpi_client_accounts_queue_transaction;
+#[cfg(feature = "cpi")]
+pub(crate) use execute_transaction::__cpi_client_accounts_execute_transaction;
+#[cfg(feature = "cpi")]
+pub(crate) use cancel_transaction::__cpi_client_accounts_cancel_transaction;
+#[cfg(feature = "cpi")]
+pub(crate) use update_config::__cpi_client_accounts_update_config;
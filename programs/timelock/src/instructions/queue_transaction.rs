@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{QueuedTransaction, Timelock, TransactionAccount, MAX_ACCOUNTS, MAX_DATA_LEN};
+use crate::errors::TimelockError;
+
+/// Queue a governance-approved instruction
+#[derive(Accounts)]
+#[instruction(program_id: Pubkey, accounts: Vec<TransactionAccount>, data: Vec<u8>)]
+pub struct QueueTransaction<'info> {
+    /// Governance authority (pays for the queued transaction account)
+    #[account(mut)]
+    pub governance: Signer<'info>,
+
+    /// The timelock PDA
+    #[account(
+        mut,
+        seeds = [Timelock::SEED_PREFIX],
+        bump = timelock.bump,
+        has_one = governance @ TimelockError::InvalidAuthority
+    )]
+    pub timelock: Account<'info, Timelock>,
+
+    /// The queued transaction
+    #[account(
+        init,
+        payer = governance,
+        space = QueuedTransaction::space(accounts.len(), data.len()),
+        seeds = [
+            QueuedTransaction::SEED_PREFIX,
+            timelock.key().as_ref(),
+            &timelock.transaction_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub queued_transaction: Account<'info, QueuedTransaction>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Event emitted when a transaction is queued
+#[event]
+pub struct TransactionQueuedEvent {
+    pub id: u64,
+    pub program_id: Pubkey,
+    pub eta: i64,
+    pub timestamp: i64,
+}
+
+pub fn handler(
+    ctx: Context<QueueTransaction>,
+    program_id: Pubkey,
+    accounts: Vec<TransactionAccount>,
+    data: Vec<u8>,
+) -> Result<()> {
+    require!(accounts.len() <= MAX_ACCOUNTS, TimelockError::TooManyAccounts);
+    require!(data.len() <= MAX_DATA_LEN, TimelockError::DataTooLarge);
+
+    let now = Clock::get()?.unix_timestamp;
+    let timelock = &mut ctx.accounts.timelock;
+    let eta = now
+        .checked_add(timelock.delay)
+        .ok_or(TimelockError::MathOverflow)?;
+
+    let queued = &mut ctx.accounts.queued_transaction;
+    queued.timelock = timelock.key();
+    queued.id = timelock.transaction_count;
+    queued.program_id = program_id;
+    queued.accounts = accounts;
+    queued.data = data;
+    queued.eta = eta;
+    queued.executed = false;
+    queued.cancelled = false;
+    queued.bump = ctx.bumps.queued_transaction;
+
+    timelock.transaction_count = timelock.transaction_count
+        .checked_add(1)
+        .ok_or(TimelockError::MathOverflow)?;
+
+    emit!(TransactionQueuedEvent {
+        id: queued.id,
+        program_id,
+        eta,
+        timestamp: now,
+    });
+
+    msg!("Queued transaction {} for {}", queued.id, program_id);
+    msg!("Executable from: {}", eta);
+
+    Ok(())
+}
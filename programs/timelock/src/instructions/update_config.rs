@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Timelock;
+
+/// Change the timelock's governance or delay. The timelock PDA must sign,
+/// so this only runs as a queued transaction after the current delay.
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    /// The timelock PDA, signing via `execute_transaction`
+    #[account(
+        mut,
+        signer,
+        seeds = [Timelock::SEED_PREFIX],
+        bump = timelock.bump
+    )]
+    pub timelock: Account<'info, Timelock>,
+}
+
+/// Event emitted when the timelock config changes
+#[event]
+pub struct TimelockConfigUpdatedEvent {
+    pub governance: Pubkey,
+    pub delay: i64,
+    pub timestamp: i64,
+}
+
+pub fn handler(ctx: Context<UpdateConfig>, governance: Option<Pubkey>, delay: Option<i64>) -> Result<()> {
+    let timelock = &mut ctx.accounts.timelock;
+
+    if let Some(governance) = governance {
+        timelock.governance = governance;
+        msg!("Governance: {}", governance);
+    }
+
+    if let Some(delay) = delay {
+        Timelock::validate_delay(delay)?;
+        timelock.delay = delay;
+        msg!("Delay: {} seconds", delay);
+    }
+
+    emit!(TimelockConfigUpdatedEvent {
+        governance: timelock.governance,
+        delay: timelock.delay,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
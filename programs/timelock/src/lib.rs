@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+
+pub mod errors;
+pub mod instructions;
+pub mod state;
+
+use instructions::*;
+use state::*;
+
+declare_id!("KR8TivTimeLock11111111111111111111111111111");
+
+#[program]
+pub mod timelock {
+    use super::*;
+
+    /// Create the timelock
+    ///
+    /// # Arguments
+    /// * `ctx` - Initialize context
+    /// * `governance` - Authority allowed to queue and cancel transactions
+    /// * `delay` - Seconds between queueing and execution
+    pub fn initialize(ctx: Context<Initialize>, governance: Pubkey, delay: i64) -> Result<()> {
+        instructions::initialize::handler(ctx, governance, delay)
+    }
+
+    /// Queue an instruction to run after the delay (governance only)
+    ///
+    /// # Arguments
+    /// * `ctx` - QueueTransaction context
+    /// * `program_id` - Target program
+    /// * `accounts` - Instruction accounts; the timelock PDA may be a signer
+    /// * `data` - Instruction data
+    pub fn queue_transaction(
+        ctx: Context<QueueTransaction>,
+        program_id: Pubkey,
+        accounts: Vec<TransactionAccount>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        instructions::queue_transaction::handler(ctx, program_id, accounts, data)
+    }
+
+    /// Execute a queued instruction once ready (permissionless)
+    ///
+    /// # Arguments
+    /// * `ctx` - ExecuteTransaction context; remaining accounts are the
+    ///   queued accounts followed by the target program
+    pub fn execute_transaction<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteTransaction<'info>>,
+    ) -> Result<()> {
+        instructions::execute_transaction::handler(ctx)
+    }
+
+    /// Cancel a queued instruction (governance only)
+    ///
+    /// # Arguments
+    /// * `ctx` - CancelTransaction context
+    pub fn cancel_transaction(ctx: Context<CancelTransaction>) -> Result<()> {
+        instructions::cancel_transaction::handler(ctx)
+    }
+
+    /// Change governance or the delay (timelock PDA only, i.e. via a
+    /// queued transaction)
+    ///
+    /// # Arguments
+    /// * `ctx` - UpdateConfig context
+    /// * `governance` - New governance authority
+    /// * `delay` - New delay in seconds
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        governance: Option<Pubkey>,
+        delay: Option<i64>,
+    ) -> Result<()> {
+        instructions::update_config::handler(ctx, governance, delay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay_bounds() {
+        assert!(Timelock::validate_delay(MIN_DELAY).is_ok());
+        assert!(Timelock::validate_delay(MAX_DELAY).is_ok());
+        assert!(Timelock::validate_delay(MIN_DELAY - 1).is_err());
+        assert!(Timelock::validate_delay(MAX_DELAY + 1).is_err());
+    }
+
+    #[test]
+    fn test_execution_window() {
+        let queued = QueuedTransaction { eta: 1_000, ..Default::default() };
+        assert!(queued.check_executable(999).is_err());
+        assert!(queued.check_executable(1_000).is_ok());
+        assert!(queued.check_executable(1_000 + GRACE_PERIOD).is_ok());
+        assert!(queued.check_executable(1_001 + GRACE_PERIOD).is_err());
+
+        let executed = QueuedTransaction { eta: 0, executed: true, ..Default::default() };
+        assert!(executed.check_executable(1).is_err());
+        let cancelled = QueuedTransaction { eta: 0, cancelled: true, ..Default::default() };
+        assert!(cancelled.check_executable(1).is_err());
+    }
+
+    #[test]
+    fn test_queued_transaction_space() {
+        let queued = QueuedTransaction {
+            accounts: vec![TransactionAccount::default(); 3],
+            data: vec![0; 40],
+            ..Default::default()
+        };
+        let serialized = queued.try_to_vec().unwrap();
+        assert_eq!(QueuedTransaction::space(3, 40), 8 + serialized.len());
+    }
+}
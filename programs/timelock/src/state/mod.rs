@@ -0,0 +1,134 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::TimelockError;
+
+/// Shortest delay governance may configure (1 hour)
+pub const MIN_DELAY: i64 = 60 * 60;
+
+/// Longest delay governance may configure (30 days)
+pub const MAX_DELAY: i64 = 30 * 24 * 60 * 60;
+
+/// How long a ready transaction stays executable (14 days)
+pub const GRACE_PERIOD: i64 = 14 * 24 * 60 * 60;
+
+/// Most accounts a queued transaction may reference
+pub const MAX_ACCOUNTS: usize = 24;
+
+/// Largest instruction data a queued transaction may carry
+pub const MAX_DATA_LEN: usize = 512;
+
+/// The timelock. Its PDA is the signer handed to target programs, so it is
+/// what gets set as e.g. a stake pool's `authority`.
+#[account]
+#[derive(Default)]
+pub struct Timelock {
+    /// Governance authority allowed to queue and cancel transactions
+    pub governance: Pubkey,
+
+    /// Seconds between queueing and execution
+    pub delay: i64,
+
+    /// Number of transactions queued (next transaction id)
+    pub transaction_count: u64,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+
+    /// Reserved space for future upgrades
+    pub _reserved: [u8; 32],
+}
+
+impl Timelock {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // governance
+        8 +   // delay
+        8 +   // transaction_count
+        1 +   // bump
+        32;   // _reserved
+
+    pub const SEED_PREFIX: &'static [u8] = b"timelock";
+
+    /// Reject delays outside [MIN_DELAY, MAX_DELAY]
+    pub fn validate_delay(delay: i64) -> Result<()> {
+        require!(
+            (MIN_DELAY..=MAX_DELAY).contains(&delay),
+            TimelockError::InvalidDelay
+        );
+        Ok(())
+    }
+}
+
+/// An account referenced by a queued instruction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct TransactionAccount {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl TransactionAccount {
+    pub const LEN: usize = 32 + 1 + 1;
+}
+
+/// A governance-approved instruction waiting out the delay
+#[account]
+#[derive(Default)]
+pub struct QueuedTransaction {
+    /// The timelock this transaction belongs to
+    pub timelock: Pubkey,
+
+    /// Sequential transaction id
+    pub id: u64,
+
+    /// Program the instruction is sent to
+    pub program_id: Pubkey,
+
+    /// Instruction accounts
+    pub accounts: Vec<TransactionAccount>,
+
+    /// Instruction data
+    pub data: Vec<u8>,
+
+    /// Earliest execution time
+    pub eta: i64,
+
+    /// Whether the transaction was executed
+    pub executed: bool,
+
+    /// Whether governance cancelled the transaction
+    pub cancelled: bool,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl QueuedTransaction {
+    pub const SEED_PREFIX: &'static [u8] = b"queued_transaction";
+
+    /// Account size for an instruction with `num_accounts` accounts and
+    /// `data_len` bytes of data
+    pub fn space(num_accounts: usize, data_len: usize) -> usize {
+        8 +   // discriminator
+        32 +  // timelock
+        8 +   // id
+        32 +  // program_id
+        4 + TransactionAccount::LEN * num_accounts + // accounts
+        4 + data_len + // data
+        8 +   // eta
+        1 +   // executed
+        1 +   // cancelled
+        1     // bump
+    }
+
+    /// Check the transaction can be executed at `now`
+    pub fn check_executable(&self, now: i64) -> Result<()> {
+        require!(!self.executed, TimelockError::AlreadyExecuted);
+        require!(!self.cancelled, TimelockError::AlreadyCancelled);
+        require!(now >= self.eta, TimelockError::NotReady);
+        require!(
+            now <= self.eta.saturating_add(GRACE_PERIOD),
+            TimelockError::Expired
+        );
+        Ok(())
+    }
+}
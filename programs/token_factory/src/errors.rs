@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum FactoryError {
+    #[msg("Initial supply must be greater than zero")]
+    InvalidSupply,
+
+    #[msg("Decimals must be between 0 and 9")]
+    InvalidDecimals,
+
+    #[msg("Token name is too long - maximum 32 bytes")]
+    NameTooLong,
+
+    #[msg("Token symbol is too long - maximum 10 bytes")]
+    SymbolTooLong,
+
+    #[msg("Metadata URI is too long - maximum 200 bytes")]
+    UriTooLong,
+
+    #[msg("Account is not the Metaplex token metadata program")]
+    InvalidMetadataProgram,
+
+    #[msg("Math overflow occurred")]
+    MathOverflow,
+}
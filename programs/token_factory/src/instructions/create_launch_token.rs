@@ -0,0 +1,186 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, spl_token::instruction::AuthorityType, Mint, MintTo, SetAuthority, Token, TokenAccount};
+
+use crate::state::LaunchToken;
+use crate::errors::FactoryError;
+use crate::metadata::{self, CreateMetadataV3, METADATA_PROGRAM_ID, METADATA_SEED};
+
+/// Create a new launch token with Metaplex metadata
+#[derive(Accounts)]
+#[instruction(params: CreateLaunchTokenParams)]
+pub struct CreateLaunchToken<'info> {
+    /// Project wallet creating the token (pays for all accounts)
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// The new token mint
+    #[account(
+        init,
+        payer = creator,
+        mint::decimals = params.decimals,
+        mint::authority = creator,
+        mint::freeze_authority = creator
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// Creator's token account receiving the initial supply
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = creator
+    )]
+    pub creator_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Metaplex metadata PDA for the mint
+    /// CHECK: created and validated by the token metadata program
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, METADATA_PROGRAM_ID.as_ref(), mint.key().as_ref()],
+        seeds::program = METADATA_PROGRAM_ID,
+        bump
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
+    /// Launch registration record
+    #[account(
+        init,
+        payer = creator,
+        space = LaunchToken::LEN,
+        seeds = [LaunchToken::SEED_PREFIX, mint.key().as_ref()],
+        bump
+    )]
+    pub launch_token: Box<Account<'info, LaunchToken>>,
+
+    /// Metaplex token metadata program
+    /// CHECK: address checked against METADATA_PROGRAM_ID
+    #[account(address = METADATA_PROGRAM_ID @ FactoryError::InvalidMetadataProgram)]
+    pub metadata_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Parameters for a new launch token
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CreateLaunchTokenParams {
+    /// Token name (max 32 bytes)
+    pub name: String,
+    /// Token symbol (max 10 bytes)
+    pub symbol: String,
+    /// Metadata JSON URI (max 200 bytes)
+    pub uri: String,
+    /// Mint decimals (max 9)
+    pub decimals: u8,
+    /// Supply minted to the creator, in smallest units
+    pub initial_supply: u64,
+    /// Revoke the mint authority after minting (fixed supply)
+    pub revoke_mint_authority: bool,
+    /// Revoke the freeze authority
+    pub revoke_freeze_authority: bool,
+    /// Whether the metadata can be updated later by the creator
+    pub metadata_mutable: bool,
+}
+
+/// Event emitted when a launch token is created
+#[event]
+pub struct LaunchTokenCreatedEvent {
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub decimals: u8,
+    pub initial_supply: u64,
+    pub mint_authority_revoked: bool,
+    pub freeze_authority_revoked: bool,
+    pub timestamp: i64,
+}
+
+pub fn handler(ctx: Context<CreateLaunchToken>, params: CreateLaunchTokenParams) -> Result<()> {
+    require!(params.initial_supply > 0, FactoryError::InvalidSupply);
+    require!(params.decimals <= 9, FactoryError::InvalidDecimals);
+    metadata::validate_metadata(&params.name, &params.symbol, &params.uri)?;
+
+    let creator_info = ctx.accounts.creator.to_account_info();
+    let mint_info = ctx.accounts.mint.to_account_info();
+
+    // Mint the initial supply to the creator
+    let mint_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        MintTo {
+            mint: mint_info.clone(),
+            to: ctx.accounts.creator_token_account.to_account_info(),
+            authority: creator_info.clone(),
+        },
+    );
+    token::mint_to(mint_ctx, params.initial_supply)?;
+
+    // Metadata must be created while the creator still holds the mint authority
+    metadata::create_metadata_v3(
+        CreateMetadataV3 {
+            metadata: &ctx.accounts.metadata.to_account_info(),
+            mint: &mint_info,
+            mint_authority: &creator_info,
+            payer: &creator_info,
+            update_authority: &creator_info,
+            system_program: &ctx.accounts.system_program.to_account_info(),
+            metadata_program: &ctx.accounts.metadata_program.to_account_info(),
+        },
+        &params.name,
+        &params.symbol,
+        &params.uri,
+        params.metadata_mutable,
+    )?;
+
+    if params.revoke_mint_authority {
+        revoke_authority(&ctx, AuthorityType::MintTokens)?;
+    }
+    if params.revoke_freeze_authority {
+        revoke_authority(&ctx, AuthorityType::FreezeAccount)?;
+    }
+
+    let clock = Clock::get()?;
+    let launch_token = &mut ctx.accounts.launch_token;
+    launch_token.creator = ctx.accounts.creator.key();
+    launch_token.mint = ctx.accounts.mint.key();
+    launch_token.decimals = params.decimals;
+    launch_token.initial_supply = params.initial_supply;
+    launch_token.mint_authority_revoked = params.revoke_mint_authority;
+    launch_token.freeze_authority_revoked = params.revoke_freeze_authority;
+    launch_token.created_at = clock.unix_timestamp;
+    launch_token.bump = ctx.bumps.launch_token;
+
+    emit!(LaunchTokenCreatedEvent {
+        creator: launch_token.creator,
+        mint: launch_token.mint,
+        name: params.name.clone(),
+        symbol: params.symbol.clone(),
+        uri: params.uri.clone(),
+        decimals: params.decimals,
+        initial_supply: params.initial_supply,
+        mint_authority_revoked: params.revoke_mint_authority,
+        freeze_authority_revoked: params.revoke_freeze_authority,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Launch token created: {}", launch_token.mint);
+    msg!("Name: {} ({})", params.name, params.symbol);
+    msg!("Initial supply: {}", params.initial_supply);
+
+    Ok(())
+}
+
+/// Permanently remove one of the creator's authorities over the mint
+fn revoke_authority(ctx: &Context<CreateLaunchToken>, authority_type: AuthorityType) -> Result<()> {
+    let revoke_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        SetAuthority {
+            current_authority: ctx.accounts.creator.to_account_info(),
+            account_or_mint: ctx.accounts.mint.to_account_info(),
+        },
+    );
+    token::set_authority(revoke_ctx, authority_type, None)
+}
@@ -0,0 +1,3 @@
+pub mod create_launch_token;
+
+pub use create_launch_token::*;
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+pub mod errors;
+pub mod instructions;
+pub mod metadata;
+pub mod state;
+
+use instructions::*;
+
+declare_id!("KR8TivFactory111111111111111111111111111111");
+
+#[program]
+pub mod token_factory {
+    use super::*;
+
+    /// Create a launch token: mint, initial supply, Metaplex metadata and a
+    /// launch registration record, optionally revoking mint/freeze authority
+    ///
+    /// # Arguments
+    /// * `ctx` - CreateLaunchToken context
+    /// * `params` - Metadata, decimals, supply and authority options
+    pub fn create_launch_token(
+        ctx: Context<CreateLaunchToken>,
+        params: CreateLaunchTokenParams,
+    ) -> Result<()> {
+        instructions::create_launch_token::handler(ctx, params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metadata_limits() {
+        assert!(metadata::validate_metadata("KR8TIV", "KR8", "https://kr8tiv.io/t.json").is_ok());
+        assert!(metadata::validate_metadata(&"n".repeat(33), "KR8", "").is_err());
+        assert!(metadata::validate_metadata("KR8TIV", &"s".repeat(11), "").is_err());
+        assert!(metadata::validate_metadata("KR8TIV", "KR8", &"u".repeat(201)).is_err());
+    }
+
+    #[test]
+    fn test_create_metadata_v3_encoding() {
+        let data = metadata::create_metadata_v3_data("Ab", "C", "", true);
+        assert_eq!(
+            data,
+            vec![
+                33, // instruction
+                2, 0, 0, 0, b'A', b'b', // name
+                1, 0, 0, 0, b'C', // symbol
+                0, 0, 0, 0, // uri
+                0, 0, // seller_fee_basis_points
+                0, 0, 0, // creators, collection, uses
+                1, // is_mutable
+                0, // collection_details
+            ]
+        );
+    }
+}
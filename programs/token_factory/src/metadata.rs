@@ -0,0 +1,110 @@
+//! Minimal CPI into the Metaplex token metadata program.
+//!
+//! Only `CreateMetadataAccountV3` is needed, so the instruction is encoded
+//! here rather than pulling in the full mpl-token-metadata crate.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+
+use crate::errors::FactoryError;
+
+/// Metaplex token metadata program id
+pub mod mpl_token_metadata {
+    anchor_lang::declare_id!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+}
+
+/// Metaplex token metadata program
+pub const METADATA_PROGRAM_ID: Pubkey = mpl_token_metadata::ID;
+
+/// Metadata PDA seed prefix
+pub const METADATA_SEED: &[u8] = b"metadata";
+
+/// Metaplex field limits
+pub const MAX_NAME_LENGTH: usize = 32;
+pub const MAX_SYMBOL_LENGTH: usize = 10;
+pub const MAX_URI_LENGTH: usize = 200;
+
+/// `CreateMetadataAccountV3` instruction index
+const CREATE_METADATA_ACCOUNT_V3: u8 = 33;
+
+/// Reject metadata strings Metaplex would refuse
+pub fn validate_metadata(name: &str, symbol: &str, uri: &str) -> Result<()> {
+    require!(name.len() <= MAX_NAME_LENGTH, FactoryError::NameTooLong);
+    require!(symbol.len() <= MAX_SYMBOL_LENGTH, FactoryError::SymbolTooLong);
+    require!(uri.len() <= MAX_URI_LENGTH, FactoryError::UriTooLong);
+    Ok(())
+}
+
+/// Borsh-encode `CreateMetadataAccountV3` args with no creators,
+/// collection, uses or collection details
+pub fn create_metadata_v3_data(name: &str, symbol: &str, uri: &str, is_mutable: bool) -> Vec<u8> {
+    let mut data = Vec::with_capacity(1 + 12 + name.len() + symbol.len() + uri.len() + 7);
+    data.push(CREATE_METADATA_ACCOUNT_V3);
+    for value in [name, symbol, uri] {
+        data.extend_from_slice(&(value.len() as u32).to_le_bytes());
+        data.extend_from_slice(value.as_bytes());
+    }
+    data.extend_from_slice(&0u16.to_le_bytes()); // seller_fee_basis_points
+    data.push(0); // creators: None
+    data.push(0); // collection: None
+    data.push(0); // uses: None
+    data.push(is_mutable as u8);
+    data.push(0); // collection_details: None
+    data
+}
+
+/// Accounts for `create_metadata_v3`
+pub struct CreateMetadataV3<'a, 'info> {
+    pub metadata: &'a AccountInfo<'info>,
+    pub mint: &'a AccountInfo<'info>,
+    pub mint_authority: &'a AccountInfo<'info>,
+    pub payer: &'a AccountInfo<'info>,
+    pub update_authority: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub metadata_program: &'a AccountInfo<'info>,
+}
+
+/// Create the Metaplex metadata account for `mint`.
+/// The mint authority, payer and update authority must all sign.
+pub fn create_metadata_v3(
+    accounts: CreateMetadataV3,
+    name: &str,
+    symbol: &str,
+    uri: &str,
+    is_mutable: bool,
+) -> Result<()> {
+    require_keys_eq!(
+        accounts.metadata_program.key(),
+        METADATA_PROGRAM_ID,
+        FactoryError::InvalidMetadataProgram
+    );
+
+    let ix = Instruction {
+        program_id: METADATA_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(accounts.metadata.key(), false),
+            AccountMeta::new_readonly(accounts.mint.key(), false),
+            AccountMeta::new_readonly(accounts.mint_authority.key(), true),
+            AccountMeta::new(accounts.payer.key(), true),
+            AccountMeta::new_readonly(accounts.update_authority.key(), true),
+            AccountMeta::new_readonly(accounts.system_program.key(), false),
+        ],
+        data: create_metadata_v3_data(name, symbol, uri, is_mutable),
+    };
+
+    invoke(
+        &ix,
+        &[
+            accounts.metadata.clone(),
+            accounts.mint.clone(),
+            accounts.mint_authority.clone(),
+            accounts.payer.clone(),
+            accounts.update_authority.clone(),
+            accounts.system_program.clone(),
+            accounts.metadata_program.clone(),
+        ],
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+/// Registration record for a token created through the factory
+#[account]
+#[derive(Default)]
+pub struct LaunchToken {
+    /// Project wallet that created the token
+    pub creator: Pubkey,
+
+    /// The token mint
+    pub mint: Pubkey,
+
+    /// Mint decimals
+    pub decimals: u8,
+
+    /// Supply minted to the creator at launch
+    pub initial_supply: u64,
+
+    /// Whether the mint authority was revoked (fixed supply)
+    pub mint_authority_revoked: bool,
+
+    /// Whether the freeze authority was revoked
+    pub freeze_authority_revoked: bool,
+
+    /// Launch timestamp
+    pub created_at: i64,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+
+    /// Reserved space for future upgrades
+    pub _reserved: [u8; 32],
+}
+
+impl LaunchToken {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // creator
+        32 +  // mint
+        1 +   // decimals
+        8 +   // initial_supply
+        1 +   // mint_authority_revoked
+        1 +   // freeze_authority_revoked
+        8 +   // created_at
+        1 +   // bump
+        32;   // _reserved
+
+    pub const SEED_PREFIX: &'static [u8] = b"launch_token";
+}
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum TreasuryError {
+    #[msg("Invalid amount - must be greater than zero")]
+    InvalidAmount,
+
+    #[msg("Invalid authority for this operation")]
+    InvalidAuthority,
+
+    #[msg("Insufficient treasury balance for this spend")]
+    InsufficientFunds,
+
+    #[msg("Token account does not match the expected mint or owner")]
+    InvalidTokenAccount,
+
+    #[msg("Math overflow occurred")]
+    MathOverflow,
+}
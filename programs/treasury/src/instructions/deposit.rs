@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::state::{FeeSource, Treasury};
+use crate::errors::TreasuryError;
+
+/// Deposit SOL into the treasury
+#[derive(Accounts)]
+pub struct DepositSol<'info> {
+    /// Depositor paying the SOL
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    /// The treasury PDA
+    #[account(
+        mut,
+        seeds = [Treasury::SEED_PREFIX],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Deposit SPL tokens into a treasury-owned token account
+#[derive(Accounts)]
+pub struct DepositToken<'info> {
+    /// Depositor paying the tokens
+    pub depositor: Signer<'info>,
+
+    /// The treasury PDA
+    #[account(
+        seeds = [Treasury::SEED_PREFIX],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// Depositor's token account
+    #[account(mut)]
+    pub depositor_token_account: Account<'info, TokenAccount>,
+
+    /// Treasury's token account for this mint
+    #[account(
+        mut,
+        constraint = treasury_token_account.owner == treasury.key() @ TreasuryError::InvalidTokenAccount,
+        constraint = treasury_token_account.mint == depositor_token_account.mint @ TreasuryError::InvalidTokenAccount
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Event emitted for every deposit
+#[event]
+pub struct DepositEvent {
+    pub depositor: Pubkey,
+    /// `Pubkey::default()` for SOL deposits
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub source: FeeSource,
+    pub timestamp: i64,
+}
+
+pub fn deposit_sol_handler(ctx: Context<DepositSol>, amount: u64, source: FeeSource) -> Result<()> {
+    require!(amount > 0, TreasuryError::InvalidAmount);
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        system_program::Transfer {
+            from: ctx.accounts.depositor.to_account_info(),
+            to: ctx.accounts.treasury.to_account_info(),
+        },
+    );
+    system_program::transfer(transfer_ctx, amount)?;
+
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.total_deposited_lamports = treasury.total_deposited_lamports
+        .checked_add(amount)
+        .ok_or(TreasuryError::MathOverflow)?;
+
+    emit!(DepositEvent {
+        depositor: ctx.accounts.depositor.key(),
+        mint: Pubkey::default(),
+        amount,
+        source,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Deposited {} lamports ({:?})", amount, source);
+
+    Ok(())
+}
+
+pub fn deposit_token_handler(ctx: Context<DepositToken>, amount: u64, source: FeeSource) -> Result<()> {
+    require!(amount > 0, TreasuryError::InvalidAmount);
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.depositor_token_account.to_account_info(),
+            to: ctx.accounts.treasury_token_account.to_account_info(),
+            authority: ctx.accounts.depositor.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    emit!(DepositEvent {
+        depositor: ctx.accounts.depositor.key(),
+        mint: ctx.accounts.treasury_token_account.mint,
+        amount,
+        source,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Deposited {} tokens ({:?})", amount, source);
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::program::Treasury as TreasuryProgram;
+use crate::state::Treasury;
+use crate::errors::TreasuryError;
+
+/// Create the treasury (program upgrade authority only)
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    /// Program upgrade authority
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The treasury PDA
+    #[account(
+        init,
+        payer = payer,
+        space = Treasury::LEN,
+        seeds = [Treasury::SEED_PREFIX],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// This program
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ TreasuryError::InvalidAuthority
+    )]
+    pub program: Program<'info, TreasuryProgram>,
+
+    /// This program's data account, proving the upgrade authority
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(payer.key()) @ TreasuryError::InvalidAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<Initialize>, authority: Pubkey) -> Result<()> {
+    let treasury = &mut ctx.accounts.treasury;
+
+    treasury.authority = authority;
+    treasury.total_deposited_lamports = 0;
+    treasury.total_spent_lamports = 0;
+    treasury.spend_count = 0;
+    treasury.bump = ctx.bumps.treasury;
+
+    msg!("Treasury initialized");
+    msg!("Spend authority: {}", authority);
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+pub mod initialize;
+pub mod deposit;
+pub mod spend;
+pub mod set_authority;
+
+// Handlers are called by module path; only accounts, params and events
+// are re-exported so the per-module `handler` fns don't collide
+pub use initialize::Initialize;
+pub use deposit::{DepositSol, DepositToken, DepositEvent};
+pub use spend::{SpendSol, SpendToken, SpendEvent};
+pub use set_authority::{SetAuthority, AuthorityChangedEvent};
+
+// Client account modules generated by `#[derive(Accounts)]`, which
+// `#[program]` expects at the crate root
+pub(crate) use initialize::__client_accounts_initialize;
+pub(crate) use deposit::{__client_accounts_deposit_sol, __client_accounts_deposit_token};
+pub(crate) use spend::{__client_accounts_spend_sol, __client_accounts_spend_token};
+pub(crate) use set_authority::__client_accounts_set_authority;
+#[cfg(feature = "cpi")]
+pub(crate) use initialize::__cpi_client_accounts_initialize;
+#[cfg(feature = "cpi")]
+pub(crate) use deposit::{__cpi_client_accounts_deposit_sol, __cpi_client_accounts_deposit_token};
+#[cfg(feature = "cpi")]
+pub(crate) use spend::{__cpi_client_accounts_spend_sol, __cpi_client_accounts_spend_token};
+#[cfg(feature = "cpi")]
+pub(crate) use set_authority::__cpi_client_accounts_set_authority;
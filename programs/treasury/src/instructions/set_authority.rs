@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Treasury;
+use crate::errors::TreasuryError;
+
+/// Hand spend approval to a new authority (e.g. a governance PDA)
+#[derive(Accounts)]
+pub struct SetAuthority<'info> {
+    /// Current spend authority
+    pub authority: Signer<'info>,
+
+    /// The treasury PDA
+    #[account(
+        mut,
+        seeds = [Treasury::SEED_PREFIX],
+        bump = treasury.bump,
+        has_one = authority @ TreasuryError::InvalidAuthority
+    )]
+    pub treasury: Account<'info, Treasury>,
+}
+
+/// Event emitted when the spend authority changes
+#[event]
+pub struct AuthorityChangedEvent {
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+pub fn handler(ctx: Context<SetAuthority>, new_authority: Pubkey) -> Result<()> {
+    let treasury = &mut ctx.accounts.treasury;
+    let previous_authority = treasury.authority;
+    treasury.authority = new_authority;
+
+    emit!(AuthorityChangedEvent {
+        previous_authority,
+        new_authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Spend authority: {} -> {}", previous_authority, new_authority);
+
+    Ok(())
+}
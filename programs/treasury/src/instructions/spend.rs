@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::state::Treasury;
+use crate::errors::TreasuryError;
+
+/// Release SOL from the treasury (spend authority only)
+#[derive(Accounts)]
+pub struct SpendSol<'info> {
+    /// Spend authority approving the release
+    pub authority: Signer<'info>,
+
+    /// The treasury PDA
+    #[account(
+        mut,
+        seeds = [Treasury::SEED_PREFIX],
+        bump = treasury.bump,
+        has_one = authority @ TreasuryError::InvalidAuthority
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// CHECK: Any account may receive SOL
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+}
+
+/// Release SPL tokens from the treasury (spend authority only)
+#[derive(Accounts)]
+pub struct SpendToken<'info> {
+    /// Spend authority approving the release
+    pub authority: Signer<'info>,
+
+    /// The treasury PDA
+    #[account(
+        mut,
+        seeds = [Treasury::SEED_PREFIX],
+        bump = treasury.bump,
+        has_one = authority @ TreasuryError::InvalidAuthority
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// Treasury's token account for this mint
+    #[account(
+        mut,
+        constraint = treasury_token_account.owner == treasury.key() @ TreasuryError::InvalidTokenAccount
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+
+    /// Recipient token account
+    #[account(
+        mut,
+        constraint = recipient_token_account.mint == treasury_token_account.mint @ TreasuryError::InvalidTokenAccount
+    )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Event emitted for every approved spend
+#[event]
+pub struct SpendEvent {
+    pub spend_id: u64,
+    pub authority: Pubkey,
+    pub recipient: Pubkey,
+    /// `Pubkey::default()` for SOL spends
+    pub mint: Pubkey,
+    pub amount: u64,
+    /// Off-chain reference (e.g. hash of the approving proposal)
+    pub reference: [u8; 32],
+    pub timestamp: i64,
+}
+
+pub fn spend_sol_handler(ctx: Context<SpendSol>, amount: u64, reference: [u8; 32]) -> Result<()> {
+    require!(amount > 0, TreasuryError::InvalidAmount);
+
+    let treasury_info = ctx.accounts.treasury.to_account_info();
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(treasury_info.data_len());
+    let spendable = Treasury::spendable_lamports(treasury_info.lamports(), rent_exempt_minimum);
+    require!(amount <= spendable, TreasuryError::InsufficientFunds);
+
+    // The treasury PDA holds data, so lamports are moved directly
+    **treasury_info.try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.recipient.try_borrow_mut_lamports()? += amount;
+
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.total_spent_lamports = treasury.total_spent_lamports
+        .checked_add(amount)
+        .ok_or(TreasuryError::MathOverflow)?;
+    let spend_id = treasury.next_spend_id()?;
+
+    emit!(SpendEvent {
+        spend_id,
+        authority: ctx.accounts.authority.key(),
+        recipient: ctx.accounts.recipient.key(),
+        mint: Pubkey::default(),
+        amount,
+        reference,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Spend #{}: {} lamports to {}", spend_id, amount, ctx.accounts.recipient.key());
+
+    Ok(())
+}
+
+pub fn spend_token_handler(ctx: Context<SpendToken>, amount: u64, reference: [u8; 32]) -> Result<()> {
+    require!(amount > 0, TreasuryError::InvalidAmount);
+    require!(
+        amount <= ctx.accounts.treasury_token_account.amount,
+        TreasuryError::InsufficientFunds
+    );
+
+    let treasury_bump = ctx.accounts.treasury.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[Treasury::SEED_PREFIX, &[treasury_bump]]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.treasury_token_account.to_account_info(),
+            to: ctx.accounts.recipient_token_account.to_account_info(),
+            authority: ctx.accounts.treasury.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    let spend_id = ctx.accounts.treasury.next_spend_id()?;
+
+    emit!(SpendEvent {
+        spend_id,
+        authority: ctx.accounts.authority.key(),
+        recipient: ctx.accounts.recipient_token_account.key(),
+        mint: ctx.accounts.treasury_token_account.mint,
+        amount,
+        reference,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Spend #{}: {} tokens to {}", spend_id, amount, ctx.accounts.recipient_token_account.key());
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+
+pub mod errors;
+pub mod instructions;
+pub mod state;
+
+use instructions::*;
+use state::*;
+
+declare_id!("KR8TivTreasury11111111111111111111111111111");
+
+#[program]
+pub mod treasury {
+    use super::*;
+
+    /// Create the protocol treasury
+    ///
+    /// # Arguments
+    /// * `ctx` - Initialize context
+    /// * `authority` - Authority allowed to approve spends
+    pub fn initialize(ctx: Context<Initialize>, authority: Pubkey) -> Result<()> {
+        instructions::initialize::handler(ctx, authority)
+    }
+
+    /// Deposit SOL into the treasury
+    ///
+    /// # Arguments
+    /// * `ctx` - DepositSol context
+    /// * `amount` - Lamports to deposit
+    /// * `source` - What the deposit represents
+    pub fn deposit_sol(ctx: Context<DepositSol>, amount: u64, source: FeeSource) -> Result<()> {
+        instructions::deposit::deposit_sol_handler(ctx, amount, source)
+    }
+
+    /// Deposit SPL tokens into a treasury-owned token account
+    ///
+    /// # Arguments
+    /// * `ctx` - DepositToken context
+    /// * `amount` - Tokens to deposit
+    /// * `source` - What the deposit represents
+    pub fn deposit_token(ctx: Context<DepositToken>, amount: u64, source: FeeSource) -> Result<()> {
+        instructions::deposit::deposit_token_handler(ctx, amount, source)
+    }
+
+    /// Release SOL from the treasury (spend authority only)
+    ///
+    /// # Arguments
+    /// * `ctx` - SpendSol context
+    /// * `amount` - Lamports to release
+    /// * `reference` - Off-chain reference for the approval
+    pub fn spend_sol(ctx: Context<SpendSol>, amount: u64, reference: [u8; 32]) -> Result<()> {
+        instructions::spend::spend_sol_handler(ctx, amount, reference)
+    }
+
+    /// Release SPL tokens from the treasury (spend authority only)
+    ///
+    /// # Arguments
+    /// * `ctx` - SpendToken context
+    /// * `amount` - Tokens to release
+    /// * `reference` - Off-chain reference for the approval
+    pub fn spend_token(ctx: Context<SpendToken>, amount: u64, reference: [u8; 32]) -> Result<()> {
+        instructions::spend::spend_token_handler(ctx, amount, reference)
+    }
+
+    /// Hand spend approval to a new authority
+    ///
+    /// # Arguments
+    /// * `ctx` - SetAuthority context
+    /// * `new_authority` - The new spend authority
+    pub fn set_authority(ctx: Context<SetAuthority>, new_authority: Pubkey) -> Result<()> {
+        instructions::set_authority::handler(ctx, new_authority)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spendable_lamports_keeps_rent_reserve() {
+        assert_eq!(Treasury::spendable_lamports(5_000_000, 1_000_000), 4_000_000);
+        assert_eq!(Treasury::spendable_lamports(1_000_000, 1_000_000), 0);
+        assert_eq!(Treasury::spendable_lamports(500_000, 1_000_000), 0);
+    }
+
+    #[test]
+    fn test_spend_ids_are_sequential() {
+        let mut treasury = Treasury::default();
+        assert_eq!(treasury.next_spend_id().unwrap(), 0);
+        assert_eq!(treasury.next_spend_id().unwrap(), 1);
+        assert_eq!(treasury.spend_count, 2);
+    }
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+/// Where a deposit into the treasury originated
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FeeSource {
+    /// Platform fees charged on launches and trades
+    PlatformFee,
+    /// Early-exit and other staking penalties
+    Penalty,
+    /// Stake pool creation fees
+    PoolCreation,
+    /// Anything else (donations, manual top-ups)
+    Other,
+}
+
+/// The protocol treasury
+///
+/// SOL is held directly on this PDA. SPL tokens are held in token accounts
+/// whose owner is this PDA.
+#[account]
+#[derive(Default)]
+pub struct Treasury {
+    /// Authority allowed to approve spends (a governance PDA once it exists)
+    pub authority: Pubkey,
+
+    /// Total lamports deposited through `deposit_sol`
+    pub total_deposited_lamports: u64,
+
+    /// Total lamports released through `spend_sol`
+    pub total_spent_lamports: u64,
+
+    /// Number of approved spends, used as the spend id
+    pub spend_count: u64,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+
+    /// Reserved space for future upgrades
+    pub _reserved: [u8; 32],
+}
+
+impl Treasury {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // authority
+        8 +   // total_deposited_lamports
+        8 +   // total_spent_lamports
+        8 +   // spend_count
+        1 +   // bump
+        32;   // _reserved
+
+    pub const SEED_PREFIX: &'static [u8] = b"treasury";
+
+    /// Lamports that can be spent without dropping below rent exemption
+    pub fn spendable_lamports(current_lamports: u64, rent_exempt_minimum: u64) -> u64 {
+        current_lamports.saturating_sub(rent_exempt_minimum)
+    }
+
+    /// Allocate the next spend id
+    pub fn next_spend_id(&mut self) -> Result<u64> {
+        let id = self.spend_count;
+        self.spend_count = self.spend_count
+            .checked_add(1)
+            .ok_or(crate::errors::TreasuryError::MathOverflow)?;
+        Ok(id)
+    }
+}
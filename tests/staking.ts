@@ -24,6 +24,13 @@ const program = anchor.workspace.Staking as Program<Staking>;
 const connection = provider.connection;
 const wallet = provider.wallet as anchor.Wallet;
 
+const BPF_LOADER_UPGRADEABLE_PROGRAM_ID = new PublicKey(
+  "BPFLoaderUpgradeab1e11111111111111111111111"
+);
+const MEMO_PROGRAM_ID = new PublicKey(
+  "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr"
+);
+
 const DAY = 24 * 60 * 60;
 const MIN_LOCK = 7 * DAY;
 const MAX_LOCK = 365 * DAY;
@@ -92,9 +99,14 @@ async function airdrop(pubkey: PublicKey, sol = 2): Promise<void> {
   await connection.confirmTransaction(sig, "confirmed");
 }
 
-function getStakePoolPda(stakeMint: PublicKey): PublicKey {
+// Pool 0 keeps the original [prefix, mint] address
+function getStakePoolPda(stakeMint: PublicKey, poolId = 0): PublicKey {
+  const poolIdSeed =
+    poolId === 0
+      ? Buffer.alloc(0)
+      : new anchor.BN(poolId).toArrayLike(Buffer, "le", 8);
   return PublicKey.findProgramAddressSync(
-    [Buffer.from("stake_pool"), stakeMint.toBuffer()],
+    [Buffer.from("stake_pool"), stakeMint.toBuffer(), poolIdSeed],
     program.programId
   )[0];
 }
@@ -113,6 +125,79 @@ function getRewardVaultPda(stakePool: PublicKey): PublicKey {
   )[0];
 }
 
+function getPoolStatsPda(stakePool: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("pool_stats"), stakePool.toBuffer()],
+    program.programId
+  )[0];
+}
+
+function getProtocolTreasuryPda(): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("protocol_treasury")],
+    program.programId
+  )[0];
+}
+
+function getProgramConfigPda(): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("program_config")],
+    program.programId
+  )[0];
+}
+
+async function ensureProtocolTreasury() {
+  const protocolTreasury = getProtocolTreasuryPda();
+  const existing = await connection.getAccountInfo(protocolTreasury);
+  if (existing) return protocolTreasury;
+
+  const programData = PublicKey.findProgramAddressSync(
+    [program.programId.toBuffer()],
+    BPF_LOADER_UPGRADEABLE_PROGRAM_ID
+  )[0];
+
+  await program.methods
+    .initializeProtocolTreasury(new anchor.BN(0))
+    .accounts({
+      admin: wallet.publicKey,
+      protocolTreasury,
+      program: program.programId,
+      programData,
+      systemProgram: SystemProgram.programId,
+    })
+    .rpc();
+
+  return protocolTreasury;
+}
+
+function getPoolMetadataPda(stakePool: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("pool_metadata"), stakePool.toBuffer()],
+    program.programId
+  )[0];
+}
+
+function getPoolRegistryPda(): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("pool_registry")],
+    program.programId
+  )[0];
+}
+
+function getRegistryEntryPda(index: anchor.BN): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("registry_entry"), index.toArrayLike(Buffer, "le", 8)],
+    program.programId
+  )[0];
+}
+
+async function nextRegistryIndex(): Promise<anchor.BN> {
+  const registry = await program.account.poolRegistry.fetchNullable(
+    getPoolRegistryPda()
+  );
+  return registry ? registry.poolCount : new anchor.BN(0);
+}
+
 function getUserStakePda(stakePool: PublicKey, user: PublicKey): PublicKey {
   return PublicKey.findProgramAddressSync(
     [Buffer.from("user_stake"), stakePool.toBuffer(), user.toBuffer()],
@@ -147,16 +232,30 @@ async function setupPool(params?: {
   const stakePool = getStakePoolPda(stakeMint);
   const stakeVault = getStakeVaultPda(stakePool);
   const rewardVault = getRewardVaultPda(stakePool);
+  const poolStats = getPoolStatsPda(stakePool);
+  const protocolTreasury = await ensureProtocolTreasury();
+  const registryIndex = await nextRegistryIndex();
 
   await program.methods
     .initialize({
+      poolId: new anchor.BN(0),
       rewardRate,
       minLockDuration: new anchor.BN(minLock),
       maxLockDuration: new anchor.BN(maxLock),
+      name: "KR8TIV Staking",
+      symbol: "KR8",
+      uri: "https://kr8tiv.io/pools/0.json",
+      allowFreezable: false,
     })
     .accounts({
       authority: wallet.publicKey,
       stakePool,
+      poolStats,
+      poolMetadata: getPoolMetadataPda(stakePool),
+      poolRegistry: getPoolRegistryPda(),
+      registryEntry: getRegistryEntryPda(registryIndex),
+      protocolTreasury,
+      programConfig: null,
       stakeMint,
       rewardMint,
       stakeVault,
@@ -182,6 +281,8 @@ async function setupPool(params?: {
     stakePool,
     stakeVault,
     rewardVault,
+    poolStats,
+    registryIndex,
     minLock,
     maxLock,
     rewardRate,
@@ -234,6 +335,10 @@ async function getStakeEvent(signature: string) {
 }
 
 describe("staking", () => {
+  before(async () => {
+    await ensureProtocolTreasury();
+  });
+
   it("initializes stake pool with valid parameters", async () => {
     const pool = await setupPool({
       minLock: MIN_LOCK,
@@ -253,7 +358,26 @@ describe("staking", () => {
     expect(stakePool.rewardRate.eq(new anchor.BN(500))).to.equal(true);
     expect(stakePool.minLockDuration.toNumber()).to.equal(MIN_LOCK);
     expect(stakePool.maxLockDuration.toNumber()).to.equal(MAX_LOCK);
+    expect(stakePool.stakeMintDecimals).to.equal(DECIMALS);
+    expect(stakePool.rewardMintDecimals).to.equal(DECIMALS);
     expect(stakePool.paused).to.equal(false);
+
+    const metadata = await program.account.poolMetadata.fetch(
+      getPoolMetadataPda(pool.stakePool)
+    );
+    const decode = (bytes: number[]) =>
+      Buffer.from(bytes).toString("utf8").replace(/\0+$/, "");
+    expect(decode(metadata.name)).to.equal("KR8TIV Staking");
+    expect(decode(metadata.symbol)).to.equal("KR8");
+    expect(decode(metadata.uri)).to.equal("https://kr8tiv.io/pools/0.json");
+
+    const entry = await program.account.registryEntry.fetch(
+      getRegistryEntryPda(pool.registryIndex)
+    );
+    expect(entry.index.eq(pool.registryIndex)).to.equal(true);
+    expect(entry.stakePool.equals(pool.stakePool)).to.equal(true);
+    const registry = await program.account.poolRegistry.fetch(getPoolRegistryPda());
+    expect(registry.poolCount.gt(pool.registryIndex)).to.equal(true);
   });
 
   it("stakes with various lock durations", async () => {
@@ -273,10 +397,18 @@ describe("staking", () => {
         .accounts({
           user: user.publicKey,
           stakePool: pool.stakePool,
+          programConfig: getProgramConfigPda(),
+          poolStats: pool.poolStats,
           userStake,
           userTokenAccount: userTokenAccount.address,
           stakeVault: pool.stakeVault,
+          rewardVault: null,
+          credentialAccount: null,
+          hookRegistry: null,
+          rewardStreams: null,
+          userRewardStreams: null,
           tokenProgram: TOKEN_PROGRAM_ID,
+          memoProgram: MEMO_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
         .signers([user])
@@ -285,7 +417,11 @@ describe("staking", () => {
       const userStakeAccount = await program.account.userStake.fetch(userStake);
       const expected = expectedWeighted(amount, duration, pool.minLock, pool.maxLock);
       expect(userStakeAccount.stakedAmount.eq(amount)).to.equal(true);
-      expect(userStakeAccount.weightedStake.eq(expected)).to.equal(true);
+      expect(userStakeAccount.lockWeightedStake.eq(expected)).to.equal(true);
+      // HOLDER tier multiplier (1.1x) is folded into the pool weight
+      expect(
+        userStakeAccount.weightedStake.eq(expected.muln(11000).div(BPS))
+      ).to.equal(true);
     }
   });
 
@@ -305,10 +441,18 @@ describe("staking", () => {
       .accounts({
         user: user.publicKey,
         stakePool: pool.stakePool,
+        programConfig: getProgramConfigPda(),
+        poolStats: pool.poolStats,
         userStake,
         userTokenAccount: userTokenAccount.address,
         stakeVault: pool.stakeVault,
+        rewardVault: null,
+        credentialAccount: null,
+        hookRegistry: null,
+        rewardStreams: null,
+        userRewardStreams: null,
         tokenProgram: TOKEN_PROGRAM_ID,
+        memoProgram: MEMO_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
       .signers([user])
@@ -316,6 +460,7 @@ describe("staking", () => {
 
     const userStakeAccount = await program.account.userStake.fetch(userStake);
     const expected = expectedWeighted(amount, duration, pool.minLock, pool.maxLock);
+    expect(userStakeAccount.lockWeightedStake.eq(expected)).to.equal(true);
     expect(userStakeAccount.weightedStake.eq(expected)).to.equal(true);
   });
 
@@ -340,10 +485,18 @@ describe("staking", () => {
         .accounts({
           user: user.publicKey,
           stakePool: pool.stakePool,
+          programConfig: getProgramConfigPda(),
+          poolStats: pool.poolStats,
           userStake,
           userTokenAccount: userTokenAccount.address,
           stakeVault: pool.stakeVault,
+          rewardVault: null,
+          credentialAccount: null,
+          hookRegistry: null,
+          rewardStreams: null,
+          userRewardStreams: null,
           tokenProgram: TOKEN_PROGRAM_ID,
+          memoProgram: MEMO_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
         .signers([user])
@@ -370,10 +523,18 @@ describe("staking", () => {
       .accounts({
         user: user.publicKey,
         stakePool: pool.stakePool,
+        programConfig: getProgramConfigPda(),
+        poolStats: pool.poolStats,
         userStake,
         userTokenAccount: userTokenAccount.address,
         stakeVault: pool.stakeVault,
+        rewardVault: null,
+        credentialAccount: null,
+        hookRegistry: null,
+        rewardStreams: null,
+        userRewardStreams: null,
         tokenProgram: TOKEN_PROGRAM_ID,
+        memoProgram: MEMO_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
       .signers([user])
@@ -386,10 +547,21 @@ describe("staking", () => {
         .accounts({
           user: user.publicKey,
           stakePool: pool.stakePool,
+          programConfig: getProgramConfigPda(),
           userStake,
           userTokenAccount: userTokenAccount.address,
           stakeVault: pool.stakeVault,
+          rewardVault: null,
+          hookRegistry: null,
+          rewardStreams: null,
+          userRewardStreams: null,
+          stakeMint: null,
+          penaltyTreasury: null,
+          insuranceFund: null,
+          insuranceVault: null,
           tokenProgram: TOKEN_PROGRAM_ID,
+          memoProgram: MEMO_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
         })
         .signers([user])
         .rpc();
@@ -402,7 +574,7 @@ describe("staking", () => {
   });
 
   it("allows unstake after lock expires", async () => {
-    const pool = await setupPool({ minLock: 0 });
+    const pool = await setupPool({ minLock: 1 });
     const amount = ONE.muln(100);
     const { user, userTokenAccount } = await createUserWithTokens(
       pool.stakeMint,
@@ -415,10 +587,18 @@ describe("staking", () => {
       .accounts({
         user: user.publicKey,
         stakePool: pool.stakePool,
+        programConfig: getProgramConfigPda(),
+        poolStats: pool.poolStats,
         userStake,
         userTokenAccount: userTokenAccount.address,
         stakeVault: pool.stakeVault,
+        rewardVault: null,
+        credentialAccount: null,
+        hookRegistry: null,
+        rewardStreams: null,
+        userRewardStreams: null,
         tokenProgram: TOKEN_PROGRAM_ID,
+        memoProgram: MEMO_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
       .signers([user])
@@ -431,10 +611,21 @@ describe("staking", () => {
       .accounts({
         user: user.publicKey,
         stakePool: pool.stakePool,
+        programConfig: getProgramConfigPda(),
         userStake,
         userTokenAccount: userTokenAccount.address,
         stakeVault: pool.stakeVault,
+        rewardVault: null,
+        hookRegistry: null,
+        rewardStreams: null,
+        userRewardStreams: null,
+        stakeMint: null,
+        penaltyTreasury: null,
+        insuranceFund: null,
+        insuranceVault: null,
         tokenProgram: TOKEN_PROGRAM_ID,
+        memoProgram: MEMO_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
       })
       .signers([user])
       .rpc();
@@ -446,7 +637,7 @@ describe("staking", () => {
   });
 
   it("claims staking rewards", async () => {
-    const pool = await setupPool({ minLock: 0, rewardRate: new anchor.BN(1000) });
+    const pool = await setupPool({ minLock: 1, rewardRate: new anchor.BN(1000) });
     const amount = ONE.muln(1000);
     const { user, userTokenAccount } = await createUserWithTokens(
       pool.stakeMint,
@@ -461,14 +652,22 @@ describe("staking", () => {
     const userStake = getUserStakePda(pool.stakePool, user.publicKey);
 
     await program.methods
-      .stake(amount, new anchor.BN(0))
+      .stake(amount, new anchor.BN(1))
       .accounts({
         user: user.publicKey,
         stakePool: pool.stakePool,
+        programConfig: getProgramConfigPda(),
+        poolStats: pool.poolStats,
         userStake,
         userTokenAccount: userTokenAccount.address,
         stakeVault: pool.stakeVault,
+        rewardVault: null,
+        credentialAccount: null,
+        hookRegistry: null,
+        rewardStreams: null,
+        userRewardStreams: null,
         tokenProgram: TOKEN_PROGRAM_ID,
+        memoProgram: MEMO_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
       .signers([user])
@@ -481,16 +680,159 @@ describe("staking", () => {
       .accounts({
         user: user.publicKey,
         stakePool: pool.stakePool,
+        poolStats: pool.poolStats,
+        userStake,
+        userRewardAccount: userRewardAccount.address,
+        rewardVault: pool.rewardVault,
+        feeTreasury: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        memoProgram: MEMO_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    const userStakeAccount = await program.account.userStake.fetch(userStake);
+    expect(userStakeAccount.totalClaimed.toNumber()).to.be.greaterThan(0);
+    const rewardAccount = await getAccount(connection, userRewardAccount.address);
+    expect(rewardAccount.amount).to.be.greaterThan(0n);
+  });
+
+  it("lets a keeper crank claims for opted-in positions", async () => {
+    const pool = await setupPool({ minLock: 1, rewardRate: new anchor.BN(1000) });
+    const amount = ONE.muln(1000);
+    const { user, userTokenAccount } = await createUserWithTokens(
+      pool.stakeMint,
+      amount
+    );
+    const userRewardAccount = await getOrCreateAssociatedTokenAccount(
+      connection,
+      wallet.payer,
+      pool.rewardMint,
+      user.publicKey
+    );
+    const userStake = getUserStakePda(pool.stakePool, user.publicKey);
+
+    await program.methods
+      .stake(amount, new anchor.BN(1))
+      .accounts({
+        user: user.publicKey,
+        stakePool: pool.stakePool,
+        programConfig: getProgramConfigPda(),
+        poolStats: pool.poolStats,
+        userStake,
+        userTokenAccount: userTokenAccount.address,
+        stakeVault: pool.stakeVault,
+        rewardVault: null,
+        credentialAccount: null,
+        hookRegistry: null,
+        rewardStreams: null,
+        userRewardStreams: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        memoProgram: MEMO_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([user])
+      .rpc();
+
+    await program.methods
+      .setAutoClaim(true)
+      .accounts({ user: user.publicKey, stakePool: pool.stakePool, userStake })
+      .signers([user])
+      .rpc();
+
+    await new Promise((resolve) => setTimeout(resolve, 1500));
+
+    // The keeper is the provider wallet, not the user
+    await program.methods
+      .crankClaims()
+      .accounts({
+        keeper: wallet.publicKey,
+        stakePool: pool.stakePool,
+        poolStats: pool.poolStats,
+        rewardVault: pool.rewardVault,
+        feeTreasury: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        memoProgram: MEMO_PROGRAM_ID,
+      })
+      .remainingAccounts([
+        { pubkey: userStake, isSigner: false, isWritable: true },
+        { pubkey: userRewardAccount.address, isSigner: false, isWritable: true },
+      ])
+      .rpc();
+
+    const userStakeAccount = await program.account.userStake.fetch(userStake);
+    expect(userStakeAccount.autoClaim).to.equal(true);
+    expect(userStakeAccount.totalClaimed.toNumber()).to.be.greaterThan(0);
+  });
+
+  it("claims rewards and unstakes atomically", async () => {
+    const pool = await setupPool({ minLock: 1, rewardRate: new anchor.BN(1000) });
+    const amount = ONE.muln(1000);
+    const { user, userTokenAccount } = await createUserWithTokens(
+      pool.stakeMint,
+      amount
+    );
+    const userRewardAccount = await getOrCreateAssociatedTokenAccount(
+      connection,
+      wallet.payer,
+      pool.rewardMint,
+      user.publicKey
+    );
+    const userStake = getUserStakePda(pool.stakePool, user.publicKey);
+
+    await program.methods
+      .stake(amount, new anchor.BN(1))
+      .accounts({
+        user: user.publicKey,
+        stakePool: pool.stakePool,
+        programConfig: getProgramConfigPda(),
+        poolStats: pool.poolStats,
+        userStake,
+        userTokenAccount: userTokenAccount.address,
+        stakeVault: pool.stakeVault,
+        rewardVault: null,
+        credentialAccount: null,
+        hookRegistry: null,
+        rewardStreams: null,
+        userRewardStreams: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        memoProgram: MEMO_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([user])
+      .rpc();
+
+    await new Promise((resolve) => setTimeout(resolve, 1500));
+
+    await program.methods
+      .claimAndUnstake(amount)
+      .accounts({
+        user: user.publicKey,
+        stakePool: pool.stakePool,
+        programConfig: getProgramConfigPda(),
+        poolStats: pool.poolStats,
         userStake,
+        userTokenAccount: userTokenAccount.address,
+        stakeVault: pool.stakeVault,
         userRewardAccount: userRewardAccount.address,
         rewardVault: pool.rewardVault,
+        feeTreasury: null,
+        hookRegistry: null,
+        rewardStreams: null,
+        userRewardStreams: null,
         tokenProgram: TOKEN_PROGRAM_ID,
+        memoProgram: MEMO_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
       })
       .signers([user])
       .rpc();
 
     const userStakeAccount = await program.account.userStake.fetch(userStake);
+    expect(userStakeAccount.stakedAmount.toNumber()).to.equal(0);
     expect(userStakeAccount.totalClaimed.toNumber()).to.be.greaterThan(0);
+    expect(userStakeAccount.owedRewards.toNumber()).to.equal(0);
+    const tokenAccount = await getAccount(connection, userTokenAccount.address);
+    expect(tokenAccount.amount).to.equal(bnToBigInt(amount));
     const rewardAccount = await getAccount(connection, userRewardAccount.address);
     expect(rewardAccount.amount).to.be.greaterThan(0n);
   });
@@ -510,10 +852,18 @@ describe("staking", () => {
       .accounts({
         user: user.publicKey,
         stakePool: pool.stakePool,
+        programConfig: getProgramConfigPda(),
+        poolStats: pool.poolStats,
         userStake,
         userTokenAccount: userTokenAccount.address,
         stakeVault: pool.stakeVault,
+        rewardVault: null,
+        credentialAccount: null,
+        hookRegistry: null,
+        rewardStreams: null,
+        userRewardStreams: null,
         tokenProgram: TOKEN_PROGRAM_ID,
+        memoProgram: MEMO_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
       .signers([user])
@@ -526,10 +876,18 @@ describe("staking", () => {
       .accounts({
         user: user.publicKey,
         stakePool: pool.stakePool,
+        programConfig: getProgramConfigPda(),
+        poolStats: pool.poolStats,
         userStake,
         userTokenAccount: userTokenAccount.address,
         stakeVault: pool.stakeVault,
+        rewardVault: null,
+        credentialAccount: null,
+        hookRegistry: null,
+        rewardStreams: null,
+        userRewardStreams: null,
         tokenProgram: TOKEN_PROGRAM_ID,
+        memoProgram: MEMO_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
       .signers([user])
@@ -540,4 +898,89 @@ describe("staking", () => {
     expect(after.lockEndTime.gt(before.lockEndTime)).to.equal(true);
     expect(after.lockDuration.toNumber()).to.equal(30 * DAY);
   });
+
+  it("charges the pool creation fee into the protocol treasury", async () => {
+    const protocolTreasury = getProtocolTreasuryPda();
+    const fee = new anchor.BN(LAMPORTS_PER_SOL / 10);
+
+    await program.methods
+      .setPoolCreationFee(fee)
+      .accounts({ admin: wallet.publicKey, protocolTreasury })
+      .rpc();
+
+    const lamportsBefore = await connection.getBalance(protocolTreasury);
+    const treasuryBefore = await program.account.protocolTreasury.fetch(
+      protocolTreasury
+    );
+
+    try {
+      await setupPool();
+    } finally {
+      await program.methods
+        .setPoolCreationFee(new anchor.BN(0))
+        .accounts({ admin: wallet.publicKey, protocolTreasury })
+        .rpc();
+    }
+
+    const lamportsAfter = await connection.getBalance(protocolTreasury);
+    const treasuryAfter = await program.account.protocolTreasury.fetch(
+      protocolTreasury
+    );
+    expect(lamportsAfter - lamportsBefore).to.equal(fee.toNumber());
+    expect(
+      treasuryAfter.totalFeesCollected
+        .sub(treasuryBefore.totalFeesCollected)
+        .eq(fee)
+    ).to.equal(true);
+  });
+
+  it("hands pool authority to a new key (e.g. the timelock PDA)", async () => {
+    const pool = await setupPool();
+    const [timelockPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("timelock")],
+      new PublicKey("KR8TivTimeLock11111111111111111111111111111")
+    );
+
+    await program.methods
+      .setPoolAuthority(timelockPda)
+      .accounts({ authority: wallet.publicKey, stakePool: pool.stakePool })
+      .rpc();
+
+    const stakePool = await program.account.stakePool.fetch(pool.stakePool);
+    expect(stakePool.authority.equals(timelockPda)).to.equal(true);
+
+    let threw = false;
+    try {
+      await program.methods
+        .updatePoolConfig({
+          tierActivationDelay: new anchor.BN(60),
+          tierGracePeriod: null,
+          claimWindow: null,
+          claimBlackout: null,
+          credentialMint: null,
+          minStakeAmount: null,
+          earlyUnstakePenaltyBps: null,
+          streakBonus: null,
+          penaltySplit: null,
+          penaltyInsuranceBps: null,
+          rewardEpochDuration: null,
+          actionCooldown: null,
+          protocolFeeBps: null,
+          earlyAdopter: null,
+          allowUnstakeWhenPaused: null,
+          targetAprBps: null,
+          fixedAprBps: null,
+          minClaimInterval: null,
+          claimTax: null,
+          tierMinLockDurations: null,
+          crossBoost: null,
+        })
+        .accounts({ authority: wallet.publicKey, stakePool: pool.stakePool })
+        .rpc();
+    } catch (error) {
+      threw = true;
+      expect(String(error)).to.include("InvalidAuthority");
+    }
+    expect(threw).to.equal(true);
+  });
 });
@@ -0,0 +1,96 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Treasury } from "../target/types/treasury";
+import { Keypair, PublicKey, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { expect } from "chai";
+
+const provider = anchor.AnchorProvider.env();
+anchor.setProvider(provider);
+
+const program = anchor.workspace.Treasury as Program<Treasury>;
+const connection = provider.connection;
+const wallet = provider.wallet as anchor.Wallet;
+
+const BPF_LOADER_UPGRADEABLE_PROGRAM_ID = new PublicKey(
+  "BPFLoaderUpgradeab1e11111111111111111111111"
+);
+
+function getTreasuryPda(): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("treasury")],
+    program.programId
+  )[0];
+}
+
+async function ensureTreasury() {
+  const treasury = getTreasuryPda();
+  const existing = await connection.getAccountInfo(treasury);
+  if (existing) return treasury;
+
+  const programData = PublicKey.findProgramAddressSync(
+    [program.programId.toBuffer()],
+    BPF_LOADER_UPGRADEABLE_PROGRAM_ID
+  )[0];
+
+  await program.methods
+    .initialize(wallet.publicKey)
+    .accounts({
+      payer: wallet.publicKey,
+      treasury,
+      program: program.programId,
+      programData,
+      systemProgram: SystemProgram.programId,
+    })
+    .rpc();
+
+  return treasury;
+}
+
+describe("treasury", () => {
+  it("records deposits and authority-approved spends", async () => {
+    const treasury = await ensureTreasury();
+    const amount = new anchor.BN(LAMPORTS_PER_SOL / 2);
+
+    await program.methods
+      .depositSol(amount, { platformFee: {} })
+      .accounts({
+        depositor: wallet.publicKey,
+        treasury,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const before = await program.account.treasury.fetch(treasury);
+    const recipient = Keypair.generate().publicKey;
+
+    await program.methods
+      .spendSol(amount, Array(32).fill(0))
+      .accounts({ authority: wallet.publicKey, treasury, recipient })
+      .rpc();
+
+    const after = await program.account.treasury.fetch(treasury);
+    expect(await connection.getBalance(recipient)).to.equal(amount.toNumber());
+    expect(after.spendCount.toNumber()).to.equal(before.spendCount.toNumber() + 1);
+    expect(after.totalSpentLamports.sub(before.totalSpentLamports).eq(amount)).to.equal(true);
+  });
+
+  it("rejects spends from anyone but the authority", async () => {
+    const treasury = await ensureTreasury();
+    const outsider = Keypair.generate();
+
+    try {
+      await program.methods
+        .spendSol(new anchor.BN(1), Array(32).fill(0))
+        .accounts({
+          authority: outsider.publicKey,
+          treasury,
+          recipient: outsider.publicKey,
+        })
+        .signers([outsider])
+        .rpc();
+      expect.fail("spend should have been rejected");
+    } catch (err) {
+      expect(String(err)).to.include("InvalidAuthority");
+    }
+  });
+});